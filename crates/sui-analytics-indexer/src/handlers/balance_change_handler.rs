@@ -0,0 +1,356 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Result;
+use move_core_types::language_storage::TypeTag;
+use sui_data_ingestion_core::Worker;
+use tokio::sync::Mutex;
+
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::coin::Coin;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::execution_status::ExecutionStatus;
+use sui_types::gas_coin::GAS;
+use sui_types::object::{Object, Owner};
+
+use crate::handlers::{owner_address, owner_type, AnalyticsHandler};
+use crate::tables::BalanceChangeEntry;
+use crate::FileType;
+
+pub struct BalanceChangeHandler {
+    pub(crate) state: Mutex<State>,
+}
+
+pub(crate) struct State {
+    pub(crate) balance_changes: Vec<BalanceChangeEntry>,
+}
+
+#[async_trait::async_trait]
+impl Worker for BalanceChangeHandler {
+    async fn process_checkpoint(&self, checkpoint_data: CheckpointData) -> Result<()> {
+        let CheckpointData {
+            checkpoint_summary,
+            transactions: checkpoint_transactions,
+            ..
+        } = checkpoint_data;
+        let mut state = self.state.lock().await;
+        for checkpoint_transaction in checkpoint_transactions {
+            self.process_transaction(
+                checkpoint_summary.epoch,
+                checkpoint_summary.sequence_number,
+                checkpoint_summary.timestamp_ms,
+                &checkpoint_transaction,
+                &mut state,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsHandler<BalanceChangeEntry> for BalanceChangeHandler {
+    async fn read(&self) -> Result<Vec<BalanceChangeEntry>> {
+        let mut state = self.state.lock().await;
+        let cloned = state.balance_changes.clone();
+        state.balance_changes.clear();
+        Ok(cloned)
+    }
+
+    fn file_type(&self) -> Result<FileType> {
+        Ok(FileType::BalanceChange)
+    }
+
+    fn name(&self) -> &str {
+        "balance_change"
+    }
+}
+
+impl BalanceChangeHandler {
+    pub fn new() -> Self {
+        let state = State {
+            balance_changes: vec![],
+        };
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    fn process_transaction(
+        &self,
+        epoch: u64,
+        checkpoint: u64,
+        timestamp_ms: u64,
+        checkpoint_transaction: &CheckpointTransaction,
+        state: &mut State,
+    ) -> Result<()> {
+        let transaction_digest = checkpoint_transaction.transaction.digest().base58_encode();
+        for ((owner, coin_type), amount) in balance_changes(checkpoint_transaction)? {
+            state.balance_changes.push(BalanceChangeEntry {
+                transaction_digest: transaction_digest.clone(),
+                checkpoint,
+                epoch,
+                timestamp_ms,
+                owner_type: owner_type(&owner),
+                owner_address: owner_address(&owner),
+                coin_type: coin_type.to_canonical_string(/* with_prefix */ true),
+                amount: amount.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Computes the net balance change of each owner and coin type in a transaction, like the
+/// JSON-RPC's balance changes: the coins the transaction modified are subtracted at their input
+/// versions, and added at their output versions. Failed transactions only charge gas.
+fn balance_changes(
+    transaction: &CheckpointTransaction,
+) -> Result<BTreeMap<(Owner, TypeTag), i128>> {
+    let effects = &transaction.effects;
+    let mut balances = BTreeMap::new();
+
+    if effects.status() != &ExecutionStatus::Success {
+        let (_, gas_owner) = effects.gas_object();
+        let gas_usage = effects.gas_cost_summary().net_gas_usage() as i128;
+        if gas_usage != 0 {
+            balances.insert((gas_owner, GAS::type_tag()), -gas_usage);
+        }
+        return Ok(balances);
+    }
+
+    let modified_at_versions: HashSet<_> = effects.modified_at_versions().into_iter().collect();
+    for object in &transaction.input_objects {
+        if modified_at_versions.contains(&(object.id(), object.version())) {
+            if let Some((owner, coin_type, value)) = coin_balance(object)? {
+                *balances.entry((owner, coin_type)).or_default() -= value as i128;
+            }
+        }
+    }
+    for object in &transaction.output_objects {
+        if let Some((owner, coin_type, value)) = coin_balance(object)? {
+            *balances.entry((owner, coin_type)).or_default() += value as i128;
+        }
+    }
+
+    balances.retain(|_, amount| *amount != 0);
+    Ok(balances)
+}
+
+fn coin_balance(object: &Object) -> Result<Option<(Owner, TypeTag, u64)>> {
+    let Some(coin_type) = object.coin_type_maybe() else {
+        return Ok(None);
+    };
+    Ok(Coin::extract_balance_if_coin(object)?.map(|value| (object.owner, coin_type, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::handlers::balance_change_handler::BalanceChangeHandler;
+    use crate::tables::BalanceChangeEntry;
+    use move_core_types::ident_str;
+    use simulacrum::Simulacrum;
+    use sui_data_ingestion_core::Worker;
+    use sui_move_build::BuildConfig;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+    use sui_types::gas_coin::{GAS, MIST_PER_SUI};
+    use sui_types::messages_checkpoint::VerifiedCheckpoint;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::storage::ReadStore;
+    use sui_types::transaction::{
+        GasData, ObjectArg, ProgrammableTransaction, Transaction, TransactionData,
+        TransactionDataAPI, TransactionKind,
+    };
+    use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+
+    /// Executes `pt` as the first account in `sim`, paying for gas with one of its gas coins.
+    fn execute(
+        sim: &mut Simulacrum,
+        pt: ProgrammableTransaction,
+    ) -> (Transaction, TransactionEffects) {
+        let (sender, key) = sim.keystore().accounts().next().unwrap();
+        let sender = *sender;
+        let gas = sim
+            .store()
+            .owned_objects(sender)
+            .find(|object| object.is_gas_coin())
+            .unwrap()
+            .compute_object_reference();
+        let gas_data = GasData {
+            payment: vec![gas],
+            owner: sender,
+            price: sim.reference_gas_price(),
+            budget: 5 * MIST_PER_SUI,
+        };
+        let tx_data = TransactionData::new_with_gas_data(
+            TransactionKind::ProgrammableTransaction(pt),
+            sender,
+            gas_data,
+        );
+        let transaction = Transaction::from_data_and_signer(tx_data, vec![key]);
+        let (effects, _) = sim.execute_transaction(transaction.clone()).unwrap();
+        (transaction, effects)
+    }
+
+    /// Creates a checkpoint with the transactions executed so far, and returns the balance changes
+    /// indexed from it, sorted by owner.
+    async fn index_checkpoint(
+        sim: &mut Simulacrum,
+    ) -> anyhow::Result<(VerifiedCheckpoint, Vec<BalanceChangeEntry>)> {
+        let checkpoint = sim.create_checkpoint();
+        let checkpoint_data = sim.get_checkpoint_data(
+            checkpoint.clone(),
+            sim.get_checkpoint_contents_by_digest(&checkpoint.content_digest)?
+                .unwrap(),
+        )?;
+        let handler = BalanceChangeHandler::new();
+        handler.process_checkpoint(checkpoint_data).await?;
+        let mut entries = handler.state.lock().await.balance_changes.clone();
+        entries.sort_by_key(|entry| entry.owner_address.clone());
+        Ok((checkpoint, entries))
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_change_handler() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+
+        // Execute a simple transfer of SUI.
+        let transfer_recipient = SuiAddress::random_for_testing_only();
+        let (transaction, transfer_amount) = sim.transfer_txn(transfer_recipient);
+        let sender = transaction.transaction_data().sender();
+        let (effects, err) = sim.execute_transaction(transaction.clone()).unwrap();
+        assert!(err.is_none());
+        let gas_usage = effects.gas_cost_summary().net_gas_usage() as i128;
+
+        let (checkpoint, entries) = index_checkpoint(&mut sim).await?;
+
+        // The sender pays for the transfer and gas, and the recipient receives the transfer.
+        let mut expected = vec![
+            (sender, -(transfer_amount as i128) - gas_usage),
+            (transfer_recipient, transfer_amount as i128),
+        ];
+        expected.sort_by_key(|(owner, _)| owner.to_string());
+        assert_eq!(entries.len(), 2);
+        for (entry, (owner, amount)) in entries.iter().zip(expected) {
+            assert_eq!(entry.transaction_digest, transaction.digest().to_string());
+            assert_eq!(entry.checkpoint, checkpoint.sequence_number);
+            assert_eq!(entry.owner_address, Some(owner.to_string()));
+            assert_eq!(
+                entry.coin_type,
+                GAS::type_tag().to_canonical_string(/* with_prefix */ true)
+            );
+            assert_eq!(entry.amount, amount.to_string());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_change_handler_failed_transaction() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+
+        // Try to transfer more SUI than the sender has, which fails after charging gas.
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.transfer_sui(SuiAddress::random_for_testing_only(), Some(u64::MAX));
+            builder.finish()
+        };
+        let (transaction, effects) = execute(&mut sim, pt);
+        assert!(!effects.status().is_ok());
+        let sender = transaction.transaction_data().sender();
+        let gas_usage = effects.gas_cost_summary().net_gas_usage() as i128;
+
+        let (_, entries) = index_checkpoint(&mut sim).await?;
+
+        // Only the gas is charged.
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.transaction_digest, transaction.digest().to_string());
+        assert_eq!(entry.owner_address, Some(sender.to_string()));
+        assert_eq!(
+            entry.coin_type,
+            GAS::type_tag().to_canonical_string(/* with_prefix */ true)
+        );
+        assert_eq!(entry.amount, (-gas_usage).to_string());
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_change_handler_non_sui_coin() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+
+        // Publish a coin, whose treasury cap goes to the publisher.
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/test_coin");
+        let package = BuildConfig::new_for_testing().build(path)?;
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.publish_immutable(
+                package.get_package_bytes(/* with_unpublished_deps */ false),
+                package.published_dependency_ids(),
+            );
+            builder.finish()
+        };
+        let (_, effects) = execute(&mut sim, pt);
+        assert!(effects.status().is_ok());
+        let (treasury_cap, cap_type) = effects
+            .created()
+            .into_iter()
+            .find_map(|(obj_ref, _)| {
+                let tag = sim.store().get_object(&obj_ref.0)?.struct_tag()?;
+                (tag.name.as_str() == "TreasuryCap").then_some((obj_ref, tag))
+            })
+            .unwrap();
+        let coin_type = cap_type.type_params[0].clone();
+
+        // Mint some of it to another address.
+        let recipient = SuiAddress::random_for_testing_only();
+        let amount = 1_000u64;
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            let arguments = vec![
+                builder.obj(ObjectArg::ImmOrOwnedObject(treasury_cap))?,
+                builder.pure(amount)?,
+                builder.pure(recipient)?,
+            ];
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                ident_str!("coin").to_owned(),
+                ident_str!("mint_and_transfer").to_owned(),
+                vec![coin_type.clone()],
+                arguments,
+            );
+            builder.finish()
+        };
+        let (transaction, effects) = execute(&mut sim, pt);
+        assert!(effects.status().is_ok());
+        let sender = transaction.transaction_data().sender();
+        let gas_usage = effects.gas_cost_summary().net_gas_usage() as i128;
+
+        // The checkpoint also includes the publish, so only look at the mint.
+        let (_, entries) = index_checkpoint(&mut sim).await?;
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| entry.transaction_digest == transaction.digest().to_string())
+            .collect();
+
+        // The sender pays for gas in SUI, and the recipient receives the minted coin.
+        let mut expected = vec![
+            (sender, GAS::type_tag(), -gas_usage),
+            (recipient, coin_type, amount as i128),
+        ];
+        expected.sort_by_key(|(owner, _, _)| owner.to_string());
+        assert_eq!(entries.len(), 2);
+        for (entry, (owner, coin_type, amount)) in entries.iter().zip(expected) {
+            assert_eq!(entry.owner_address, Some(owner.to_string()));
+            assert_eq!(
+                entry.coin_type,
+                coin_type.to_canonical_string(/* with_prefix */ true)
+            );
+            assert_eq!(entry.amount, amount.to_string());
+        }
+        Ok(())
+    }
+}
@@ -29,6 +29,7 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
 use crate::analytics_processor::AnalyticsProcessor;
+use crate::handlers::balance_change_handler::BalanceChangeHandler;
 use crate::handlers::checkpoint_handler::CheckpointHandler;
 use crate::handlers::df_handler::DynamicFieldHandler;
 use crate::handlers::event_handler::EventHandler;
@@ -40,8 +41,8 @@ use crate::handlers::transaction_objects_handler::TransactionObjectsHandler;
 use crate::handlers::wrapped_object_handler::WrappedObjectHandler;
 use crate::handlers::AnalyticsHandler;
 use crate::tables::{
-    CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind, MoveCallEntry,
-    MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
+    BalanceChangeEntry, CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind,
+    MoveCallEntry, MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
     TransactionObjectEntry, WrappedObjectEntry,
 };
 use crate::writers::csv_writer::CSVWriter;
@@ -67,6 +68,7 @@ const MOVE_PACKAGE_PREFIX: &str = "move_package";
 const DYNAMIC_FIELD_PREFIX: &str = "dynamic_field";
 
 const WRAPPED_OBJECT_PREFIX: &str = "wrapped_object";
+const BALANCE_CHANGE_PREFIX: &str = "balance_change";
 
 #[derive(Parser, Clone, Debug)]
 #[clap(
@@ -327,6 +329,7 @@ pub enum FileType {
     MovePackage,
     DynamicField,
     WrappedObject,
+    BalanceChange,
 }
 
 impl FileType {
@@ -341,6 +344,7 @@ impl FileType {
             FileType::MovePackage => Path::from(MOVE_PACKAGE_PREFIX),
             FileType::DynamicField => Path::from(DYNAMIC_FIELD_PREFIX),
             FileType::WrappedObject => Path::from(WRAPPED_OBJECT_PREFIX),
+            FileType::BalanceChange => Path::from(BALANCE_CHANGE_PREFIX),
         }
     }
 
@@ -857,6 +861,31 @@ pub async fn make_wrapped_object_processor(
     .await
 }
 
+pub async fn make_balance_change_processor(
+    config: AnalyticsIndexerConfig,
+    metrics: AnalyticsMetrics,
+) -> Result<Processor> {
+    let starting_checkpoint_seq_num =
+        get_starting_checkpoint_seq_num(config.clone(), FileType::BalanceChange).await?;
+    let handler: Box<dyn AnalyticsHandler<BalanceChangeEntry>> =
+        Box::new(BalanceChangeHandler::new());
+    let writer = make_writer::<BalanceChangeEntry>(
+        config.clone(),
+        FileType::BalanceChange,
+        starting_checkpoint_seq_num,
+    )?;
+    let max_checkpoint_reader = make_max_checkpoint_reader(&config).await?;
+    Processor::new::<BalanceChangeEntry>(
+        handler,
+        writer,
+        max_checkpoint_reader,
+        starting_checkpoint_seq_num,
+        metrics,
+        config,
+    )
+    .await
+}
+
 pub fn make_writer<S: Serialize + ParquetSchema>(
     config: AnalyticsIndexerConfig,
     file_type: FileType,
@@ -907,6 +936,7 @@ pub async fn make_analytics_processor(
         FileType::MovePackage => make_move_package_processor(config, metrics).await,
         FileType::DynamicField => make_dynamic_field_processor(config, metrics).await,
         FileType::WrappedObject => make_wrapped_object_processor(config, metrics).await,
+        FileType::BalanceChange => make_balance_change_processor(config, metrics).await,
     }
 }
 
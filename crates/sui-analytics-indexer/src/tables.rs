@@ -269,3 +269,19 @@ pub(crate) struct WrappedObjectEntry {
     pub(crate) json_path: String,
     pub(crate) struct_tag: Option<String>,
 }
+
+// Balance change of an owner for a coin type, in a transaction.
+#[derive(Serialize, Clone, SerializeParquet)]
+pub(crate) struct BalanceChangeEntry {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // balance change
+    pub(crate) owner_type: OwnerType,
+    pub(crate) owner_address: Option<String>,
+    pub(crate) coin_type: String,
+    // signed amount, as a string because it does not always fit in 64 bits
+    pub(crate) amount: String,
+}
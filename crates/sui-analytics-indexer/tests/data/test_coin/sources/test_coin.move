@@ -0,0 +1,19 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A coin whose `TreasuryCap` is given to the publisher, to test indexing non-SUI coins.
+module test_coin::test_coin {
+    use std::option;
+    use sui::coin;
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    public struct TEST_COIN has drop {}
+
+    fun init(witness: TEST_COIN, ctx: &mut TxContext) {
+        let (treasury_cap, metadata) =
+            coin::create_currency(witness, 2, b"TEST", b"", b"", option::none(), ctx);
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+    }
+}
@@ -597,6 +597,14 @@ pub struct AuthorityStorePruningConfig {
     pub killswitch_tombstone_pruning: bool,
     #[serde(default = "default_smoothing", skip_serializing_if = "is_true")]
     pub smooth: bool,
+    /// number of epochs to keep the JSON-RPC transaction and event indexes for. Indexes are
+    /// kept forever if neither this nor `num_checkpoints_to_retain_for_indexes` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_epochs_to_retain_for_indexes: Option<u64>,
+    /// number of the latest checkpoints to keep the JSON-RPC transaction and event indexes for.
+    /// If both retention settings are set, indexes are kept for the longer of the two.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_checkpoints_to_retain_for_indexes: Option<u64>,
 }
 
 fn default_num_latest_epoch_dbs_to_retain() -> usize {
@@ -632,6 +640,8 @@ impl Default for AuthorityStorePruningConfig {
             num_epochs_to_retain_for_checkpoints: if cfg!(msim) { Some(2) } else { None },
             killswitch_tombstone_pruning: false,
             smooth: true,
+            num_epochs_to_retain_for_indexes: None,
+            num_checkpoints_to_retain_for_indexes: None,
         }
     }
 }
@@ -654,6 +664,23 @@ impl AuthorityStorePruningConfig {
             })
     }
 
+    pub fn set_num_epochs_to_retain_for_indexes(&mut self, num_epochs_to_retain: Option<u64>) {
+        self.num_epochs_to_retain_for_indexes = num_epochs_to_retain;
+    }
+
+    pub fn set_num_checkpoints_to_retain_for_indexes(
+        &mut self,
+        num_checkpoints_to_retain: Option<u64>,
+    ) {
+        self.num_checkpoints_to_retain_for_indexes = num_checkpoints_to_retain;
+    }
+
+    /// Whether the JSON-RPC indexes are pruned at all.
+    pub fn prunes_indexes(&self) -> bool {
+        self.num_epochs_to_retain_for_indexes.is_some()
+            || self.num_checkpoints_to_retain_for_indexes.is_some()
+    }
+
     pub fn set_killswitch_tombstone_pruning(&mut self, killswitch_tombstone_pruning: bool) {
         self.killswitch_tombstone_pruning = killswitch_tombstone_pruning;
     }
@@ -33,7 +33,7 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{
     collections::{HashMap, HashSet},
     fs,
@@ -64,8 +64,8 @@ use sui_config::genesis::Genesis;
 use sui_config::node::{DBCheckpointConfig, ExpensiveSafetyCheckConfig};
 use sui_framework::{BuiltInFramework, SystemPackage};
 use sui_json_rpc_types::{
-    DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent, SuiMoveValue,
-    SuiObjectDataFilter, SuiTransactionBlockData, SuiTransactionBlockEffects,
+    DevInspectResults, DryRunTransactionBlockResponse, EventFilter, StateOverrides, SuiEvent,
+    SuiMoveValue, SuiObjectDataFilter, SuiTransactionBlockData, SuiTransactionBlockEffects,
     SuiTransactionBlockEvents, TransactionFilter,
 };
 use sui_macros::{fail_point, fail_point_async, fail_point_if};
@@ -121,7 +121,7 @@ use sui_types::{
     fp_ensure,
     object::{Object, ObjectRead},
     transaction::*,
-    SUI_SYSTEM_ADDRESS,
+    SUI_CLOCK_OBJECT_ID, SUI_SYSTEM_ADDRESS,
 };
 use sui_types::{is_system_package, TypeTag};
 use typed_store::TypedStoreError;
@@ -832,6 +832,19 @@ impl AuthorityState {
             self.get_backing_package_store().as_ref(),
         )?;
 
+        // Time-based expirations are checked against the local clock when signing, and against
+        // the consensus commit timestamp when the certificate is sequenced.
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Sui did not exist prior to 1970")
+            .as_millis() as u64;
+        sui_transaction_checks::check_transaction_expiration(
+            epoch_store.protocol_config(),
+            tx_data,
+            epoch_store.epoch(),
+            now_ms,
+        )?;
+
         let (input_objects, receiving_objects) = self
             .input_loader
             .read_objects_for_signing(
@@ -1812,6 +1825,7 @@ impl AuthorityState {
         gas_objects: Option<Vec<ObjectRef>>,
         show_raw_txn_data_and_effects: Option<bool>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
     ) -> SuiResult<DevInspectResults> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
 
@@ -1885,11 +1899,21 @@ impl AuthorityState {
             .await?;
 
         // Create and use a dummy gas object if there is no gas object provided.
-        let dummy_gas_object = Object::new_gas_with_balance_and_owner_for_testing(
+        let mut dummy_gas_object = Object::new_gas_with_balance_and_owner_for_testing(
             DEV_INSPECT_GAS_COIN_VALUE,
             transaction.gas_owner(),
         );
 
+        if let Some(state_overrides) = state_overrides {
+            Self::apply_state_overrides(
+                &transaction,
+                &mut input_objects,
+                &mut dummy_gas_object,
+                state_overrides,
+                protocol_config,
+            )?;
+        }
+
         let gas_objects = if transaction.gas().is_empty() {
             let gas_object_ref = dummy_gas_object.compute_object_reference();
             vec![gas_object_ref]
@@ -2002,6 +2026,138 @@ impl AuthorityState {
         )
     }
 
+    /// Applies the state overrides of a dev inspect transaction to its input objects (or to the
+    /// dummy gas object, if the transaction does not pay for gas). The references of overridden
+    /// owned objects are updated to their new digest, so that they still pass input checks.
+    fn apply_state_overrides(
+        transaction: &TransactionData,
+        input_objects: &mut InputObjects,
+        dummy_gas_object: &mut Object,
+        state_overrides: StateOverrides,
+        protocol_config: &ProtocolConfig,
+    ) -> SuiResult {
+        let StateOverrides {
+            objects,
+            extra_sender_balance,
+            clock_timestamp_ms,
+        } = state_overrides;
+        let mut contents_overrides: BTreeMap<ObjectID, Vec<u8>> = objects
+            .into_iter()
+            .map(|o| (o.object_id, o.bcs_bytes))
+            .collect();
+        let extra_sender_balance = extra_sender_balance.map(|b| *b);
+        let clock_timestamp_ms = clock_timestamp_ms.map(|t| *t);
+
+        // All gas coins are smashed into the first one, so that is where the balance is added.
+        let gas_coin_id = transaction.gas().first().map(|(id, _, _)| *id);
+        if let Some(extra_balance) = extra_sender_balance {
+            fp_ensure!(
+                transaction.gas_owner() == transaction.sender(),
+                UserInputError::Unsupported(
+                    "An extra sender balance can only be simulated if the sender pays for gas"
+                        .to_string()
+                )
+                .into()
+            );
+            if gas_coin_id.is_none() {
+                Self::add_to_gas_coin(dummy_gas_object, extra_balance)?;
+            }
+        }
+
+        let mut clock_overridden = false;
+        for input in input_objects.iter_mut() {
+            let ObjectReadResultKind::Object(object) = &mut input.object else {
+                continue;
+            };
+            let id = object.id();
+            let mut overridden = false;
+
+            if let Some(contents) = contents_overrides.remove(&id) {
+                let Some(move_object) = object.data.try_as_move_mut() else {
+                    return Err(UserInputError::Unsupported(format!(
+                        "Object {id} is a package, its contents cannot be overridden"
+                    ))
+                    .into());
+                };
+                fp_ensure!(
+                    contents.get(..ObjectID::LENGTH) == Some(id.as_ref()),
+                    UserInputError::Unsupported(format!(
+                        "The overridden contents of object {id} must start with its ID"
+                    ))
+                    .into()
+                );
+                move_object
+                    .update_contents(contents, protocol_config)
+                    .map_err(|e| {
+                        UserInputError::Unsupported(format!(
+                            "Invalid overridden contents for object {id}: {e}"
+                        ))
+                    })?;
+                overridden = true;
+            }
+
+            if let (Some(extra_balance), Some(gas_coin_id)) = (extra_sender_balance, gas_coin_id) {
+                if id == gas_coin_id {
+                    Self::add_to_gas_coin(object, extra_balance)?;
+                    overridden = true;
+                }
+            }
+
+            if let Some(timestamp_ms) = clock_timestamp_ms {
+                if id == SUI_CLOCK_OBJECT_ID {
+                    if let Some(clock) = object.data.try_as_move_mut() {
+                        clock.set_clock_timestamp_ms_unsafe(timestamp_ms);
+                        clock_overridden = true;
+                    }
+                }
+            }
+
+            if overridden {
+                if let InputObjectKind::ImmOrOwnedMoveObject(_) = input.input_object_kind {
+                    input.input_object_kind =
+                        InputObjectKind::ImmOrOwnedMoveObject(object.compute_object_reference());
+                }
+            }
+        }
+
+        if let Some(id) = contents_overrides.keys().next() {
+            return Err(UserInputError::Unsupported(format!(
+                "Object {id} is overridden but is not an input of the transaction"
+            ))
+            .into());
+        }
+        fp_ensure!(
+            clock_timestamp_ms.is_none() || clock_overridden,
+            UserInputError::Unsupported(
+                "The clock timestamp is overridden but the Clock is not an input of the transaction"
+                    .to_string()
+            )
+            .into()
+        );
+        Ok(())
+    }
+
+    fn add_to_gas_coin(gas_coin: &mut Object, extra_balance: u64) -> SuiResult {
+        let id = gas_coin.id();
+        let coin = gas_coin
+            .data
+            .try_as_move_mut()
+            .filter(|o| o.type_().is_gas_coin())
+            .ok_or_else(|| {
+                UserInputError::Unsupported(format!("Gas object {id} is not a SUI coin"))
+            })?;
+        let balance = coin
+            .get_coin_value_unsafe()
+            .checked_add(extra_balance)
+            .ok_or_else(|| {
+                UserInputError::Unsupported(format!(
+                    "The extra sender balance overflows the balance of gas coin {id}"
+                ))
+            })?;
+        coin.set_coin_value_unsafe(balance);
+        Ok(())
+    }
+
     // Only used for testing because of how epoch store is loaded.
     pub fn reference_gas_price_for_testing(&self) -> Result<u64, anyhow::Error> {
         let epoch_store = self.epoch_store_for_testing();
@@ -2602,6 +2758,7 @@ impl AuthorityState {
             prometheus_registry,
             indirect_objects_threshold,
             archive_readers,
+            indexes.clone(),
         );
         let input_loader =
             TransactionInputLoader::new(execution_cache_trait_pointers.object_cache_reader.clone());
@@ -3724,11 +3881,9 @@ impl AuthorityState {
 
         //Get the tx_num from tx_digest
         let (tx_num, event_num) = if let Some(cursor) = cursor.as_ref() {
-            let tx_seq = index_store.get_transaction_seq(&cursor.tx_digest)?.ok_or(
-                SuiError::TransactionNotFound {
-                    digest: cursor.tx_digest,
-                },
-            )?;
+            let tx_seq = index_store
+                .get_transaction_seq(&cursor.tx_digest)?
+                .ok_or_else(|| index_store.cursor_not_found(cursor.tx_digest))?;
             (tx_seq, cursor.event_seq as usize)
         } else if descending {
             (u64::MAX, usize::MAX)
@@ -132,6 +132,8 @@ type JwkAggregator = GenericMultiStakeAggregator<(JwkId, JWK), true>;
 
 pub enum CancelConsensusCertificateReason {
     CongestionOnObjects(Vec<ObjectID>),
+    /// The transaction was sequenced outside of its expiration window.
+    Expired,
 }
 
 pub enum ConsensusCertificateResult {
@@ -2749,6 +2751,31 @@ impl AuthorityPerEpochStore {
         cache_reader: &dyn ObjectCacheRead,
         authority_metrics: &Arc<AuthorityMetrics>,
         skip_consensus_commit_prologue_in_test: bool,
+    ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
+        self.process_consensus_transactions_at_timestamp_for_tests(
+            transactions,
+            checkpoint_service,
+            cache_reader,
+            authority_metrics,
+            skip_consensus_commit_prologue_in_test,
+            0,
+        )
+        .await
+    }
+
+    // Like process_consensus_transactions_for_tests(), but with a given consensus commit
+    // timestamp.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub async fn process_consensus_transactions_at_timestamp_for_tests<
+        C: CheckpointServiceNotify,
+    >(
+        self: &Arc<Self>,
+        transactions: Vec<SequencedConsensusTransaction>,
+        checkpoint_service: &Arc<C>,
+        cache_reader: &dyn ObjectCacheRead,
+        authority_metrics: &Arc<AuthorityMetrics>,
+        skip_consensus_commit_prologue_in_test: bool,
+        commit_timestamp: u64,
     ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         self.process_consensus_transactions_and_commit_boundary(
             transactions,
@@ -2757,7 +2784,7 @@ impl AuthorityPerEpochStore {
             cache_reader,
             &ConsensusCommitInfo::new_for_test(
                 self.get_highest_pending_checkpoint_height() + 1,
-                0,
+                commit_timestamp,
                 skip_consensus_commit_prologue_in_test,
             ),
             authority_metrics,
@@ -2855,6 +2882,7 @@ impl AuthorityPerEpochStore {
                     tx,
                     checkpoint_service,
                     consensus_commit_info.round,
+                    consensus_commit_info.timestamp,
                     &previously_deferred_tx_digests,
                     randomness_manager.as_deref_mut(),
                     dkg_failed,
@@ -3071,6 +3099,7 @@ impl AuthorityPerEpochStore {
         transaction: &VerifiedSequencedConsensusTransaction,
         checkpoint_service: &Arc<C>,
         commit_round: Round,
+        commit_timestamp: u64,
         previously_deferred_tx_digests: &HashMap<TransactionDigest, DeferralKey>,
         mut randomness_manager: Option<&mut RandomnessManager>,
         dkg_failed: bool,
@@ -3131,6 +3160,29 @@ impl AuthorityPerEpochStore {
                     return Ok(ConsensusCertificateResult::Ignored);
                 }
 
+                // A certificate that only uses owned objects may already have been executed, so
+                // its expiration is only enforced when signing. A certificate that uses shared
+                // objects is cancelled instead, which still unlocks its owned objects. The commit
+                // timestamp is used rather than the local clock, so that all validators agree on
+                // whether the transaction has expired.
+                if certificate.contains_shared_object() {
+                    if let Err(e) = sui_transaction_checks::check_transaction_expiration(
+                        self.protocol_config(),
+                        certificate.data().transaction_data(),
+                        self.epoch(),
+                        commit_timestamp,
+                    ) {
+                        debug!(
+                            "Cancelling consensus certificate for transaction {:?} outside of its expiration window: {e}",
+                            certificate.digest(),
+                        );
+                        return Ok(ConsensusCertificateResult::Cancelled((
+                            certificate,
+                            CancelConsensusCertificateReason::Expired,
+                        )));
+                    }
+                }
+
                 let deferral_info = self.should_defer(
                     &certificate,
                     commit_round,
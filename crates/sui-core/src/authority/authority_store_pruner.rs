@@ -18,6 +18,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use std::{sync::Arc, time::Duration};
 use sui_archival::reader::ArchiveReaderBalancer;
 use sui_config::node::AuthorityStorePruningConfig;
+use sui_storage::indexes::IndexStore;
 use sui_storage::mutex_table::RwLockTable;
 use sui_types::base_types::SequenceNumber;
 use sui_types::committee::EpochId;
@@ -63,6 +64,8 @@ pub struct AuthorityStorePruningMetrics {
     pub last_pruned_effects_checkpoint: IntGauge,
     pub num_epochs_to_retain_for_objects: IntGauge,
     pub num_epochs_to_retain_for_checkpoints: IntGauge,
+    pub last_pruned_index_checkpoint: IntGauge,
+    pub lowest_retained_index_tx_seq: IntGauge,
 }
 
 impl AuthorityStorePruningMetrics {
@@ -104,6 +107,18 @@ impl AuthorityStorePruningMetrics {
                 registry
             )
             .unwrap(),
+            last_pruned_index_checkpoint: register_int_gauge_with_registry!(
+                "last_pruned_index_checkpoint",
+                "Last checkpoint pruned from the JSON-RPC indexes",
+                registry
+            )
+            .unwrap(),
+            lowest_retained_index_tx_seq: register_int_gauge_with_registry!(
+                "lowest_retained_index_tx_seq",
+                "Lowest transaction sequence number retained in the JSON-RPC indexes",
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
@@ -400,6 +415,94 @@ impl AuthorityStorePruner {
         .await
     }
 
+    /// Prunes the JSON-RPC indexes of all transactions from checkpoints outside the configured
+    /// retention window.
+    ///
+    /// Index entries are keyed by the order transactions were executed in on this node, which can
+    /// differ slightly from checkpoint order, so the retention boundary is approximate: everything
+    /// indexed before the first transaction of the oldest retained checkpoint is pruned.
+    pub async fn prune_indexes_for_eligible_epochs(
+        indexes: &Arc<IndexStore>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        config: AuthorityStorePruningConfig,
+        metrics: Arc<AuthorityStorePruningMetrics>,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("PruneIndexesForEligibleEpochs");
+        let Some(highest_executed) = checkpoint_store.get_highest_executed_checkpoint()? else {
+            return Ok(());
+        };
+        let highest_executed_number = *highest_executed.sequence_number();
+
+        let mut max_eligible_checkpoint = highest_executed_number;
+        if let Some(num_epochs_to_retain) = config.num_epochs_to_retain_for_indexes {
+            let Some(last_pruned_epoch) = highest_executed.epoch.checked_sub(num_epochs_to_retain)
+            else {
+                return Ok(());
+            };
+            let Some(last_checkpoint) =
+                checkpoint_store.get_epoch_last_checkpoint(last_pruned_epoch)?
+            else {
+                return Ok(());
+            };
+            max_eligible_checkpoint =
+                min(max_eligible_checkpoint, *last_checkpoint.sequence_number());
+        }
+        if let Some(num_checkpoints_to_retain) = config.num_checkpoints_to_retain_for_indexes {
+            let Some(last_checkpoint) =
+                highest_executed_number.checked_sub(num_checkpoints_to_retain)
+            else {
+                return Ok(());
+            };
+            max_eligible_checkpoint = min(max_eligible_checkpoint, last_checkpoint);
+        }
+        if max_eligible_checkpoint >= highest_executed_number {
+            return Ok(());
+        }
+
+        // Everything indexed before the oldest retained checkpoint is pruned.
+        let oldest_retained = max_eligible_checkpoint + 1;
+        let Some(contents) = checkpoint_store
+            .get_checkpoint_by_sequence_number(oldest_retained)?
+            .map(|checkpoint| checkpoint.content_digest)
+            .map(|digest| checkpoint_store.get_checkpoint_contents(&digest))
+            .transpose()?
+            .flatten()
+        else {
+            warn!(
+                "Contents of checkpoint {} are missing, skipping index pruning",
+                oldest_retained
+            );
+            return Ok(());
+        };
+        let mut cutoff = None;
+        for digests in contents.iter() {
+            if let Some(seq) = indexes.get_transaction_seq(&digests.transaction)? {
+                cutoff = Some(cutoff.map_or(seq, |cutoff| min(cutoff, seq)));
+            }
+        }
+        let Some(cutoff) = cutoff else {
+            return Ok(());
+        };
+
+        // Prune in batches, yielding in between so that indexing isn't starved.
+        let mut lowest_retained = indexes.lowest_retained_sequence_number();
+        while lowest_retained < cutoff {
+            lowest_retained = min(
+                cutoff,
+                lowest_retained.saturating_add(config.max_transactions_in_batch as u64),
+            );
+            indexes.prune(lowest_retained)?;
+            metrics
+                .lowest_retained_index_tx_seq
+                .set(lowest_retained as i64);
+            tokio::task::yield_now().await;
+        }
+        metrics
+            .last_pruned_index_checkpoint
+            .set(max_eligible_checkpoint as i64);
+        Ok(())
+    }
+
     /// Prunes old object versions based on effects from all checkpoints from epochs eligible for pruning
     pub async fn prune_for_eligible_epochs(
         perpetual_db: &Arc<AuthorityPerpetualTables>,
@@ -613,6 +716,7 @@ impl AuthorityStorePruner {
         metrics: Arc<AuthorityStorePruningMetrics>,
         indirect_objects_threshold: usize,
         archive_readers: ArchiveReaderBalancer,
+        indexes: Option<Arc<IndexStore>>,
     ) -> Sender<()> {
         let (sender, mut recv) = tokio::sync::oneshot::channel();
         debug!(
@@ -631,6 +735,8 @@ impl AuthorityStorePruner {
             tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
         let mut checkpoints_prune_interval =
             tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
+        let mut indexes_prune_interval =
+            tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
 
         let perpetual_db_for_compaction = perpetual_db.clone();
         if let Some(delay_days) = config.periodic_compaction_threshold_days {
@@ -679,6 +785,13 @@ impl AuthorityStorePruner {
                             error!("Failed to prune checkpoints: {:?}", err);
                         }
                     },
+                    _ = indexes_prune_interval.tick(), if indexes.is_some() && config.prunes_indexes() => {
+                        if let Some(indexes) = &indexes {
+                            if let Err(err) = Self::prune_indexes_for_eligible_epochs(indexes, &checkpoint_store, config, metrics.clone()).await {
+                                error!("Failed to prune indexes: {:?}", err);
+                            }
+                        }
+                    },
                     _ = &mut recv => break,
                 }
             }
@@ -696,6 +809,7 @@ impl AuthorityStorePruner {
         registry: &Registry,
         indirect_objects_threshold: usize,
         archive_readers: ArchiveReaderBalancer,
+        indexes: Option<Arc<IndexStore>>,
     ) -> Self {
         if pruning_config.num_epochs_to_retain > 0 && pruning_config.num_epochs_to_retain < u64::MAX
         {
@@ -717,6 +831,7 @@ impl AuthorityStorePruner {
                 AuthorityStorePruningMetrics::new(registry),
                 indirect_objects_threshold,
                 archive_readers,
+                indexes,
             ),
         }
     }
@@ -435,6 +435,36 @@ pub async fn send_batch_consensus_no_execution(
         .unwrap()
 }
 
+/// Like `send_batch_consensus_no_execution`, but the consensus commit has the given timestamp.
+pub async fn send_batch_consensus_no_execution_at_timestamp(
+    authority: &AuthorityState,
+    certificates: &[VerifiedCertificate],
+    commit_timestamp: u64,
+) -> Vec<VerifiedExecutableTransaction> {
+    let transactions = certificates
+        .iter()
+        .map(|cert| {
+            SequencedConsensusTransaction::new_test(ConsensusTransaction::new_certificate_message(
+                &authority.name,
+                cert.clone().into_inner(),
+            ))
+        })
+        .collect();
+
+    authority
+        .epoch_store_for_testing()
+        .process_consensus_transactions_at_timestamp_for_tests(
+            transactions,
+            &Arc::new(CheckpointServiceNoop {}),
+            authority.get_object_cache_reader().as_ref(),
+            &authority.metrics,
+            true,
+            commit_timestamp,
+        )
+        .await
+        .unwrap()
+}
+
 pub fn build_test_modules_with_dep_addr(
     path: PathBuf,
     dep_original_addresses: impl IntoIterator<Item = (&'static str, ObjectID)>,
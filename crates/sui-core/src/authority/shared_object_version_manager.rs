@@ -7,7 +7,6 @@ use crate::authority::AuthorityPerEpochStore;
 use crate::execution_cache::ObjectCacheRead;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use sui_types::base_types::TransactionDigest;
 use sui_types::crypto::RandomnessRound;
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
@@ -165,15 +164,8 @@ fn assign_versions_for_certificate(
 ) -> Vec<(ObjectID, SequenceNumber)> {
     let tx_digest = cert.digest();
 
-    // Check if the transaction is cancelled due to congestion.
-    let cancellation_info: Option<HashSet<_>> =
-        if let Some(CancelConsensusCertificateReason::CongestionOnObjects(congested_objects)) =
-            cancelled_txns.get(tx_digest)
-        {
-            Some(congested_objects.clone().into_iter().collect())
-        } else {
-            None
-        };
+    // Check if the transaction is cancelled, due to congestion or expiration.
+    let cancellation_info = cancelled_txns.get(tx_digest);
     let txn_cancelled = cancellation_info.is_some();
 
     // Make an iterator to update the locks of the transaction's shared objects.
@@ -187,14 +179,19 @@ fn assign_versions_for_certificate(
     let receiving_object_keys = transaction_receiving_object_keys(cert);
     input_object_keys.extend(receiving_object_keys);
 
-    if let Some(congested_objects) = cancellation_info {
-        // For cancelled transaction due to congestion, assign special versions to all shared objects.
+    if let Some(reason) = cancellation_info {
+        // For cancelled transaction, assign special versions to all shared objects.
         // Note that new lamport version does not depend on any shared objects.
         for SharedInputObject { id, .. } in shared_input_objects.iter() {
-            let assigned_version = if congested_objects.contains(id) {
-                SequenceNumber::CONGESTED
-            } else {
-                SequenceNumber::CANCELLED_READ
+            let assigned_version = match reason {
+                CancelConsensusCertificateReason::CongestionOnObjects(congested_objects) => {
+                    if congested_objects.contains(id) {
+                        SequenceNumber::CONGESTED
+                    } else {
+                        SequenceNumber::CANCELLED_READ
+                    }
+                }
+                CancelConsensusCertificateReason::Expired => SequenceNumber::EXPIRED,
             };
             assigned_versions.push((*id, assigned_version));
             is_mutable_input.push(false);
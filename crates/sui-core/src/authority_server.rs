@@ -666,6 +666,12 @@ impl ValidatorService {
             });
         }
 
+        if !config.passkey_auth() && transaction.has_passkey_sig() {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "passkey is not enabled on this network".to_string(),
+            });
+        }
+
         if !config.supports_upgraded_multisig() && transaction.has_upgraded_multisig() {
             return Err(SuiError::UnsupportedFeatureError {
                 error: "upgraded multisig format not enabled on this network".to_string(),
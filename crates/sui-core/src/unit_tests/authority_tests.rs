@@ -27,7 +27,8 @@ use std::fs;
 use std::{convert::TryInto, env};
 
 use sui_json_rpc_types::{
-    SuiArgument, SuiExecutionResult, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTypeTag,
+    ObjectContentsOverride, StateOverrides, SuiArgument, SuiExecutionResult, SuiExecutionStatus,
+    SuiTransactionBlockEffectsAPI, SuiTypeTag,
 };
 use sui_macros::sim_test;
 use sui_protocol_config::{
@@ -45,6 +46,7 @@ use sui_types::object::Data;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::randomness_state::get_randomness_state_obj_initial_shared_version;
 use sui_types::storage::GetSharedLocks;
+use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::SuiSystemStateWrapper;
 use sui_types::utils::{
     to_sender_signed_transaction, to_sender_signed_transaction_with_multi_signers,
@@ -579,7 +581,7 @@ async fn test_dev_inspect_dynamic_field() {
     };
     let kind = TransactionKind::programmable(pt);
     let DevInspectResults { error, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, None, None, None, None, None, None)
+        .dev_inspect_transaction_block(sender, kind, None, None, None, None, None, None, None)
         .await
         .unwrap();
     // produces an error
@@ -821,7 +823,7 @@ async fn test_dev_inspect_gas_coin_argument() {
     };
     let kind = TransactionKind::programmable(pt);
     let results = fullnode
-        .dev_inspect_transaction_block(sender, kind, None, None, None, None, None, None)
+        .dev_inspect_transaction_block(sender, kind, None, None, None, None, None, None, None)
         .await
         .unwrap()
         .results
@@ -870,7 +872,17 @@ async fn test_dev_inspect_gas_price() {
     };
     let kind = TransactionKind::programmable(pt);
     let error = fullnode
-        .dev_inspect_transaction_block(sender, kind.clone(), Some(1), None, None, None, None, None)
+        .dev_inspect_transaction_block(
+            sender,
+            kind.clone(),
+            Some(1),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
         .await
         .unwrap_err();
     assert!(
@@ -893,6 +905,7 @@ async fn test_dev_inspect_gas_price() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap_err();
@@ -947,12 +960,272 @@ async fn test_dev_inspect_uses_unbound_object() {
             None,
             None,
             None,
+            None,
         )
         .await;
     let Err(err) = result else { panic!() };
     assert!(err.to_string().contains("ObjectNotFound"));
 }
 
+#[tokio::test]
+async fn test_dev_inspect_override_object_contents() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (validator, fullnode, object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![(sender, gas_object_id)]).await;
+
+    let effects = call_move_(
+        &validator,
+        Some(&fullnode),
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &object_basics.0,
+        "object_basics",
+        "create",
+        vec![],
+        vec![
+            TestCallArg::Pure(bcs::to_bytes(&16_u64).unwrap()),
+            TestCallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+        ],
+        false,
+    )
+    .await
+    .unwrap();
+    let object_ref = effects.created()[0].0;
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                object_basics.0,
+                Identifier::new("object_basics").unwrap(),
+                Identifier::new("borrow_value").unwrap(),
+                vec![],
+                vec![CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref))],
+            )
+            .unwrap();
+        builder.finish()
+    };
+    let kind = TransactionKind::programmable(pt);
+    let mut contents = object_ref.0.to_vec();
+    contents.extend(bcs::to_bytes(&42_u64).unwrap());
+    let state_overrides = StateOverrides {
+        objects: vec![ObjectContentsOverride {
+            object_id: object_ref.0,
+            bcs_bytes: contents,
+        }],
+        ..Default::default()
+    };
+    let mut results = fullnode
+        .dev_inspect_transaction_block(
+            sender,
+            kind,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(state_overrides),
+        )
+        .await
+        .unwrap()
+        .results
+        .unwrap();
+    let (value, _) = results.pop().unwrap().return_values.pop().unwrap();
+    assert_eq!(bcs::from_bytes::<u64>(&value).unwrap(), 42);
+
+    // the override is never written
+    let object = fullnode.get_object(&object_ref.0).await.unwrap().unwrap();
+    assert_eq!(object.compute_object_reference(), object_ref);
+}
+
+#[tokio::test]
+async fn test_dev_inspect_override_gas_balance() {
+    let (_, fullnode, _object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![]).await;
+    let epoch_store = fullnode.epoch_store_for_testing();
+    let protocol_config = epoch_store.protocol_config();
+
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let amount = 500;
+    let extra_balance = 1_000;
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_sui(vec![recipient], vec![amount]).unwrap();
+        builder.finish()
+    };
+    let kind = TransactionKind::programmable(pt);
+    let state_overrides = StateOverrides {
+        extra_sender_balance: Some(BigInt::from(extra_balance)),
+        ..Default::default()
+    };
+    let results = fullnode
+        .dev_inspect_transaction_block(
+            sender,
+            kind,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(state_overrides),
+        )
+        .await
+        .unwrap()
+        .results
+        .unwrap();
+    let (arg, arg_value, arg_type) = &results[0].mutable_reference_outputs[0];
+    assert_eq!(arg, &SuiArgument::GasCoin);
+    check_coin_value(
+        arg_value,
+        arg_type,
+        DEV_INSPECT_GAS_COIN_VALUE + extra_balance - protocol_config.max_tx_gas() - amount,
+    );
+}
+
+#[tokio::test]
+async fn test_dev_inspect_override_clock() {
+    let (_, fullnode, _object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![]).await;
+    let sender = SuiAddress::random_for_testing_only();
+
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("clock").unwrap(),
+                Identifier::new("timestamp_ms").unwrap(),
+                vec![],
+                vec![CallArg::CLOCK_IMM],
+            )
+            .unwrap();
+        builder.finish()
+    };
+    let kind = TransactionKind::programmable(pt);
+    let timestamp_ms = 1_700_000_000_000_u64;
+    let state_overrides = StateOverrides {
+        clock_timestamp_ms: Some(BigInt::from(timestamp_ms)),
+        ..Default::default()
+    };
+    let mut results = fullnode
+        .dev_inspect_transaction_block(
+            sender,
+            kind,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(state_overrides),
+        )
+        .await
+        .unwrap()
+        .results
+        .unwrap();
+    let (value, _) = results.pop().unwrap().return_values.pop().unwrap();
+    assert_eq!(bcs::from_bytes::<u64>(&value).unwrap(), timestamp_ms);
+}
+
+#[tokio::test]
+async fn test_dev_inspect_invalid_state_overrides() {
+    let (_, fullnode, _object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![]).await;
+    let sender = SuiAddress::random_for_testing_only();
+    let sponsor = SuiAddress::random_for_testing_only();
+    let kind = TransactionKind::programmable({
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay_sui(vec![SuiAddress::random_for_testing_only()], vec![500])
+            .unwrap();
+        builder.finish()
+    });
+
+    // an object that is not an input of the transaction
+    let unknown_id = ObjectID::random();
+    let mut contents = unknown_id.to_vec();
+    contents.extend(bcs::to_bytes(&0_u64).unwrap());
+    let err = fullnode
+        .dev_inspect_transaction_block(
+            sender,
+            kind.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(StateOverrides {
+                objects: vec![ObjectContentsOverride {
+                    object_id: unknown_id,
+                    bcs_bytes: contents,
+                }],
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains(&format!(
+            "Object {unknown_id} is overridden but is not an input"
+        )),
+        "{err}"
+    );
+
+    // the gas is paid by a sponsor, not by the sender
+    let err = fullnode
+        .dev_inspect_transaction_block(
+            sender,
+            kind.clone(),
+            None,
+            None,
+            Some(sponsor),
+            None,
+            None,
+            None,
+            Some(StateOverrides {
+                extra_sender_balance: Some(BigInt::from(1_000)),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("can only be simulated if the sender pays for gas"),
+        "{err}"
+    );
+
+    // the clock is not an input of the transaction
+    let err = fullnode
+        .dev_inspect_transaction_block(
+            sender,
+            kind,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(StateOverrides {
+                clock_timestamp_ms: Some(BigInt::from(0)),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("the Clock is not an input of the transaction"),
+        "{err}"
+    );
+}
+
 #[tokio::test]
 async fn test_dev_inspect_on_validator() {
     let (sender, _sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1088,7 +1361,7 @@ async fn test_dry_run_dev_inspect_dynamic_field_too_new() {
     let rgp = fullnode.reference_gas_price_for_testing().unwrap();
     // dev inspect
     let DevInspectResults { effects, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(rgp), None, None, None, None, None)
+        .dev_inspect_transaction_block(sender, kind, Some(rgp), None, None, None, None, None, None)
         .await
         .unwrap();
     assert_eq!(effects.deleted().len(), 0);
@@ -1141,7 +1414,17 @@ async fn test_dry_run_dev_inspect_max_gas_version() {
     let kind = TransactionKind::programmable(pt.clone());
     // dev inspect
     let DevInspectResults { effects, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(rgp + 100), None, None, None, None, None)
+        .dev_inspect_transaction_block(
+            sender,
+            kind,
+            Some(rgp + 100),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
         .await
         .unwrap();
     assert_eq!(effects.status(), &SuiExecutionStatus::Success);
@@ -4546,7 +4829,7 @@ pub async fn call_dev_inspect(
     let kind = TransactionKind::programmable(builder.finish());
     let rgp = authority.reference_gas_price_for_testing().unwrap();
     authority
-        .dev_inspect_transaction_block(*sender, kind, Some(rgp), None, None, None, None, None)
+        .dev_inspect_transaction_block(*sender, kind, Some(rgp), None, None, None, None, None, None)
         .await
 }
 
@@ -5338,6 +5621,7 @@ async fn test_for_inc_201_dev_inspect() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -6092,3 +6376,138 @@ async fn test_consensus_handler_congestion_control_transaction_cancellation() {
     let congested_objects = input_objects.get_congested_objects().unwrap();
     assert_eq!(congested_objects, vec![shared_objects[0].id()]);
 }
+
+// Tests transactions that are sequenced by consensus after they expire:
+//   1. Transactions that only use owned objects are still scheduled.
+//   2. Transactions that use shared objects are cancelled, and their owned objects can be used
+//      again.
+#[tokio::test]
+async fn test_consensus_handler_expired_transaction_cancellation() {
+    let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+
+    let shared_objects = create_shared_objects(1);
+    let gas_objects = create_gas_objects(3, sender);
+    let owned_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+
+    let authority = TestAuthorityBuilder::new().build().await;
+    let mut genesis_objects = gas_objects.clone();
+    genesis_objects.extend(shared_objects.clone());
+    genesis_objects.push(owned_object.clone());
+    authority.insert_genesis_objects(&genesis_objects).await;
+    let rgp = authority.reference_gas_price_for_testing().unwrap();
+
+    // Both transactions are signed before they expire, and sequenced after.
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let expiration = TransactionExpiration::Timestamp(now_ms + 60 * 60 * 1000);
+    let commit_timestamp = now_ms + 2 * 60 * 60 * 1000;
+
+    let owned_txn = TransactionData::new_transfer_sui(
+        recipient,
+        sender,
+        Some(1),
+        gas_objects[0].compute_object_reference(),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        rgp,
+    )
+    .with_expiration(expiration.clone());
+    let owned_txn = certify_transaction(
+        &authority,
+        to_sender_signed_transaction(owned_txn, &keypair),
+    )
+    .await
+    .unwrap();
+
+    let shared_txn = TransactionData::new_move_call(
+        sender,
+        SUI_FRAMEWORK_PACKAGE_ID,
+        ident_str!("object_basics").to_owned(),
+        ident_str!("set_value").to_owned(),
+        /* type_args */ vec![],
+        gas_objects[1].compute_object_reference(),
+        vec![
+            CallArg::Object(ObjectArg::SharedObject {
+                id: shared_objects[0].id(),
+                initial_shared_version: OBJECT_START_VERSION,
+                mutable: true,
+            }),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                owned_object.compute_object_reference(),
+            )),
+        ],
+        TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        rgp,
+    )
+    .unwrap()
+    .with_expiration(expiration);
+    let shared_txn = certify_transaction(
+        &authority,
+        to_sender_signed_transaction(shared_txn, &keypair),
+    )
+    .await
+    .unwrap();
+
+    // Both transactions are scheduled, but the shared object transaction is cancelled.
+    let scheduled_txns = send_batch_consensus_no_execution_at_timestamp(
+        &authority,
+        &[owned_txn.clone(), shared_txn.clone()],
+        commit_timestamp,
+    )
+    .await;
+    assert_eq!(scheduled_txns.len(), 2);
+
+    let shared_locks = authority
+        .epoch_store_for_testing()
+        .get_shared_locks(&shared_txn.key())
+        .expect("Reading shared locks should not fail");
+    assert_eq!(
+        shared_locks,
+        vec![(shared_objects[0].id(), SequenceNumber::EXPIRED)]
+    );
+
+    let (effects, _) = authority.try_execute_for_test(&owned_txn).await.unwrap();
+    assert!(effects.status().is_ok());
+
+    let (effects, _) = authority.try_execute_for_test(&shared_txn).await.unwrap();
+    assert_eq!(
+        effects.status(),
+        &ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::ExecutionCancelledDueToExpiration,
+            command: None,
+        }
+    );
+
+    // The shared object is untouched, and the owned object can be used by another transaction.
+    let shared_object = authority
+        .get_object(&shared_objects[0].id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(shared_object.version(), OBJECT_START_VERSION);
+
+    let owned_object_ref = authority
+        .get_object(&owned_object.id())
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+    assert!(owned_object_ref.1 > owned_object.version());
+
+    let transfer = TransactionData::new_transfer(
+        recipient,
+        owned_object_ref,
+        sender,
+        gas_objects[2].compute_object_reference(),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        rgp,
+    );
+    let transfer =
+        certify_transaction(&authority, to_sender_signed_transaction(transfer, &keypair))
+            .await
+            .unwrap();
+    let (effects, _) = authority.try_execute_for_test(&transfer).await.unwrap();
+    assert!(effects.status().is_ok());
+}
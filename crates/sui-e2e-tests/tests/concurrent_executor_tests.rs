@@ -0,0 +1,195 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+
+use move_core_types::ident_str;
+use move_core_types::language_storage::TypeTag;
+use sui_json_rpc_types::{SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions};
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
+use sui_macros::sim_test;
+use sui_sdk::concurrent_executor::{ConcurrentExecutor, ConcurrentExecutorConfig};
+use sui_types::base_types::SuiAddress;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, ProgrammableTransaction};
+use sui_types::MOVE_STDLIB_PACKAGE_ID;
+use test_cluster::{TestCluster, TestClusterBuilder};
+
+const GAS_BUDGET: u64 = 50_000_000;
+
+/// Create an executor for a fresh address, funded with 10 SUI, and a pool of two coins.
+async fn setup() -> (TestCluster, ConcurrentExecutor) {
+    let cluster = TestClusterBuilder::new().build().await;
+    let keystore = InMemKeystore::new_insecure_for_tests(1);
+    let sender = keystore.addresses()[0];
+
+    let rgp = cluster.get_reference_gas_price().await;
+    cluster
+        .fund_address_and_return_gas(rgp, Some(10_000_000_000), sender)
+        .await;
+
+    let config = ConcurrentExecutorConfig {
+        pool_size: 2,
+        min_coin_balance: 100_000_000,
+        target_coin_balance: 1_000_000_000,
+        ..Default::default()
+    };
+
+    let executor = ConcurrentExecutor::new(
+        cluster.sui_client().clone(),
+        Keystore::InMem(keystore),
+        sender,
+        config,
+    )
+    .await
+    .unwrap();
+
+    (cluster, executor)
+}
+
+/// Execute `pt`, and check that the balance the executor tracks for its pool matches the balance
+/// of the sender's coins on chain.
+async fn execute_and_check_balance(
+    cluster: &TestCluster,
+    executor: &ConcurrentExecutor,
+    pt: ProgrammableTransaction,
+) {
+    let response = executor.execute(pt, GAS_BUDGET).await.unwrap();
+    assert!(response.effects.unwrap().status().is_ok());
+
+    // Wait for the fullnode to execute the transaction before reading the sender's coins.
+    let client = cluster.sui_client();
+    let read_api = client.read_api();
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while read_api
+            .get_transaction_with_options(
+                response.digest,
+                SuiTransactionBlockResponseOptions::new(),
+            )
+            .await
+            .is_err()
+        {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let balance = client
+        .coin_read_api()
+        .get_balance(executor.sender(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(executor.pool_size(), 2);
+    assert_eq!(executor.available_balance() as u128, balance.total_balance);
+}
+
+#[sim_test]
+async fn test_gas_only_transaction() {
+    let (cluster, executor) = setup().await;
+
+    // A transaction that only uses the gas coin to pay for gas.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.programmable_move_call(
+        MOVE_STDLIB_PACKAGE_ID,
+        ident_str!("option").to_owned(),
+        ident_str!("none").to_owned(),
+        vec![TypeTag::U64],
+        vec![],
+    );
+
+    let before = executor.available_balance();
+    execute_and_check_balance(&cluster, &executor, builder.finish()).await;
+    assert!(executor.available_balance() < before);
+}
+
+#[sim_test]
+async fn test_pay_from_gas_coin() {
+    let (cluster, executor) = setup().await;
+
+    // Paying from the gas coin changes its balance by more than the cost of gas.
+    let recipient = SuiAddress::random_for_testing_only();
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pay_sui(vec![recipient], vec![500_000_000]).unwrap();
+
+    let before = executor.available_balance();
+    execute_and_check_balance(&cluster, &executor, builder.finish()).await;
+    assert!(executor.available_balance() < before - 500_000_000);
+
+    // The coin can be used again at its new balance.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(recipient, Some(1_000));
+    execute_and_check_balance(&cluster, &executor, builder.finish()).await;
+}
+
+#[sim_test]
+async fn test_concurrent_shared_owned_input() {
+    let (_cluster, executor) = setup().await;
+    let executor = Arc::new(executor);
+
+    // Create a coin outside the pool, for transactions to share as an owned input.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .pay_sui(vec![executor.sender()], vec![1_000_000_000])
+        .unwrap();
+    let response = executor
+        .execute(builder.finish(), GAS_BUDGET)
+        .await
+        .unwrap();
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    let coin = effects.created()[0].reference.to_object_ref();
+
+    // Every transaction refers to the coin at the version it was created at, so each one relies
+    // on the executor to serialize it behind the others and bump the input to its latest version.
+    let recipient = SuiAddress::random_for_testing_only();
+    let responses = join_all((0..6).map(|_| {
+        let executor = executor.clone();
+        async move {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder
+                .pay(vec![coin], vec![recipient], vec![1_000])
+                .unwrap();
+            executor.execute(builder.finish(), GAS_BUDGET).await
+        }
+    }))
+    .await;
+
+    for response in responses {
+        assert!(response.unwrap().effects.unwrap().status().is_ok());
+    }
+
+    let latest = executor.object_ref(coin.0).unwrap();
+    assert!(latest.1 > coin.1);
+    assert_eq!(executor.pool_size(), 2);
+}
+
+#[sim_test]
+async fn test_transfer_gas_coin() {
+    let (_cluster, executor) = setup().await;
+
+    // Giving the gas coin away drops it from the pool, rather than returning it.
+    let recipient = SuiAddress::random_for_testing_only();
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_arg(recipient, Argument::GasCoin);
+    let response = executor
+        .execute(builder.finish(), GAS_BUDGET)
+        .await
+        .unwrap();
+    assert!(response.effects.unwrap().status().is_ok());
+    assert_eq!(executor.pool_size(), 1);
+
+    // The remaining coin can still pay for transactions.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(recipient, Some(1_000));
+    let response = executor
+        .execute(builder.finish(), GAS_BUDGET)
+        .await
+        .unwrap();
+    assert!(response.effects.unwrap().status().is_ok());
+    assert_eq!(executor.pool_size(), 1);
+}
@@ -5,7 +5,7 @@ use futures::future::join_all;
 use rand::rngs::OsRng;
 use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sui_core::authority::epoch_start_configuration::EpochFlag;
 use sui_core::consensus_adapter::position_submit_certificate;
 use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
@@ -115,6 +115,71 @@ async fn test_transaction_expiration() {
         .unwrap();
 }
 
+#[sim_test]
+async fn test_transaction_expiration_window() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let (sender, gas) = test_cluster
+        .wallet
+        .get_one_gas_object()
+        .await
+        .unwrap()
+        .unwrap();
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let data = TestTransactionBuilder::new(sender, gas, rgp)
+        .transfer_sui(Some(1), sender)
+        .build();
+
+    // Transaction past its expiration timestamp returns an error
+    let expired_data = data
+        .clone()
+        .with_expiration(TransactionExpiration::Timestamp(0));
+    let expired_transaction = test_cluster.wallet.sign_transaction(&expired_data);
+    let result = test_cluster
+        .wallet
+        .execute_transaction_may_fail(expired_transaction)
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains(&SuiError::TransactionExpired.to_string()));
+
+    // Transaction before its window returns an error
+    let early_data = data.clone().with_expiration(TransactionExpiration::ValidDuring {
+        min_epoch: Some(10),
+        max_epoch: None,
+        min_timestamp_ms: None,
+        max_timestamp_ms: None,
+    });
+    let early_transaction = test_cluster.wallet.sign_transaction(&early_data);
+    let result = test_cluster
+        .wallet
+        .execute_transaction_may_fail(early_transaction)
+        .await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Transaction is not yet valid"));
+
+    // Transaction within its window signed without issue
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let data = data.with_expiration(TransactionExpiration::ValidDuring {
+        min_epoch: Some(0),
+        max_epoch: Some(10),
+        min_timestamp_ms: None,
+        max_timestamp_ms: Some(now_ms + 60 * 60 * 1000),
+    });
+    let transaction = test_cluster.wallet.sign_transaction(&data);
+    test_cluster
+        .wallet
+        .execute_transaction_may_fail(transaction)
+        .await
+        .unwrap();
+}
+
 // TODO: This test does not guarantee that tx would be reverted, and hence the code path
 // may not always be tested.
 #[sim_test]
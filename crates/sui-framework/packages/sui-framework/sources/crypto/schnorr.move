@@ -0,0 +1,22 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Schnorr signatures over secp256k1, as specified in BIP-340:
+/// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+module sui::schnorr {
+
+    #[allow(unused_const)]
+    /// Error if BIP-340 signature verification is not enabled by the protocol.
+    const ENotSupported: u64 = 0;
+
+    /// @param signature: A 64-byte BIP-340 signature, the x coordinate of the nonce point R
+    /// followed by the scalar s.
+    /// @param public_key: A 32-byte x-only public key, the x coordinate of a point on the
+    /// secp256k1 curve with an even y coordinate.
+    /// @param msg: The 32-byte message that the signature is signed against, usually a tagged hash
+    /// of the data being signed. Messages of other lengths are rejected.
+    ///
+    /// If the signature is a valid BIP-340 signature of the message and public key, return true.
+    /// Otherwise, return false.
+    public native fun bip340_verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;
+}
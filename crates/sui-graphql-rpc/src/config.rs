@@ -7,6 +7,7 @@ use async_graphql::*;
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use sui_indexer::index_filter::IndexFilterConfig;
 use sui_json_rpc::name_service::NameServiceConfig;
 // TODO: calculate proper cost limits
 
@@ -108,6 +109,9 @@ pub struct ServiceConfig {
 
     #[serde(default)]
     pub(crate) zklogin: ZkLoginConfig,
+
+    #[serde(default)]
+    pub(crate) index_filter: IndexFilterConfig,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -538,6 +542,7 @@ impl Default for BackgroundTasksConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sui_types::base_types::ObjectID;
 
     #[test]
     fn test_read_empty_service_config() {
@@ -626,6 +631,28 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_index_filter_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [index-filter]
+                packages = ["0x0000000000000000000000000000000000000000000000000000000000000042"]
+                move-types = ["0x2::kiosk::Kiosk"]
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            index_filter: IndexFilterConfig {
+                packages: vec![ObjectID::from_hex_literal("0x42").unwrap()],
+                move_types: vec!["0x2::kiosk::Kiosk".to_string()],
+                addresses: vec![],
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
     #[test]
     fn test_read_everything_in_service_config() {
         let actual = ServiceConfig::read(
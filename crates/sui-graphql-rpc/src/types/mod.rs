@@ -40,6 +40,7 @@ pub(crate) mod query;
 pub(crate) mod safe_mode;
 pub(crate) mod stake;
 pub(crate) mod stake_subsidy;
+pub(crate) mod state_overrides;
 pub(crate) mod storage_fund;
 pub(crate) mod string_input;
 pub(crate) mod sui_address;
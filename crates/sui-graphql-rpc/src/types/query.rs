@@ -7,6 +7,7 @@ use async_graphql::{connection::Connection, *};
 use fastcrypto::encoding::{Base64, Encoding};
 use move_core_types::account_address::AccountAddress;
 use serde::de::DeserializeOwned;
+use sui_indexer::errors::IndexerError;
 use sui_json_rpc_types::DevInspectArgs;
 use sui_sdk::SuiClient;
 use sui_types::transaction::{TransactionData, TransactionKind};
@@ -29,6 +30,7 @@ use super::{
     object::{self, Object, ObjectFilter},
     owner::Owner,
     protocol_config::ProtocolConfigs,
+    state_overrides::StateOverrides,
     sui_address::SuiAddress,
     suins_registration::Domain,
     transaction_block::{self, TransactionBlock, TransactionBlockFilter},
@@ -90,12 +92,18 @@ impl Query {
     ///     checks that prevent access to objects that are owned by
     ///     addresses other than the sender, and calling non-public,
     ///     non-entry functions, and some other checks.  Defaults to false.
+    ///
+    /// `stateOverrides` optional changes to the state the transaction is
+    ///     run against (the contents of its input objects, the balance of
+    ///     its gas coin and the clock's timestamp), to preview what it
+    ///     would do in different circumstances.
     async fn dry_run_transaction_block(
         &self,
         ctx: &Context<'_>,
         tx_bytes: String,
         tx_meta: Option<TransactionMetadata>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
     ) -> Result<DryRunResult> {
         let skip_checks = skip_checks.unwrap_or(false);
 
@@ -159,6 +167,7 @@ impl Query {
             gas_objects,
             show_raw_txn_data_and_effects: Some(true),
             skip_checks: Some(skip_checks),
+            state_overrides: state_overrides.map(Into::into),
         };
 
         let res = sui_sdk_client
@@ -193,14 +202,19 @@ impl Query {
     ) -> Result<Option<Object>> {
         let Watermark { checkpoint, .. } = *ctx.data()?;
 
-        match version {
+        let object = match version {
             Some(version) => Object::query(ctx, address, Object::at_version(version, checkpoint))
                 .await
-                .extend(),
+                .extend()?,
             None => Object::query(ctx, address, Object::latest_at(checkpoint))
                 .await
-                .extend(),
+                .extend()?,
+        };
+
+        if object.is_none() {
+            check_indexed(ctx, format!("Object {address}"))?;
         }
+        Ok(object)
     }
 
     /// Look-up an Account by its SuiAddress.
@@ -249,9 +263,14 @@ impl Query {
         digest: Digest,
     ) -> Result<Option<TransactionBlock>> {
         let Watermark { checkpoint, .. } = *ctx.data()?;
-        TransactionBlock::query(ctx, digest, checkpoint)
+        let transaction = TransactionBlock::query(ctx, digest, checkpoint)
             .await
-            .extend()
+            .extend()?;
+
+        if transaction.is_none() {
+            check_indexed(ctx, format!("Transaction {digest}"))?;
+        }
+        Ok(transaction)
     }
 
     /// The coin objects that exist in the network.
@@ -439,6 +458,20 @@ impl Query {
     }
 }
 
+/// Lookups that come up empty are errors if the service reads from an indexer that filters what
+/// it indexes, because the data may well exist without having been indexed.
+fn check_indexed(ctx: &Context<'_>, what: String) -> Result<()> {
+    let cfg: &ServiceConfig = ctx
+        .data()
+        .map_err(|_| Error::Internal("Unable to fetch service configuration.".to_string()))
+        .extend()?;
+    if cfg.index_filter.is_empty() {
+        return Ok(());
+    }
+
+    Err(Error::Client(IndexerError::NotIndexedError(what).to_string()).extend())
+}
+
 fn deserialize_tx_data<T>(tx_bytes: &str) -> Result<T>
 where
     T: DeserializeOwned,
@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::base64::Base64;
+use super::sui_address::SuiAddress;
+use async_graphql::*;
+use sui_json_rpc_types::{
+    ObjectContentsOverride as NativeObjectContentsOverride, StateOverrides as NativeStateOverrides,
+};
+
+/// Changes to the state a transaction dry run is executed against, to preview what the
+/// transaction would do in different circumstances. They only affect the dry run, and are never
+/// written to the chain. Overrides can be combined with `skipChecks`, and signatures are never
+/// checked during a dry run.
+#[derive(Clone, Debug, PartialEq, Eq, InputObject)]
+pub(crate) struct StateOverrides {
+    /// Replacement contents for input objects of the transaction.
+    pub objects: Option<Vec<ObjectContentsOverride>>,
+    /// SUI (in MIST) to add to the gas coin, e.g. to account for a pending deposit. Only
+    /// supported if the sender pays for gas.
+    pub extra_sender_balance: Option<u64>,
+    /// The timestamp to set on the `Clock` object, which must be an input of the transaction.
+    pub clock_timestamp_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, InputObject)]
+pub(crate) struct ObjectContentsOverride {
+    /// ID of the object, which must be an input of the transaction.
+    pub address: SuiAddress,
+    /// BCS bytes of the new contents of the object, which must keep the object's type and ID.
+    pub bcs: Base64,
+}
+
+impl From<StateOverrides> for NativeStateOverrides {
+    fn from(overrides: StateOverrides) -> Self {
+        NativeStateOverrides {
+            objects: overrides
+                .objects
+                .unwrap_or_default()
+                .into_iter()
+                .map(|o| NativeObjectContentsOverride {
+                    object_id: o.address.into(),
+                    bcs_bytes: o.bcs.0,
+                })
+                .collect(),
+            extra_sender_balance: overrides.extra_sender_balance.map(|b| b.into()),
+            clock_timestamp_ms: overrides.clock_timestamp_ms.map(|t| t.into()),
+        }
+    }
+}
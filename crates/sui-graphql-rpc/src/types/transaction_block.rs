@@ -41,6 +41,7 @@ use super::{
     address::Address,
     base64::Base64,
     cursor::{self, Page, Paginated, Target},
+    date_time::DateTime,
     digest::Digest,
     epoch::Epoch,
     gas::GasInput,
@@ -209,7 +210,12 @@ impl TransactionBlock {
     /// deadline after which validators will no longer consider the transaction valid. By default,
     /// there is no deadline for when a transaction must execute.
     async fn expiration(&self, ctx: &Context<'_>) -> Result<Option<Epoch>> {
-        let TransactionExpiration::Epoch(id) = self.native().expiration() else {
+        let (TransactionExpiration::Epoch(id)
+        | TransactionExpiration::ValidDuring {
+            max_epoch: Some(id),
+            ..
+        }) = self.native().expiration()
+        else {
             return Ok(None);
         };
 
@@ -218,6 +224,22 @@ impl TransactionBlock {
             .extend()
     }
 
+    /// This field is set by senders of a transaction block. It is a timestamp that sets a deadline
+    /// after which validators will no longer sign the transaction, and consensus will no longer
+    /// schedule it. By default, there is no deadline for when a transaction must execute.
+    async fn expiration_timestamp(&self) -> Result<Option<DateTime>> {
+        let (TransactionExpiration::Timestamp(ms)
+        | TransactionExpiration::ValidDuring {
+            max_timestamp_ms: Some(ms),
+            ..
+        }) = self.native().expiration()
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(DateTime::from_ms(*ms as i64).extend()?))
+    }
+
     /// Serialized form of this transaction's `SenderSignedData`, BCS serialized and Base64 encoded.
     async fn bcs(&self) -> Option<Base64> {
         match &self.inner {
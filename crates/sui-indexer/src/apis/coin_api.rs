@@ -1,11 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::IndexerError;
 use crate::indexer_reader::IndexerReader;
 use async_trait::async_trait;
 use diesel::r2d2::R2D2Connection;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
+use move_core_types::language_storage::TypeTag;
 use sui_json_rpc::coin_api::{parse_to_struct_tag, parse_to_type_tag};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_api::{cap_page_limit, CoinReadApiServer};
@@ -14,6 +16,7 @@ use sui_open_rpc::Module;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::gas_coin::{GAS, TOTAL_SUPPLY_MIST};
+use sui_types::sui_serde::BigInt;
 
 pub(crate) struct CoinReadApi<T: R2D2Connection + 'static> {
     inner: IndexerReader<T>,
@@ -23,6 +26,26 @@ impl<T: R2D2Connection> CoinReadApi<T> {
     pub fn new(inner: IndexerReader<T>) -> Self {
         Self { inner }
     }
+
+    /// Coin queries are scoped by owner, so they would silently return partial results if the
+    /// indexer has left some of the owner's coins out.
+    fn check_coins_indexed(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<&TypeTag>,
+    ) -> Result<(), IndexerError> {
+        if self
+            .inner
+            .index_filter()
+            .are_coins_indexed(&owner, coin_type)
+        {
+            Ok(())
+        } else {
+            Err(IndexerError::NotIndexedError(format!(
+                "Coins owned by {owner}"
+            )))
+        }
+    }
 }
 
 #[async_trait]
@@ -33,6 +56,7 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         coin_type: Option<String>,
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<CoinPage> {
         let limit = cap_page_limit(limit);
         if limit == 0 {
@@ -40,8 +64,9 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         }
 
         // Normalize coin type tag and default to Gas
-        let coin_type =
-            parse_to_type_tag(coin_type)?.to_canonical_string(/* with_prefix */ true);
+        let coin_type_tag = parse_to_type_tag(coin_type)?;
+        self.check_coins_indexed(owner, Some(&coin_type_tag))?;
+        let coin_type = coin_type_tag.to_canonical_string(/* with_prefix */ true);
 
         let cursor = match cursor {
             Some(c) => c,
@@ -50,7 +75,13 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         };
         let mut results = self
             .inner
-            .get_owned_coins_in_blocking_task(owner, Some(coin_type), cursor, limit + 1)
+            .get_owned_coins_in_blocking_task(
+                owner,
+                Some(coin_type),
+                cursor,
+                limit + 1,
+                at_checkpoint.map(BigInt::into_inner),
+            )
             .await?;
 
         let has_next_page = results.len() > limit;
@@ -68,12 +99,15 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         owner: SuiAddress,
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<CoinPage> {
         let limit = cap_page_limit(limit);
         if limit == 0 {
             return Ok(CoinPage::empty());
         }
 
+        self.check_coins_indexed(owner, None)?;
+
         let cursor = match cursor {
             Some(c) => c,
             // If cursor is not specified, we need to start from the beginning of the coin type, which is the minimal possible ObjectID.
@@ -81,7 +115,13 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         };
         let mut results = self
             .inner
-            .get_owned_coins_in_blocking_task(owner, None, cursor, limit + 1)
+            .get_owned_coins_in_blocking_task(
+                owner,
+                None,
+                cursor,
+                limit + 1,
+                at_checkpoint.map(BigInt::into_inner),
+            )
             .await?;
 
         let has_next_page = results.len() > limit;
@@ -100,8 +140,9 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         coin_type: Option<String>,
     ) -> RpcResult<Balance> {
         // Normalize coin type tag and default to Gas
-        let coin_type =
-            parse_to_type_tag(coin_type)?.to_canonical_string(/* with_prefix */ true);
+        let coin_type_tag = parse_to_type_tag(coin_type)?;
+        self.check_coins_indexed(owner, Some(&coin_type_tag))?;
+        let coin_type = coin_type_tag.to_canonical_string(/* with_prefix */ true);
 
         let mut results = self
             .inner
@@ -114,6 +155,7 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
     }
 
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        self.check_coins_indexed(owner, None)?;
         self.inner
             .get_coin_balances_in_blocking_task(owner, None)
             .await
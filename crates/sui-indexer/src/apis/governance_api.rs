@@ -97,7 +97,11 @@ impl<T: R2D2Connection + 'static> GovernanceReadApi<T> {
         ids: Vec<ObjectID>,
     ) -> Result<Vec<DelegatedStake>, IndexerError> {
         let mut stakes = vec![];
-        for stored_object in self.inner.multi_get_objects_in_blocking_task(ids).await? {
+        for stored_object in self
+            .inner
+            .multi_get_objects_in_blocking_task(ids, None)
+            .await?
+        {
             let object = sui_types::object::Object::try_from(stored_object)?;
             let stake_object = StakedSui::try_from(&object)?;
             stakes.push(stake_object);
@@ -121,6 +125,7 @@ impl<T: R2D2Connection + 'static> GovernanceReadApi<T> {
                 None,
                 // Allow querying for up to 1000 staked objects
                 1000,
+                None,
             )
             .await?
         {
@@ -232,6 +237,7 @@ impl<T: R2D2Connection + 'static> GovernanceReadApi<T> {
                 system_state_summary.inactive_pools_id,
                 None,
                 system_state_summary.inactive_pools_size as usize,
+                None,
             )
             .await?
         {
@@ -24,6 +24,7 @@ use sui_types::dynamic_field::{DynamicFieldName, Field};
 use sui_types::error::SuiObjectResponseError;
 use sui_types::event::EventID;
 use sui_types::object::ObjectRead;
+use sui_types::sui_serde::BigInt;
 use sui_types::TypeTag;
 
 use crate::indexer_reader::IndexerReader;
@@ -48,12 +49,13 @@ impl<T: R2D2Connection + 'static> IndexerApi<T> {
         query: Option<SuiObjectResponseQuery>,
         cursor: Option<ObjectID>,
         limit: usize,
+        checkpoint: Option<u64>,
     ) -> RpcResult<ObjectsPage> {
         let SuiObjectResponseQuery { filter, options } = query.unwrap_or_default();
         let options = options.unwrap_or_default();
         let objects = self
             .inner
-            .get_owned_objects_in_blocking_task(address, filter, cursor, limit + 1)
+            .get_owned_objects_in_blocking_task(address, filter, cursor, limit + 1, checkpoint)
             .await?;
 
         let mut object_futures = vec![];
@@ -140,13 +142,20 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         query: Option<SuiObjectResponseQuery>,
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<ObjectsPage> {
         let limit = cap_page_limit(limit);
         if limit == 0 {
             return Ok(ObjectsPage::empty());
         }
-        self.get_owned_objects_internal(address, query, cursor, limit)
-            .await
+        self.get_owned_objects_internal(
+            address,
+            query,
+            cursor,
+            limit,
+            at_checkpoint.map(BigInt::into_inner),
+        )
+        .await
     }
 
     async fn query_transaction_blocks(
@@ -215,6 +224,7 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         parent_object_id: ObjectID,
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<DynamicFieldPage> {
         let limit = cap_page_limit(limit);
         if limit == 0 {
@@ -222,7 +232,12 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         }
         let mut results = self
             .inner
-            .get_dynamic_fields_in_blocking_task(parent_object_id, cursor, limit + 1)
+            .get_dynamic_fields_in_blocking_task(
+                parent_object_id,
+                cursor,
+                limit + 1,
+                at_checkpoint.map(BigInt::into_inner),
+            )
             .await?;
 
         let has_next_page = results.len() > limit;
@@ -289,7 +304,12 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         ))
     }
 
-    fn subscribe_event(&self, _sink: SubscriptionSink, _filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: EventFilter,
+        _cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
 
@@ -297,6 +317,7 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         &self,
         _sink: SubscriptionSink,
         _filter: TransactionFilter,
+        _cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
@@ -328,7 +349,7 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         // We do this as we do not know if the subdomain is a node or leaf record.
         let domains: Vec<_> = self
             .inner
-            .multi_get_objects_in_blocking_task(requests)
+            .multi_get_objects_in_blocking_task(requests, None)
             .await?
             .into_iter()
             .map(|o| sui_types::object::Object::try_from(o).ok())
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use diesel::r2d2::R2D2Connection;
 use jsonrpsee::core::RpcResult;
@@ -60,22 +62,53 @@ impl<T: R2D2Connection + 'static> ReadApi<T> {
         let genesis_checkpoint = self.get_checkpoint(CheckpointId::SequenceNumber(0)).await?;
         Ok(ChainIdentifier::from(genesis_checkpoint.digest))
     }
-}
 
-#[async_trait]
-impl<T: R2D2Connection + 'static> ReadApiServer for ReadApi<T> {
-    async fn get_object(
+    /// Reads the objects as of `checkpoint`, in the order they were requested. Objects that did
+    /// not exist or were deleted or wrapped at `checkpoint` are reported as not existing.
+    async fn multi_get_objects_at_checkpoint(
         &self,
-        object_id: ObjectID,
+        object_ids: Vec<ObjectID>,
         options: Option<SuiObjectDataOptions>,
-    ) -> RpcResult<SuiObjectResponse> {
+        checkpoint: u64,
+    ) -> RpcResult<Vec<SuiObjectResponse>> {
         let options = options.unwrap_or_default();
-        let object_read = self
+        let stored_objects = self
             .inner
-            .get_object_read_in_blocking_task(object_id)
+            .multi_get_objects_in_blocking_task(object_ids.clone(), Some(checkpoint))
             .await?;
 
+        let mut object_reads = HashMap::new();
+        for stored_object in stored_objects {
+            let object_read = stored_object
+                .try_into_object_read(self.inner.package_resolver())
+                .await?;
+            object_reads.insert(object_read.object_id(), object_read);
+        }
+
+        let mut responses = vec![];
+        for object_id in object_ids {
+            let object_read = object_reads
+                .remove(&object_id)
+                .unwrap_or(ObjectRead::NotExists(object_id));
+            responses.push(
+                self.object_read_to_response(object_read, options.clone())
+                    .await?,
+            );
+        }
+        Ok(responses)
+    }
+
+    async fn object_read_to_response(
+        &self,
+        object_read: ObjectRead,
+        options: SuiObjectDataOptions,
+    ) -> RpcResult<SuiObjectResponse> {
         match object_read {
+            ObjectRead::NotExists(id) if !self.inner.index_filter().is_empty() => Err(self
+                .inner
+                .index_filter()
+                .not_found_error(format!("Object {id}"))
+                .into()),
             ObjectRead::NotExists(id) => Ok(SuiObjectResponse::new_with_error(
                 SuiObjectResponseError::NotExists { object_id: id },
             )),
@@ -107,6 +140,23 @@ impl<T: R2D2Connection + 'static> ReadApiServer for ReadApi<T> {
             ),
         }
     }
+}
+
+#[async_trait]
+impl<T: R2D2Connection + 'static> ReadApiServer for ReadApi<T> {
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse> {
+        let options = options.unwrap_or_default();
+        let object_read = self
+            .inner
+            .get_object_read_in_blocking_task(object_id)
+            .await?;
+
+        self.object_read_to_response(object_read, options).await
+    }
 
     // For ease of implementation we just forward to the single object query, although in the
     // future we may want to improve the performance by having a more naitive multi_get
@@ -115,6 +165,7 @@ impl<T: R2D2Connection + 'static> ReadApiServer for ReadApi<T> {
         &self,
         object_ids: Vec<ObjectID>,
         options: Option<SuiObjectDataOptions>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<Vec<SuiObjectResponse>> {
         if object_ids.len() > *QUERY_MAX_RESULT_LIMIT {
             return Err(
@@ -122,6 +173,12 @@ impl<T: R2D2Connection + 'static> ReadApiServer for ReadApi<T> {
             );
         }
 
+        if let Some(checkpoint) = at_checkpoint {
+            return self
+                .multi_get_objects_at_checkpoint(object_ids, options, checkpoint.into_inner())
+                .await;
+        }
+
         let mut futures = vec![];
         for object_id in object_ids {
             futures.push(self.get_object(object_id, options.clone()));
@@ -148,7 +205,9 @@ impl<T: R2D2Connection + 'static> ReadApiServer for ReadApi<T> {
             .await?;
 
         let txn = txn.pop().ok_or_else(|| {
-            IndexerError::InvalidArgumentError(format!("Transaction {digest} not found"))
+            self.inner
+                .index_filter()
+                .not_found_error(format!("Transaction {digest}"))
         })?;
 
         Ok(txn)
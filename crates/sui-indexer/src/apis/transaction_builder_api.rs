@@ -37,6 +37,7 @@ impl<T: R2D2Connection> DataReader for TransactionBuilderApi<T> {
                 Some(SuiObjectDataFilter::StructType(object_type)),
                 None,
                 50, // Limit the number of objects returned to 50
+                None,
             )
             .await?;
 
@@ -82,6 +82,9 @@ pub enum IndexerError {
     #[error("Indexer does not support the feature with error: `{0}`")]
     NotSupportedError(String),
 
+    #[error("{0} not indexed: this indexer only indexes data matching its index filter")]
+    NotIndexedError(String),
+
     #[error("Indexer read corrupted/incompatible data from persistent storage: `{0}`")]
     PersistentStorageDataCorruptionError(String),
 
@@ -0,0 +1,388 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use diesel::r2d2::R2D2Connection;
+use futures::{StreamExt, TryStreamExt};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use sui_package_resolver::{PackageStore, PackageStoreWithLruCache, Resolver};
+use sui_rest_api::Client;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::errors::IndexerError;
+use crate::index_filter::IndexFilter;
+use crate::metrics::IndexerMetrics;
+use crate::store::package_resolver::RemotePackageResolver;
+use crate::store::IndexerStore;
+use crate::types::IndexerResult;
+
+use super::checkpoint_handler::CheckpointHandler;
+
+// Number of checkpoints each worker downloads and indexes concurrently.
+const BACKFILL_DOWNLOAD_CONCURRENCY: usize = 20;
+// Number of checkpoints of objects history folded into `objects` and `objects_snapshot` at a time,
+// once backfill is done.
+const BACKFILL_OBJECTS_WINDOW: u64 = 10000;
+
+#[derive(Clone, Debug)]
+pub struct BackfillConfig {
+    /// First checkpoint to backfill (inclusive).
+    pub start_checkpoint: CheckpointSequenceNumber,
+    /// Last checkpoint to backfill (inclusive).
+    pub end_checkpoint: CheckpointSequenceNumber,
+    /// Number of workers that the range is split across.
+    pub workers: usize,
+    /// Number of checkpoints each worker commits in one DB transaction.
+    pub batch_size: usize,
+    /// Applied to every checkpoint before it is written, as it is when tailing.
+    pub index_filter: Arc<IndexFilter>,
+}
+
+impl BackfillConfig {
+    fn validate(&self) -> IndexerResult<()> {
+        if self.workers == 0 {
+            return Err(IndexerError::InvalidArgumentError(
+                "Backfill needs at least one worker".to_string(),
+            ));
+        }
+
+        if self.batch_size == 0 {
+            return Err(IndexerError::InvalidArgumentError(
+                "Backfill batch size must be at least one checkpoint".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Index `[start_checkpoint, end_checkpoint]` by splitting it across many workers, each writing
+/// its checkpoints with batched `INSERT`s, rather than in order through the committer. Tables are
+/// not written with `COPY`, which the version of diesel in use does not support, but each batch
+/// of checkpoints is written in one DB transaction with multi-row `INSERT`s. Once every
+/// checkpoint in the range is committed, `objects` and `objects_snapshot` are brought up to
+/// `end_checkpoint` from `objects_history`, so that the indexer can carry on tailing from there.
+///
+/// Backfill can be interrupted and re-run with the same `end_checkpoint`: checkpoints are written
+/// atomically with their data, committed checkpoints are skipped, and the final steps are
+/// idempotent.
+///
+/// Epochs are written up-front, in order, and table partitions are advanced for them, because
+/// workers write checkpoints from many epochs at once. If the store prunes partitions, the range
+/// should not start before the retention period.
+pub async fn run_backfill<S, T>(
+    config: BackfillConfig,
+    store: S,
+    client: Client,
+    metrics: IndexerMetrics,
+    cancel: CancellationToken,
+) -> IndexerResult<()>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+    T: R2D2Connection + 'static,
+{
+    config.validate()?;
+    let BackfillConfig {
+        start_checkpoint: start,
+        end_checkpoint: end,
+        workers,
+        batch_size,
+        index_filter,
+    } = config;
+
+    if start <= end {
+        info!("Backfilling checkpoints {start} to {end} with {workers} workers...");
+        index_epochs::<S, T>(&store, &client, start, end).await?;
+
+        let package_resolver = Arc::new(Resolver::new(PackageStoreWithLruCache::new(
+            RemotePackageResolver::new(client.clone()),
+        )));
+
+        let tasks = split_range(start, end, workers)
+            .into_iter()
+            .map(|range| {
+                tokio::spawn(backfill_range::<S, T>(
+                    store.clone(),
+                    client.clone(),
+                    metrics.clone(),
+                    package_resolver.clone(),
+                    index_filter.clone(),
+                    range,
+                    batch_size,
+                    cancel.clone(),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        for result in futures::future::join_all(tasks).await {
+            result??;
+        }
+    }
+
+    if cancel.is_cancelled() {
+        info!("Backfill cancelled before it finished");
+        return Ok(());
+    }
+
+    let first_missing = store.get_first_missing_checkpoint_sequence_number().await?;
+    if first_missing <= end {
+        return Err(IndexerError::UncategorizedError(anyhow::anyhow!(
+            "Backfill finished, but checkpoint {first_missing} is missing"
+        )));
+    }
+
+    hand_off(&store, end).await
+}
+
+/// Backfill `range` in batches of consecutive checkpoints, skipping the ones already committed.
+async fn backfill_range<S, T>(
+    store: S,
+    client: Client,
+    metrics: IndexerMetrics,
+    package_resolver: Arc<Resolver<impl PackageStore>>,
+    index_filter: Arc<IndexFilter>,
+    range: RangeInclusive<CheckpointSequenceNumber>,
+    batch_size: usize,
+    cancel: CancellationToken,
+) -> IndexerResult<()>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+    T: R2D2Connection + 'static,
+{
+    info!("Backfill worker started for checkpoints {:?}", range);
+    let state = Arc::new(store.clone());
+    let metrics_arc = Arc::new(metrics.clone());
+
+    let mut batch_start = *range.start();
+    while batch_start <= *range.end() {
+        if cancel.is_cancelled() {
+            return Ok(());
+        }
+
+        let batch_end = (batch_start + batch_size as u64 - 1).min(*range.end());
+        let committed = store
+            .get_committed_checkpoints_in_range(batch_start, batch_end)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        let missing = (batch_start..=batch_end).filter(|seq| !committed.contains(seq));
+        let batch = futures::stream::iter(missing)
+            .map(|seq| {
+                let client = client.clone();
+                let state = state.clone();
+                let metrics = metrics_arc.clone();
+                let package_resolver = package_resolver.clone();
+                let index_filter = index_filter.clone();
+                async move {
+                    let checkpoint = client.get_full_checkpoint(seq).await?;
+                    let packages = CheckpointHandler::<S, T>::index_packages(
+                        std::slice::from_ref(&checkpoint),
+                        &metrics,
+                    );
+                    let filter = index_filter.filter_checkpoint(&checkpoint);
+                    let checkpoint_data = CheckpointHandler::<S, T>::index_checkpoint(
+                        state,
+                        checkpoint,
+                        metrics,
+                        packages,
+                        package_resolver,
+                    )
+                    .await?;
+                    Ok::<_, IndexerError>(filter.apply(checkpoint_data))
+                }
+            })
+            .buffered(BACKFILL_DOWNLOAD_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let checkpoint_num = batch.len();
+        let tx_count = batch.iter().map(|c| c.transactions.len()).sum::<usize>();
+        store.persist_checkpoints_in_bulk(batch).await?;
+        metrics
+            .total_tx_checkpoint_committed
+            .inc_by(checkpoint_num as u64);
+        metrics.total_transaction_committed.inc_by(tx_count as u64);
+
+        batch_start = batch_end + 1;
+    }
+
+    info!("Backfill worker finished checkpoints {:?}", range);
+    Ok(())
+}
+
+/// Write the epochs that begin in `[start, end]`, in order, advancing table partitions as the
+/// committer would, so that workers can then write checkpoints from any of them.
+async fn index_epochs<S, T>(
+    store: &S,
+    client: &Client,
+    start: CheckpointSequenceNumber,
+    end: CheckpointSequenceNumber,
+) -> IndexerResult<()>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+    T: R2D2Connection + 'static,
+{
+    let start_epoch = client.get_checkpoint_summary(start).await?.epoch;
+    let end_summary = client.get_checkpoint_summary(end).await?;
+
+    // The first checkpoint of each epoch from `start_epoch` to `end_summary.epoch`.
+    let mut first_checkpoints = vec![];
+    let mut lo = 0;
+    for epoch in start_epoch..=end_summary.epoch {
+        lo = first_checkpoint_of_epoch(client, epoch, lo, end).await?;
+        first_checkpoints.push(lo);
+    }
+
+    let mut epoch_checkpoints = vec![];
+    if start == 0 {
+        epoch_checkpoints.push(0);
+    }
+    epoch_checkpoints.extend(first_checkpoints.iter().skip(1).map(|cp| cp - 1));
+    if end_summary.end_of_epoch_data.is_some() {
+        epoch_checkpoints.push(end);
+    }
+
+    for seq in epoch_checkpoints {
+        let data = client.get_full_checkpoint(seq).await?;
+        let epoch = data.checkpoint_summary.epoch;
+
+        // Total transactions at the end of the epoch before the one that `data` ends.
+        let first_checkpoint = first_checkpoints[(epoch - start_epoch) as usize];
+        let network_tx_count_prev_epoch = if first_checkpoint == 0 {
+            0
+        } else {
+            client
+                .get_checkpoint_summary(first_checkpoint - 1)
+                .await?
+                .network_total_transactions
+        };
+
+        if let Some(epoch) =
+            CheckpointHandler::<S, T>::epoch_to_commit(&data, network_tx_count_prev_epoch)?
+        {
+            info!("Backfilling epoch {}", epoch.new_epoch.epoch);
+            store.persist_epoch(epoch.clone()).await?;
+            store.advance_epoch(epoch).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The first checkpoint in `[lo, hi]` that is in `epoch` or later, or `hi` if there is none.
+async fn first_checkpoint_of_epoch(
+    client: &Client,
+    epoch: u64,
+    mut lo: CheckpointSequenceNumber,
+    mut hi: CheckpointSequenceNumber,
+) -> IndexerResult<CheckpointSequenceNumber> {
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if client.get_checkpoint_summary(mid).await?.epoch < epoch {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(lo)
+}
+
+/// Bring `objects` and `objects_snapshot` up to `end` from `objects_history`, and write the latest
+/// versions of system packages, which workers skip.
+async fn hand_off<S>(store: &S, end: CheckpointSequenceNumber) -> IndexerResult<()>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let objects_start = store
+        .get_latest_object_checkpoint_sequence_number()
+        .await?
+        .map_or(0, |cp| cp + 1);
+    for (start_cp, end_cp) in windows(objects_start, end) {
+        info!("Updating objects from history for checkpoints {start_cp} to {end_cp}");
+        store.update_objects_from_history(start_cp, end_cp).await?;
+    }
+
+    store.persist_system_packages_from_objects().await?;
+
+    let snapshot_start = store
+        .get_latest_object_snapshot_checkpoint_sequence_number()
+        .await?
+        .map_or(0, |cp| cp + 1);
+    for (start_cp, end_cp) in windows(snapshot_start, end) {
+        info!("Updating objects snapshot for checkpoints {start_cp} to {end_cp}");
+        store.update_objects_snapshot(start_cp, end_cp).await?;
+    }
+
+    info!("Backfill finished at checkpoint {end}");
+    Ok(())
+}
+
+/// Split `[start, end]` into at most `parts` contiguous ranges of roughly equal size.
+fn split_range(
+    start: CheckpointSequenceNumber,
+    end: CheckpointSequenceNumber,
+    parts: usize,
+) -> Vec<RangeInclusive<CheckpointSequenceNumber>> {
+    let len = end - start + 1;
+    let size = len.div_ceil(parts.max(1) as u64);
+    (start..=end)
+        .step_by(size as usize)
+        .map(|lo| lo..=(lo + size - 1).min(end))
+        .collect()
+}
+
+/// Half-open windows of at most `BACKFILL_OBJECTS_WINDOW` checkpoints, covering `[start, end]`.
+fn windows(
+    start: CheckpointSequenceNumber,
+    end: CheckpointSequenceNumber,
+) -> impl Iterator<Item = (CheckpointSequenceNumber, CheckpointSequenceNumber)> {
+    (start..=end)
+        .step_by(BACKFILL_OBJECTS_WINDOW as usize)
+        .map(move |lo| (lo, (lo + BACKFILL_OBJECTS_WINDOW).min(end + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_range_covers_range() {
+        assert_eq!(split_range(0, 9, 3), vec![0..=3, 4..=7, 8..=9]);
+        assert_eq!(split_range(5, 5, 4), vec![5..=5]);
+        assert_eq!(split_range(10, 12, 8), vec![10..=10, 11..=11, 12..=12]);
+    }
+
+    #[test]
+    fn config_rejects_empty_batches() {
+        let config = |workers, batch_size| BackfillConfig {
+            start_checkpoint: 0,
+            end_checkpoint: 10,
+            workers,
+            batch_size,
+            index_filter: Arc::new(IndexFilter::default()),
+        };
+
+        assert!(config(8, 100).validate().is_ok());
+        assert!(config(8, 0).validate().is_err());
+        assert!(config(0, 100).validate().is_err());
+    }
+
+    #[test]
+    fn windows_cover_range() {
+        assert_eq!(windows(0, 0).collect::<Vec<_>>(), vec![(0, 1)]);
+        assert_eq!(
+            windows(5, 2 * BACKFILL_OBJECTS_WINDOW).collect::<Vec<_>>(),
+            vec![
+                (5, BACKFILL_OBJECTS_WINDOW + 5),
+                (BACKFILL_OBJECTS_WINDOW + 5, 2 * BACKFILL_OBJECTS_WINDOW + 1),
+            ]
+        );
+        assert_eq!(windows(3, 2).count(), 0);
+    }
+}
@@ -43,6 +43,7 @@ use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary
 use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 
 use crate::errors::IndexerError;
+use crate::index_filter::IndexFilter;
 use crate::metrics::IndexerMetrics;
 
 use crate::db::ConnectionPool;
@@ -66,6 +67,7 @@ pub async fn new_handlers<S, T>(
     client: Client,
     metrics: IndexerMetrics,
     next_checkpoint_sequence_number: CheckpointSequenceNumber,
+    index_filter: Arc<IndexFilter>,
     cancel: CancellationToken,
 ) -> Result<CheckpointHandler<S, T>, IndexerError>
 where
@@ -103,6 +105,7 @@ where
         metrics,
         indexed_checkpoint_sender,
         package_tx,
+        index_filter,
     ))
 }
 
@@ -114,6 +117,7 @@ pub struct CheckpointHandler<S, T: R2D2Connection + 'static> {
     // they will be periodically GCed to avoid OOM.
     package_buffer: Arc<Mutex<IndexingPackageBuffer>>,
     package_resolver: Arc<Resolver<PackageStoreWithLruCache<InterimPackageResolver<T>>>>,
+    index_filter: Arc<IndexFilter>,
 }
 
 #[async_trait]
@@ -130,14 +134,17 @@ where
             checkpoint.checkpoint_summary.sequence_number, cp_download_lag
         );
         self.metrics.download_lag_ms.set(cp_download_lag);
+        let packages = Self::index_packages(std::slice::from_ref(&checkpoint), &self.metrics);
+        let filter = self.index_filter.filter_checkpoint(&checkpoint);
         let checkpoint_data = Self::index_checkpoint(
             self.state.clone().into(),
-            checkpoint.clone(),
+            checkpoint,
             Arc::new(self.metrics.clone()),
-            Self::index_packages(&[checkpoint], &self.metrics),
+            packages,
             self.package_resolver.clone(),
         )
         .await?;
+        let checkpoint_data = filter.apply(checkpoint_data);
         self.indexed_checkpoint_sender.send(checkpoint_data).await?;
         Ok(())
     }
@@ -162,6 +169,7 @@ where
         metrics: IndexerMetrics,
         indexed_checkpoint_sender: mysten_metrics::metered_channel::Sender<CheckpointDataToCommit>,
         package_tx: watch::Receiver<Option<CheckpointSequenceNumber>>,
+        index_filter: Arc<IndexFilter>,
     ) -> Self {
         let package_buffer = IndexingPackageBuffer::start(package_tx);
         let pg_blocking_cp = Self::pg_blocking_cp(state.clone()).unwrap();
@@ -179,12 +187,37 @@ where
             indexed_checkpoint_sender,
             package_buffer,
             package_resolver,
+            index_filter,
         }
     }
 
     async fn index_epoch(
         state: Arc<S>,
         data: &CheckpointData,
+    ) -> Result<Option<EpochToCommit>, IndexerError> {
+        let checkpoint_summary = &data.checkpoint_summary;
+
+        // Now we just entered epoch X, we want to calculate the diff between
+        // TotalTransactionsByEndOfEpoch(X-1) and TotalTransactionsByEndOfEpoch(X-2)
+        let network_tx_count_prev_epoch = match checkpoint_summary.end_of_epoch_data {
+            // If first epoch change, this number is 0
+            Some(_) if checkpoint_summary.epoch > 0 => {
+                state
+                    .get_network_total_transactions_by_end_of_epoch(checkpoint_summary.epoch - 1)
+                    .await?
+            }
+            _ => 0,
+        };
+
+        Self::epoch_to_commit(data, network_tx_count_prev_epoch)
+    }
+
+    /// Index the epoch change in `data`, if it is the genesis checkpoint or the last checkpoint of
+    /// an epoch, given the total number of transactions in the network at the end of the epoch
+    /// before the one that `data` ends.
+    pub(crate) fn epoch_to_commit(
+        data: &CheckpointData,
+        network_tx_count_prev_epoch: u64,
     ) -> Result<Option<EpochToCommit>, IndexerError> {
         let checkpoint_object_store = EpochEndIndexingObjectStore::new(data);
 
@@ -231,19 +264,6 @@ where
 
         let event = bcs::from_bytes::<SystemEpochInfoEvent>(&epoch_event.contents)?;
 
-        // Now we just entered epoch X, we want to calculate the diff between
-        // TotalTransactionsByEndOfEpoch(X-1) and TotalTransactionsByEndOfEpoch(X-2)
-        let network_tx_count_prev_epoch = match system_state.epoch {
-            // If first epoch change, this number is 0
-            1 => Ok(0),
-            _ => {
-                let last_epoch = system_state.epoch - 2;
-                state
-                    .get_network_total_transactions_by_end_of_epoch(last_epoch)
-                    .await
-            }
-        }?;
-
         Ok(Some(EpochToCommit {
             last_epoch: Some(IndexedEpochInfo::from_end_of_epoch_data(
                 &system_state,
@@ -259,7 +279,7 @@ where
         }))
     }
 
-    async fn index_checkpoint(
+    pub(crate) async fn index_checkpoint(
         state: Arc<S>,
         data: CheckpointData,
         metrics: Arc<IndexerMetrics>,
@@ -631,7 +651,7 @@ where
         })
     }
 
-    fn index_packages(
+    pub(crate) fn index_packages(
         checkpoint_data: &[CheckpointData],
         metrics: &IndexerMetrics,
     ) -> Vec<IndexedPackage> {
@@ -11,6 +11,7 @@ use crate::{
     },
 };
 
+pub mod backfill;
 pub mod checkpoint_handler;
 pub mod committer;
 pub mod objects_snapshot_processor;
@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use clap::Args;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::{Deserialize, Serialize};
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::coin::Coin;
+use sui_types::digests::TransactionDigest;
+use sui_types::object::{Object, Owner};
+use sui_types::transaction::TransactionDataAPI;
+
+use crate::errors::IndexerError;
+use crate::handlers::{CheckpointDataToCommit, TransactionObjectChangesToCommit};
+use crate::types::IndexedDeletedObject;
+
+/// Restricts the indexer to the data relevant to a set of packages, Move types and addresses.
+/// When every list is empty, everything is indexed.
+///
+/// The same configuration is read by the writer, which drops everything outside the filter before
+/// it is persisted, and by the read APIs, which report lookups that miss as "not indexed" rather
+/// than "not found".
+#[derive(Args, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexFilterConfig {
+    /// Index transactions that call into these packages or touch objects or events whose types
+    /// are defined in them, as well as those objects and events. Can be repeated.
+    #[clap(long = "index-package")]
+    #[serde(default)]
+    pub packages: Vec<ObjectID>,
+    /// Index objects and events of this Move type, and the transactions that touch them, e.g.
+    /// `0x2::coin::Coin` (any instantiation) or `0x2::coin::Coin<0x2::sui::SUI>`. Can be repeated.
+    #[clap(long = "index-move-type")]
+    #[serde(default)]
+    pub move_types: Vec<String>,
+    /// Index transactions sent by, or touching objects owned by, this address, as well as the
+    /// objects it owns. Can be repeated.
+    #[clap(long = "index-address")]
+    #[serde(default)]
+    pub addresses: Vec<SuiAddress>,
+}
+
+impl IndexFilterConfig {
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.move_types.is_empty() && self.addresses.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IndexFilter {
+    packages: HashSet<ObjectID>,
+    move_types: Vec<StructTag>,
+    addresses: HashSet<SuiAddress>,
+}
+
+impl IndexFilter {
+    pub fn new(config: &IndexFilterConfig) -> Result<Self, IndexerError> {
+        let move_types = config
+            .move_types
+            .iter()
+            .map(|t| {
+                StructTag::from_str(t).map_err(|e| {
+                    IndexerError::InvalidArgumentError(format!(
+                        "Invalid Move type `{t}` in index filter: {e}"
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            packages: config.packages.iter().copied().collect(),
+            move_types,
+            addresses: config.addresses.iter().copied().collect(),
+        })
+    }
+
+    /// Whether this filter lets everything through.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.move_types.is_empty() && self.addresses.is_empty()
+    }
+
+    /// Whether everything owned by or sent by `address` is indexed, so that queries scoped to it
+    /// (such as balances) are complete.
+    pub fn is_address_indexed(&self, address: &SuiAddress) -> bool {
+        self.is_empty() || self.addresses.contains(address)
+    }
+
+    /// Whether every coin owned by `owner` (of `coin_type`, if given) is indexed, either because
+    /// the owner is, or because the coin type is.
+    pub fn are_coins_indexed(&self, owner: &SuiAddress, coin_type: Option<&TypeTag>) -> bool {
+        self.is_address_indexed(owner)
+            || coin_type.is_some_and(|t| self.matches_type(&Coin::type_(t.clone())))
+    }
+
+    /// The error to return when a lookup for `what` finds nothing: when filtering, the data may
+    /// exist on-chain but have been left out of the index.
+    pub fn not_found_error(&self, what: impl std::fmt::Display) -> IndexerError {
+        if self.is_empty() {
+            IndexerError::InvalidArgumentError(format!("{what} not found"))
+        } else {
+            IndexerError::NotIndexedError(what.to_string())
+        }
+    }
+
+    /// A type matches if it is one of the configured Move types (ignoring type parameters when
+    /// the configured type has none), if it is defined in one of the configured packages, or if
+    /// any of its type parameters match.
+    pub fn matches_type(&self, tag: &StructTag) -> bool {
+        self.packages.contains(&ObjectID::from(tag.address))
+            || self.move_types.iter().any(|t| {
+                t.address == tag.address
+                    && t.module == tag.module
+                    && t.name == tag.name
+                    && (t.type_params.is_empty() || t.type_params == tag.type_params)
+            })
+            || tag.type_params.iter().any(|p| self.matches_type_tag(p))
+    }
+
+    fn matches_type_tag(&self, tag: &TypeTag) -> bool {
+        match tag {
+            TypeTag::Struct(s) => self.matches_type(s),
+            TypeTag::Vector(inner) => self.matches_type_tag(inner),
+            _ => false,
+        }
+    }
+
+    /// An object matches if it is owned by one of the configured addresses, or if its type
+    /// matches. Objects owned by other objects (such as dynamic fields) only match by type: their
+    /// owner is the parent object's ID, not an address, and their parent is not resolved.
+    pub fn matches_object(&self, object: &Object) -> bool {
+        if object.is_package() {
+            return self.packages.contains(&object.id());
+        }
+        let owner_matches = match object.owner {
+            Owner::AddressOwner(address) => self.addresses.contains(&address),
+            _ => false,
+        };
+        owner_matches || object.struct_tag().is_some_and(|t| self.matches_type(&t))
+    }
+
+    pub fn matches_transaction(&self, tx: &CheckpointTransaction) -> bool {
+        let data = tx.transaction.transaction_data();
+        self.addresses.contains(&data.sender())
+            || data
+                .move_calls()
+                .into_iter()
+                .any(|(package, _, _)| self.packages.contains(package))
+            || tx
+                .events
+                .iter()
+                .flat_map(|e| e.data.iter())
+                .any(|e| self.packages.contains(&e.package_id) || self.matches_type(&e.type_))
+            || tx
+                .input_objects
+                .iter()
+                .chain(tx.output_objects.iter())
+                .any(|o| self.matches_object(o))
+    }
+
+    /// Decide what to keep of `data`, before it is indexed, so that the decision can be applied
+    /// to the indexed checkpoint without holding on to the raw checkpoint data.
+    pub fn filter_checkpoint(&self, data: &CheckpointData) -> CheckpointFilter<'_> {
+        if self.is_empty() {
+            return CheckpointFilter {
+                filter: self,
+                kept_txs: HashSet::new(),
+                matched_inputs: HashSet::new(),
+            };
+        }
+
+        let kept_txs = data
+            .transactions
+            .iter()
+            .filter(|tx| self.matches_transaction(tx))
+            .map(|tx| *tx.transaction.digest())
+            .collect();
+
+        // Deleted and wrapped objects only appear in the effects, so whether they were indexed is
+        // read from the transaction inputs.
+        let matched_inputs = data
+            .transactions
+            .iter()
+            .flat_map(|tx| tx.input_objects.iter())
+            .filter(|o| self.matches_object(o))
+            .map(|o| o.id())
+            .collect();
+
+        CheckpointFilter {
+            filter: self,
+            kept_txs,
+            matched_inputs,
+        }
+    }
+}
+
+/// The filter's decisions for one checkpoint, see [`IndexFilter::filter_checkpoint`].
+pub struct CheckpointFilter<'a> {
+    filter: &'a IndexFilter,
+    /// Transactions that match the filter.
+    kept_txs: HashSet<TransactionDigest>,
+    /// Objects that matched the filter before the checkpoint modified them.
+    matched_inputs: HashSet<ObjectID>,
+}
+
+impl CheckpointFilter<'_> {
+    /// Drop everything in `checkpoint` that falls outside the filter.
+    ///
+    /// Checkpoints and epochs are always kept, as are packages, which are needed to resolve the
+    /// layouts of the objects that are kept. Transactions are kept along with all of their events
+    /// and indices. Objects that matched the filter before the checkpoint but no longer do are
+    /// removed from the index, as though they were deleted.
+    pub fn apply(self, mut checkpoint: CheckpointDataToCommit) -> CheckpointDataToCommit {
+        if self.filter.is_empty() {
+            return checkpoint;
+        }
+
+        checkpoint
+            .transactions
+            .retain(|t| self.kept_txs.contains(&t.tx_digest));
+        checkpoint
+            .events
+            .retain(|e| self.kept_txs.contains(&e.transaction_digest));
+        checkpoint
+            .tx_indices
+            .retain(|i| self.kept_txs.contains(&i.transaction_digest));
+        checkpoint.display_updates.retain(|_, display| {
+            StructTag::from_str(&display.object_type).is_ok_and(|t| self.filter.matches_type(&t))
+        });
+
+        self.filter_object_changes(&mut checkpoint.object_changes);
+        self.filter_object_changes(&mut checkpoint.object_history_changes);
+        checkpoint
+    }
+
+    fn filter_object_changes(&self, changes: &mut TransactionObjectChangesToCommit) {
+        changes
+            .deleted_objects
+            .retain(|o| self.matched_inputs.contains(&o.object_id));
+
+        let (kept, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut changes.changed_objects)
+            .into_iter()
+            .partition(|o| o.object.is_package() || self.filter.matches_object(&o.object));
+        changes.changed_objects = kept;
+        changes.deleted_objects.extend(
+            dropped
+                .into_iter()
+                .filter(|o| self.matched_inputs.contains(&o.object_id))
+                .map(|o| IndexedDeletedObject {
+                    object_id: o.object_id,
+                    object_version: o.object_version,
+                    checkpoint_sequence_number: o.checkpoint_sequence_number,
+                }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::IndexedObject;
+    use sui_types::base_types::dbg_addr;
+
+    fn filter(packages: &[ObjectID], move_types: &[&str], addresses: &[SuiAddress]) -> IndexFilter {
+        IndexFilter::new(&IndexFilterConfig {
+            packages: packages.to_vec(),
+            move_types: move_types.iter().map(|t| t.to_string()).collect(),
+            addresses: addresses.to_vec(),
+        })
+        .unwrap()
+    }
+
+    fn tag(s: &str) -> StructTag {
+        StructTag::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn empty_filter_indexes_everything() {
+        let filter = IndexFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.is_address_indexed(&dbg_addr(1)));
+        assert!(matches!(
+            filter.not_found_error("Transaction"),
+            IndexerError::InvalidArgumentError(_)
+        ));
+    }
+
+    #[test]
+    fn matches_configured_types() {
+        let filter = filter(
+            &[],
+            &["0x2::coin::Coin<0x2::sui::SUI>", "0x2::kiosk::Kiosk"],
+            &[],
+        );
+        assert!(filter.matches_type(&tag("0x2::coin::Coin<0x2::sui::SUI>")));
+        assert!(!filter.matches_type(&tag("0x2::coin::Coin<0x3::foo::FOO>")));
+        assert!(filter.matches_type(&tag("0x2::kiosk::Kiosk")));
+        assert!(filter.matches_type(&tag("0x2::dynamic_field::Field<u64, 0x2::kiosk::Kiosk>")));
+        assert!(!filter.matches_type(&tag("0x2::kiosk::KioskOwnerCap")));
+        assert!(!filter.is_address_indexed(&dbg_addr(1)));
+        assert!(matches!(
+            filter.not_found_error("Transaction"),
+            IndexerError::NotIndexedError(_)
+        ));
+    }
+
+    #[test]
+    fn matches_types_from_packages() {
+        let package = ObjectID::from_hex_literal("0x42").unwrap();
+        let filter = filter(&[package], &[], &[]);
+        assert!(filter.matches_type(&tag("0x42::token::TOKEN")));
+        assert!(filter.matches_type(&tag("0x2::coin::Coin<0x42::token::TOKEN>")));
+        assert!(filter.matches_type(&tag("0x2::table::Table<vector<0x42::token::TOKEN>, u64>")));
+        assert!(!filter.matches_type(&tag("0x2::coin::Coin<0x2::sui::SUI>")));
+    }
+
+    #[test]
+    fn removes_objects_that_stop_matching() {
+        let (indexed, other) = (dbg_addr(1), dbg_addr(2));
+        let filter = filter(&[], &[], &[indexed]);
+
+        // Objects that were indexed before the checkpoint, and are now owned by another address,
+        // transferred between indexed addresses, or deleted.
+        let transferred_away = ObjectID::random();
+        let still_owned = ObjectID::random();
+        let deleted = ObjectID::random();
+        // Objects that were never indexed, and are now transferred or deleted.
+        let unrelated = ObjectID::random();
+        let unrelated_deleted = ObjectID::random();
+
+        let checkpoint_filter = CheckpointFilter {
+            filter: &filter,
+            kept_txs: HashSet::new(),
+            matched_inputs: [transferred_away, still_owned, deleted].into(),
+        };
+
+        let changed = |id, owner| {
+            IndexedObject::from_object(1, Object::with_id_owner_for_testing(id, owner), None)
+        };
+        let deleted_object = |object_id| IndexedDeletedObject {
+            object_id,
+            object_version: 2,
+            checkpoint_sequence_number: 1,
+        };
+        let mut changes = TransactionObjectChangesToCommit {
+            changed_objects: vec![
+                changed(transferred_away, other),
+                changed(still_owned, indexed),
+                changed(unrelated, other),
+            ],
+            deleted_objects: vec![deleted_object(deleted), deleted_object(unrelated_deleted)],
+        };
+        checkpoint_filter.filter_object_changes(&mut changes);
+
+        let changed: Vec<_> = changes
+            .changed_objects
+            .iter()
+            .map(|o| o.object_id)
+            .collect();
+        assert_eq!(changed, vec![still_owned]);
+        let removed: Vec<_> = changes
+            .deleted_objects
+            .iter()
+            .map(|o| o.object_id)
+            .collect();
+        assert_eq!(removed, vec![deleted, transferred_away]);
+    }
+
+    #[test]
+    fn matches_address_owned_objects_only() {
+        let indexed = dbg_addr(1);
+        let filter = filter(&[], &[], &[indexed]);
+
+        let owned = Object::with_id_owner_for_testing(ObjectID::random(), indexed);
+        assert!(filter.matches_object(&owned));
+
+        // An object whose parent object happens to share its ID with an indexed address is not
+        // owned by that address.
+        let child = Object::with_object_owner_for_testing(ObjectID::random(), indexed.into());
+        assert!(!filter.matches_object(&child));
+
+        // Children still match by type.
+        let filter = self::filter(&[], &["0x2::coin::Coin"], &[]);
+        assert!(filter.matches_object(&child));
+    }
+
+    #[test]
+    fn rejects_invalid_types() {
+        let config = IndexFilterConfig {
+            move_types: vec!["not a type".to_string()],
+            ..Default::default()
+        };
+        assert!(IndexFilter::new(&config).is_err());
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::env;
+use std::sync::Arc;
 
 use anyhow::Result;
 use diesel::r2d2::R2D2Connection;
@@ -17,8 +18,10 @@ use sui_data_ingestion_core::{
 
 use crate::build_json_rpc_server;
 use crate::errors::IndexerError;
+use crate::handlers::backfill::{run_backfill, BackfillConfig};
 use crate::handlers::checkpoint_handler::new_handlers;
 use crate::handlers::objects_snapshot_processor::{ObjectsSnapshotProcessor, SnapshotLagConfig};
+use crate::index_filter::IndexFilter;
 use crate::indexer_reader::IndexerReader;
 use crate::metrics::IndexerMetrics;
 use crate::store::IndexerStore;
@@ -68,6 +71,33 @@ impl Indexer {
             env!("CARGO_PKG_VERSION")
         );
 
+        let rest_client = sui_rest_api::Client::new(format!("{}/rest", config.rpc_client_url));
+        let index_filter = Arc::new(IndexFilter::new(&config.index_filter)?);
+        if !index_filter.is_empty() {
+            info!("Indexing only data matching {:?}", config.index_filter);
+        }
+
+        if let Some(end_checkpoint) = config.backfill_end_checkpoint {
+            let backfill_config = BackfillConfig {
+                start_checkpoint: store.get_first_missing_checkpoint_sequence_number().await?,
+                end_checkpoint,
+                workers: config.backfill_workers,
+                batch_size: config.backfill_batch_size,
+                index_filter: index_filter.clone(),
+            };
+            run_backfill::<S, T>(
+                backfill_config,
+                store.clone(),
+                rest_client.clone(),
+                metrics.clone(),
+                cancel.clone(),
+            )
+            .await?;
+            if cancel.is_cancelled() {
+                return Ok(());
+            }
+        }
+
         let watermark = store
             .get_latest_checkpoint_sequence_number()
             .await
@@ -87,8 +117,6 @@ impl Indexer {
             .parse::<usize>()
             .unwrap();
 
-        let rest_client = sui_rest_api::Client::new(format!("{}/rest", config.rpc_client_url));
-
         let objects_snapshot_processor = ObjectsSnapshotProcessor::new_with_config(
             rest_client.clone(),
             store.clone(),
@@ -111,8 +139,15 @@ impl Indexer {
             1,
             DataIngestionMetrics::new(&Registry::new()),
         );
-        let worker =
-            new_handlers::<S, T>(store, rest_client, metrics, watermark, cancel.clone()).await?;
+        let worker = new_handlers::<S, T>(
+            store,
+            rest_client,
+            metrics,
+            watermark,
+            index_filter,
+            cancel.clone(),
+        )
+        .await?;
         let worker_pool = WorkerPool::new(worker, "workflow".to_string(), download_queue_size);
         let extra_reader_options = ReaderOptions {
             batch_size: download_queue_size,
@@ -146,7 +181,8 @@ impl Indexer {
             "Sui Indexer Reader (version {:?}) started...",
             env!("CARGO_PKG_VERSION")
         );
-        let indexer_reader = IndexerReader::<T>::new(db_url)?;
+        let index_filter = IndexFilter::new(&config.index_filter)?;
+        let indexer_reader = IndexerReader::<T>::new(db_url)?.with_index_filter(index_filter);
         let handle = build_json_rpc_server(registry, indexer_reader, config, None)
             .await
             .expect("Json rpc server should not run into errors upon start.");
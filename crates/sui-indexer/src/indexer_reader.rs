@@ -44,6 +44,7 @@ use sui_types::{
 use sui_types::{coin::CoinMetadata, event::EventID};
 
 use crate::db::{ConnectionConfig, ConnectionPool, ConnectionPoolConfig};
+use crate::index_filter::IndexFilter;
 use crate::models::transactions::{stored_events_to_events, StoredTransactionEvents};
 use crate::store::diesel_macro::*;
 use crate::{
@@ -53,13 +54,13 @@ use crate::{
         display::StoredDisplay,
         epoch::StoredEpochInfo,
         events::StoredEvent,
-        objects::{CoinBalance, ObjectRefColumn, StoredObject},
+        objects::{CoinBalance, ObjectRefColumn, StoredHistoryObject, StoredObject},
         transactions::{tx_events_to_sui_tx_events, StoredTransaction},
         tx_indices::TxSequenceNumber,
     },
     schema::{checkpoints, display, epochs, events, objects, objects_snapshot, transactions},
     store::package_resolver::IndexerStorePackageResolver,
-    types::{IndexerResult, OwnerType},
+    types::{IndexerResult, ObjectStatus, OwnerType},
 };
 
 pub const TX_SEQUENCE_NUMBER_STR: &str = "tx_sequence_number";
@@ -73,6 +74,7 @@ where
     pool: ConnectionPool<T>,
     package_resolver: PackageResolver<T>,
     package_obj_type_cache: Arc<Mutex<SizedCache<String, Option<ObjectID>>>>,
+    index_filter: Arc<IndexFilter>,
 }
 
 impl<T> Clone for IndexerReader<T>
@@ -84,6 +86,7 @@ where
             pool: self.pool.clone(),
             package_resolver: self.package_resolver.clone(),
             package_obj_type_cache: self.package_obj_type_cache.clone(),
+            index_filter: self.index_filter.clone(),
         }
     }
 }
@@ -124,9 +127,21 @@ impl<U: R2D2Connection + 'static> IndexerReader<U> {
             pool,
             package_resolver,
             package_obj_type_cache,
+            index_filter: Arc::new(IndexFilter::default()),
         })
     }
 
+    /// Let callers know that this reader serves a database written through `index_filter`, so
+    /// that they can tell data that was filtered out apart from data that does not exist.
+    pub fn with_index_filter(mut self, index_filter: IndexFilter) -> Self {
+        self.index_filter = Arc::new(index_filter);
+        self
+    }
+
+    pub fn index_filter(&self) -> &IndexFilter {
+        &self.index_filter
+    }
+
     pub async fn spawn_blocking<F, R, E>(&self, f: F) -> Result<R, E>
     where
         F: FnOnce(Self) -> Result<R, E> + Send + 'static,
@@ -549,9 +564,12 @@ impl<U: R2D2Connection> IndexerReader<U> {
         filter: Option<SuiObjectDataFilter>,
         cursor: Option<ObjectID>,
         limit: usize,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<StoredObject>, IndexerError> {
-        self.spawn_blocking(move |this| this.get_owned_objects_impl(address, filter, cursor, limit))
-            .await
+        self.spawn_blocking(move |this| {
+            this.get_owned_objects_impl(address, filter, cursor, limit, checkpoint)
+        })
+        .await
     }
 
     fn get_owned_objects_impl(
@@ -560,7 +578,16 @@ impl<U: R2D2Connection> IndexerReader<U> {
         filter: Option<SuiObjectDataFilter>,
         cursor: Option<ObjectID>,
         limit: usize,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<StoredObject>, IndexerError> {
+        if let Some(checkpoint) = checkpoint {
+            let mut condition = owner_condition(OwnerType::Address, &address.to_vec(), cursor);
+            if let Some(filter) = filter {
+                condition = format!("{condition} AND {}", object_filter_condition(&filter)?);
+            }
+            return self.get_objects_at_checkpoint(checkpoint, condition, "object_id ASC", limit);
+        }
+
         run_query!(&self.pool, |conn| {
             let mut query = objects::dsl::objects
                 .filter(objects::dsl::owner_type.eq(OwnerType::Address as i16))
@@ -663,15 +690,30 @@ impl<U: R2D2Connection> IndexerReader<U> {
     pub async fn multi_get_objects_in_blocking_task(
         &self,
         object_ids: Vec<ObjectID>,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<StoredObject>, IndexerError> {
-        self.spawn_blocking(move |this| this.multi_get_objects_impl(object_ids))
+        self.spawn_blocking(move |this| this.multi_get_objects_impl(object_ids, checkpoint))
             .await
     }
 
     fn multi_get_objects_impl(
         &self,
         object_ids: Vec<ObjectID>,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<StoredObject>, IndexerError> {
+        if let Some(checkpoint) = checkpoint {
+            if object_ids.is_empty() {
+                return Ok(vec![]);
+            }
+            let limit = object_ids.len();
+            let ids = object_ids
+                .iter()
+                .map(|id| bytea_literal(id.as_ref()))
+                .join(", ");
+            let condition = format!("object_id IN ({ids})");
+            return self.get_objects_at_checkpoint(checkpoint, condition, "object_id ASC", limit);
+        }
+
         let object_ids = object_ids.into_iter().map(|id| id.to_vec()).collect_vec();
         run_query!(&self.pool, |conn| {
             objects::dsl::objects
@@ -1190,10 +1232,17 @@ impl<U: R2D2Connection> IndexerReader<U> {
         parent_object_id: ObjectID,
         cursor: Option<ObjectID>,
         limit: usize,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<DynamicFieldInfo>, IndexerError> {
         let objects = self
-            .spawn_blocking(move |this| {
-                this.get_dynamic_fields_raw(parent_object_id, cursor, limit)
+            .spawn_blocking(move |this| match checkpoint {
+                Some(checkpoint) => this.get_objects_at_checkpoint(
+                    checkpoint,
+                    owner_condition(OwnerType::Object, &parent_object_id.to_vec(), cursor),
+                    "object_id ASC",
+                    limit,
+                ),
+                None => this.get_dynamic_fields_raw(parent_object_id, cursor, limit),
             })
             .await?;
 
@@ -1220,7 +1269,10 @@ impl<U: R2D2Connection> IndexerReader<U> {
             .collect::<Vec<_>>();
 
         let object_refs = self
-            .spawn_blocking(move |this| this.get_object_refs(dfo_ids))
+            .spawn_blocking(move |this| match checkpoint {
+                Some(checkpoint) => this.get_object_refs_at_checkpoint(dfo_ids, checkpoint),
+                None => this.get_object_refs(dfo_ids),
+            })
             .await?;
         let mut df_futures = vec![];
         for object in objects {
@@ -1337,6 +1389,26 @@ impl<U: R2D2Connection> IndexerReader<U> {
         .collect::<IndexerResult<HashMap<_, _>>>()
     }
 
+    fn get_object_refs_at_checkpoint(
+        &self,
+        object_ids: Vec<Vec<u8>>,
+        checkpoint: u64,
+    ) -> IndexerResult<HashMap<ObjectID, ObjectRef>> {
+        if object_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let limit = object_ids.len();
+        let ids = object_ids.iter().map(|id| bytea_literal(id)).join(", ");
+        let condition = format!("object_id IN ({ids})");
+        self.get_objects_at_checkpoint(checkpoint, condition, "object_id ASC", limit)?
+            .into_iter()
+            .map(|o| {
+                let object_ref = o.get_object_ref()?;
+                Ok((object_ref.0, object_ref))
+            })
+            .collect()
+    }
+
     pub async fn get_display_object_by_type(
         &self,
         object_type: &move_core_types::language_storage::StructTag,
@@ -1373,9 +1445,12 @@ impl<U: R2D2Connection> IndexerReader<U> {
         coin_type: Option<String>,
         cursor: ObjectID,
         limit: usize,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<SuiCoin>, IndexerError> {
-        self.spawn_blocking(move |this| this.get_owned_coins(owner, coin_type, cursor, limit))
-            .await
+        self.spawn_blocking(move |this| {
+            this.get_owned_coins(owner, coin_type, cursor, limit, checkpoint)
+        })
+        .await
     }
 
     fn get_owned_coins(
@@ -1385,7 +1460,29 @@ impl<U: R2D2Connection> IndexerReader<U> {
         coin_type: Option<String>,
         cursor: ObjectID,
         limit: usize,
+        checkpoint: Option<u64>,
     ) -> Result<Vec<SuiCoin>, IndexerError> {
+        if let Some(checkpoint) = checkpoint {
+            let coin_type_condition = match coin_type {
+                Some(coin_type) => format!("coin_type = '{coin_type}'"),
+                None => "coin_type IS NOT NULL".to_string(),
+            };
+            let condition = format!(
+                "{} AND {coin_type_condition}",
+                owner_condition(OwnerType::Address, &owner.to_vec(), Some(cursor)),
+            );
+            return self
+                .get_objects_at_checkpoint(
+                    checkpoint,
+                    condition,
+                    "coin_type ASC, object_id ASC",
+                    limit,
+                )?
+                .into_iter()
+                .map(|o| o.try_into())
+                .collect::<IndexerResult<Vec<_>>>();
+        }
+
         let mut query = objects::dsl::objects
             .filter(objects::dsl::owner_type.eq(OwnerType::Address as i16))
             .filter(objects::dsl::owner_id.eq(owner.to_vec()))
@@ -1570,6 +1667,55 @@ impl<U: R2D2Connection> IndexerReader<U> {
         ))
     }
 
+    /// Loads the live objects that match `condition`, a SQL condition on object columns, as they
+    /// were at `checkpoint`, sorted by `order` and limited to `limit` objects.
+    ///
+    /// An object's state at a checkpoint is its latest version at or before that checkpoint
+    /// across `objects_snapshot` and `objects_history`. This only holds for checkpoints between
+    /// the snapshot's checkpoint and the latest indexed checkpoint, as the snapshot may already
+    /// include changes made after earlier checkpoints. The checkpoint is checked against that
+    /// range in the same transaction as the query, so that every read pinned to the same
+    /// checkpoint sees the same objects, for as long as the checkpoint is in range.
+    fn get_objects_at_checkpoint(
+        &self,
+        checkpoint: u64,
+        condition: String,
+        order: &str,
+        limit: usize,
+    ) -> Result<Vec<StoredObject>, IndexerError> {
+        let checkpoint = i64::try_from(checkpoint).unwrap_or(i64::MAX);
+        let ((first, last), objects) = run_query_repeatable!(&self.pool, |conn| {
+            let first = objects_snapshot::table
+                .select(objects_snapshot::checkpoint_sequence_number)
+                .order(objects_snapshot::checkpoint_sequence_number.desc())
+                .first::<i64>(conn)
+                .optional()?
+                .unwrap_or_default();
+            let last = checkpoints::table
+                .select(checkpoints::sequence_number)
+                .order(checkpoints::sequence_number.desc())
+                .first::<i64>(conn)
+                .optional()?
+                .unwrap_or_default();
+            if checkpoint < first || checkpoint > last {
+                return Ok::<_, diesel::result::Error>(((first, last), vec![]));
+            }
+
+            let query = objects_at_checkpoint_query(first, checkpoint, &condition, order, limit);
+            tracing::debug!("objects at checkpoint query: {query}");
+            let objects = diesel::sql_query(query).load::<StoredHistoryObject>(conn)?;
+            Ok(((first, last), objects))
+        })?;
+
+        if checkpoint < first || checkpoint > last {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Cannot read objects at checkpoint {checkpoint}, objects can only be read at \
+                 checkpoints {first} to {last}"
+            )));
+        }
+        objects.into_iter().map(StoredObject::try_from).collect()
+    }
+
     pub fn package_resolver(&self) -> PackageResolver<U> {
         self.package_resolver.clone()
     }
@@ -1633,3 +1779,98 @@ fn get_single_obj_id_from_package_publish<U: R2D2Connection>(
         Ok(None)
     }
 }
+
+/// The columns shared by `objects_snapshot` and `objects_history`, in the order of
+/// `StoredHistoryObject`'s fields.
+const HISTORY_OBJECT_COLUMNS: &str = "object_id, object_version, object_status, object_digest, \
+    checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, \
+    object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, \
+    df_name, df_object_type, df_object_id";
+
+/// Query for the latest version of each object matching `condition`, at or before `checkpoint`,
+/// when `objects_snapshot` is at checkpoint `first`. Candidate versions come from the snapshot and
+/// from the history since the snapshot, and are discarded if there is a newer version of the same
+/// object by `checkpoint`, so that an object that used to match `condition` but has since changed
+/// (e.g. was transferred or deleted) is left out. Only live objects are returned.
+fn objects_at_checkpoint_query(
+    first: i64,
+    checkpoint: i64,
+    condition: &str,
+    order: &str,
+    limit: usize,
+) -> String {
+    format!(
+        "SELECT * FROM ( \
+            SELECT DISTINCT ON (candidates.object_id) candidates.* FROM ( \
+                SELECT {HISTORY_OBJECT_COLUMNS} FROM objects_snapshot \
+                WHERE {condition} \
+                UNION ALL \
+                SELECT {HISTORY_OBJECT_COLUMNS} FROM objects_history \
+                WHERE checkpoint_sequence_number BETWEEN {first} AND {checkpoint} \
+                AND {condition} \
+            ) candidates \
+            WHERE NOT EXISTS ( \
+                SELECT 1 FROM objects_history newer \
+                WHERE newer.checkpoint_sequence_number BETWEEN {first} AND {checkpoint} \
+                AND newer.object_id = candidates.object_id \
+                AND newer.object_version > candidates.object_version \
+            ) \
+            ORDER BY candidates.object_id, candidates.object_version DESC \
+        ) objects \
+        WHERE object_status = {} \
+        ORDER BY {order} \
+        LIMIT {limit}",
+        ObjectStatus::Active as i16,
+    )
+}
+
+/// SQL condition for objects owned by `owner_id`, with an ID greater than `cursor`.
+fn owner_condition(owner_type: OwnerType, owner_id: &[u8], cursor: Option<ObjectID>) -> String {
+    let mut condition = format!(
+        "owner_type = {} AND owner_id = {}",
+        owner_type as i16,
+        bytea_literal(owner_id),
+    );
+    if let Some(cursor) = cursor {
+        condition += &format!(" AND object_id > {}", bytea_literal(cursor.as_ref()));
+    }
+    condition
+}
+
+/// SQL condition for objects matching `filter`, which supports the same struct type filters as
+/// `get_owned_objects`.
+fn object_filter_condition(filter: &SuiObjectDataFilter) -> Result<String, IndexerError> {
+    let type_condition = |filter: &SuiObjectDataFilter, op: &str| {
+        match filter {
+        SuiObjectDataFilter::StructType(struct_tag) => Ok(format!(
+            "object_type {op} '{}%'",
+            struct_tag.to_canonical_string(/* with_prefix */ true)
+        )),
+        _ => Err(IndexerError::InvalidArgumentError(
+            "Invalid filter type. Only struct, MatchAny and MatchNone of struct filters are supported.".into(),
+        )),
+    }
+    };
+    Ok(match filter {
+        SuiObjectDataFilter::MatchAny(filters) if filters.is_empty() => "FALSE".to_string(),
+        SuiObjectDataFilter::MatchAny(filters) => format!(
+            "({})",
+            filters
+                .iter()
+                .map(|f| type_condition(f, "LIKE"))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(" OR ")
+        ),
+        SuiObjectDataFilter::MatchNone(filters) if filters.is_empty() => "TRUE".to_string(),
+        SuiObjectDataFilter::MatchNone(filters) => filters
+            .iter()
+            .map(|f| type_condition(f, "NOT LIKE"))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(" AND "),
+        filter => type_condition(filter, "LIKE")?,
+    })
+}
+
+fn bytea_literal(bytes: &[u8]) -> String {
+    format!("'\\x{}'::BYTEA", Hex::encode(bytes))
+}
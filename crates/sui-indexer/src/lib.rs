@@ -29,6 +29,7 @@ use crate::apis::{
     CoinReadApi, ExtendedApi, GovernanceReadApi, IndexerApi, MoveUtilsApi, ReadApi,
     TransactionBuilderApi, WriteApi,
 };
+use crate::index_filter::IndexFilterConfig;
 use crate::indexer_reader::IndexerReader;
 use errors::IndexerError;
 
@@ -36,6 +37,7 @@ pub mod apis;
 pub mod db;
 pub mod errors;
 pub mod handlers;
+pub mod index_filter;
 pub mod indexer;
 pub mod indexer_reader;
 pub mod metrics;
@@ -91,6 +93,18 @@ pub struct IndexerConfig {
     pub name_service_registry_id: Option<ObjectID>,
     #[clap(long)]
     pub name_service_reverse_registry_id: Option<ObjectID>,
+    /// Before tailing, index every checkpoint up to and including this one in parallel, using
+    /// bulk writes, starting from the first checkpoint that has not been indexed.
+    #[clap(long)]
+    pub backfill_end_checkpoint: Option<u64>,
+    /// Number of workers to split the backfill range across.
+    #[clap(long, default_value = "8")]
+    pub backfill_workers: usize,
+    /// Number of checkpoints each backfill worker writes in one DB transaction.
+    #[clap(long, default_value = "100")]
+    pub backfill_batch_size: usize,
+    #[clap(flatten)]
+    pub index_filter: IndexFilterConfig,
 }
 
 impl IndexerConfig {
@@ -147,6 +161,10 @@ impl Default for IndexerConfig {
             name_service_package_address: None,
             name_service_registry_id: None,
             name_service_reverse_registry_id: None,
+            backfill_end_checkpoint: None,
+            backfill_workers: 8,
+            backfill_batch_size: 100,
+            index_filter: IndexFilterConfig::default(),
         }
     }
 }
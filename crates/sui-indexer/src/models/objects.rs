@@ -180,6 +180,44 @@ impl From<StoredObject> for StoredHistoryObject {
     }
 }
 
+impl TryFrom<StoredHistoryObject> for StoredObject {
+    type Error = IndexerError;
+
+    fn try_from(o: StoredHistoryObject) -> Result<Self, Self::Error> {
+        let missing = |field: &str| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Object {:?} at version {} is missing its {field}",
+                o.object_id, o.object_version,
+            ))
+        };
+        if o.object_status != ObjectStatus::Active as i16 {
+            return Err(IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Object {:?} at version {} is not live",
+                o.object_id, o.object_version,
+            )));
+        }
+        Ok(Self {
+            object_digest: o.object_digest.ok_or_else(|| missing("digest"))?,
+            owner_type: o.owner_type.ok_or_else(|| missing("owner type"))?,
+            serialized_object: o.serialized_object.ok_or_else(|| missing("contents"))?,
+            object_id: o.object_id,
+            object_version: o.object_version,
+            checkpoint_sequence_number: o.checkpoint_sequence_number,
+            owner_id: o.owner_id,
+            object_type: o.object_type,
+            object_type_package: o.object_type_package,
+            object_type_module: o.object_type_module,
+            object_type_name: o.object_type_name,
+            coin_type: o.coin_type,
+            coin_balance: o.coin_balance,
+            df_kind: o.df_kind,
+            df_name: o.df_name,
+            df_object_type: o.df_object_type,
+            df_object_id: o.df_object_id,
+        })
+    }
+}
+
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
 #[diesel(table_name = objects, primary_key(object_id))]
 pub struct StoredDeletedObject {
@@ -631,4 +669,27 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_history_object_round_trip() {
+        let test_obj = Object::new_gas_for_testing();
+        let indexed_obj = IndexedObject::from_object(1, test_obj, None);
+        let stored_obj = StoredObject::from(indexed_obj);
+
+        let history_obj = StoredHistoryObject::from(stored_obj.clone());
+        let round_tripped = StoredObject::try_from(history_obj.clone()).unwrap();
+        assert_eq!(round_tripped.object_id, stored_obj.object_id);
+        assert_eq!(round_tripped.object_digest, stored_obj.object_digest);
+        assert_eq!(
+            round_tripped.serialized_object,
+            stored_obj.serialized_object
+        );
+
+        // Only live objects can be read back as objects
+        let wrapped_obj = StoredHistoryObject {
+            object_status: ObjectStatus::WrappedOrDeleted as i16,
+            ..history_obj
+        };
+        assert!(StoredObject::try_from(wrapped_obj).is_err());
+    }
 }
@@ -7,7 +7,7 @@ use std::collections::BTreeMap;
 use async_trait::async_trait;
 
 use crate::errors::IndexerError;
-use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
+use crate::handlers::{CheckpointDataToCommit, EpochToCommit, TransactionObjectChangesToCommit};
 use crate::models::display::StoredDisplay;
 use crate::models::objects::{StoredDeletedObject, StoredObject};
 use crate::types::{IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex};
@@ -75,5 +75,34 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
         epoch: u64,
     ) -> Result<u64, IndexerError>;
 
+    // the lowest checkpoint that has not been committed, even if later ones have been
+    async fn get_first_missing_checkpoint_sequence_number(&self) -> Result<u64, IndexerError>;
+
+    async fn get_committed_checkpoints_in_range(
+        &self,
+        start_cp: u64,
+        end_cp: u64,
+    ) -> Result<Vec<u64>, IndexerError>;
+
+    async fn get_latest_object_checkpoint_sequence_number(
+        &self,
+    ) -> Result<Option<u64>, IndexerError>;
+
+    // persist checkpoints in bulk during backfill, skipping `objects` and `objects_snapshot`
+    async fn persist_checkpoints_in_bulk(
+        &self,
+        checkpoints: Vec<CheckpointDataToCommit>,
+    ) -> Result<(), IndexerError>;
+
+    // update objects from objects history after backfill is done
+    async fn update_objects_from_history(
+        &self,
+        start_cp: u64,
+        end_cp: u64,
+    ) -> Result<(), IndexerError>;
+
+    // persist the latest versions of system packages from objects after backfill is done
+    async fn persist_system_packages_from_objects(&self) -> Result<(), IndexerError>;
+
     fn as_any(&self) -> &dyn Any;
 }
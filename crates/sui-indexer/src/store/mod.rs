@@ -229,6 +229,21 @@ pub mod diesel_macro {
         }};
     }
 
+    /// Insert `$values` into `$table`. Unlike `insert_or_ignore_into!`, this fails if any of the
+    /// rows already exist, so callers must make sure that they are new.
+    #[macro_export]
+    macro_rules! insert_new_into {
+        ($table:expr, $values:expr, $conn:expr) => {{
+            use diesel::RunQueryDsl;
+            let error_message = concat!("Failed to write to ", stringify!($table), " DB");
+            diesel::insert_into($table)
+                .values($values)
+                .execute($conn)
+                .map_err(IndexerError::from)
+                .context(error_message)?;
+        }};
+    }
+
     #[macro_export]
     macro_rules! on_conflict_do_update {
         ($table:expr, $values:expr, $target:expr, $pg_columns:expr, $mysql_columns:expr, $conn:expr) => {{
@@ -11,6 +11,7 @@ use diesel::{QueryDsl, RunQueryDsl};
 
 use move_core_types::account_address::AccountAddress;
 use sui_package_resolver::{error::Error as PackageResolverError, Package, PackageStore};
+use sui_rest_api::Client;
 use sui_types::base_types::ObjectID;
 use sui_types::object::Object;
 
@@ -123,3 +124,36 @@ impl<T: R2D2Connection> PackageStore for InterimPackageResolver<T> {
         }
     }
 }
+
+/// A package resolver that reads packages from a fullnode. Used by backfill, where packages may be
+/// published by a checkpoint that another worker has yet to commit, and the `objects` table that
+/// [`IndexerStorePackageResolver`] reads from is only populated once backfill is done.
+///
+/// System packages are upgraded in place, so this returns their latest version, even when
+/// indexing older checkpoints. This is fine for resolving layouts, because upgrades must preserve
+/// the layouts of existing types.
+#[derive(Clone)]
+pub struct RemotePackageResolver {
+    client: Client,
+}
+
+impl RemotePackageResolver {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl PackageStore for RemotePackageResolver {
+    async fn fetch(&self, id: AccountAddress) -> Result<Arc<Package>, PackageResolverError> {
+        let object = self
+            .client
+            .get_object(ObjectID::from(id))
+            .await
+            .map_err(|e| PackageResolverError::Store {
+                store: "Fullnode",
+                source: Arc::new(IndexerError::FullNodeReadingError(e.to_string())),
+            })?;
+        Ok(Arc::new(Package::read_from_object(&object)?))
+    }
+}
@@ -14,16 +14,19 @@ use diesel::dsl::max;
 use diesel::r2d2::R2D2Connection;
 use diesel::ExpressionMethods;
 use diesel::OptionalExtension;
-use diesel::{QueryDsl, RunQueryDsl};
+use diesel::{QueryDsl, QueryableByName, RunQueryDsl};
 use downcast::Any;
 use itertools::Itertools;
 use tap::TapFallible;
 use tracing::info;
 
 use sui_types::base_types::ObjectID;
+use sui_types::object::Object;
+use sui_types::{is_system_package, SYSTEM_PACKAGE_ADDRESSES};
 
 use crate::db::ConnectionPool;
 use crate::errors::{Context, IndexerError};
+use crate::handlers::CheckpointDataToCommit;
 use crate::handlers::EpochToCommit;
 use crate::handlers::TransactionObjectChangesToCommit;
 use crate::metrics::IndexerMetrics;
@@ -44,7 +47,7 @@ use crate::schema::{
 };
 use crate::types::{IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex};
 use crate::{
-    insert_or_ignore_into, on_conflict_do_update, read_only_blocking,
+    insert_new_into, insert_or_ignore_into, on_conflict_do_update, read_only_blocking,
     transactional_blocking_with_retry,
 };
 
@@ -112,6 +115,81 @@ SET object_version = EXCLUDED.object_version,
     df_object_id = EXCLUDED.df_object_id;
 ";
 
+// Like `UPDATE_OBJECTS_SNAPSHOT_QUERY`, but for the live `objects` table, which only holds active
+// objects, so the latest versions that were deleted or wrapped are removed by
+// `DELETE_OBJECTS_FROM_HISTORY_QUERY` instead. Rows are only replaced by newer versions, so that
+// re-applying a range is harmless.
+const UPDATE_OBJECTS_FROM_HISTORY_QUERY: &str = r"
+INSERT INTO objects (object_id, object_version, object_digest, checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, df_name, df_object_type, df_object_id)
+SELECT object_id, object_version, object_digest, checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, df_name, df_object_type, df_object_id
+FROM (
+    SELECT *,
+           ROW_NUMBER() OVER (PARTITION BY object_id ORDER BY object_version DESC) as rn
+    FROM objects_history
+    WHERE checkpoint_sequence_number >= $1 AND checkpoint_sequence_number < $2
+) as subquery
+WHERE rn = 1 AND object_status = 0
+ON CONFLICT (object_id) DO UPDATE
+SET object_version = EXCLUDED.object_version,
+    object_digest = EXCLUDED.object_digest,
+    checkpoint_sequence_number = EXCLUDED.checkpoint_sequence_number,
+    owner_type = EXCLUDED.owner_type,
+    owner_id = EXCLUDED.owner_id,
+    object_type = EXCLUDED.object_type,
+    object_type_package = EXCLUDED.object_type_package,
+    object_type_module = EXCLUDED.object_type_module,
+    object_type_name = EXCLUDED.object_type_name,
+    serialized_object = EXCLUDED.serialized_object,
+    coin_type = EXCLUDED.coin_type,
+    coin_balance = EXCLUDED.coin_balance,
+    df_kind = EXCLUDED.df_kind,
+    df_name = EXCLUDED.df_name,
+    df_object_type = EXCLUDED.df_object_type,
+    df_object_id = EXCLUDED.df_object_id
+WHERE objects.object_version < EXCLUDED.object_version;
+";
+
+const DELETE_OBJECTS_FROM_HISTORY_QUERY: &str = r"
+DELETE FROM objects
+USING (
+    SELECT object_id, object_version, object_status,
+           ROW_NUMBER() OVER (PARTITION BY object_id ORDER BY object_version DESC) as rn
+    FROM objects_history
+    WHERE checkpoint_sequence_number >= $1 AND checkpoint_sequence_number < $2
+) as subquery
+WHERE subquery.rn = 1
+  AND subquery.object_status = 1
+  AND objects.object_id = subquery.object_id
+  AND objects.object_version < subquery.object_version;
+";
+
+// The lowest checkpoint sequence number that has not been committed, which is where the indexer
+// needs to resume from, even if later checkpoints have been committed by backfill workers.
+const FIRST_MISSING_CHECKPOINT_QUERY: &str = r"
+SELECT CASE
+    WHEN NOT EXISTS (SELECT 1 FROM checkpoints WHERE sequence_number = 0) THEN 0
+    ELSE (
+        SELECT MIN(c.sequence_number) + 1
+        FROM checkpoints c
+        WHERE NOT EXISTS (
+            SELECT 1 FROM checkpoints n WHERE n.sequence_number = c.sequence_number + 1
+        )
+    )
+END AS sequence_number;
+";
+
+// Display updates from backfill workers can arrive out of order, so only newer versions replace
+// existing rows.
+const BACKFILL_DISPLAY_QUERY: &str = r"
+INSERT INTO display (object_type, id, version, bcs)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT (object_type) DO UPDATE
+SET id = EXCLUDED.id,
+    version = EXCLUDED.version,
+    bcs = EXCLUDED.bcs
+WHERE display.version < EXCLUDED.version;
+";
+
 #[derive(Clone)]
 pub struct PgIndexerStoreConfig {
     pub parallel_chunk_size: usize,
@@ -513,6 +591,232 @@ impl<T: R2D2Connection + 'static> PgIndexerStore<T> {
         Ok(())
     }
 
+    fn update_objects_from_history(&self, start_cp: u64, end_cp: u64) -> Result<(), IndexerError> {
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                RunQueryDsl::execute(
+                    diesel::sql_query(UPDATE_OBJECTS_FROM_HISTORY_QUERY)
+                        .bind::<diesel::sql_types::BigInt, _>(start_cp as i64)
+                        .bind::<diesel::sql_types::BigInt, _>(end_cp as i64),
+                    conn,
+                )?;
+                RunQueryDsl::execute(
+                    diesel::sql_query(DELETE_OBJECTS_FROM_HISTORY_QUERY)
+                        .bind::<diesel::sql_types::BigInt, _>(start_cp as i64)
+                        .bind::<diesel::sql_types::BigInt, _>(end_cp as i64),
+                    conn,
+                )
+            },
+            PG_DB_COMMIT_SLEEP_DURATION
+        )?;
+        Ok(())
+    }
+
+    fn get_latest_object_checkpoint_sequence_number(&self) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            objects::dsl::objects
+                .select(max(objects::checkpoint_sequence_number))
+                .first::<Option<i64>>(conn)
+                .map(|v| v.map(|v| v as u64))
+        })
+        .context("Failed reading latest object checkpoint sequence number from PostgresDB")
+    }
+
+    fn get_first_missing_checkpoint_sequence_number(&self) -> Result<u64, IndexerError> {
+        #[derive(QueryableByName)]
+        struct FirstMissingCheckpoint {
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            sequence_number: i64,
+        }
+
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            diesel::sql_query(FIRST_MISSING_CHECKPOINT_QUERY)
+                .get_result::<FirstMissingCheckpoint>(conn)
+        })
+        .context("Failed reading first missing checkpoint sequence number from PostgresDB")
+        .map(|c| c.sequence_number as u64)
+    }
+
+    fn get_committed_checkpoints_in_range(
+        &self,
+        start_cp: u64,
+        end_cp: u64,
+    ) -> Result<Vec<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            checkpoints::dsl::checkpoints
+                .select(checkpoints::sequence_number)
+                .filter(checkpoints::sequence_number.between(start_cp as i64, end_cp as i64))
+                .load::<i64>(conn)
+        })
+        .context("Failed reading committed checkpoints from PostgresDB")
+        .map(|seqs| seqs.into_iter().map(|s| s as u64).collect())
+    }
+
+    fn persist_checkpoints_in_bulk(
+        &self,
+        checkpoints: Vec<CheckpointDataToCommit>,
+    ) -> Result<(), IndexerError> {
+        if checkpoints.is_empty() {
+            return Ok(());
+        }
+        let guard = self.metrics.checkpoint_db_commit_latency.start_timer();
+
+        let mut stored_checkpoints = vec![];
+        let mut stored_transactions = vec![];
+        let mut stored_events = vec![];
+        let mut indices = vec![];
+        let mut object_history_changes = vec![];
+        let mut display_updates = BTreeMap::new();
+        let mut stored_packages = vec![];
+        for checkpoint in checkpoints {
+            stored_checkpoints.push(StoredCheckpoint::from(&checkpoint.checkpoint));
+            stored_transactions.extend(checkpoint.transactions.iter().map(StoredTransaction::from));
+            stored_events.extend(checkpoint.events.into_iter().map(StoredEvent::from));
+            indices.extend(checkpoint.tx_indices);
+            display_updates.extend(checkpoint.display_updates);
+            object_history_changes.push(checkpoint.object_history_changes);
+            // System packages are upgraded in place, so their latest versions are written once
+            // backfill is done, rather than by whichever worker happens to commit last.
+            stored_packages.extend(
+                checkpoint
+                    .packages
+                    .into_iter()
+                    .filter(|p| !is_system_package(p.package_id))
+                    .map(StoredPackage::from),
+            );
+        }
+
+        let (mut senders, mut recipients, mut input_objects, mut changed_objects) =
+            (vec![], vec![], vec![], vec![]);
+        let (mut calls, mut digests) = (vec![], vec![]);
+        for index in indices {
+            let (s, r, i, c, f, d) = index.split();
+            senders.extend(s);
+            recipients.extend(r);
+            input_objects.extend(i);
+            changed_objects.extend(c);
+            calls.extend(f);
+            digests.extend(d);
+        }
+
+        let mut mutated_objects: Vec<StoredHistoryObject> = vec![];
+        let mut deleted_objects: Vec<StoredDeletedHistoryObject> = vec![];
+        for object in make_objects_history_to_commit(object_history_changes) {
+            match object {
+                ObjectChangeToCommit::MutatedObject(o) => mutated_objects.push(o.into()),
+                ObjectChangeToCommit::DeletedObject(o) => deleted_objects.push(o.into()),
+            }
+        }
+
+        let checkpoint_num = stored_checkpoints.len();
+        let tx_num = stored_transactions.len();
+
+        // Everything derived from a checkpoint is written in the same DB transaction as the
+        // checkpoint itself, so that a committed checkpoint is always complete, and backfill can
+        // resume by skipping committed checkpoints.
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                for chunk in stored_transactions.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(transactions::table, chunk, conn);
+                }
+                for chunk in stored_events.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(events::table, chunk, conn);
+                }
+                for chunk in senders.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(tx_senders::table, chunk, conn);
+                }
+                for chunk in recipients.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(tx_recipients::table, chunk, conn);
+                }
+                for chunk in input_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(tx_input_objects::table, chunk, conn);
+                }
+                for chunk in changed_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(tx_changed_objects::table, chunk, conn);
+                }
+                for chunk in calls.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(tx_calls::table, chunk, conn);
+                }
+                for chunk in digests.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(tx_digests::table, chunk, conn);
+                }
+                for chunk in mutated_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(objects_history::table, chunk, conn);
+                }
+                for chunk in deleted_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(objects_history::table, chunk, conn);
+                }
+                // Packages are immutable, apart from system packages, which are excluded above.
+                for chunk in stored_packages.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_or_ignore_into!(packages::table, chunk, conn);
+                }
+                for display in display_updates.values() {
+                    RunQueryDsl::execute(
+                        diesel::sql_query(BACKFILL_DISPLAY_QUERY)
+                            .bind::<diesel::sql_types::Text, _>(&display.object_type)
+                            .bind::<diesel::sql_types::Bytea, _>(&display.id)
+                            .bind::<diesel::sql_types::SmallInt, _>(display.version)
+                            .bind::<diesel::sql_types::Bytea, _>(&display.bcs),
+                        conn,
+                    )?;
+                }
+                for chunk in stored_checkpoints.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_new_into!(checkpoints::table, chunk, conn);
+                }
+                Ok::<(), IndexerError>(())
+            },
+            PG_DB_COMMIT_SLEEP_DURATION
+        )
+        .tap_ok(|_| {
+            let elapsed = guard.stop_and_record();
+            info!(
+                elapsed,
+                "Bulk persisted {} checkpoints with {} transactions", checkpoint_num, tx_num,
+            );
+        })
+        .tap_err(|e| {
+            tracing::error!("Failed to bulk persist checkpoints with error: {}", e);
+        })
+    }
+
+    fn persist_system_packages_from_objects(&self) -> Result<(), IndexerError> {
+        let package_ids = SYSTEM_PACKAGE_ADDRESSES
+            .iter()
+            .map(|addr| addr.to_vec())
+            .collect::<Vec<_>>();
+        let objects: Vec<(Vec<u8>, i64)> = read_only_blocking!(&self.blocking_cp, |conn| {
+            objects::dsl::objects
+                .select((
+                    objects::serialized_object,
+                    objects::checkpoint_sequence_number,
+                ))
+                .filter(objects::object_id.eq_any(package_ids))
+                .load::<(Vec<u8>, i64)>(conn)
+        })
+        .context("Failed reading system packages from PostgresDB")?;
+
+        let packages = objects
+            .into_iter()
+            .map(|(bcs, checkpoint_sequence_number)| {
+                let object: Object = bcs::from_bytes(&bcs)?;
+                let move_package = object.data.try_as_package().cloned().ok_or_else(|| {
+                    IndexerError::PersistentStorageDataCorruptionError(format!(
+                        "System package {} is not a package",
+                        object.id()
+                    ))
+                })?;
+                Ok(IndexedPackage {
+                    package_id: object.id(),
+                    move_package,
+                    checkpoint_sequence_number: checkpoint_sequence_number as u64,
+                })
+            })
+            .collect::<Result<Vec<_>, IndexerError>>()?;
+        self.persist_packages(packages)
+    }
+
     fn persist_checkpoints(&self, checkpoints: Vec<IndexedCheckpoint>) -> Result<(), IndexerError> {
         if checkpoints.is_empty() {
             return Ok(());
@@ -1438,6 +1742,60 @@ impl<T: R2D2Connection> IndexerStore for PgIndexerStore<T> {
         .await
     }
 
+    async fn get_first_missing_checkpoint_sequence_number(&self) -> Result<u64, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_first_missing_checkpoint_sequence_number())
+            .await
+    }
+
+    async fn get_committed_checkpoints_in_range(
+        &self,
+        start_cp: u64,
+        end_cp: u64,
+    ) -> Result<Vec<u64>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| {
+            this.get_committed_checkpoints_in_range(start_cp, end_cp)
+        })
+        .await
+    }
+
+    async fn get_latest_object_checkpoint_sequence_number(
+        &self,
+    ) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_latest_object_checkpoint_sequence_number())
+            .await
+    }
+
+    async fn persist_checkpoints_in_bulk(
+        &self,
+        checkpoints: Vec<CheckpointDataToCommit>,
+    ) -> Result<(), IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.persist_checkpoints_in_bulk(checkpoints))
+            .await
+    }
+
+    async fn update_objects_from_history(
+        &self,
+        start_cp: u64,
+        end_cp: u64,
+    ) -> Result<(), IndexerError> {
+        let now = Instant::now();
+        self.execute_in_blocking_worker(move |this| {
+            this.update_objects_from_history(start_cp, end_cp)
+        })
+        .await?;
+        let elapsed = now.elapsed().as_secs_f64();
+        info!(
+            elapsed,
+            "Updated objects for checkpoints from {} to {}", start_cp, end_cp
+        );
+        Ok(())
+    }
+
+    async fn persist_system_packages_from_objects(&self) -> Result<(), IndexerError> {
+        self.execute_in_blocking_worker(|this| this.persist_system_packages_from_objects())
+            .await
+    }
+
     fn as_any(&self) -> &dyn StdAny {
         self
     }
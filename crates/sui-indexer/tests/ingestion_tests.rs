@@ -14,16 +14,22 @@ mod ingestion_tests {
     use sui_indexer::db::get_pool_connection;
     use sui_indexer::errors::Context;
     use sui_indexer::errors::IndexerError;
+    use sui_indexer::indexer_reader::IndexerReader;
     use sui_indexer::models::{
         events::StoredEvent, objects::StoredObject, transactions::StoredTransaction,
     };
     use sui_indexer::schema::{events, objects, transactions};
     use sui_indexer::store::{indexer_store::IndexerStore, PgIndexerStore};
     use sui_indexer::test_utils::{start_test_indexer, ReaderWriterConfig};
-    use sui_types::base_types::SuiAddress;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
     use sui_types::effects::TransactionEffectsAPI;
-    use sui_types::gas_coin::GasCoin;
+    use sui_types::gas_coin::{GasCoin, MIST_PER_SUI};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
     use sui_types::storage::ReadStore;
+    use sui_types::transaction::{
+        Argument, Command, GasData, ObjectArg, Transaction, TransactionData, TransactionKind,
+    };
     use sui_types::{
         Identifier, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID,
     };
@@ -249,4 +255,101 @@ mod ingestion_tests {
         assert_eq!(db_object.object_type_name, Some("Coin".to_string()));
         Ok(())
     }
+
+    #[tokio::test]
+    pub async fn test_owned_objects_at_checkpoint() -> Result<(), IndexerError> {
+        let mut sim = Simulacrum::new();
+        let data_ingestion_path = tempdir().unwrap().into_path();
+        sim.set_data_ingestion_path(data_ingestion_path.clone());
+
+        // Give an account some coins in checkpoint 1.
+        let (owner, key): (SuiAddress, AccountKeyPair) = get_key_pair();
+        for _ in 0..5 {
+            sim.request_gas(owner, 10 * MIST_PER_SUI).unwrap();
+        }
+        sim.create_checkpoint();
+        let mut coins: Vec<_> = sim
+            .store()
+            .owned_objects(owner)
+            .map(|o| o.compute_object_reference())
+            .collect();
+        coins.sort();
+
+        // In checkpoint 2, use one of them as gas, transfer another away, and merge (delete) a
+        // third, and in checkpoint 3, give the account another coin.
+        let recipient = SuiAddress::random_for_testing_only();
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.transfer_object(recipient, coins[1]).unwrap();
+            let merged = builder.obj(ObjectArg::ImmOrOwnedObject(coins[2])).unwrap();
+            builder.command(Command::MergeCoins(Argument::GasCoin, vec![merged]));
+            builder.finish()
+        };
+        let gas_data = GasData {
+            payment: vec![coins[0]],
+            owner,
+            price: sim.reference_gas_price(),
+            budget: MIST_PER_SUI,
+        };
+        let tx_data = TransactionData::new_with_gas_data(
+            TransactionKind::ProgrammableTransaction(pt),
+            owner,
+            gas_data,
+        );
+        let tx = Transaction::from_data_and_signer(tx_data, vec![&key]);
+        let (effects, err) = sim.execute_transaction(tx).unwrap();
+        assert!(err.is_none());
+        assert!(effects.status().is_ok());
+        sim.create_checkpoint();
+
+        sim.request_gas(owner, 10 * MIST_PER_SUI).unwrap();
+        sim.create_checkpoint();
+
+        let (_, pg_store, _) = set_up(Arc::new(sim), data_ingestion_path).await;
+        wait_for_checkpoint(&pg_store, 3).await?;
+
+        // Page through the account's objects at checkpoint 1, two at a time.
+        let reader = IndexerReader::<diesel::PgConnection>::new(DEFAULT_DB_URL).unwrap();
+        let mut at_checkpoint = vec![];
+        let mut cursor = None;
+        loop {
+            let page = reader
+                .get_owned_objects_in_blocking_task(owner, None, cursor, 2, Some(1))
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = Some(ObjectID::from_bytes(&last.object_id).unwrap());
+            at_checkpoint.extend(page.into_iter().map(|o| {
+                (
+                    ObjectID::from_bytes(&o.object_id).unwrap(),
+                    o.object_version as u64,
+                )
+            }));
+        }
+
+        // The pages see the objects as they were at checkpoint 1, despite the later changes.
+        let expected: Vec<_> = coins.iter().map(|(id, v, _)| (*id, v.value())).collect();
+        assert_eq!(at_checkpoint, expected);
+
+        // Whereas the latest objects reflect them.
+        let latest = reader
+            .get_owned_objects_in_blocking_task(owner, None, None, 10, None)
+            .await?;
+        let latest_ids: Vec<_> = latest
+            .iter()
+            .map(|o| ObjectID::from_bytes(&o.object_id).unwrap())
+            .collect();
+        assert_eq!(latest_ids.len(), coins.len() - 1);
+        assert!(latest_ids.contains(&coins[0].0));
+        assert!(!latest_ids.contains(&coins[1].0));
+        assert!(!latest_ids.contains(&coins[2].0));
+
+        // Checkpoints outside of the indexed range are rejected.
+        assert!(reader
+            .get_owned_objects_in_blocking_task(owner, None, None, 10, Some(4))
+            .await
+            .is_err());
+        Ok(())
+    }
 }
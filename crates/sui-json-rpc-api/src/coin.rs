@@ -7,6 +7,7 @@ use sui_json_rpc_types::{Balance, CoinPage, SuiCoinMetadata};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Coin Query API")]
 #[rpc(server, client, namespace = "suix")]
@@ -23,6 +24,8 @@ pub trait CoinReadApi {
         cursor: Option<ObjectID>,
         /// maximum number of items per page
         limit: Option<usize>,
+        /// Optional checkpoint to read at. Pages read at the same checkpoint form a consistent snapshot, as long as the checkpoint is still within the range the node serves checkpoint-pinned reads for. Full nodes only serve reads at their highest executed checkpoint, and reject any other checkpoint.
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<CoinPage>;
 
     /// Return all Coin objects owned by an address.
@@ -35,6 +38,8 @@ pub trait CoinReadApi {
         cursor: Option<ObjectID>,
         /// maximum number of items per page
         limit: Option<usize>,
+        /// Optional checkpoint to read at. Pages read at the same checkpoint form a consistent snapshot, as long as the checkpoint is still within the range the node serves checkpoint-pinned reads for. Full nodes only serve reads at their highest executed checkpoint, and reject any other checkpoint.
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<CoinPage>;
 
     /// Return the total coin balance for one coin type, owned by the address owner.
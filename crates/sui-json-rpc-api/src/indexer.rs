@@ -15,6 +15,7 @@ use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
 #[rpc(server, client, namespace = "suix")]
@@ -22,8 +23,7 @@ pub trait IndexerApi {
     /// Return the list of objects owned by an address.
     /// Note that if the address owns more than `QUERY_MAX_RESULT_LIMIT` objects,
     /// the pagination is not accurate, because previous page may have been updated when
-    /// the next page is fetched.
-    /// Please use suix_queryObjects if this is a concern.
+    /// the next page is fetched, unless all pages are read at the same `at_checkpoint`.
     #[method(name = "getOwnedObjects")]
     async fn get_owned_objects(
         &self,
@@ -35,6 +35,8 @@ pub trait IndexerApi {
         cursor: Option<ObjectID>,
         /// Max number of items returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
         limit: Option<usize>,
+        /// Optional checkpoint to read at. Pages read at the same checkpoint form a consistent snapshot, as long as the checkpoint is still within the range the node serves checkpoint-pinned reads for. Full nodes only serve reads at their highest executed checkpoint, and reject any other checkpoint.
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<ObjectsPage>;
 
     /// Return list of transactions for a specified query criteria.
@@ -71,11 +73,18 @@ pub trait IndexerApi {
         &self,
         /// The filter criteria of the event stream. See [Event filter](https://docs.sui.io/build/event_api#event-filters) documentation for examples.
         filter: EventFilter,
+        /// An optional cursor to resume the stream from. If provided, the matching events after the cursor are replayed first, followed by new events without gaps or duplicates. Default to only stream new events if not specified.
+        cursor: Option<EventID>,
     );
 
     /// Subscribe to a stream of Sui transaction effects
     #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
-    fn subscribe_transaction(&self, filter: TransactionFilter);
+    fn subscribe_transaction(
+        &self,
+        filter: TransactionFilter,
+        /// An optional cursor to resume the stream from. If provided, the matching transactions after the cursor are replayed first, followed by new transactions without gaps or duplicates. Default to only stream new transactions if not specified.
+        cursor: Option<TransactionDigest>,
+    );
 
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
@@ -87,6 +96,8 @@ pub trait IndexerApi {
         cursor: Option<ObjectID>,
         /// Maximum item returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
         limit: Option<usize>,
+        /// Optional checkpoint to read at. Pages read at the same checkpoint form a consistent snapshot, as long as the checkpoint is still within the range the node serves checkpoint-pinned reads for. Full nodes only serve reads at their highest executed checkpoint, and reject any other checkpoint.
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<DynamicFieldPage>;
 
     /// Return the dynamic field object information for a specified object
@@ -57,6 +57,8 @@ pub trait ReadApi {
         object_ids: Vec<ObjectID>,
         /// options for specifying the content to be returned
         options: Option<SuiObjectDataOptions>,
+        /// Optional checkpoint to read at. Reads at the same checkpoint see a consistent snapshot, as long as the checkpoint is still within the range the node serves checkpoint-pinned reads for. Full nodes only serve reads at their highest executed checkpoint, and reject any other checkpoint.
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<Vec<SuiObjectResponse>>;
 
     /// Note there is no software-level guarantee/SLA that objects with past versions
@@ -28,6 +28,7 @@ use sui_types::coin::{TreasuryCap, COIN_MODULE_NAME};
 use sui_types::digests::ObjectDigest;
 use sui_types::gas_coin::GAS;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::sui_serde::BigInt;
 use sui_types::{parse_sui_struct_tag, SUI_FRAMEWORK_ADDRESS};
 use test_cluster::TestClusterBuilder;
 use tokio::time::sleep;
@@ -47,6 +48,7 @@ async fn test_get_objects() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?;
     assert_eq!(5, objects.data.len());
@@ -58,7 +60,9 @@ async fn test_get_objects() -> Result<(), anyhow::Error> {
         .map(|o| o.object().unwrap().object_id)
         .collect();
 
-    let object_resp = http_client.multi_get_objects(object_digests, None).await?;
+    let object_resp = http_client
+        .multi_get_objects(object_digests, None, None)
+        .await?;
     assert_eq!(5, object_resp.len());
     Ok(())
 }
@@ -102,6 +106,7 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -184,6 +189,7 @@ async fn test_publish() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?;
     let gas = objects.data.first().unwrap().object().unwrap();
@@ -238,6 +244,7 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -298,6 +305,7 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -330,6 +338,7 @@ async fn test_get_object_data_with_content() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -355,24 +364,26 @@ async fn test_get_coins() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client();
     let address = cluster.get_address_0();
 
-    let result: CoinPage = http_client.get_coins(address, None, None, None).await?;
+    let result: CoinPage = http_client
+        .get_coins(address, None, None, None, None)
+        .await?;
     assert_eq!(5, result.data.len());
     assert!(!result.has_next_page);
 
     let result: CoinPage = http_client
-        .get_coins(address, Some("0x2::sui::TestCoin".into()), None, None)
+        .get_coins(address, Some("0x2::sui::TestCoin".into()), None, None, None)
         .await?;
     assert_eq!(0, result.data.len());
 
     let result: CoinPage = http_client
-        .get_coins(address, Some("0x2::sui::SUI".into()), None, None)
+        .get_coins(address, Some("0x2::sui::SUI".into()), None, None, None)
         .await?;
     assert_eq!(5, result.data.len());
     assert!(!result.has_next_page);
 
     // Test paging
     let result: CoinPage = http_client
-        .get_coins(address, Some("0x2::sui::SUI".into()), None, Some(3))
+        .get_coins(address, Some("0x2::sui::SUI".into()), None, Some(3), None)
         .await?;
     assert_eq!(3, result.data.len());
     assert!(result.has_next_page);
@@ -383,6 +394,7 @@ async fn test_get_coins() -> Result<(), anyhow::Error> {
             Some("0x2::sui::SUI".into()),
             result.next_cursor,
             Some(3),
+            None,
         )
         .await?;
     assert_eq!(2, result.data.len(), "{:?}", result);
@@ -394,6 +406,7 @@ async fn test_get_coins() -> Result<(), anyhow::Error> {
             Some("0x2::sui::SUI".into()),
             result.next_cursor,
             None,
+            None,
         )
         .await?;
     assert_eq!(0, result.data.len(), "{:?}", result);
@@ -402,6 +415,59 @@ async fn test_get_coins() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_reads_at_unexecuted_checkpoint() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let http_client = cluster.rpc_client();
+    let address = cluster.get_address_0();
+
+    // A full node only serves reads at its highest executed checkpoint, which can never be this
+    // one.
+    let at_checkpoint = Some(BigInt::from(u64::MAX));
+    let unsupported = "is not supported by this node";
+
+    let err = http_client
+        .get_coins(address, None, None, None, at_checkpoint)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(unsupported), "{err}");
+
+    let err = http_client
+        .get_all_coins(address, None, None, at_checkpoint)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(unsupported), "{err}");
+
+    let err = http_client
+        .get_owned_objects(address, None, None, None, at_checkpoint)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(unsupported), "{err}");
+
+    let err = http_client
+        .get_dynamic_fields(
+            ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+            None,
+            None,
+            at_checkpoint,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(unsupported), "{err}");
+
+    let err = http_client
+        .multi_get_objects(
+            vec![ObjectID::from(SUI_FRAMEWORK_ADDRESS)],
+            None,
+            at_checkpoint,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(unsupported), "{err}");
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_get_balance() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await;
@@ -441,6 +507,7 @@ async fn test_get_metadata() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -526,6 +593,7 @@ async fn test_get_total_supply() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -663,6 +731,7 @@ async fn test_staking() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?;
     assert_eq!(5, objects.data.len());
@@ -733,7 +802,9 @@ async fn test_unstaking() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client();
     let address = cluster.get_address_0();
 
-    let coins: CoinPage = http_client.get_coins(address, None, None, None).await?;
+    let coins: CoinPage = http_client
+        .get_coins(address, None, None, None, None)
+        .await?;
     assert_eq!(5, coins.data.len());
 
     // Check StakedSui object before test
@@ -866,7 +937,9 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client();
     let address = cluster.get_address_0();
 
-    let coins: CoinPage = http_client.get_coins(address, None, None, None).await?;
+    let coins: CoinPage = http_client
+        .get_coins(address, None, None, None, None)
+        .await?;
     assert_eq!(5, coins.data.len());
 
     let genesis_coin_amount = coins.data[0].balance;
@@ -940,7 +1013,9 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
     ));
 
     // Coins should be merged into one and returned to the sender.
-    let coins: CoinPage = http_client.get_coins(address, None, None, None).await?;
+    let coins: CoinPage = http_client
+        .get_coins(address, None, None, None, None)
+        .await?;
     assert_eq!(3, coins.data.len());
 
     // Find the new coin
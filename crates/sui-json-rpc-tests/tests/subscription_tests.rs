@@ -10,7 +10,8 @@ use tokio::time::timeout;
 
 use sui_core::test_utils::wait_for_tx;
 use sui_json_rpc_types::{
-    SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, TransactionFilter,
+    EventFilter, SuiEvent, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    TransactionFilter,
 };
 use test_cluster::TestClusterBuilder;
 
@@ -46,3 +47,88 @@ async fn test_subscribe_transaction() -> Result<(), anyhow::Error> {
     assert_eq!(&digest, effects.transaction_digest());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_resume_transaction_subscription() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+
+    let address = &cluster.get_address_0();
+    let wallet = cluster.wallet;
+
+    let ws_client = cluster.fullnode_handle.ws_client().await;
+
+    let package_id = publish_nfts_package(&wallet).await.0;
+    let (_, _, cursor) = create_devnet_nft(&wallet, package_id).await;
+    let (_, _, missed) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(missed, cluster.fullnode_handle.sui_node.state()).await;
+
+    let mut sub: Subscription<SuiTransactionBlockEffects> = ws_client
+        .subscribe(
+            "suix_subscribeTransaction",
+            rpc_params![TransactionFilter::FromAddress(*address), cursor],
+            "suix_unsubscribeTransaction",
+        )
+        .await
+        .unwrap();
+
+    let (_, _, digest) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(digest, cluster.fullnode_handle.sui_node.state()).await;
+
+    // The transaction after the cursor is replayed first, followed by the new one, once.
+    for expected in [missed, digest] {
+        let effects = match timeout(Duration::from_secs(5), sub.next()).await {
+            Ok(Some(Ok(tx))) => tx,
+            _ => panic!("Failed to get tx"),
+        };
+        assert_eq!(&expected, effects.transaction_digest());
+    }
+    assert!(timeout(Duration::from_secs(1), sub.next()).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_event_subscription() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+
+    let address = &cluster.get_address_0();
+    let client = cluster.sui_client().clone();
+    let wallet = cluster.wallet;
+
+    let ws_client = cluster.fullnode_handle.ws_client().await;
+
+    let package_id = publish_nfts_package(&wallet).await.0;
+    let (_, _, cursor_tx) = create_devnet_nft(&wallet, package_id).await;
+    let (_, _, missed) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(missed, cluster.fullnode_handle.sui_node.state()).await;
+
+    // Resume after the event emitted by the first mint.
+    let cursor = client
+        .event_api()
+        .query_events(EventFilter::Transaction(cursor_tx), None, None, false)
+        .await?
+        .data[0]
+        .id;
+
+    let mut sub: Subscription<SuiEvent> = ws_client
+        .subscribe(
+            "suix_subscribeEvent",
+            rpc_params![EventFilter::Sender(*address), cursor],
+            "suix_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+
+    let (_, _, digest) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(digest, cluster.fullnode_handle.sui_node.state()).await;
+
+    // The event after the cursor is replayed first, followed by the new one, once.
+    for expected in [missed, digest] {
+        let event = match timeout(Duration::from_secs(5), sub.next()).await {
+            Ok(Some(Ok(event))) => event,
+            _ => panic!("Failed to get event"),
+        };
+        assert_eq!(expected, event.id.tx_digest);
+    }
+    assert!(timeout(Duration::from_secs(1), sub.next()).await.is_err());
+    Ok(())
+}
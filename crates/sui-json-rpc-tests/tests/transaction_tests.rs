@@ -42,6 +42,7 @@ async fn test_get_transaction_block() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -125,6 +126,7 @@ async fn test_get_raw_transaction() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
@@ -182,6 +184,7 @@ async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
                 )),
                 None,
                 None,
+                None,
             )
             .await?
             .data;
@@ -359,6 +362,7 @@ async fn test_query_transaction_blocks() -> Result<(), anyhow::Error> {
             )),
             None,
             None,
+            None,
         )
         .await?
         .data;
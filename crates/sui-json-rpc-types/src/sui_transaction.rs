@@ -1155,6 +1155,36 @@ pub struct DevInspectArgs {
     pub skip_checks: Option<bool>,
     /// Whether to return the raw transaction data and effects.
     pub show_raw_txn_data_and_effects: Option<bool>,
+    /// Changes to the state the transaction is executed against, for previewing what the
+    /// transaction would do under different circumstances. Signatures are never checked by dev
+    /// inspect, so the transaction does not need to be signed.
+    pub state_overrides: Option<StateOverrides>,
+}
+
+/// Changes applied to the inputs of a dev inspect transaction before it is executed. They only
+/// affect the simulated execution and are never written to the chain.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "StateOverrides", rename_all = "camelCase")]
+pub struct StateOverrides {
+    /// Replacement contents for input objects of the transaction.
+    #[serde(default)]
+    pub objects: Vec<ObjectContentsOverride>,
+    /// SUI (in MIST) to add to the gas coin, e.g. to account for a pending deposit. Only
+    /// supported if the sender pays for gas.
+    pub extra_sender_balance: Option<BigInt<u64>>,
+    /// The timestamp to set on the `Clock` object, which must be an input of the transaction.
+    pub clock_timestamp_ms: Option<BigInt<u64>>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectContentsOverride", rename_all = "camelCase")]
+pub struct ObjectContentsOverride {
+    pub object_id: ObjectID,
+    /// BCS bytes of the new contents of the object, which must keep the object's type and ID.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub bcs_bytes: Vec<u8>,
 }
 
 /// The response from processing a dev inspect transaction
@@ -12,8 +12,8 @@ use sui_core::authority::AuthorityState;
 use sui_core::execution_cache::ObjectCacheRead;
 use sui_core::subscription_handler::SubscriptionHandler;
 use sui_json_rpc_types::{
-    Coin as SuiCoin, DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent,
-    SuiObjectDataFilter, TransactionFilter,
+    Coin as SuiCoin, DevInspectResults, DryRunTransactionBlockResponse, EventFilter,
+    StateOverrides, SuiEvent, SuiObjectDataFilter, TransactionFilter,
 };
 use sui_storage::indexes::TotalBalance;
 use sui_storage::key_value_store::{
@@ -46,6 +46,7 @@ use tokio::task::JoinError;
 #[cfg(test)]
 use mockall::automock;
 
+use crate::error::{Error, SuiRpcInputError};
 use crate::ObjectProvider;
 
 pub type StateReadResult<T = ()> = Result<T, StateReadError>;
@@ -134,6 +135,7 @@ pub trait StateRead: Send + Sync {
         gas_objects: Option<Vec<ObjectRef>>,
         show_raw_txn_data_and_effects: Option<bool>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
     ) -> StateReadResult<DevInspectResults>;
 
     // indexer_api
@@ -243,6 +245,25 @@ pub trait StateRead: Send + Sync {
     fn get_chain_identifier(&self) -> StateReadResult<ChainIdentifier>;
 }
 
+/// A full node only indexes its latest state, so a read pinned to `at_checkpoint` is only served
+/// if that is the node's highest executed checkpoint.
+pub(crate) fn check_checkpoint_pinned_read(
+    state: &dyn StateRead,
+    at_checkpoint: Option<BigInt<u64>>,
+) -> Result<(), Error> {
+    let Some(at_checkpoint) = at_checkpoint.map(BigInt::into_inner) else {
+        return Ok(());
+    };
+    let latest = state.get_latest_checkpoint_sequence_number()?;
+    if at_checkpoint != latest {
+        Err(SuiRpcInputError::CheckpointPinnedReadsUnsupported(
+            at_checkpoint,
+            latest,
+        ))?
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl StateRead for AuthorityState {
     async fn multi_get(
@@ -373,6 +394,7 @@ impl StateRead for AuthorityState {
         gas_objects: Option<Vec<ObjectRef>>,
         show_raw_txn_data_and_effects: Option<bool>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
     ) -> StateReadResult<DevInspectResults> {
         Ok(self
             .dev_inspect_transaction_block(
@@ -384,6 +406,7 @@ impl StateRead for AuthorityState {
                 gas_objects,
                 show_raw_txn_data_and_effects,
                 skip_checks,
+                state_overrides,
             )
             .await?)
     }
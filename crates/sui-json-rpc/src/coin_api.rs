@@ -28,11 +28,12 @@ use sui_types::effects::TransactionEffectsAPI;
 use sui_types::gas_coin::{GAS, TOTAL_SUPPLY_MIST};
 use sui_types::object::Object;
 use sui_types::parse_sui_struct_tag;
+use sui_types::sui_serde::BigInt;
 
 #[cfg(test)]
 use mockall::automock;
 
-use crate::authority_state::StateRead;
+use crate::authority_state::{check_checkpoint_pinned_read, StateRead};
 use crate::error::{Error, RpcInterimResult, SuiRpcInputError};
 use crate::{with_tracing, SuiRpcModule};
 
@@ -89,8 +90,10 @@ impl CoinReadApiServer for CoinReadApi {
         // exclusive cursor if `Some`, otherwise start from the beginning
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<CoinPage> {
         with_tracing!(async move {
+            check_checkpoint_pinned_read(self.internal.get_state().as_ref(), at_checkpoint)?;
             let coin_type_tag = parse_to_type_tag(coin_type)?;
 
             let cursor = match cursor {
@@ -114,8 +117,10 @@ impl CoinReadApiServer for CoinReadApi {
         // exclusive cursor if `Some`, otherwise start from the beginning
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<CoinPage> {
         with_tracing!(async move {
+            check_checkpoint_pinned_read(self.internal.get_state().as_ref(), at_checkpoint)?;
             let cursor = match cursor {
                 Some(object_id) => {
                     let obj = self.internal.get_object(&object_id).await?;
@@ -580,7 +585,7 @@ mod tests {
                 .return_once(move |_, _, _, _| Ok(vec![gas_coin_clone]));
 
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
-            let response = coin_read_api.get_coins(owner, None, None, None).await;
+            let response = coin_read_api.get_coins(owner, None, None, None, None).await;
             assert!(response.is_ok());
             let result = response.unwrap();
             assert_eq!(
@@ -616,7 +621,13 @@ mod tests {
 
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, None, Some(coins[0].coin_object_id), Some(limit))
+                .get_coins(
+                    owner,
+                    None,
+                    Some(coins[0].coin_object_id),
+                    Some(limit),
+                    None,
+                )
                 .await;
             assert!(response.is_ok());
             let result = response.unwrap();
@@ -653,7 +664,7 @@ mod tests {
 
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, Some(coin_type), None, None)
+                .get_coins(owner, Some(coin_type), None, None, None)
                 .await;
 
             assert!(response.is_ok());
@@ -697,7 +708,7 @@ mod tests {
 
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, Some(coin_type), Some(cursor), Some(limit))
+                .get_coins(owner, Some(coin_type), Some(cursor), Some(limit), None)
                 .await;
 
             assert!(response.is_ok());
@@ -720,7 +731,7 @@ mod tests {
             let mock_state = MockStateRead::new();
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, Some(coin_type.to_string()), None, None)
+                .get_coins(owner, Some(coin_type.to_string()), None, None, None)
                 .await;
 
             assert!(response.is_err());
@@ -739,7 +750,7 @@ mod tests {
             let mock_state = MockStateRead::new();
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, Some(coin_type.to_string()), None, None)
+                .get_coins(owner, Some(coin_type.to_string()), None, None, None)
                 .await;
 
             assert!(response.is_err());
@@ -752,6 +763,49 @@ mod tests {
             expected.assert_eq(error_object.message());
         }
 
+        #[tokio::test]
+        async fn test_get_coins_at_checkpoint_unsupported() {
+            let owner = get_test_owner();
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_latest_checkpoint_sequence_number()
+                .return_once(|| Ok(2));
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_coins(owner, None, None, None, Some(BigInt::from(1)))
+                .await;
+
+            assert!(response.is_err());
+            let error_result = response.unwrap_err();
+            let error_object: ErrorObjectOwned = error_result.into();
+            assert_eq!(
+                error_object.code(),
+                jsonrpsee::types::error::INVALID_PARAMS_CODE
+            );
+            let expected = expect!["Reading at checkpoint 1 is not supported by this node, which only serves reads at its latest executed checkpoint: 2"];
+            expected.assert_eq(error_object.message());
+        }
+
+        #[tokio::test]
+        async fn test_get_coins_at_latest_checkpoint() {
+            let owner = get_test_owner();
+            let gas_coin = get_test_coin(None, CoinType::Gas);
+            let gas_coin_clone = gas_coin.clone();
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_latest_checkpoint_sequence_number()
+                .return_once(|| Ok(2));
+            mock_state
+                .expect_get_owned_coins()
+                .return_once(move |_, _, _, _| Ok(vec![gas_coin_clone]));
+
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_coins(owner, None, None, None, Some(BigInt::from(2)))
+                .await;
+            assert_eq!(response.unwrap().data, vec![gas_coin]);
+        }
+
         // Unexpected error scenarios
         #[tokio::test]
         async fn test_get_coins_iterator_index_store_not_available() {
@@ -767,7 +821,7 @@ mod tests {
                 });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, Some(coin_type.to_string()), None, None)
+                .get_coins(owner, Some(coin_type.to_string()), None, None, None)
                 .await;
 
             assert!(response.is_err());
@@ -793,7 +847,7 @@ mod tests {
                 });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_coins(owner, Some(coin_type.to_string()), None, None)
+                .get_coins(owner, Some(coin_type.to_string()), None, None, None)
                 .await;
 
             assert!(response.is_err());
@@ -832,7 +886,7 @@ mod tests {
                 .return_once(move |_, _, _, _| Ok(vec![gas_coin_clone]));
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_all_coins(owner, None, Some(51))
+                .get_all_coins(owner, None, Some(51), None)
                 .await
                 .unwrap();
             assert_eq!(response.data.len(), 1);
@@ -874,7 +928,7 @@ mod tests {
                 .return_once(move |_, _, _, _| Ok(coins_clone));
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_all_coins(owner, Some(coins[0].coin_object_id), Some(limit))
+                .get_all_coins(owner, Some(coins[0].coin_object_id), Some(limit), None)
                 .await
                 .unwrap();
             assert_eq!(response.data.len(), limit);
@@ -897,7 +951,7 @@ mod tests {
             });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_all_coins(owner, Some(object_id), None)
+                .get_all_coins(owner, Some(object_id), None, None)
                 .await;
 
             assert!(response.is_err());
@@ -919,7 +973,7 @@ mod tests {
 
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_all_coins(owner, Some(object_id), None)
+                .get_all_coins(owner, Some(object_id), None, None)
                 .await;
 
             assert!(response.is_err());
@@ -275,6 +275,9 @@ pub enum SuiRpcInputError {
     #[error("Unsupported protocol version requested. Min supported: {0}, max supported: {1}")]
     ProtocolVersionUnsupported(u64, u64),
 
+    #[error("Reading at checkpoint {0} is not supported by this node, which only serves reads at its latest executed checkpoint: {1}")]
+    CheckpointPinnedReadsUnsupported(u64, u64),
+
     #[error("{0}")]
     CannotParseSuiStructTag(String),
 
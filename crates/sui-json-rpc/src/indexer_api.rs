@@ -1,14 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use std::collections::HashSet;
+use std::hash::Hash;
 use std::sync::Arc;
 
 use anyhow::bail;
 use async_trait::async_trait;
-use futures::{future, Stream};
+use futures::{future, stream, Stream, StreamExt};
 use jsonrpsee::{
-    core::{error::SubscriptionClosed, RpcResult},
-    types::SubscriptionResult,
+    core::{error::SubscriptionClosed, Error as RpcError, RpcResult},
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, SubscriptionResult},
     RpcModule, SubscriptionSink,
 };
 use move_bytecode_utils::layout::TypeLayoutBuilder;
@@ -16,15 +17,17 @@ use move_core_types::language_storage::TypeTag;
 use mysten_metrics::spawn_monitored_task;
 use serde::Serialize;
 use sui_core::authority::AuthorityState;
+use sui_core::subscription_handler::EVENT_DISPATCH_BUFFER_SIZE;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_api::{
     cap_page_limit, validate_limit, IndexerApiOpenRpc, IndexerApiServer, JsonRpcMetrics,
     ReadApiServer, QUERY_MAX_RESULT_LIMIT,
 };
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataOptions,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
+    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiEvent, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseQuery,
+    TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc::Module;
 use sui_storage::key_value_store::TransactionKeyValueStore;
@@ -32,14 +35,15 @@ use sui_types::{
     base_types::{ObjectID, SuiAddress},
     digests::TransactionDigest,
     dynamic_field::{DynamicFieldName, Field},
-    error::SuiObjectResponseError,
+    error::{SuiError, SuiObjectResponseError},
     event::EventID,
+    sui_serde::BigInt,
 };
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, instrument, warn};
 
 use crate::{
-    authority_state::{StateRead, StateReadResult},
+    authority_state::{check_checkpoint_pinned_read, StateRead, StateReadResult},
     error::{Error, SuiRpcInputError},
     name_service::{Domain, NameRecord, NameServiceConfig, NameServiceError},
     with_tracing, SuiRpcModule,
@@ -71,8 +75,164 @@ pub fn spawn_subscription<S, T>(
         };
     });
 }
+
+/// Streams `history`, followed by the items of `live` that aren't part of it. `live` must be
+/// subscribed to before `history` is read, so that nothing falls in between: items processed while
+/// `history` was being read can show up in both, and are only sent once.
+fn replay_then_stream<T, K, S>(
+    history: Vec<T>,
+    live: S,
+    key: impl Fn(&T) -> K,
+) -> impl Stream<Item = T>
+where
+    S: Stream<Item = T>,
+    K: Eq + Hash,
+{
+    let mut replayed: HashSet<K> = history.iter().map(&key).collect();
+    stream::iter(history)
+        .chain(live.filter(move |item| future::ready(!replayed.remove(&key(item)))))
+}
+
+/// Like `spawn_subscription`, for a subscription resumed from a cursor, which replays its history
+/// before streaming live items. Live items are buffered while the history is read and sent, and
+/// if they overflow the buffer, the live stream ends: the subscription is then closed with an
+/// error, so that the client knows to resume from its last cursor.
+fn spawn_resumed_subscription<S, T>(
+    mut sink: SubscriptionSink,
+    rx: S,
+    permit: Option<OwnedSemaphorePermit>,
+) where
+    S: Stream<Item = T> + Unpin + Send + 'static,
+    T: Serialize,
+{
+    spawn_monitored_task!(async move {
+        let _permit = permit;
+        match sink.pipe_from_stream(rx).await {
+            SubscriptionClosed::Success => {
+                debug!("Resumed subscription fell behind.");
+                close_with_error(
+                    sink,
+                    SuiRpcInputError::GenericInvalid(
+                        "Subscription fell behind, resume it from the last item received"
+                            .to_string(),
+                    )
+                    .into(),
+                );
+            }
+            SubscriptionClosed::RemotePeerAborted => {
+                debug!("Subscription aborted by remote peer.");
+                sink.close(SubscriptionClosed::RemotePeerAborted);
+            }
+            SubscriptionClosed::Failed(err) => {
+                debug!("Subscription failed: {err:?}");
+                sink.close(err);
+            }
+        };
+    });
+}
+
+/// Closes a resumed subscription that failed to replay its history, or fell behind.
+fn close_with_error(sink: SubscriptionSink, err: Error) {
+    let error_object = match RpcError::from(err) {
+        RpcError::Call(err) => err.into(),
+        err => ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>),
+    };
+    sink.close(SubscriptionClosed::Failed(error_object));
+}
+
+fn too_far_behind(catch_up_method: &str) -> SuiRpcInputError {
+    SuiRpcInputError::GenericInvalid(format!(
+        "Cursor is more than {MAX_SUBSCRIPTION_REPLAY_SIZE} items behind, catch up with \
+         {catch_up_method} before resuming the subscription"
+    ))
+}
+
+/// Read the events matching `filter` after `cursor`, up to the latest indexed one.
+async fn replay_events(
+    state: &Arc<dyn StateRead>,
+    transaction_kv_store: &Arc<TransactionKeyValueStore>,
+    filter: EventFilter,
+    cursor: EventID,
+) -> Result<Vec<SuiEvent>, Error> {
+    let mut events = vec![];
+    let mut cursor = cursor;
+    loop {
+        let page = state
+            .query_events(
+                transaction_kv_store,
+                filter.clone(),
+                Some(cursor),
+                QUERY_MAX_RESULT_LIMIT,
+                false,
+            )
+            .await?;
+        let is_last_page = page.len() < QUERY_MAX_RESULT_LIMIT;
+        if let Some(last) = page.last() {
+            cursor = last.id;
+        }
+        events.extend(page);
+        if events.len() > MAX_SUBSCRIPTION_REPLAY_SIZE {
+            return Err(too_far_behind("suix_queryEvents").into());
+        }
+        if is_last_page {
+            return Ok(events);
+        }
+    }
+}
+
+/// Read the effects of the transactions matching `filter` after `cursor`, up to the latest
+/// indexed one.
+async fn replay_transactions(
+    state: &Arc<dyn StateRead>,
+    transaction_kv_store: &Arc<TransactionKeyValueStore>,
+    filter: TransactionFilter,
+    cursor: TransactionDigest,
+) -> Result<Vec<SuiTransactionBlockEffects>, Error> {
+    let mut digests = vec![];
+    let mut cursor = cursor;
+    loop {
+        let page = state
+            .get_transactions(
+                transaction_kv_store,
+                Some(filter.clone()),
+                Some(cursor),
+                Some(QUERY_MAX_RESULT_LIMIT),
+                false,
+            )
+            .await?;
+        let is_last_page = page.len() < QUERY_MAX_RESULT_LIMIT;
+        if let Some(last) = page.last() {
+            cursor = *last;
+        }
+        digests.extend(page);
+        if digests.len() > MAX_SUBSCRIPTION_REPLAY_SIZE {
+            return Err(too_far_behind("suix_queryTransactionBlocks").into());
+        }
+        if is_last_page {
+            break;
+        }
+    }
+
+    let effects = transaction_kv_store
+        .multi_get_fx_by_tx_digest(&digests)
+        .await?;
+    digests
+        .into_iter()
+        .zip(effects)
+        .map(|(digest, effects)| {
+            let effects = effects.ok_or(SuiError::TransactionNotFound { digest })?;
+            Ok(SuiTransactionBlockEffects::try_from(effects)?)
+        })
+        .collect()
+}
+
 const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
 
+/// The most items a subscription replays when resuming from a cursor. Clients further behind need
+/// to catch up through `queryEvents`/`queryTransactionBlocks` first. This is no more than the
+/// buffer of live items, which fills up while the history is replayed.
+const MAX_SUBSCRIPTION_REPLAY_SIZE: usize = EVENT_DISPATCH_BUFFER_SIZE;
+
 pub struct IndexerApi<R> {
     state: Arc<dyn StateRead>,
     read_api: R,
@@ -144,8 +304,10 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         query: Option<SuiObjectResponseQuery>,
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<ObjectsPage> {
         with_tracing!(async move {
+            check_checkpoint_pinned_read(self.state.as_ref(), at_checkpoint)?;
             let limit =
                 validate_limit(limit, *QUERY_MAX_RESULT_LIMIT).map_err(SuiRpcInputError::from)?;
             self.metrics.get_owned_objects_limit.report(limit as u64);
@@ -168,7 +330,7 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
                 true => {
                     let object_ids = objects.iter().map(|obj| obj.object_id).collect();
                     self.read_api
-                        .multi_get_objects(object_ids, Some(options))
+                        .multi_get_objects(object_ids, Some(options), None)
                         .await?
                 }
                 false => objects
@@ -295,31 +457,69 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
     }
 
     #[instrument(skip(self))]
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        sink: SubscriptionSink,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
-            sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_events(filter),
-            Some(permit),
-        );
+        let live = self
+            .state
+            .get_subscription_handler()
+            .subscribe_events(filter.clone());
+        let Some(cursor) = cursor else {
+            spawn_subscription(sink, live, Some(permit));
+            return Ok(());
+        };
+
+        // Replay from the indexes, only after subscribing to live events so that none are missed.
+        let state = self.state.clone();
+        let transaction_kv_store = self.transaction_kv_store.clone();
+        spawn_monitored_task!(async move {
+            match replay_events(&state, &transaction_kv_store, filter, cursor).await {
+                Ok(history) => spawn_resumed_subscription(
+                    sink,
+                    replay_then_stream(history, live, |event| event.id),
+                    Some(permit),
+                ),
+                Err(err) => close_with_error(sink, err),
+            }
+        });
         Ok(())
     }
 
+    #[instrument(skip(self))]
     fn subscribe_transaction(
         &self,
         sink: SubscriptionSink,
         filter: TransactionFilter,
+        cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
-            sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_transactions(filter),
-            Some(permit),
-        );
+        let live = self
+            .state
+            .get_subscription_handler()
+            .subscribe_transactions(filter.clone());
+        let Some(cursor) = cursor else {
+            spawn_subscription(sink, live, Some(permit));
+            return Ok(());
+        };
+
+        // Replay from the indexes, only after subscribing to live transactions so that none are
+        // missed.
+        let state = self.state.clone();
+        let transaction_kv_store = self.transaction_kv_store.clone();
+        spawn_monitored_task!(async move {
+            match replay_transactions(&state, &transaction_kv_store, filter, cursor).await {
+                Ok(history) => spawn_resumed_subscription(
+                    sink,
+                    replay_then_stream(history, live, |effects| *effects.transaction_digest()),
+                    Some(permit),
+                ),
+                Err(err) => close_with_error(sink, err),
+            }
+        });
         Ok(())
     }
 
@@ -330,8 +530,10 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         // If `Some`, the query will start from the next item after the specified cursor
         cursor: Option<ObjectID>,
         limit: Option<usize>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<DynamicFieldPage> {
         with_tracing!(async move {
+            check_checkpoint_pinned_read(self.state.as_ref(), at_checkpoint)?;
             let limit = cap_page_limit(limit);
             self.metrics.get_dynamic_fields_limit.report(limit as u64);
             let mut data = self
@@ -50,7 +50,9 @@ use sui_types::sui_serde::BigInt;
 use sui_types::transaction::Transaction;
 use sui_types::transaction::TransactionDataAPI;
 
-use crate::authority_state::{StateRead, StateReadError, StateReadResult};
+use crate::authority_state::{
+    check_checkpoint_pinned_read, StateRead, StateReadError, StateReadResult,
+};
 use crate::error::{Error, RpcInterimResult, SuiRpcInputError};
 use crate::with_tracing;
 use crate::{
@@ -538,8 +540,10 @@ impl ReadApiServer for ReadApi {
         &self,
         object_ids: Vec<ObjectID>,
         options: Option<SuiObjectDataOptions>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<Vec<SuiObjectResponse>> {
         with_tracing!(async move {
+            check_checkpoint_pinned_read(self.state.as_ref(), at_checkpoint)?;
             if object_ids.len() <= *QUERY_MAX_RESULT_LIMIT {
                 self.metrics
                     .get_objects_limit
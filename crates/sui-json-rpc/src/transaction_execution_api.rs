@@ -327,6 +327,7 @@ impl WriteApiServer for TransactionExecutionApi {
                 gas_objects,
                 show_raw_txn_data_and_effects,
                 skip_checks,
+                state_overrides,
             } = additional_args.unwrap_or_default();
             let tx_kind: TransactionKind = self.convert_bytes(tx_bytes)?;
             self.state
@@ -339,6 +340,7 @@ impl WriteApiServer for TransactionExecutionApi {
                     gas_objects,
                     show_raw_txn_data_and_effects,
                     skip_checks,
+                    state_overrides,
                 )
                 .await
                 .map_err(Error::from)
@@ -63,7 +63,8 @@ pub fn derive_key_pair_from_path(
         }
         SignatureScheme::BLS12381
         | SignatureScheme::MultiSig
-        | SignatureScheme::ZkLoginAuthenticator => Err(SuiError::UnsupportedFeatureError {
+        | SignatureScheme::ZkLoginAuthenticator
+        | SignatureScheme::PasskeyAuthenticator => Err(SuiError::UnsupportedFeatureError {
             error: format!("key derivation not supported {:?}", key_scheme),
         }),
     }
@@ -160,7 +161,8 @@ pub fn validate_path(
         }
         SignatureScheme::BLS12381
         | SignatureScheme::MultiSig
-        | SignatureScheme::ZkLoginAuthenticator => Err(SuiError::UnsupportedFeatureError {
+        | SignatureScheme::ZkLoginAuthenticator
+        | SignatureScheme::PasskeyAuthenticator => Err(SuiError::UnsupportedFeatureError {
             error: format!("key derivation not supported {:?}", key_scheme),
         }),
     }
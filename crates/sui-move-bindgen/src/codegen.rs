@@ -0,0 +1,592 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::{anyhow, bail, Result};
+use move_binary_format::file_format::{AbilitySet, StructTypeParameter, Visibility};
+use move_core_types::account_address::AccountAddress;
+use sui_package_resolver::{
+    DatatypeKey, FunctionDef, Module, OpenSignature, OpenSignatureBody, Package, StructDef,
+};
+use sui_types::base_types::ObjectID;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+/// Rust keywords that cannot be used as plain identifiers, and need to be escaped as raw
+/// identifiers when they appear as Move module, field or parameter names.
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "try", "type", "unsafe", "use", "where",
+    "while", "yield",
+];
+
+/// Identifiers that cannot be escaped as raw identifiers, so get a trailing underscore instead.
+const RESERVED_IDENTS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Wrapper for the arguments of generated `MoveCall` builders, emitted once per package.
+const ARG_TYPE: &str = "\
+/// A `ProgrammableTransactionBuilder` argument holding a value of the Move type that `T` mirrors.
+/// Arguments that are passed by reference have the type of the value they refer to.
+#[derive(Debug)]
+pub struct Arg<T> {
+    pub argument: ::sui_types::transaction::Argument,
+    _type: ::std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Arg<T> {
+    /// Wraps `argument`, which must hold a value of the Move type that `T` mirrors.
+    pub fn new(argument: ::sui_types::transaction::Argument) -> Self {
+        Self {
+            argument,
+            _type: ::std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> ::std::clone::Clone for Arg<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> ::std::marker::Copy for Arg<T> {}";
+
+/// Generates Rust source for the structs and functions of a single package.
+pub(crate) struct Generator<'p> {
+    package: &'p Package,
+
+    /// The ID that generated `MoveCall`s target.
+    package_id: ObjectID,
+
+    /// Rust paths to use in place of Move types that the generator does not know how to represent
+    /// itself, keyed by the type's fully-qualified (runtime) name.
+    external_types: &'p BTreeMap<DatatypeKey, String>,
+
+    /// Output buffer.
+    out: String,
+}
+
+impl<'p> Generator<'p> {
+    pub(crate) fn new(
+        package: &'p Package,
+        package_id: ObjectID,
+        external_types: &'p BTreeMap<DatatypeKey, String>,
+    ) -> Self {
+        Self {
+            package,
+            package_id,
+            external_types,
+            out: String::new(),
+        }
+    }
+
+    pub(crate) fn generate(mut self) -> Result<String> {
+        writeln!(
+            self.out,
+            "// @generated by sui-move-bindgen from package {}. Do not edit by hand.",
+            self.package_id,
+        )?;
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "/// The ID of the package that generated `MoveCall`s target."
+        )?;
+        writeln!(
+            self.out,
+            "pub const PACKAGE_ID: ::sui_types::base_types::ObjectID = \
+             ::sui_types::base_types::ObjectID::new({:?});",
+            self.package_id.into_bytes(),
+        )?;
+        writeln!(self.out)?;
+        writeln!(self.out, "{ARG_TYPE}")?;
+
+        for (name, module) in self.package.modules() {
+            self.module(name, module)?;
+        }
+
+        Ok(self.out)
+    }
+
+    fn module(&mut self, name: &str, module: &Module) -> Result<()> {
+        let runtime_id = *module.bytecode().address();
+
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "#[allow(dead_code, unused_imports, non_camel_case_types, non_snake_case, clippy::all)]"
+        )?;
+        writeln!(self.out, "pub mod {} {{", ident(name))?;
+        writeln!(self.out, "    use super::{{Arg, PACKAGE_ID}};")?;
+        writeln!(self.out)?;
+        writeln!(self.out, "    pub const MODULE_NAME: &str = {name:?};")?;
+
+        for struct_ in module.structs(None, None) {
+            let Some(def) = module.struct_def(struct_)? else {
+                continue;
+            };
+
+            self.struct_(runtime_id, name, struct_, &def)
+                .map_err(|e| anyhow!("Failed to generate {name}::{struct_}: {e}"))?;
+        }
+
+        for function in module.functions(None, None) {
+            let Some(def) = module.function_def(function)? else {
+                continue;
+            };
+
+            // Only functions that can be called from a PTB get a builder.
+            if def.visibility != Visibility::Public && !def.is_entry {
+                continue;
+            }
+
+            self.function(runtime_id, name, function, &def)
+                .map_err(|e| anyhow!("Failed to generate {name}::{function}: {e}"))?;
+        }
+
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn struct_(
+        &mut self,
+        runtime_id: AccountAddress,
+        module: &str,
+        name: &str,
+        def: &StructDef,
+    ) -> Result<()> {
+        let params = type_param_names(def.type_params.len());
+        let type_params = generics(&params, "");
+        let bounded = generics(&params, ": ::sui_types::MoveTypeTagTrait");
+
+        // Only non-phantom type parameters appear in the serialized representation, so only they
+        // need to be (de)serializable.
+        let serialized: Vec<_> = params
+            .iter()
+            .zip(&def.type_params)
+            .filter(|(_, p)| !p.is_phantom)
+            .map(|(p, _)| p.as_str())
+            .collect();
+
+        let ser_bounds = bounds(&serialized, "::serde::Serialize");
+        let de_bounds = bounds(&serialized, "::serde::Deserialize<'de>");
+
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "    /// Rust mirror of `{}::{module}::{name}`, with abilities: {}.",
+            def.defining_id.to_canonical_display(/* with_prefix */ true),
+            abilities(def.abilities),
+        )?;
+        writeln!(
+            self.out,
+            "    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]"
+        )?;
+        writeln!(
+            self.out,
+            "    #[serde(bound(serialize = {ser_bounds:?}, deserialize = {de_bounds:?}))]"
+        )?;
+        writeln!(self.out, "    pub struct {name}{type_params} {{")?;
+        for (field, sig) in &def.fields {
+            let ty = self.type_(runtime_id, sig, &params)?;
+            writeln!(self.out, "        pub {}: {ty},", ident(field))?;
+        }
+
+        for (param, StructTypeParameter { is_phantom, .. }) in params.iter().zip(&def.type_params) {
+            if *is_phantom {
+                writeln!(self.out, "        #[serde(skip)]")?;
+                writeln!(
+                    self.out,
+                    "        pub _phantom_{param}: ::std::marker::PhantomData<{param}>,"
+                )?;
+            }
+        }
+        writeln!(self.out, "    }}")?;
+
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "    impl{bounded} ::sui_types::MoveTypeTagTrait for {name}{type_params} {{"
+        )?;
+        writeln!(
+            self.out,
+            "        fn get_type_tag() -> ::sui_types::TypeTag {{"
+        )?;
+        writeln!(
+            self.out,
+            "            ::sui_types::TypeTag::Struct(::std::boxed::Box::new(Self::struct_tag()))"
+        )?;
+        writeln!(self.out, "        }}")?;
+        writeln!(self.out, "    }}")?;
+
+        writeln!(self.out)?;
+        writeln!(self.out, "    impl{bounded} {name}{type_params} {{")?;
+        writeln!(
+            self.out,
+            "        /// The `StructTag` for this instantiation of `{module}::{name}`."
+        )?;
+        writeln!(
+            self.out,
+            "        pub fn struct_tag() -> ::move_core_types::language_storage::StructTag {{"
+        )?;
+        writeln!(
+            self.out,
+            "            ::move_core_types::language_storage::StructTag {{"
+        )?;
+        writeln!(
+            self.out,
+            "                address: ::move_core_types::account_address::AccountAddress::new({:?}),",
+            def.defining_id.into_bytes(),
+        )?;
+        writeln!(
+            self.out,
+            "                module: ::sui_types::Identifier::new(MODULE_NAME).unwrap(),"
+        )?;
+        writeln!(
+            self.out,
+            "                name: ::sui_types::Identifier::new({name:?}).unwrap(),"
+        )?;
+        write!(self.out, "                type_params: ::std::vec![")?;
+        for param in &params {
+            write!(
+                self.out,
+                "<{param} as ::sui_types::MoveTypeTagTrait>::get_type_tag(), "
+            )?;
+        }
+        writeln!(self.out, "],")?;
+        writeln!(self.out, "            }}")?;
+        writeln!(self.out, "        }}")?;
+        writeln!(self.out, "    }}")?;
+
+        Ok(())
+    }
+
+    fn function(
+        &mut self,
+        runtime_id: AccountAddress,
+        module: &str,
+        name: &str,
+        def: &FunctionDef,
+    ) -> Result<()> {
+        // The `TxContext` parameter is supplied by the runtime, not the caller.
+        let parameters = match def.parameters.as_slice() {
+            [rest @ .., last] if is_tx_context(last) => rest,
+            all => all,
+        };
+
+        // Type parameters become generic parameters, so that the types of arguments that mention
+        // them can be checked, and their type arguments derived from the Rust types.
+        let type_params = type_param_names(def.type_params.len());
+
+        writeln!(self.out)?;
+        write!(self.out, "    /// Appends a call to `{module}::{name}")?;
+        if !type_params.is_empty() {
+            write!(self.out, "<{}>", type_params.join(", "))?;
+        }
+        writeln!(self.out, "` to `builder`.")?;
+
+        if !parameters.is_empty() {
+            writeln!(self.out, "    ///")?;
+            writeln!(self.out, "    /// Arguments:")?;
+            for (ix, param) in parameters.iter().enumerate() {
+                writeln!(
+                    self.out,
+                    "    /// - `arg{ix}`: `{}`",
+                    self.move_signature(runtime_id, param),
+                )?;
+            }
+        }
+
+        if !def.return_.is_empty() {
+            writeln!(self.out, "    ///")?;
+            write!(self.out, "    /// Returns: `(")?;
+            for (ix, ret) in def.return_.iter().enumerate() {
+                if ix > 0 {
+                    write!(self.out, ", ")?;
+                }
+                write!(self.out, "{}", self.move_signature(runtime_id, ret))?;
+            }
+            writeln!(self.out, ")`")?;
+        }
+
+        writeln!(
+            self.out,
+            "    pub fn {}{}(",
+            ident(name),
+            generics(&type_params, ": ::sui_types::MoveTypeTagTrait"),
+        )?;
+        writeln!(
+            self.out,
+            "        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,"
+        )?;
+
+        // Arguments are typed by the Rust mirror of their Move type (references are passed as
+        // arguments of the referenced type). Arguments whose type has no mirror stay untyped.
+        let mut typed = vec![];
+        for (ix, param) in parameters.iter().enumerate() {
+            match self.type_(runtime_id, &param.body, &type_params) {
+                Ok(ty) => {
+                    writeln!(self.out, "        arg{ix}: Arg<{ty}>,")?;
+                    typed.push(true);
+                }
+                Err(_) => {
+                    writeln!(
+                        self.out,
+                        "        arg{ix}: ::sui_types::transaction::Argument,"
+                    )?;
+                    typed.push(false);
+                }
+            }
+        }
+        writeln!(self.out, "    ) -> ::sui_types::transaction::Argument {{")?;
+        writeln!(self.out, "        builder.programmable_move_call(")?;
+        writeln!(self.out, "            PACKAGE_ID,")?;
+        writeln!(
+            self.out,
+            "            ::sui_types::Identifier::new(MODULE_NAME).unwrap(),"
+        )?;
+        writeln!(
+            self.out,
+            "            ::sui_types::Identifier::new({name:?}).unwrap(),"
+        )?;
+        write!(self.out, "            ::std::vec![")?;
+        for (ix, param) in type_params.iter().enumerate() {
+            if ix > 0 {
+                write!(self.out, ", ")?;
+            }
+            write!(
+                self.out,
+                "<{param} as ::sui_types::MoveTypeTagTrait>::get_type_tag()"
+            )?;
+        }
+        writeln!(self.out, "],")?;
+        write!(self.out, "            ::std::vec![")?;
+        for (ix, typed) in typed.into_iter().enumerate() {
+            if ix > 0 {
+                write!(self.out, ", ")?;
+            }
+            if typed {
+                write!(self.out, "arg{ix}.argument")?;
+            } else {
+                write!(self.out, "arg{ix}")?;
+            }
+        }
+        writeln!(self.out, "],")?;
+        writeln!(self.out, "        )")?;
+        writeln!(self.out, "    }}")?;
+
+        Ok(())
+    }
+
+    /// The Rust type that mirrors the Move type `sig`, as it appears in the fields of a struct or
+    /// the parameters of a function defined in the package at `runtime_id`.
+    fn type_(
+        &self,
+        runtime_id: AccountAddress,
+        sig: &OpenSignatureBody,
+        params: &[String],
+    ) -> Result<String> {
+        use OpenSignatureBody as O;
+        Ok(match sig {
+            O::Address => "::sui_types::base_types::SuiAddress".to_string(),
+            O::Bool => "bool".to_string(),
+            O::U8 => "u8".to_string(),
+            O::U16 => "u16".to_string(),
+            O::U32 => "u32".to_string(),
+            O::U64 => "u64".to_string(),
+            O::U128 => "u128".to_string(),
+            O::U256 => "::move_core_types::u256::U256".to_string(),
+            O::Vector(sig) => format!("::std::vec::Vec<{}>", self.type_(runtime_id, sig, params)?),
+
+            O::TypeParameter(ix) => params
+                .get(*ix as usize)
+                .cloned()
+                .ok_or_else(|| anyhow!("Type parameter {ix} out of bounds"))?,
+
+            O::Datatype(key, type_args) => {
+                let type_args = type_args
+                    .iter()
+                    .map(|arg| self.type_(runtime_id, arg, params))
+                    .collect::<Result<Vec<_>>>()?;
+
+                if let Some(path) = self.external_types.get(key) {
+                    format!("{path}{}", generics(&type_args, ""))
+                } else if let Some(ty) = well_known_type(key, &type_args) {
+                    ty
+                } else if key.package == runtime_id {
+                    format!(
+                        "super::{}::{}{}",
+                        ident(&key.module),
+                        key.name,
+                        generics(&type_args, ""),
+                    )
+                } else {
+                    bail!(
+                        "No Rust type for 0x{}::{}::{}, add a mapping for it with \
+                         `Bindgen::external_type`",
+                        key.package.short_str_lossless(),
+                        key.module,
+                        key.name,
+                    )
+                }
+            }
+        })
+    }
+
+    /// A human-readable rendering of the Move signature `sig`, for documentation.
+    fn move_signature(&self, runtime_id: AccountAddress, sig: &OpenSignature) -> String {
+        let prefix = match sig.ref_ {
+            None => "",
+            Some(sui_package_resolver::Reference::Immutable) => "&",
+            Some(sui_package_resolver::Reference::Mutable) => "&mut ",
+        };
+
+        format!("{prefix}{}", move_type(runtime_id, &sig.body))
+    }
+}
+
+/// Rust types for Move types from the standard library and Sui framework that already have a
+/// counterpart in `sui-types` (or `std`) with the same BCS representation.
+fn well_known_type(key: &DatatypeKey, type_args: &[String]) -> Option<String> {
+    let module = key.module.as_ref();
+    let name = key.name.as_ref();
+
+    if key.package == MOVE_STDLIB_ADDRESS {
+        return match (module, name, type_args) {
+            ("string", "String", []) | ("ascii", "String", []) => {
+                Some("::std::string::String".to_string())
+            }
+            ("option", "Option", [t]) => Some(format!("::std::option::Option<{t}>")),
+            _ => None,
+        };
+    }
+
+    if key.package == SUI_FRAMEWORK_ADDRESS {
+        return match (module, name, type_args) {
+            ("object", "UID", []) => Some("::sui_types::id::UID".to_string()),
+            ("object", "ID", []) => Some("::sui_types::id::ID".to_string()),
+            ("balance", "Balance", [_]) => Some("::sui_types::balance::Balance".to_string()),
+            ("balance", "Supply", [_]) => Some("::sui_types::balance::Supply".to_string()),
+            ("coin", "Coin", [_]) => Some("::sui_types::coin::Coin".to_string()),
+            ("bag", "Bag", []) => Some("::sui_types::collection_types::Bag".to_string()),
+            ("table", "Table", [_, _]) => Some("::sui_types::collection_types::Table".to_string()),
+            ("vec_map", "VecMap", [k, v]) => {
+                Some(format!("::sui_types::collection_types::VecMap<{k}, {v}>"))
+            }
+            _ => None,
+        };
+    }
+
+    None
+}
+
+fn is_tx_context(sig: &OpenSignature) -> bool {
+    matches!(
+        &sig.body,
+        OpenSignatureBody::Datatype(key, _)
+            if key.package == SUI_FRAMEWORK_ADDRESS
+                && key.module == "tx_context"
+                && key.name == "TxContext"
+    )
+}
+
+fn move_type(runtime_id: AccountAddress, sig: &OpenSignatureBody) -> String {
+    use OpenSignatureBody as O;
+    match sig {
+        O::Address => "address".to_string(),
+        O::Bool => "bool".to_string(),
+        O::U8 => "u8".to_string(),
+        O::U16 => "u16".to_string(),
+        O::U32 => "u32".to_string(),
+        O::U64 => "u64".to_string(),
+        O::U128 => "u128".to_string(),
+        O::U256 => "u256".to_string(),
+        O::Vector(sig) => format!("vector<{}>", move_type(runtime_id, sig)),
+        O::TypeParameter(ix) => format!("T{ix}"),
+        O::Datatype(key, type_args) => {
+            let mut s = if key.package == runtime_id {
+                format!("{}::{}", key.module, key.name)
+            } else {
+                format!(
+                    "{}::{}::{}",
+                    key.package.short_str_lossless(),
+                    key.module,
+                    key.name
+                )
+            };
+
+            if !type_args.is_empty() {
+                let args: Vec<_> = type_args.iter().map(|t| move_type(runtime_id, t)).collect();
+                s.push('<');
+                s.push_str(&args.join(", "));
+                s.push('>');
+            }
+
+            s
+        }
+    }
+}
+
+fn abilities(set: AbilitySet) -> String {
+    let names: Vec<_> = set
+        .into_iter()
+        .map(|a| format!("{a:?}").to_lowercase())
+        .collect();
+
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn type_param_names(count: usize) -> Vec<String> {
+    (0..count).map(|ix| format!("T{ix}")).collect()
+}
+
+/// Render a generic parameter list, `<A{bound}, B{bound}, ...>`, or nothing, if there are no
+/// parameters.
+fn generics(params: &[String], bound: &str) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+
+    let params: Vec<_> = params.iter().map(|p| format!("{p}{bound}")).collect();
+    format!("<{}>", params.join(", "))
+}
+
+/// Render a where-clause-style list of bounds, for use in `#[serde(bound(...))]`.
+fn bounds(params: &[&str], bound: &str) -> String {
+    let bounds: Vec<_> = params.iter().map(|p| format!("{p}: {bound}")).collect();
+    bounds.join(", ")
+}
+
+/// Escape a Move identifier so that it can be used as a Rust identifier.
+fn ident(name: &str) -> String {
+    if RESERVED_IDENTS.contains(&name) {
+        format!("{name}_")
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_idents() {
+        assert_eq!(ident("pool"), "pool");
+        assert_eq!(ident("ref"), "r#ref");
+        assert_eq!(ident("move"), "r#move");
+
+        // These can't be raw identifiers.
+        assert_eq!(ident("crate"), "crate_");
+        assert_eq!(ident("self"), "self_");
+        assert_eq!(ident("Self"), "Self_");
+        assert_eq!(ident("super"), "super_");
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates typed Rust bindings for Move packages.
+//!
+//! For every module in a package, the generated code contains a Rust module with:
+//!
+//! - A struct for each Move struct, whose BCS representation matches the Move type's, and which
+//!   implements `MoveTypeTagTrait` so that its `TypeTag` can be recovered from the Rust type.
+//! - A builder function for each `public` or `entry` function, which appends a `MoveCall` to a
+//!   `ProgrammableTransactionBuilder`. Its arguments are typed by the Rust mirrors of their Move
+//!   types (as `Arg<T>`), and its type arguments are derived from its generic parameters.
+//!
+//! The generated code depends on `serde`, `sui-types` and `move-core-types`. It can be produced by
+//! the `sui-move-bindgen` CLI, or from a build script:
+//!
+//! ```ignore
+//! // build.rs
+//! let out = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+//! sui_move_bindgen::Bindgen::from_path("move/my_package")?
+//!     .generate()?
+//!     .write_to_file(out.join("my_package.rs"))?;
+//!
+//! // lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/my_package.rs"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use move_core_types::language_storage::StructTag;
+use sui_move_build::{BuildConfig, CompiledPackage, SuiPackageHooks};
+use sui_package_resolver::{DatatypeKey, DatatypeRef, Package};
+use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiRawData};
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::move_package::MovePackage;
+
+mod codegen;
+
+/// Configures the generation of Rust bindings for a single Move package.
+pub struct Bindgen {
+    package: Package,
+
+    /// The ID that generated `MoveCall`s target.
+    package_id: ObjectID,
+
+    /// Rust paths for Move types defined outside the package being generated.
+    external_types: BTreeMap<DatatypeKey, String>,
+}
+
+/// Generated Rust source for a package's bindings.
+pub struct Bindings(String);
+
+impl Bindgen {
+    /// Generate bindings for an on-chain package. Calls target the package at `package`'s ID.
+    pub fn new(package: &MovePackage) -> Result<Self> {
+        Ok(Self {
+            package: Package::read_from_package(package)?,
+            package_id: package.id(),
+            external_types: BTreeMap::new(),
+        })
+    }
+
+    /// Build the Move package at `path` and generate bindings for it. Calls target the package's
+    /// `published-at` address if it has one, and its own address otherwise.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
+        let mut config = BuildConfig::default();
+        config.print_diags_to_stderr = true;
+
+        let compiled = config
+            .build(path.to_path_buf())
+            .with_context(|| format!("Failed to build package at {}", path.display()))?;
+
+        Self::from_compiled_package(&compiled)
+    }
+
+    /// Generate bindings for a package that has already been built.
+    pub fn from_compiled_package(compiled: &CompiledPackage) -> Result<Self> {
+        let modules: Vec<_> = compiled.get_modules().cloned().collect();
+        if modules.is_empty() {
+            bail!("Package at {} has no modules", compiled.path.display());
+        }
+
+        // The package has not necessarily been published, so there are no type origin or linkage
+        // tables to read: Treat it as the first version of a package, linked against the
+        // dependencies it was built against.
+        let package = MovePackage::new_system(
+            SequenceNumber::new(),
+            &modules,
+            compiled.dependency_ids.published.values().copied(),
+        );
+
+        let mut bindgen = Self::new(&package)?;
+        if let Ok(published_at) = &compiled.published_at {
+            bindgen.package_id = *published_at;
+        }
+
+        Ok(bindgen)
+    }
+
+    /// Fetch the package at `id` over JSON-RPC, and generate bindings for it.
+    pub async fn from_chain(client: &SuiClient, id: ObjectID) -> Result<Self> {
+        let object = client
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+
+        let Some(SuiRawData::Package(package)) = object.bcs else {
+            bail!("Object {id} is not a package");
+        };
+
+        let package = MovePackage::new(
+            package.id,
+            package.version,
+            package.module_map,
+            // This package came from on-chain, so there is no need to enforce the size limit.
+            u64::MAX,
+            package.type_origin_table,
+            package.linkage_table,
+        )?;
+
+        Self::new(&package)
+    }
+
+    /// Override the ID of the package that generated `MoveCall`s target, e.g. to call the latest
+    /// version of an upgraded package.
+    pub fn package_id(mut self, id: ObjectID) -> Self {
+        self.package_id = id;
+        self
+    }
+
+    /// Use the Rust type at `path` to represent the Move type `type_` (a fully-qualified struct
+    /// name, without type parameters, like `0x42::pool::Pool`). Any type parameters are passed on
+    /// to the Rust type. Required for types from packages other than the standard library and the
+    /// Sui framework.
+    pub fn external_type(mut self, type_: &str, path: impl Into<String>) -> Result<Self> {
+        let tag = StructTag::from_str(type_)
+            .map_err(|e| anyhow!("Invalid struct name '{type_}': {e}"))?;
+
+        if !tag.type_params.is_empty() {
+            bail!("External type '{type_}' must not include type parameters");
+        }
+
+        self.external_types
+            .insert(DatatypeRef::from(&tag).as_key(), path.into());
+        Ok(self)
+    }
+
+    pub fn generate(&self) -> Result<Bindings> {
+        let source = codegen::Generator::new(&self.package, self.package_id, &self.external_types)
+            .generate()?;
+
+        Ok(Bindings(source))
+    }
+}
+
+impl Bindings {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Write the bindings to `path`, only touching the file if its contents would change, to
+    /// avoid triggering unnecessary rebuilds when called from a build script.
+    pub fn write_to_file(&self, path: impl Into<PathBuf>) -> Result<()> {
+        let path = path.into();
+        if std::fs::read_to_string(&path).is_ok_and(|existing| existing == self.0) {
+            return Ok(());
+        }
+
+        std::fs::write(&path, &self.0)
+            .with_context(|| format!("Failed to write bindings to {}", path.display()))
+    }
+}
+
+impl std::fmt::Display for Bindings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(dir: &str) -> String {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "packages", dir]);
+
+        let compiled = BuildConfig::new_for_testing().build(path).unwrap();
+        Bindgen::from_compiled_package(&compiled)
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    fn bindings_with_url(dir: &str, url: &str) -> String {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "packages", dir]);
+
+        let compiled = BuildConfig::new_for_testing().build(path).unwrap();
+        Bindgen::from_compiled_package(&compiled)
+            .unwrap()
+            .external_type("0x2::url::Url", url)
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn structs() {
+        let src = bindings("example");
+
+        assert!(src.contains("pub mod pool {"));
+        assert!(src.contains("pub struct Pool<T0> {"));
+        assert!(src.contains("pub id: ::sui_types::id::UID,"));
+        assert!(src.contains("pub balance: ::sui_types::balance::Balance,"));
+        assert!(src.contains("pub fees: ::std::vec::Vec<u64>,"));
+        assert!(src.contains("pub name: ::std::string::String,"));
+        assert!(src.contains("pub _phantom_T0: ::std::marker::PhantomData<T0>,"));
+        assert!(src.contains("pub receipt: ::std::option::Option<super::pool::Receipt>,"));
+        assert!(src.contains("pub r#ref: u8,"));
+    }
+
+    #[test]
+    fn functions() {
+        let src = bindings("example");
+
+        // Public and entry functions get builders, private functions do not.
+        assert!(src.contains("pub fn deposit("));
+        assert!(src.contains("pub fn withdraw("));
+        assert!(!src.contains("pub fn check_amount("));
+
+        // Generic functions take their type arguments as generic parameters, arguments are typed
+        // by their Move types, and the `TxContext` parameter is dropped.
+        assert!(src.contains("pub fn deposit<T0: ::sui_types::MoveTypeTagTrait>("));
+        assert!(src.contains("arg0: Arg<super::pool::Pool<T0>>,"));
+        assert!(src.contains("arg1: Arg<::sui_types::coin::Coin>,"));
+        assert!(src.contains("arg1: Arg<u64>,"));
+        assert!(!src.contains("arg2:"));
+    }
+
+    #[test]
+    fn generated() {
+        // The bindings for the example package are checked in, so that `tests/bindings.rs` can
+        // compile them. Run with `UPDATE_EXPECT=1` to regenerate them.
+        expect_test::expect_file!["../tests/generated/example.rs"].assert_eq(&bindings("example"));
+    }
+
+    #[test]
+    fn parses() {
+        for src in [
+            bindings("example"),
+            bindings_with_url("external", "my_crate::Url"),
+        ] {
+            if let Err(e) = syn::parse_file(&src) {
+                panic!("Generated bindings do not parse: {e}\n{src}");
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_external_type() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "packages", "external"]);
+
+        let compiled = BuildConfig::new_for_testing().build(path).unwrap();
+        let bindgen = Bindgen::from_compiled_package(&compiled).unwrap();
+
+        // `0x2::url::Url` is not one of the types with a built-in mapping, so it needs to be
+        // supplied.
+        let err = bindgen.generate().err().unwrap().to_string();
+        assert!(err.contains("0x2::url::Url"), "{err}");
+
+        let src = bindgen
+            .external_type("0x2::url::Url", "my_crate::Url")
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string();
+
+        assert!(src.contains("pub url: my_crate::Url,"));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use sui_move_bindgen::Bindgen;
+use sui_sdk::SuiClientBuilder;
+use sui_types::base_types::ObjectID;
+
+/// Generate typed Rust bindings for a Move package, either from a local package or from a package
+/// published on-chain.
+#[derive(Parser)]
+#[clap(name = "sui-move-bindgen", rename_all = "kebab-case", author, version)]
+struct Args {
+    /// Path to a local Move package to build and generate bindings for.
+    #[clap(long, conflicts_with = "package_id")]
+    path: Option<PathBuf>,
+
+    /// ID of an on-chain package to generate bindings for.
+    #[clap(long, requires = "rpc_url")]
+    package_id: Option<ObjectID>,
+
+    /// JSON-RPC endpoint to fetch on-chain packages from.
+    #[clap(long)]
+    rpc_url: Option<String>,
+
+    /// Target this package ID in generated `MoveCall`s, instead of the package the bindings were
+    /// generated from (e.g. to call the latest version of an upgraded package).
+    #[clap(long)]
+    call_package_id: Option<ObjectID>,
+
+    /// Rust types for Move types from other packages, as `<Move type>=<Rust path>`, e.g.
+    /// `0x42::pool::Pool=pool_bindings::pool::Pool`.
+    #[clap(long = "external-type", value_parser = parse_external_type)]
+    external_types: Vec<(String, String)>,
+
+    /// File to write bindings to. Bindings are written to stdout if this is not supplied.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut bindgen = match (args.path, args.package_id, args.rpc_url) {
+        (Some(path), _, _) => Bindgen::from_path(path)?,
+        (None, Some(id), Some(url)) => {
+            let client = SuiClientBuilder::default().build(url).await?;
+            Bindgen::from_chain(&client, id).await?
+        }
+        _ => bail!("One of --path or --package-id must be supplied"),
+    };
+
+    if let Some(id) = args.call_package_id {
+        bindgen = bindgen.package_id(id);
+    }
+
+    for (type_, path) in args.external_types {
+        bindgen = bindgen.external_type(&type_, path)?;
+    }
+
+    let bindings = bindgen.generate()?;
+    match args.output {
+        Some(output) => bindings.write_to_file(output)?,
+        None => print!("{bindings}"),
+    }
+
+    Ok(())
+}
+
+fn parse_external_type(s: &str) -> Result<(String, String)> {
+    let Some((type_, path)) = s.split_once('=') else {
+        bail!("Expected '<Move type>=<Rust path>', got '{s}'");
+    };
+
+    Ok((type_.trim().to_string(), path.trim().to_string()))
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compiles the bindings generated for `tests/packages/example` (kept up to date by the `generated`
+//! unit test), and checks that they behave like their Move counterparts.
+
+// Formatting the generated file would make it differ from the generator's output.
+#[rustfmt::skip]
+#[path = "generated/example.rs"]
+mod example;
+
+use std::marker::PhantomData;
+
+use example::pool::{self, Pool, Receipt};
+use example::Arg;
+use sui_types::balance::Balance;
+use sui_types::base_types::ObjectID;
+use sui_types::id::UID;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, Command};
+use sui_types::{MoveTypeTagTrait, TypeTag};
+
+#[test]
+fn bcs_round_trip() {
+    let receipt = Receipt { amount: 42 };
+    let bytes = bcs::to_bytes(&receipt).unwrap();
+    assert_eq!(bytes, 42u64.to_le_bytes());
+    assert_eq!(bcs::from_bytes::<Receipt>(&bytes).unwrap(), receipt);
+
+    let pool: Pool<u64> = Pool {
+        id: UID::new(ObjectID::random()),
+        balance: Balance::new(100),
+        fees: vec![1, 2, 3],
+        name: "pool".to_string(),
+        receipt: Some(receipt),
+        r#ref: 7,
+        _phantom_T0: PhantomData,
+    };
+    let bytes = bcs::to_bytes(&pool).unwrap();
+    assert_eq!(bcs::from_bytes::<Pool<u64>>(&bytes).unwrap(), pool);
+}
+
+#[test]
+fn type_tags() {
+    let TypeTag::Struct(tag) = Pool::<u64>::get_type_tag() else {
+        panic!("Expected a struct type");
+    };
+
+    assert_eq!(ObjectID::from(tag.address), example::PACKAGE_ID);
+    assert_eq!(tag.module.as_str(), "pool");
+    assert_eq!(tag.name.as_str(), "Pool");
+    assert_eq!(tag.type_params, vec![TypeTag::U64]);
+}
+
+#[test]
+fn typed_calls() {
+    // Arguments are wrapped in the type the bindings expect, without being checked, so any input
+    // will do to stand in for the pool.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let pool_arg: Arg<Pool<u64>> = Arg::new(builder.pure(0u8).unwrap());
+    let amount_arg: Arg<u64> = Arg::new(builder.pure(10u64).unwrap());
+    pool::withdraw::<u64>(&mut builder, pool_arg, amount_arg);
+
+    let ptb = builder.finish();
+    let [Command::MoveCall(call)] = ptb.commands.as_slice() else {
+        panic!("Expected a single MoveCall, got {:?}", ptb.commands);
+    };
+
+    assert_eq!(call.package, example::PACKAGE_ID);
+    assert_eq!(call.module.as_str(), "pool");
+    assert_eq!(call.function.as_str(), "withdraw");
+    assert_eq!(call.type_arguments, vec![TypeTag::U64]);
+    assert_eq!(call.arguments, vec![Argument::Input(0), Argument::Input(1)]);
+}
@@ -0,0 +1,148 @@
+// @generated by sui-move-bindgen from package 0x0000000000000000000000000000000000000000000000000000000000000000. Do not edit by hand.
+
+/// The ID of the package that generated `MoveCall`s target.
+pub const PACKAGE_ID: ::sui_types::base_types::ObjectID = ::sui_types::base_types::ObjectID::new([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+]);
+
+/// A `ProgrammableTransactionBuilder` argument holding a value of the Move type that `T` mirrors.
+/// Arguments that are passed by reference have the type of the value they refer to.
+#[derive(Debug)]
+pub struct Arg<T> {
+    pub argument: ::sui_types::transaction::Argument,
+    _type: ::std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Arg<T> {
+    /// Wraps `argument`, which must hold a value of the Move type that `T` mirrors.
+    pub fn new(argument: ::sui_types::transaction::Argument) -> Self {
+        Self {
+            argument,
+            _type: ::std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> ::std::clone::Clone for Arg<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> ::std::marker::Copy for Arg<T> {}
+
+#[allow(
+    dead_code,
+    unused_imports,
+    non_camel_case_types,
+    non_snake_case,
+    clippy::all
+)]
+pub mod pool {
+    use super::{Arg, PACKAGE_ID};
+
+    pub const MODULE_NAME: &str = "pool";
+
+    /// Rust mirror of `0x0000000000000000000000000000000000000000000000000000000000000000::pool::Pool`, with abilities: key.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(bound(serialize = "", deserialize = ""))]
+    pub struct Pool<T0> {
+        pub id: ::sui_types::id::UID,
+        pub balance: ::sui_types::balance::Balance,
+        pub fees: ::std::vec::Vec<u64>,
+        pub name: ::std::string::String,
+        pub receipt: ::std::option::Option<super::pool::Receipt>,
+        pub r#ref: u8,
+        #[serde(skip)]
+        pub _phantom_T0: ::std::marker::PhantomData<T0>,
+    }
+
+    impl<T0: ::sui_types::MoveTypeTagTrait> ::sui_types::MoveTypeTagTrait for Pool<T0> {
+        fn get_type_tag() -> ::sui_types::TypeTag {
+            ::sui_types::TypeTag::Struct(::std::boxed::Box::new(Self::struct_tag()))
+        }
+    }
+
+    impl<T0: ::sui_types::MoveTypeTagTrait> Pool<T0> {
+        /// The `StructTag` for this instantiation of `pool::Pool`.
+        pub fn struct_tag() -> ::move_core_types::language_storage::StructTag {
+            ::move_core_types::language_storage::StructTag {
+                address: ::move_core_types::account_address::AccountAddress::new([
+                    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                    0, 0, 0, 0, 0, 0,
+                ]),
+                module: ::sui_types::Identifier::new(MODULE_NAME).unwrap(),
+                name: ::sui_types::Identifier::new("Pool").unwrap(),
+                type_params: ::std::vec![<T0 as ::sui_types::MoveTypeTagTrait>::get_type_tag(),],
+            }
+        }
+    }
+
+    /// Rust mirror of `0x0000000000000000000000000000000000000000000000000000000000000000::pool::Receipt`, with abilities: copy, drop, store.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(bound(serialize = "", deserialize = ""))]
+    pub struct Receipt {
+        pub amount: u64,
+    }
+
+    impl ::sui_types::MoveTypeTagTrait for Receipt {
+        fn get_type_tag() -> ::sui_types::TypeTag {
+            ::sui_types::TypeTag::Struct(::std::boxed::Box::new(Self::struct_tag()))
+        }
+    }
+
+    impl Receipt {
+        /// The `StructTag` for this instantiation of `pool::Receipt`.
+        pub fn struct_tag() -> ::move_core_types::language_storage::StructTag {
+            ::move_core_types::language_storage::StructTag {
+                address: ::move_core_types::account_address::AccountAddress::new([
+                    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                    0, 0, 0, 0, 0, 0,
+                ]),
+                module: ::sui_types::Identifier::new(MODULE_NAME).unwrap(),
+                name: ::sui_types::Identifier::new("Receipt").unwrap(),
+                type_params: ::std::vec![],
+            }
+        }
+    }
+
+    /// Appends a call to `pool::deposit<T0>` to `builder`.
+    ///
+    /// Arguments:
+    /// - `arg0`: `&mut pool::Pool<T0>`
+    /// - `arg1`: `2::coin::Coin<T0>`
+    ///
+    /// Returns: `(pool::Receipt)`
+    pub fn deposit<T0: ::sui_types::MoveTypeTagTrait>(
+        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        arg0: Arg<super::pool::Pool<T0>>,
+        arg1: Arg<::sui_types::coin::Coin>,
+    ) -> ::sui_types::transaction::Argument {
+        builder.programmable_move_call(
+            PACKAGE_ID,
+            ::sui_types::Identifier::new(MODULE_NAME).unwrap(),
+            ::sui_types::Identifier::new("deposit").unwrap(),
+            ::std::vec![<T0 as ::sui_types::MoveTypeTagTrait>::get_type_tag()],
+            ::std::vec![arg0.argument, arg1.argument],
+        )
+    }
+
+    /// Appends a call to `pool::withdraw<T0>` to `builder`.
+    ///
+    /// Arguments:
+    /// - `arg0`: `&mut pool::Pool<T0>`
+    /// - `arg1`: `u64`
+    pub fn withdraw<T0: ::sui_types::MoveTypeTagTrait>(
+        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        arg0: Arg<super::pool::Pool<T0>>,
+        arg1: Arg<u64>,
+    ) -> ::sui_types::transaction::Argument {
+        builder.programmable_move_call(
+            PACKAGE_ID,
+            ::sui_types::Identifier::new(MODULE_NAME).unwrap(),
+            ::sui_types::Identifier::new("withdraw").unwrap(),
+            ::std::vec![<T0 as ::sui_types::MoveTypeTagTrait>::get_type_tag()],
+            ::std::vec![arg0.argument, arg1.argument],
+        )
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module example::pool {
+    use std::string::String;
+    use sui::balance::{Self, Balance};
+    use sui::coin::{Self, Coin};
+
+    public struct Pool<phantom T> has key {
+        id: UID,
+        balance: Balance<T>,
+        fees: vector<u64>,
+        name: String,
+        receipt: Option<Receipt>,
+        ref: u8,
+    }
+
+    public struct Receipt has store, copy, drop {
+        amount: u64,
+    }
+
+    public fun deposit<T>(pool: &mut Pool<T>, coin: Coin<T>, _ctx: &mut TxContext): Receipt {
+        let amount = coin.value();
+        check_amount(amount);
+        pool.balance.join(coin.into_balance());
+        Receipt { amount }
+    }
+
+    public entry fun withdraw<T>(pool: &mut Pool<T>, amount: u64, ctx: &mut TxContext) {
+        check_amount(amount);
+        let coin = coin::from_balance(balance::split(&mut pool.balance, amount), ctx);
+        transfer::public_transfer(coin, ctx.sender());
+    }
+
+    fun check_amount(amount: u64) {
+        assert!(amount > 0, 0);
+    }
+}
@@ -0,0 +1,11 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module external::link {
+    use sui::url::Url;
+
+    public struct Link has key {
+        id: UID,
+        url: Url,
+    }
+}
@@ -35,8 +35,8 @@ use move_package::{
     BuildConfig as MoveBuildConfig,
 };
 use move_package::{
-    resolution::resolution_graph::Package, source_package::parsed_manifest::CustomDepInfo,
-    source_package::parsed_manifest::SourceManifest,
+    resolution::resolution_graph::Package,
+    source_package::parsed_manifest::{CustomDepInfo, DependencyKind, OnChainInfo, SourceManifest},
 };
 use move_symbol_pool::Symbol;
 use serde_reflection::Registry;
@@ -51,6 +51,8 @@ use sui_types::{
 };
 use sui_verifier::verifier as sui_bytecode_verifier;
 
+mod on_chain_dependency;
+
 #[cfg(test)]
 #[path = "unit_tests/build_tests.rs"]
 mod build_tests;
@@ -611,6 +613,15 @@ impl PackageHooks for SuiPackageHooks {
         Ok(())
     }
 
+    fn resolve_on_chain_dependency(
+        &self,
+        dep_name: Symbol,
+        info: &OnChainInfo,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        on_chain_dependency::resolve_on_chain_dependency(dep_name, info, path)
+    }
+
     fn custom_resolve_pkg_id(
         &self,
         manifest: &SourceManifest,
@@ -634,6 +645,9 @@ pub struct PackageDependencies {
     pub unpublished: BTreeSet<Symbol>,
     /// Set of dependencies with invalid `published-at` addresses.
     pub invalid: BTreeMap<Symbol, String>,
+    /// Set of published dependencies that were resolved from on-chain dependencies (name). Their
+    /// sources are interface stubs generated from the bytecode on chain.
+    pub on_chain: BTreeSet<Symbol>,
 }
 
 #[derive(Debug, Clone)]
@@ -647,6 +661,9 @@ pub enum PublishedAtError {
 /// - The IDs of dependencies that have been published
 /// - The names of packages that have not been published on chain.
 /// - The names of packages that have a `published-at` field that isn't filled with a valid address.
+///
+/// Published dependencies that were resolved from on-chain dependencies are also recorded
+/// separately.
 pub fn gather_published_ids(
     resolution_graph: &ResolvedGraph,
 ) -> (Result<ObjectID, PublishedAtError>, PackageDependencies) {
@@ -655,6 +672,7 @@ pub fn gather_published_ids(
     let mut published = BTreeMap::new();
     let mut unpublished = BTreeSet::new();
     let mut invalid = BTreeMap::new();
+    let mut on_chain = BTreeSet::new();
     let mut published_at = Err(PublishedAtError::NotPresent);
 
     for (name, package) in &resolution_graph.package_table {
@@ -667,6 +685,14 @@ pub fn gather_published_ids(
 
         match property {
             Ok(id) => {
+                if let Some(DependencyKind::OnChain(_)) = resolution_graph
+                    .graph
+                    .package_table
+                    .get(name)
+                    .map(|p| &p.kind)
+                {
+                    on_chain.insert(*name);
+                }
                 published.insert(*name, id);
             }
             Err(PublishedAtError::NotPresent) => {
@@ -684,6 +710,7 @@ pub fn gather_published_ids(
             published,
             unpublished,
             invalid,
+            on_chain,
         },
     )
 }
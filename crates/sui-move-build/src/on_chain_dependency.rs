@@ -0,0 +1,264 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolution of on-chain dependencies (`Pkg = { id = "0x...", network = "testnet" }`) in package
+//! manifests. The published package is fetched from a fullnode of the given network, and a source
+//! package is generated for it, with one interface stub per module: public and friend functions
+//! are declared `native`, and private functions are left out. A stub package depends on the
+//! non-system packages in its linkage table as on-chain dependencies in turn, but does not declare
+//! a dependency on the system packages, so packages with on-chain dependencies need to depend on
+//! the Sui framework (and any other system package they use) themselves.
+//!
+//! Generated packages are cached, along with the identifier of the chain they were fetched from,
+//! and fetched again if the network's chain identifier changes (e.g. after a devnet wipe).
+
+use std::{collections::BTreeMap, fmt::Write as _, fs, path::Path};
+
+use anyhow::{anyhow, bail, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use move_binary_format::CompiledModule;
+use move_compiler::interface_generator::write_module_to_string;
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use move_package::source_package::parsed_manifest::OnChainInfo;
+use move_symbol_pool::Symbol;
+use serde_json::{json, Value};
+use sui_types::{base_types::ObjectID, is_system_package};
+
+use crate::PUBLISHED_AT_MANIFEST_FIELD;
+
+const MAINNET_URL: &str = "https://fullnode.mainnet.sui.io:443";
+const TESTNET_URL: &str = "https://fullnode.testnet.sui.io:443";
+const DEVNET_URL: &str = "https://fullnode.devnet.sui.io:443";
+const LOCALNET_URL: &str = "http://127.0.0.1:9000";
+
+/// Prefix of the names given to the on-chain dependencies of on-chain packages.
+const ON_CHAIN_PACKAGE_PREFIX: &str = "OnChain_";
+
+/// File in a generated package that records the identifier of the chain it was fetched from.
+pub(crate) const CHAIN_ID_FILE: &str = "chain-id";
+
+/// The fullnode RPC URL for `network`, which is either one of `mainnet`, `testnet`, `devnet` or
+/// `localnet`, or the URL of a fullnode.
+pub(crate) fn network_url(network: &str) -> anyhow::Result<&str> {
+    Ok(match network {
+        "mainnet" => MAINNET_URL,
+        "testnet" => TESTNET_URL,
+        "devnet" => DEVNET_URL,
+        "localnet" => LOCALNET_URL,
+        url if url.starts_with("http://") || url.starts_with("https://") => url,
+        _ => bail!(
+            "Unknown network '{network}': expected one of 'mainnet', 'testnet', 'devnet', \
+             'localnet', or a fullnode URL"
+        ),
+    })
+}
+
+/// A package fetched from the network.
+pub(crate) struct OnChainPackage {
+    pub id: ObjectID,
+    pub modules: Vec<CompiledModule>,
+    /// Original ID to upgraded ID, for each package in the linkage table
+    pub linkage: BTreeMap<ObjectID, ObjectID>,
+}
+
+/// Fetches the package described by `info`, and writes a source package for it at `path`, named
+/// `dep_name`. A package already at `path` is kept if it was fetched from the same chain, or if the
+/// network can't be reached to check.
+pub(crate) fn resolve_on_chain_dependency(
+    dep_name: Symbol,
+    info: &OnChainInfo,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let id = ObjectID::from_hex_literal(info.id.as_str())
+        .map_err(|_| anyhow!("Invalid on-chain package ID '{}' for '{dep_name}'", info.id))?;
+    if is_system_package(id) {
+        bail!(
+            "On-chain dependency '{dep_name}' is the system package {id}, which must be depended \
+             on from source"
+        );
+    }
+
+    let url = network_url(info.network.as_str())?;
+    let chain_id = fetch_chain_identifier(url);
+    if path.exists() {
+        let cached = fs::read_to_string(path.join(CHAIN_ID_FILE)).ok();
+        match &chain_id {
+            Ok(chain_id) if cached.as_deref() != Some(chain_id.as_str()) => {
+                fs::remove_dir_all(path).with_context(|| {
+                    format!("Removing outdated on-chain package at {}", path.display())
+                })?;
+            }
+            _ => return Ok(()),
+        }
+    }
+
+    let chain_id =
+        chain_id.with_context(|| format!("Fetching chain identifier of '{}'", info.network))?;
+    let package = fetch_package(url, id)
+        .with_context(|| format!("Fetching on-chain package {id} for '{dep_name}'"))?;
+    write_package(dep_name, info.network.as_str(), &chain_id, &package, path)
+}
+
+/// Writes a source package named `dep_name` for `package`, fetched from `network` (whose chain
+/// identifier is `chain_id`), at `path`.
+pub(crate) fn write_package(
+    dep_name: Symbol,
+    network: &str,
+    chain_id: &str,
+    package: &OnChainPackage,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let Some(original_id) = package
+        .modules
+        .first()
+        .map(|m| ObjectID::from(*m.address()))
+    else {
+        bail!("On-chain package {} has no modules", package.id);
+    };
+
+    // Write the package to a temporary directory next to `path`, so that it only becomes visible
+    // once it is complete.
+    let Some(parent) = path.parent() else {
+        bail!("Invalid location for on-chain package: {}", path.display());
+    };
+    fs::create_dir_all(parent)?;
+    let tmp = tempfile::tempdir_in(parent)?;
+
+    let address_name = dep_name.as_str().to_lowercase();
+    let mut manifest = String::new();
+    writeln!(manifest, "[package]")?;
+    writeln!(manifest, "name = \"{dep_name}\"")?;
+    writeln!(
+        manifest,
+        "{PUBLISHED_AT_MANIFEST_FIELD} = \"{}\"",
+        package.id
+    )?;
+    writeln!(manifest)?;
+    writeln!(manifest, "[dependencies]")?;
+    for (original, upgraded) in &package.linkage {
+        if !is_system_package(*original) {
+            writeln!(
+                manifest,
+                "{} = {{ id = \"{upgraded}\", network = \"{network}\" }}",
+                on_chain_package_name(original),
+            )?;
+        }
+    }
+    writeln!(manifest)?;
+    writeln!(manifest, "[addresses]")?;
+    writeln!(manifest, "{address_name} = \"{original_id}\"")?;
+    fs::write(tmp.path().join("Move.toml"), manifest)?;
+    fs::write(tmp.path().join(CHAIN_ID_FILE), chain_id)?;
+
+    let named_addresses: BTreeMap<ModuleId, &str> = package
+        .modules
+        .iter()
+        .map(|m| (m.self_id(), address_name.as_str()))
+        .collect();
+
+    let sources = tmp.path().join("sources");
+    fs::create_dir_all(&sources)?;
+    for module in &package.modules {
+        let (module_id, stub) = write_module_to_string(&named_addresses, module)?;
+        fs::write(sources.join(format!("{}.move", module_id.name())), stub)?;
+    }
+
+    fs::rename(tmp.into_path(), path).with_context(|| {
+        format!(
+            "Writing on-chain package {} for '{dep_name}' to {}",
+            package.id,
+            path.display(),
+        )
+    })
+}
+
+/// The dependency name used for the on-chain package with original ID `original_id`, when it is
+/// depended on by another on-chain package.
+pub(crate) fn on_chain_package_name(original_id: &ObjectID) -> String {
+    format!(
+        "{ON_CHAIN_PACKAGE_PREFIX}{}",
+        AccountAddress::from(*original_id).short_str_lossless()
+    )
+}
+
+/// Calls the JSON-RPC `method` on the fullnode at `url`, returning its result.
+fn rpc_call(url: &str, method: &str, params: Value) -> anyhow::Result<Value> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+
+    let mut response: Value = serde_json::from_str(
+        &ureq::post(url)
+            .set("Content-Type", "application/json")
+            .send_string(&request.to_string())?
+            .into_string()?,
+    )?;
+
+    if let Some(error) = response.get("error") {
+        bail!("RPC error: {error}");
+    }
+
+    Ok(response["result"].take())
+}
+
+/// Fetches the identifier of the chain that the fullnode at `url` belongs to.
+fn fetch_chain_identifier(url: &str) -> anyhow::Result<String> {
+    let result = rpc_call(url, "sui_getChainIdentifier", json!([]))?;
+    let Some(chain_id) = result.as_str() else {
+        bail!("Unexpected chain identifier: {result}");
+    };
+    Ok(chain_id.to_string())
+}
+
+/// Fetches the modules and linkage table of the package at `id` from the fullnode at `url`.
+fn fetch_package(url: &str, id: ObjectID) -> anyhow::Result<OnChainPackage> {
+    let result = rpc_call(
+        url,
+        "sui_getObject",
+        json!([id.to_string(), { "showBcs": true }]),
+    )?;
+    if let Some(error) = result.get("error") {
+        bail!("Object error: {error}");
+    }
+
+    let bcs = &result["data"]["bcs"];
+    if bcs["dataType"] != "package" {
+        bail!("Object {id} is not a package");
+    }
+
+    let Some(module_map) = bcs["moduleMap"].as_object() else {
+        bail!("Package {id} response is missing its modules");
+    };
+
+    let mut modules = vec![];
+    for (name, bytes) in module_map {
+        let Some(bytes) = bytes.as_str() else {
+            bail!("Module '{name}' of package {id} is not base64 encoded");
+        };
+        let bytes = Base64::decode(bytes)
+            .map_err(|e| anyhow!("Module '{name}' of package {id} is not base64 encoded: {e}"))?;
+        let module = CompiledModule::deserialize_with_defaults(&bytes)
+            .map_err(|e| anyhow!("Failed to deserialize module '{name}' of package {id}: {e}"))?;
+        modules.push(module);
+    }
+
+    let mut linkage = BTreeMap::new();
+    if let Some(table) = bcs["linkageTable"].as_object() {
+        for (original, info) in table {
+            let original = ObjectID::from_hex_literal(original)?;
+            let Some(upgraded) = info["upgraded_id"].as_str() else {
+                bail!("Linkage table of package {id} is missing an upgraded ID for {original}");
+            };
+            linkage.insert(original, ObjectID::from_hex_literal(upgraded)?);
+        }
+    }
+
+    Ok(OnChainPackage {
+        id,
+        modules,
+        linkage,
+    })
+}
@@ -1,9 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::Path;
+use std::{collections::BTreeMap, fs, path::Path};
 
-use crate::BuildConfig;
+use sui_types::{base_types::ObjectID, SUI_FRAMEWORK_ADDRESS};
+
+use crate::{
+    on_chain_dependency::{
+        network_url, on_chain_package_name, write_package, OnChainPackage, CHAIN_ID_FILE,
+    },
+    BuildConfig,
+};
 
 #[test]
 fn generate_struct_layouts() {
@@ -39,3 +46,78 @@ fn development_mode_not_allowed() {
         .join("no_development_mode");
     assert!(BuildConfig::new_for_testing().build(path).is_err());
 }
+
+#[test]
+fn on_chain_network_urls() {
+    assert_eq!(
+        network_url("mainnet").unwrap(),
+        "https://fullnode.mainnet.sui.io:443"
+    );
+    assert_eq!(network_url("localnet").unwrap(), "http://127.0.0.1:9000");
+    assert_eq!(
+        network_url("http://example.com:9000").unwrap(),
+        "http://example.com:9000"
+    );
+    assert!(network_url("mainet").is_err());
+}
+
+#[test]
+fn on_chain_stub_package() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .to_path_buf()
+        .join("src")
+        .join("unit_tests")
+        .join("data")
+        .join("on_chain_stub");
+    let pkg = BuildConfig::new_for_testing().build(path).unwrap();
+
+    let id = ObjectID::from_hex_literal("0xb0b").unwrap();
+    let original = ObjectID::from_hex_literal("0xa11ce").unwrap();
+    let dependency = ObjectID::from_hex_literal("0xca7").unwrap();
+    let package = OnChainPackage {
+        id,
+        modules: pkg.into_modules(),
+        linkage: BTreeMap::from([
+            (
+                ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+                ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+            ),
+            (dependency, ObjectID::from_hex_literal("0xd06").unwrap()),
+        ]),
+    };
+
+    let tmp = tempfile::tempdir().unwrap();
+    let stub = tmp.path().join("Example");
+    write_package("Example".into(), "testnet", "4c78adac", &package, &stub).unwrap();
+
+    // The manifest publishes the stub at the fetched ID, depends on the non-system packages in
+    // its linkage table, and names the original ID.
+    let manifest = fs::read_to_string(stub.join("Move.toml")).unwrap();
+    assert!(manifest.contains("name = \"Example\""));
+    assert!(manifest.contains(&format!("published-at = \"{id}\"")));
+    assert!(manifest.contains(&format!(
+        "{} = {{ id = \"{}\", network = \"testnet\" }}",
+        on_chain_package_name(&dependency),
+        ObjectID::from_hex_literal("0xd06").unwrap(),
+    )));
+    assert!(!manifest.contains(&ObjectID::from(SUI_FRAMEWORK_ADDRESS).to_string()));
+    assert!(manifest.contains(&format!("example = \"{original}\"")));
+
+    assert_eq!(
+        fs::read_to_string(stub.join(CHAIN_ID_FILE)).unwrap(),
+        "4c78adac"
+    );
+
+    // Public and package functions become native, private functions are left out.
+    let source = fs::read_to_string(stub.join("sources").join("counter.move")).unwrap();
+    assert!(source.contains("module example::counter"));
+    assert!(source.contains("struct Counter"));
+    assert!(source.contains("native public fun value("));
+    assert!(source.contains("native public(friend) fun increment("));
+    assert!(!source.contains("fun set("));
+
+    // The stub compiles. It is only ever built as a dependency, whose bytecode is not verified.
+    let mut config = BuildConfig::new_for_testing();
+    config.run_bytecode_verifier = false;
+    config.build(stub).unwrap();
+}
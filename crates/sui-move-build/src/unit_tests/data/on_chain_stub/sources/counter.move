@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module example::counter {
+    public struct Counter has store {
+        value: u64,
+    }
+
+    public fun value(counter: &Counter): u64 {
+        counter.value
+    }
+
+    public(package) fun increment(counter: &mut Counter) {
+        set(counter, counter.value + 1)
+    }
+
+    fun set(counter: &mut Counter, value: u64) {
+        counter.value = value
+    }
+}
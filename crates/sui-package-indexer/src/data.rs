@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+
+/// An event that matched one of a pipeline's event filters.
+#[derive(Clone, Debug)]
+pub struct IndexedEvent {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub transaction_digest: TransactionDigest,
+    /// Position of the event among the events emitted by its transaction
+    pub event_index: u64,
+    pub sender: SuiAddress,
+    /// Package and module of the function that emitted the event
+    pub package_id: ObjectID,
+    pub module: Identifier,
+    pub type_: StructTag,
+    /// BCS serialized contents of the event
+    pub contents: Vec<u8>,
+    /// The event's contents as JSON, if the pipeline decodes JSON
+    pub json: Option<Value>,
+}
+
+impl IndexedEvent {
+    /// Deserializes the event's contents into a Rust type that has the same BCS layout as the
+    /// event's Move struct.
+    pub fn to_rust<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(bcs::from_bytes(&self.contents)?)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectChangeKind {
+    /// The object was created, mutated or unwrapped
+    Written,
+    /// The object was deleted or wrapped
+    Removed,
+}
+
+/// A change to an object that matched one of a pipeline's object filters.
+#[derive(Clone, Debug)]
+pub struct IndexedObject {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub transaction_digest: TransactionDigest,
+    pub kind: ObjectChangeKind,
+    /// The object as written by the transaction or, if it was removed, as it was before the
+    /// transaction. Use `Object::to_rust` to deserialize its contents into a Rust type.
+    pub object: Object,
+    /// The object's contents as JSON, if the pipeline decodes JSON
+    pub json: Option<Value>,
+}
+
+/// The events and object changes of a range of checkpoints, handed to a sink to commit.
+#[derive(Clone, Debug)]
+pub struct Batch {
+    /// Events in the order they were emitted
+    pub events: Vec<IndexedEvent>,
+    /// Object changes in the order of their transactions
+    pub objects: Vec<IndexedObject>,
+    /// The last checkpoint covered by the batch. Committing the batch makes this the sink's
+    /// watermark.
+    pub watermark: CheckpointSequenceNumber,
+}
+
+impl Batch {
+    pub fn new(watermark: CheckpointSequenceNumber) -> Self {
+        Self {
+            events: vec![],
+            objects: vec![],
+            watermark,
+        }
+    }
+
+    /// Appends the contents of a batch of later checkpoints.
+    pub fn append(&mut self, other: Batch) {
+        self.events.extend(other.events);
+        self.objects.extend(other.objects);
+        self.watermark = other.watermark;
+    }
+
+    /// The number of events and object changes in the batch.
+    pub fn len(&self) -> usize {
+        self.events.len() + self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveTypeLayout;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde_json::Value;
+use sui_json_rpc_types::SuiMoveStruct;
+use sui_package_resolver::{PackageStoreWithLruCache, Resolver};
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::object::bounded_visitor::BoundedVisitor;
+
+use crate::data::Batch;
+use crate::package_store::CheckpointPackageStore;
+
+/// Decodes BCS serialized Move structs into JSON, using the layouts of the packages that define
+/// them. Clones share the same package store and cache.
+#[derive(Clone)]
+pub struct Decoder {
+    package_store: CheckpointPackageStore,
+    resolver: Arc<Resolver<PackageStoreWithLruCache<CheckpointPackageStore>>>,
+}
+
+impl Decoder {
+    pub fn new(package_store: CheckpointPackageStore) -> Self {
+        Self {
+            package_store: package_store.clone(),
+            resolver: Arc::new(Resolver::new(PackageStoreWithLruCache::new(package_store))),
+        }
+    }
+
+    /// Whether the decoder can decode the contents of a checkpoint without having seen the
+    /// checkpoints before it, by fetching the packages it has not seen from a full node.
+    pub(crate) fn has_fallback(&self) -> bool {
+        self.package_store.has_fallback()
+    }
+
+    /// Keeps the packages written by `checkpoint`, so they can be used to decode its contents
+    /// and those of later checkpoints.
+    pub fn update(&self, checkpoint: &CheckpointData) {
+        let updated = self.package_store.update(checkpoint);
+        // System packages are upgraded in place, so their previous version may be cached.
+        self.resolver
+            .package_store()
+            .evict(updated.into_iter().map(AccountAddress::from));
+    }
+
+    /// Decodes `contents`, a value of type `type_`, into JSON.
+    pub async fn to_json(&self, type_: &StructTag, contents: &[u8]) -> Result<Value> {
+        let layout = self
+            .resolver
+            .type_layout(TypeTag::Struct(Box::new(type_.clone())))
+            .await?;
+        let MoveTypeLayout::Struct(layout) = layout else {
+            bail!("Type {type_} is not a struct");
+        };
+        let move_struct = BoundedVisitor::deserialize_struct(contents, &layout)?;
+        let sui_move_struct = match move_struct.into() {
+            SuiMoveStruct::WithTypes { fields, .. } => SuiMoveStruct::WithFields(fields),
+            fields => fields,
+        };
+        Ok(sui_move_struct.to_json_value())
+    }
+
+    /// Fills in the JSON contents of the events and objects in `batch`.
+    pub async fn decode(&self, batch: &mut Batch) -> Result<()> {
+        for event in &mut batch.events {
+            event.json = Some(self.to_json(&event.type_, &event.contents).await?);
+        }
+        for change in &mut batch.objects {
+            let Some(move_object) = change.object.data.try_as_move() else {
+                continue;
+            };
+            let type_: StructTag = move_object.type_().clone().into();
+            change.json = Some(self.to_json(&type_, move_object.contents()).await?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::object::Object;
+use sui_types::parse_sui_struct_tag;
+
+use crate::data::{Batch, IndexedEvent, IndexedObject, ObjectChangeKind};
+
+/// Matches Move struct types. Types are identified by the package that first defined them, so a
+/// filter on a package's original ID also matches the types of its upgrades.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeFilter {
+    /// Every type defined in a package, e.g. `0x2`
+    Package(AccountAddress),
+    /// Every type defined in a module, e.g. `0x2::coin`
+    Module(AccountAddress, Identifier),
+    /// A struct type, e.g. `0x2::coin::Coin`. Without type parameters, this matches every
+    /// instantiation of the struct, and with type parameters, only that instantiation.
+    Type(StructTag),
+}
+
+impl TypeFilter {
+    pub fn matches(&self, tag: &StructTag) -> bool {
+        match self {
+            TypeFilter::Package(address) => tag.address == *address,
+            TypeFilter::Module(address, module) => tag.address == *address && tag.module == *module,
+            TypeFilter::Type(filter) if filter.type_params.is_empty() => {
+                tag.address == filter.address
+                    && tag.module == filter.module
+                    && tag.name == filter.name
+            }
+            TypeFilter::Type(filter) => tag == filter,
+        }
+    }
+}
+
+impl FromStr for TypeFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('<') {
+            return Ok(TypeFilter::Type(parse_sui_struct_tag(s)?));
+        }
+        let parts: Vec<_> = s.split("::").collect();
+        let address = || {
+            AccountAddress::from_hex_literal(parts[0])
+                .map_err(|_| anyhow!("Invalid address '{}' in type filter '{s}'", parts[0]))
+        };
+        Ok(match parts.len() {
+            1 => TypeFilter::Package(address()?),
+            2 => TypeFilter::Module(address()?, Identifier::new(parts[1])?),
+            3 => TypeFilter::Type(parse_sui_struct_tag(s)?),
+            _ => bail!(
+                "Invalid type filter '{s}', expected a package, module or struct type, e.g. \
+                 '0x2', '0x2::coin' or '0x2::coin::Coin'"
+            ),
+        })
+    }
+}
+
+impl From<StructTag> for TypeFilter {
+    fn from(tag: StructTag) -> Self {
+        TypeFilter::Type(tag)
+    }
+}
+
+/// The events and objects a pipeline indexes. Events match if their type matches any of the
+/// event filters, and objects if their type matches any of the object filters.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    pub events: Vec<TypeFilter>,
+    pub objects: Vec<TypeFilter>,
+}
+
+impl Filter {
+    fn matches_event(&self, tag: &StructTag) -> bool {
+        self.events.iter().any(|filter| filter.matches(tag))
+    }
+
+    fn matches_object(&self, object: &Object) -> bool {
+        object
+            .struct_tag()
+            .is_some_and(|tag| self.objects.iter().any(|filter| filter.matches(&tag)))
+    }
+
+    /// Selects the events and object changes in `checkpoint` that match this filter, in the order
+    /// of their transactions. Within a transaction, written objects come before removed ones. The
+    /// batch's JSON contents are left empty.
+    pub fn select(&self, checkpoint: &CheckpointData) -> Batch {
+        let summary = &checkpoint.checkpoint_summary;
+        let mut batch = Batch::new(summary.sequence_number);
+
+        for transaction in &checkpoint.transactions {
+            let transaction_digest = *transaction.transaction.digest();
+
+            if !self.events.is_empty() {
+                let events = transaction.events.iter().flat_map(|events| &events.data);
+                for (event_index, event) in events.enumerate() {
+                    if !self.matches_event(&event.type_) {
+                        continue;
+                    }
+                    batch.events.push(IndexedEvent {
+                        checkpoint: summary.sequence_number,
+                        timestamp_ms: summary.timestamp_ms,
+                        transaction_digest,
+                        event_index: event_index as u64,
+                        sender: event.sender,
+                        package_id: event.package_id,
+                        module: event.transaction_module.clone(),
+                        type_: event.type_.clone(),
+                        contents: event.contents.clone(),
+                        json: None,
+                    });
+                }
+            }
+
+            if self.objects.is_empty() {
+                continue;
+            }
+
+            let object_change = |kind, object: &Object| IndexedObject {
+                checkpoint: summary.sequence_number,
+                timestamp_ms: summary.timestamp_ms,
+                transaction_digest,
+                kind,
+                object: object.clone(),
+                json: None,
+            };
+
+            for object in &transaction.output_objects {
+                if self.matches_object(object) {
+                    batch
+                        .objects
+                        .push(object_change(ObjectChangeKind::Written, object));
+                }
+            }
+
+            let removed: HashSet<_> = transaction
+                .effects
+                .all_tombstones()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            for object in &transaction.input_objects {
+                if removed.contains(&object.id()) && self.matches_object(object) {
+                    batch
+                        .objects
+                        .push(object_change(ObjectChangeKind::Removed, object));
+                }
+            }
+        }
+
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_filter() {
+        let coin = parse_sui_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let balance = parse_sui_struct_tag("0x2::balance::Balance<0x2::sui::SUI>").unwrap();
+        let other = parse_sui_struct_tag("0x3::coin::Coin<0x2::sui::SUI>").unwrap();
+
+        let package: TypeFilter = "0x2".parse().unwrap();
+        assert!(package.matches(&coin) && package.matches(&balance) && !package.matches(&other));
+
+        let module: TypeFilter = "0x2::coin".parse().unwrap();
+        assert!(module.matches(&coin) && !module.matches(&balance) && !module.matches(&other));
+
+        let any_coin: TypeFilter = "0x2::coin::Coin".parse().unwrap();
+        assert!(any_coin.matches(&coin) && !any_coin.matches(&other));
+
+        let sui_coin: TypeFilter = "0x2::coin::Coin<0x2::sui::SUI>".parse().unwrap();
+        let usdc = parse_sui_struct_tag("0x2::coin::Coin<0x42::usdc::USDC>").unwrap();
+        assert!(sui_coin.matches(&coin) && !sui_coin.matches(&usdc));
+        assert!(any_coin.matches(&usdc));
+
+        assert!("0x2::coin::Coin::x".parse::<TypeFilter>().is_err());
+        assert!("coin".parse::<TypeFilter>().is_err());
+    }
+}
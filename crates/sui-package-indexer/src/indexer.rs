@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use sui_data_ingestion_core::{
+    DataIngestionMetrics, IndexerExecutor, ProgressStore, ReaderOptions, WorkerPool,
+};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::oneshot;
+use tracing::info;
+
+use crate::decoder::Decoder;
+use crate::package_store::CheckpointPackageStore;
+use crate::pipeline::{Pipeline, PipelineWorker};
+
+/// Runs pipelines over a stream of checkpoints.
+pub struct PackageIndexer {
+    decoder: Decoder,
+    first_checkpoint: CheckpointSequenceNumber,
+    pipelines: Vec<Pipeline>,
+    metrics: DataIngestionMetrics,
+}
+
+impl PackageIndexer {
+    /// Creates an indexer whose pipelines start from `first_checkpoint` until they have committed
+    /// a batch, and from the checkpoint after their sink's watermark after that.
+    pub fn new(
+        package_store: CheckpointPackageStore,
+        first_checkpoint: CheckpointSequenceNumber,
+        metrics: DataIngestionMetrics,
+    ) -> Self {
+        Self {
+            decoder: Decoder::new(package_store),
+            first_checkpoint,
+            pipelines: vec![],
+            metrics,
+        }
+    }
+
+    pub fn add_pipeline(&mut self, pipeline: Pipeline) -> Result<()> {
+        if self.pipelines.iter().any(|p| p.name == pipeline.name) {
+            bail!("Pipeline '{}' is already registered", pipeline.name);
+        }
+        self.pipelines.push(pipeline);
+        Ok(())
+    }
+
+    /// Reads checkpoints from `path`, and from `remote_store_url` if set, and runs the pipelines
+    /// over them until `exit_receiver` fires. Returns the progress of each pipeline.
+    ///
+    /// Fails upfront if a pipeline that decodes JSON starts after genesis, and the package store
+    /// cannot fetch the packages published before that from a full node, as it would be unable to
+    /// decode their types.
+    pub async fn run(
+        self,
+        path: PathBuf,
+        remote_store_url: Option<String>,
+        remote_store_options: Vec<(String, String)>,
+        reader_options: ReaderOptions,
+        exit_receiver: oneshot::Receiver<()>,
+    ) -> Result<HashMap<String, CheckpointSequenceNumber>> {
+        if self.pipelines.is_empty() {
+            bail!("No pipelines to run");
+        }
+
+        let mut first_checkpoints = HashMap::new();
+        for pipeline in &self.pipelines {
+            let first_checkpoint = match pipeline.sink.watermark().await? {
+                Some(watermark) => watermark + 1,
+                None => self.first_checkpoint,
+            };
+            if pipeline.decode_json && first_checkpoint > 0 && !self.decoder.has_fallback() {
+                bail!(
+                    "Pipeline '{}' decodes JSON starting at checkpoint {first_checkpoint}, which \
+                     needs the packages published before it, but the package store has no full \
+                     node REST URL to fetch them from",
+                    pipeline.name
+                );
+            }
+            info!(
+                "Pipeline {} starts at checkpoint {first_checkpoint}",
+                pipeline.name
+            );
+            first_checkpoints.insert(pipeline.name.clone(), first_checkpoint);
+        }
+
+        let progress_store = SinkProgressStore(first_checkpoints.clone());
+        let mut executor = IndexerExecutor::new(progress_store, self.pipelines.len(), self.metrics);
+        for pipeline in self.pipelines {
+            let name = pipeline.name.clone();
+            let concurrency = pipeline.concurrency;
+            let worker =
+                PipelineWorker::new(pipeline, self.decoder.clone(), first_checkpoints[&name]);
+            executor
+                .register(WorkerPool::new(worker, name, concurrency))
+                .await?;
+        }
+
+        executor
+            .run(
+                path,
+                remote_store_url,
+                remote_store_options,
+                reader_options,
+                exit_receiver,
+            )
+            .await
+    }
+}
+
+/// Progress store for pipelines whose progress is the watermark of their sink, which the sink
+/// records when it commits a batch. Loading returns the checkpoint after the watermark as of when
+/// the indexer started, and saving does nothing.
+struct SinkProgressStore(HashMap<String, CheckpointSequenceNumber>);
+
+#[async_trait]
+impl ProgressStore for SinkProgressStore {
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
+        self.0
+            .get(&task_name)
+            .copied()
+            .ok_or_else(|| anyhow!("Unknown pipeline '{task_name}'"))
+    }
+
+    async fn save(&mut self, _: String, _: CheckpointSequenceNumber) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Framework for indexing the events and objects of Move packages from checkpoints.
+//!
+//! A [`Pipeline`] selects the events and object changes whose types match its filters, optionally
+//! decodes them into JSON, and commits them in batches to a [`Sink`]. Sinks record the last
+//! checkpoint they committed with each batch, and pipelines resume from there when restarted.
+//! Pipelines are run by a [`PackageIndexer`], over checkpoints read by the data ingestion
+//! framework.
+
+mod data;
+mod decoder;
+mod filter;
+mod indexer;
+mod package_store;
+mod pipeline;
+pub mod sinks;
+
+pub use data::{Batch, IndexedEvent, IndexedObject, ObjectChangeKind};
+pub use decoder::Decoder;
+pub use filter::{Filter, TypeFilter};
+pub use indexer::PackageIndexer;
+pub use package_store::CheckpointPackageStore;
+pub use pipeline::Pipeline;
+pub use sinks::Sink;
@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use move_core_types::account_address::AccountAddress;
+use sui_package_resolver::{error::Error as PackageResolverError, Package, PackageStore, Result};
+use sui_rest_api::Client;
+use sui_types::base_types::ObjectID;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::object::Object;
+
+/// Store which keeps the packages written by the checkpoints an indexer has seen in memory. Any
+/// other package is fetched from a full node, if one is configured, and then kept as well.
+#[derive(Clone)]
+pub struct CheckpointPackageStore {
+    packages: Arc<RwLock<HashMap<ObjectID, Object>>>,
+    fallback_client: Option<Client>,
+}
+
+impl CheckpointPackageStore {
+    /// Creates a store that fetches packages it has not seen from the full node at `rest_url`,
+    /// or fails to resolve them if `rest_url` is `None`. Without `rest_url`, pipelines that decode
+    /// JSON can only index from genesis, and cannot resume once they have committed a batch.
+    pub fn new(rest_url: Option<&str>) -> Self {
+        Self {
+            packages: Arc::new(RwLock::new(HashMap::new())),
+            fallback_client: rest_url.map(|url| Client::new(format!("{url}/rest"))),
+        }
+    }
+
+    /// Whether packages the store has not seen are fetched from a full node.
+    pub fn has_fallback(&self) -> bool {
+        self.fallback_client.is_some()
+    }
+
+    /// Keeps the packages written by `checkpoint`, and returns their IDs. A package is only
+    /// replaced by a later version of itself, which only happens for system packages.
+    pub fn update(&self, checkpoint: &CheckpointData) -> Vec<ObjectID> {
+        let mut packages = self.packages.write().unwrap();
+        let mut updated = vec![];
+        for transaction in &checkpoint.transactions {
+            for object in &transaction.output_objects {
+                if !object.is_package() {
+                    continue;
+                }
+                let id = object.id();
+                if packages
+                    .get(&id)
+                    .is_some_and(|existing| existing.version() >= object.version())
+                {
+                    continue;
+                }
+                packages.insert(id, object.clone());
+                updated.push(id);
+            }
+        }
+        updated
+    }
+
+    async fn get(&self, id: AccountAddress) -> Result<Object> {
+        let id = ObjectID::from(id);
+        if let Some(object) = self.packages.read().unwrap().get(&id) {
+            return Ok(object.clone());
+        }
+
+        let Some(client) = &self.fallback_client else {
+            return Err(PackageResolverError::PackageNotFound(id.into()));
+        };
+        let object = client
+            .get_object(id)
+            .await
+            .map_err(|_| PackageResolverError::PackageNotFound(id.into()))?;
+        self.packages
+            .write()
+            .unwrap()
+            .entry(id)
+            .or_insert_with(|| object.clone());
+        Ok(object)
+    }
+}
+
+#[async_trait]
+impl PackageStore for CheckpointPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> Result<Arc<Package>> {
+        let object = self.get(id).await?;
+        Ok(Arc::new(Package::read_from_object(&object)?))
+    }
+}
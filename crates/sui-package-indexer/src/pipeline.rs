@@ -0,0 +1,286 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sui_data_ingestion_core::Worker;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::Mutex;
+
+use crate::data::Batch;
+use crate::decoder::Decoder;
+use crate::filter::{Filter, TypeFilter};
+use crate::sinks::Sink;
+
+const DEFAULT_CONCURRENCY: usize = 10;
+const DEFAULT_MAX_BATCH_ROWS: usize = 1000;
+const DEFAULT_MAX_BATCH_CHECKPOINTS: usize = 100;
+
+/// Indexes the events and objects matching a filter into a sink.
+pub struct Pipeline {
+    pub(crate) name: String,
+    pub(crate) filter: Filter,
+    pub(crate) sink: Arc<dyn Sink>,
+    pub(crate) decode_json: bool,
+    pub(crate) concurrency: usize,
+    pub(crate) max_batch_rows: usize,
+    pub(crate) max_batch_checkpoints: usize,
+}
+
+impl Pipeline {
+    /// Creates a pipeline that writes to `sink`. `name` identifies the pipeline in logs and
+    /// metrics, and must be unique within an indexer.
+    pub fn new(name: impl Into<String>, sink: impl Sink) -> Self {
+        Self {
+            name: name.into(),
+            filter: Filter::default(),
+            sink: Arc::new(sink),
+            decode_json: true,
+            concurrency: DEFAULT_CONCURRENCY,
+            max_batch_rows: DEFAULT_MAX_BATCH_ROWS,
+            max_batch_checkpoints: DEFAULT_MAX_BATCH_CHECKPOINTS,
+        }
+    }
+
+    /// Indexes events whose type matches any of `filters`.
+    pub fn events(mut self, filters: impl IntoIterator<Item = TypeFilter>) -> Self {
+        self.filter.events.extend(filters);
+        self
+    }
+
+    /// Indexes changes to objects whose type matches any of `filters`.
+    pub fn objects(mut self, filters: impl IntoIterator<Item = TypeFilter>) -> Self {
+        self.filter.objects.extend(filters);
+        self
+    }
+
+    /// Whether to decode the contents of events and objects into JSON, which requires fetching
+    /// the packages that define their types. Enabled by default.
+    pub fn decode_json(mut self, decode_json: bool) -> Self {
+        self.decode_json = decode_json;
+        self
+    }
+
+    /// Number of checkpoints processed concurrently. Batches are still committed in order.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// A batch is committed once it has at least `max_rows` events and object changes, or covers
+    /// at least `max_checkpoints` checkpoints, whichever comes first.
+    pub fn batch_size(mut self, max_rows: usize, max_checkpoints: usize) -> Self {
+        self.max_batch_rows = max_rows;
+        self.max_batch_checkpoints = max_checkpoints;
+        self
+    }
+}
+
+/// Collects the batches of checkpoints that may be processed out of order, and commits them to
+/// the sink in order.
+pub(crate) struct Committer {
+    sink: Arc<dyn Sink>,
+    max_batch_rows: usize,
+    max_batch_checkpoints: usize,
+    state: Mutex<CommitterState>,
+}
+
+struct CommitterState {
+    /// The next checkpoint to add to `pending`
+    next_checkpoint: CheckpointSequenceNumber,
+    /// Batches of processed checkpoints that come after `next_checkpoint`
+    processed: BTreeMap<CheckpointSequenceNumber, Batch>,
+    /// The batch to commit next, and the number of checkpoints it covers
+    pending: Option<(Batch, usize)>,
+    watermark: Option<CheckpointSequenceNumber>,
+}
+
+impl Committer {
+    pub(crate) fn new(
+        sink: Arc<dyn Sink>,
+        max_batch_rows: usize,
+        max_batch_checkpoints: usize,
+        first_checkpoint: CheckpointSequenceNumber,
+    ) -> Self {
+        Self {
+            sink,
+            max_batch_rows,
+            max_batch_checkpoints,
+            state: Mutex::new(CommitterState {
+                next_checkpoint: first_checkpoint,
+                processed: BTreeMap::new(),
+                pending: None,
+                watermark: first_checkpoint.checked_sub(1),
+            }),
+        }
+    }
+
+    /// Adds the batch of a single checkpoint, and commits the pending batch whenever it is full.
+    /// If a commit fails, the pending batch is kept, and adding the same checkpoint again retries
+    /// it.
+    pub(crate) async fn add(&self, batch: Batch) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let state = &mut *state;
+        if batch.watermark >= state.next_checkpoint {
+            state.processed.insert(batch.watermark, batch);
+        }
+
+        loop {
+            if let Some((pending, checkpoints)) = &state.pending {
+                if pending.len() >= self.max_batch_rows
+                    || *checkpoints >= self.max_batch_checkpoints
+                {
+                    self.sink.commit(pending).await?;
+                    state.watermark = Some(pending.watermark);
+                    state.pending = None;
+                }
+            }
+
+            let Some(next) = state.processed.remove(&state.next_checkpoint) else {
+                return Ok(());
+            };
+            state.next_checkpoint += 1;
+            match &mut state.pending {
+                Some((pending, checkpoints)) => {
+                    pending.append(next);
+                    *checkpoints += 1;
+                }
+                None => state.pending = Some((next, 1)),
+            }
+        }
+    }
+
+    /// The last checkpoint committed to the sink.
+    pub(crate) async fn watermark(&self) -> Option<CheckpointSequenceNumber> {
+        self.state.lock().await.watermark
+    }
+}
+
+pub(crate) struct PipelineWorker {
+    filter: Filter,
+    decoder: Option<Decoder>,
+    committer: Committer,
+}
+
+impl PipelineWorker {
+    pub(crate) fn new(
+        pipeline: Pipeline,
+        decoder: Decoder,
+        first_checkpoint: CheckpointSequenceNumber,
+    ) -> Self {
+        Self {
+            filter: pipeline.filter,
+            decoder: pipeline.decode_json.then_some(decoder),
+            committer: Committer::new(
+                pipeline.sink,
+                pipeline.max_batch_rows,
+                pipeline.max_batch_checkpoints,
+                first_checkpoint,
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for PipelineWorker {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        let mut batch = self.filter.select(&checkpoint);
+        if let Some(decoder) = &self.decoder {
+            decoder.decode(&mut batch).await?;
+        }
+        self.committer.add(batch).await
+    }
+
+    async fn save_progress(&self, _: CheckpointSequenceNumber) -> Option<CheckpointSequenceNumber> {
+        self.committer.watermark().await
+    }
+
+    fn preprocess_hook(&self, checkpoint: CheckpointData) -> Result<()> {
+        // Checkpoints are preprocessed in order, so packages are known before the checkpoints
+        // that use them are decoded.
+        if let Some(decoder) = &self.decoder {
+            decoder.update(&checkpoint);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use sui_types::digests::TransactionDigest;
+    use sui_types::object::Object;
+
+    use super::*;
+    use crate::data::{IndexedObject, ObjectChangeKind};
+
+    #[derive(Default)]
+    struct TestSink {
+        commits: std::sync::Mutex<Vec<(CheckpointSequenceNumber, usize)>>,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl Sink for Arc<TestSink> {
+        async fn watermark(&self) -> Result<Option<CheckpointSequenceNumber>> {
+            Ok(self.commits.lock().unwrap().last().map(|(w, _)| *w))
+        }
+
+        async fn commit(&self, batch: &Batch) -> Result<()> {
+            if self.fail.load(Ordering::SeqCst) {
+                anyhow::bail!("commit failed");
+            }
+            self.commits
+                .lock()
+                .unwrap()
+                .push((batch.watermark, batch.len()));
+            Ok(())
+        }
+    }
+
+    fn checkpoint_batch(checkpoint: CheckpointSequenceNumber, rows: usize) -> Batch {
+        let mut batch = Batch::new(checkpoint);
+        for _ in 0..rows {
+            batch.objects.push(IndexedObject {
+                checkpoint,
+                timestamp_ms: 0,
+                transaction_digest: TransactionDigest::default(),
+                kind: ObjectChangeKind::Written,
+                object: Object::new_gas_for_testing(),
+                json: None,
+            });
+        }
+        batch
+    }
+
+    #[tokio::test]
+    async fn test_committer_commits_in_order() {
+        let sink = Arc::new(TestSink::default());
+        let committer = Committer::new(Arc::new(sink.clone()), 3, 2, 5);
+        assert_eq!(committer.watermark().await, Some(4));
+
+        // Checkpoints after a gap are held back until the gap is filled
+        committer.add(checkpoint_batch(7, 1)).await.unwrap();
+        committer.add(checkpoint_batch(6, 0)).await.unwrap();
+        assert!(sink.commits.lock().unwrap().is_empty());
+
+        // The batch of checkpoints 5 and 6 is full by number of checkpoints
+        committer.add(checkpoint_batch(5, 1)).await.unwrap();
+        assert_eq!(*sink.commits.lock().unwrap(), vec![(6, 1)]);
+        assert_eq!(committer.watermark().await, Some(6));
+
+        // A failed commit is retried when its checkpoint is added again
+        sink.fail.store(true, Ordering::SeqCst);
+        assert!(committer.add(checkpoint_batch(8, 2)).await.is_err());
+        assert_eq!(committer.watermark().await, Some(6));
+        sink.fail.store(false, Ordering::SeqCst);
+        committer.add(checkpoint_batch(8, 2)).await.unwrap();
+        assert_eq!(*sink.commits.lock().unwrap(), vec![(6, 1), (8, 3)]);
+        assert_eq!(committer.watermark().await, Some(8));
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::Mutex;
+
+use crate::data::{Batch, IndexedEvent, IndexedObject, ObjectChangeKind};
+use crate::sinks::Sink;
+
+const EVENTS_FILE: &str = "events.jsonl";
+const OBJECTS_FILE: &str = "objects.jsonl";
+const WATERMARK_FILE: &str = "watermark.json";
+
+/// Sink which appends events and object changes as JSON lines to `events.jsonl` and
+/// `objects.jsonl` in a directory. The watermark is kept in `watermark.json`, along with the
+/// length of both files as of the watermark. Anything written past those lengths, by a commit
+/// that did not complete, is truncated before writing the next batch.
+pub struct JsonlSink {
+    dir: PathBuf,
+    watermark: Mutex<Option<JsonlWatermark>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+struct JsonlWatermark {
+    checkpoint: CheckpointSequenceNumber,
+    events_len: u64,
+    objects_len: u64,
+}
+
+impl JsonlSink {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let watermark_path = dir.join(WATERMARK_FILE);
+        let watermark = if watermark_path.exists() {
+            let watermark = fs::read(&watermark_path)?;
+            Some(
+                serde_json::from_slice(&watermark)
+                    .with_context(|| format!("Reading {}", watermark_path.display()))?,
+            )
+        } else {
+            None
+        };
+        Ok(Self {
+            dir,
+            watermark: Mutex::new(watermark),
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for JsonlSink {
+    async fn watermark(&self) -> Result<Option<CheckpointSequenceNumber>> {
+        Ok(self.watermark.lock().await.map(|w| w.checkpoint))
+    }
+
+    async fn commit(&self, batch: &Batch) -> Result<()> {
+        let mut watermark = self.watermark.lock().await;
+        let committed = watermark.unwrap_or_default();
+
+        let events_len = append_lines(
+            &self.dir.join(EVENTS_FILE),
+            committed.events_len,
+            batch.events.iter().map(event_json),
+        )?;
+        let objects_len = append_lines(
+            &self.dir.join(OBJECTS_FILE),
+            committed.objects_len,
+            batch.objects.iter().map(object_json),
+        )?;
+
+        let next = JsonlWatermark {
+            checkpoint: batch.watermark,
+            events_len,
+            objects_len,
+        };
+        // Replace the watermark file atomically, so it is never partially written.
+        let tmp_path = self.dir.join(format!("{WATERMARK_FILE}.tmp"));
+        fs::write(&tmp_path, serde_json::to_vec(&next)?)?;
+        File::open(&tmp_path)?.sync_all()?;
+        fs::rename(&tmp_path, self.dir.join(WATERMARK_FILE))?;
+
+        *watermark = Some(next);
+        Ok(())
+    }
+}
+
+/// Writes `lines` to the file at `path` starting at `offset`, truncating anything after it, and
+/// returns the file's new length.
+fn append_lines(path: &Path, offset: u64, lines: impl Iterator<Item = Value>) -> Result<u64> {
+    let mut file = OpenOptions::new().create(true).write(true).open(path)?;
+    file.set_len(offset)?;
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut writer = BufWriter::new(&mut file);
+    for line in lines {
+        serde_json::to_writer(&mut writer, &line)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    drop(writer);
+
+    file.sync_data()?;
+    Ok(file.stream_position()?)
+}
+
+fn event_json(event: &IndexedEvent) -> Value {
+    json!({
+        "checkpoint": event.checkpoint,
+        "timestamp_ms": event.timestamp_ms,
+        "transaction_digest": event.transaction_digest.to_string(),
+        "event_index": event.event_index,
+        "sender": event.sender.to_string(),
+        "package_id": event.package_id.to_string(),
+        "module": event.module.to_string(),
+        "type": event.type_.to_canonical_string(/* with_prefix */ true),
+        "bcs": Base64::encode(&event.contents),
+        "json": event.json,
+    })
+}
+
+fn object_json(change: &IndexedObject) -> Value {
+    let object = &change.object;
+    json!({
+        "checkpoint": change.checkpoint,
+        "timestamp_ms": change.timestamp_ms,
+        "transaction_digest": change.transaction_digest.to_string(),
+        "kind": match change.kind {
+            ObjectChangeKind::Written => "written",
+            ObjectChangeKind::Removed => "removed",
+        },
+        "object_id": object.id().to_string(),
+        "version": object.version().value(),
+        "owner": object.owner.to_string(),
+        "type": object
+            .struct_tag()
+            .map(|tag| tag.to_canonical_string(/* with_prefix */ true)),
+        "bcs": object
+            .data
+            .try_as_move()
+            .map(|move_object| Base64::encode(move_object.contents())),
+        "json": change.json,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_lines(dir: &Path, file: &str) -> Vec<Value> {
+        fs::read_to_string(dir.join(file))
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_sink_truncates_uncommitted_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = JsonlSink::new(dir.path()).unwrap();
+        assert_eq!(sink.watermark().await.unwrap(), None);
+
+        let mut batch = Batch::new(10);
+        let object = sui_types::object::Object::new_gas_for_testing();
+        batch.objects.push(IndexedObject {
+            checkpoint: 10,
+            timestamp_ms: 0,
+            transaction_digest: Default::default(),
+            kind: ObjectChangeKind::Written,
+            object,
+            json: None,
+        });
+        sink.commit(&batch).await.unwrap();
+        assert_eq!(sink.watermark().await.unwrap(), Some(10));
+
+        // A commit that was interrupted after writing its lines, but before its watermark
+        let mut objects = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(OBJECTS_FILE))
+            .unwrap();
+        objects.write_all(b"{\"partial\":").unwrap();
+        drop(objects);
+
+        let sink = JsonlSink::new(dir.path()).unwrap();
+        assert_eq!(sink.watermark().await.unwrap(), Some(10));
+        batch.watermark = 11;
+        batch.objects[0].checkpoint = 11;
+        sink.commit(&batch).await.unwrap();
+
+        let lines = read_lines(dir.path(), OBJECTS_FILE);
+        let checkpoints: Vec<_> = lines.iter().map(|line| &line["checkpoint"]).collect();
+        assert_eq!(checkpoints, vec![10, 11]);
+        assert_eq!(lines[0]["kind"], "written");
+        assert!(read_lines(dir.path(), EVENTS_FILE).is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::data::Batch;
+
+mod jsonl;
+mod postgres;
+
+pub use jsonl::JsonlSink;
+pub use postgres::{PgConnectionPool, PostgresHandler, PostgresSink};
+
+/// Destination of a pipeline's batches. A sink keeps track of the last checkpoint it committed,
+/// its watermark, and records it atomically with each batch it commits. Indexing resumes from the
+/// checkpoint after the watermark, so every checkpoint's contents are committed exactly once.
+#[async_trait]
+pub trait Sink: Send + Sync + 'static {
+    /// The watermark of the last committed batch, or `None` if no batch was committed yet.
+    async fn watermark(&self) -> Result<Option<CheckpointSequenceNumber>>;
+
+    /// Commits the contents of `batch`, and makes `batch.watermark` the sink's watermark, so that
+    /// either both or neither are committed. Batches are committed in checkpoint order, and a
+    /// batch that failed to commit is retried.
+    async fn commit(&self, batch: &Batch) -> Result<()>;
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::upsert::excluded;
+use diesel::{Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::data::Batch;
+use crate::sinks::Sink;
+
+pub type PgConnectionPool = Pool<ConnectionManager<PgConnection>>;
+
+const CREATE_WATERMARKS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS package_indexer_watermarks (
+        pipeline                    TEXT PRIMARY KEY,
+        checkpoint_sequence_number  BIGINT NOT NULL
+    )";
+
+diesel::table! {
+    package_indexer_watermarks (pipeline) {
+        pipeline -> Text,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+/// Writes the contents of batches to Postgres.
+pub trait PostgresHandler: Send + Sync + 'static {
+    /// Writes the contents of `batch`. This runs in the transaction that commits the batch, so it
+    /// must not commit or roll back the transaction itself.
+    fn commit(&self, conn: &mut PgConnection, batch: &Batch) -> Result<()>;
+}
+
+/// Sink which writes batches to Postgres with a [`PostgresHandler`], and records the watermark in
+/// the `package_indexer_watermarks` table in the same transaction.
+pub struct PostgresSink<H> {
+    pool: PgConnectionPool,
+    /// Key of the sink's watermark in the watermarks table
+    name: String,
+    handler: Arc<H>,
+}
+
+impl<H: PostgresHandler> PostgresSink<H> {
+    /// Creates a sink whose watermark is stored under `name`, creating the watermarks table if it
+    /// does not exist yet.
+    pub fn new(pool: PgConnectionPool, name: impl Into<String>, handler: H) -> Result<Self> {
+        diesel::sql_query(CREATE_WATERMARKS_TABLE).execute(&mut pool.get()?)?;
+        Ok(Self {
+            pool,
+            name: name.into(),
+            handler: Arc::new(handler),
+        })
+    }
+}
+
+#[async_trait]
+impl<H: PostgresHandler> Sink for PostgresSink<H> {
+    async fn watermark(&self) -> Result<Option<CheckpointSequenceNumber>> {
+        use package_indexer_watermarks::dsl::*;
+
+        let pool = self.pool.clone();
+        let name = self.name.clone();
+        tokio::task::spawn_blocking(move || {
+            let watermark: Option<i64> = package_indexer_watermarks
+                .filter(pipeline.eq(name))
+                .select(checkpoint_sequence_number)
+                .first(&mut pool.get()?)
+                .optional()?;
+            Ok(watermark.map(|w| w as u64))
+        })
+        .await?
+    }
+
+    async fn commit(&self, batch: &Batch) -> Result<()> {
+        use package_indexer_watermarks::dsl::*;
+
+        let pool = self.pool.clone();
+        let name = self.name.clone();
+        let handler = self.handler.clone();
+        let batch = batch.clone();
+        tokio::task::spawn_blocking(move || {
+            pool.get()?.transaction::<_, anyhow::Error, _>(|conn| {
+                handler.commit(conn, &batch)?;
+                diesel::insert_into(package_indexer_watermarks)
+                    .values((
+                        pipeline.eq(name),
+                        checkpoint_sequence_number.eq(batch.watermark as i64),
+                    ))
+                    .on_conflict(pipeline)
+                    .do_update()
+                    .set(checkpoint_sequence_number.eq(excluded(checkpoint_sequence_number)))
+                    .execute(conn)?;
+                Ok(())
+            })
+        })
+        .await?
+    }
+}
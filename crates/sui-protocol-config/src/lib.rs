@@ -13,7 +13,7 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 47;
+const MAX_PROTOCOL_VERSION: u64 = 48;
 
 // Record history of protocol version allocations here:
 //
@@ -131,6 +131,9 @@ const MAX_PROTOCOL_VERSION: u64 = 47;
 //             Enable resharing at the same initial shared version.
 // Version 47: Use tonic networking for Mysticeti.
 //             Resolve Move abort locations to the package id instead of the runtime module ID.
+// Version 48: Enable passkey auth in devnet.
+//             Enable transaction expiration by timestamp and by window in devnet.
+//             Add BIP-340 Schnorr signature verification in devnet.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -442,6 +445,17 @@ struct FeatureFlags {
     // Resolve Move abort locations to the package id instead of the runtime module ID.
     #[serde(skip_serializing_if = "is_false")]
     resolve_abort_locations_to_package_id: bool,
+    // Enable passkey (WebAuthn) auth
+    #[serde(skip_serializing_if = "is_false")]
+    passkey_auth: bool,
+
+    // Enable transaction expiration by timestamp, or by a window of epochs and timestamps.
+    #[serde(skip_serializing_if = "is_false")]
+    transaction_expiration_window: bool,
+
+    // Enable BIP-340 Schnorr signature verification over secp256k1.
+    #[serde(skip_serializing_if = "is_false")]
+    enable_schnorr_bip340: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -981,6 +995,9 @@ pub struct ProtocolConfig {
     poseidon_bn254_cost_base: Option<u64>,
     poseidon_bn254_cost_per_block: Option<u64>,
 
+    // schnorr::bip340_verify
+    schnorr_bip340_verify_cost_base: Option<u64>,
+
     // group_ops
     group_ops_bls12381_decode_scalar_cost: Option<u64>,
     group_ops_bls12381_decode_g1_cost: Option<u64>,
@@ -1331,6 +1348,18 @@ impl ProtocolConfig {
     pub fn resolve_abort_locations_to_package_id(&self) -> bool {
         self.feature_flags.resolve_abort_locations_to_package_id
     }
+
+    pub fn passkey_auth(&self) -> bool {
+        self.feature_flags.passkey_auth
+    }
+
+    pub fn transaction_expiration_window(&self) -> bool {
+        self.feature_flags.transaction_expiration_window
+    }
+
+    pub fn enable_schnorr_bip340(&self) -> bool {
+        self.feature_flags.enable_schnorr_bip340
+    }
 }
 
 #[cfg(not(msim))]
@@ -1695,6 +1724,8 @@ impl ProtocolConfig {
             poseidon_bn254_cost_base: None,
             poseidon_bn254_cost_per_block: None,
 
+            schnorr_bip340_verify_cost_base: None,
+
             // hmac::hmac_sha3_256
             hmac_hmac_sha3_256_cost_base: Some(52),
             hmac_hmac_sha3_256_input_cost_per_byte: Some(2),
@@ -2220,6 +2251,20 @@ impl ProtocolConfig {
                     // Enable resolving abort code IDs to package ID instead of runtime module ID
                     cfg.feature_flags.resolve_abort_locations_to_package_id = true;
                 }
+                48 => {
+                    // Enable passkey auth and windowed transaction expiration on devnet only.
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.passkey_auth = true;
+                        cfg.feature_flags.transaction_expiration_window = true;
+                    }
+
+                    // Add costs for schnorr::bip340_verify, on devnet only.
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.enable_schnorr_bip340 = true;
+                        // Same as the other secp256k1 signature verification natives.
+                        cfg.schnorr_bip340_verify_cost_base = Some(52);
+                    }
+                }
                 // Use this template when making changes:
                 //
                 //     // modify an existing constant.
@@ -2315,6 +2360,12 @@ impl ProtocolConfig {
     pub fn set_zklogin_auth_for_testing(&mut self, val: bool) {
         self.feature_flags.zklogin_auth = val
     }
+    pub fn set_passkey_auth_for_testing(&mut self, val: bool) {
+        self.feature_flags.passkey_auth = val
+    }
+    pub fn set_transaction_expiration_window_for_testing(&mut self, val: bool) {
+        self.feature_flags.transaction_expiration_window = val
+    }
     pub fn set_enable_jwk_consensus_updates_for_testing(&mut self, val: bool) {
         self.feature_flags.enable_jwk_consensus_updates = val
     }
@@ -18,14 +18,19 @@ use crate::config::get_rpc_url;
 use crate::replay::ExecutionSandboxState;
 use crate::replay::LocalExec;
 use crate::replay::ProtocolVersionSummary;
+use crate::trace_stepper::TraceStepper;
+use fastcrypto::encoding::{Base64, Encoding};
 use move_vm_config::runtime::get_default_output_filepath;
+use move_vm_profiler::tracer::DEFAULT_MAX_EVENTS;
 use std::env;
 use std::io::BufRead;
 use std::path::PathBuf;
 use std::str::FromStr;
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_protocol_config::Chain;
+use sui_types::crypto::default_hash;
 use sui_types::digests::TransactionDigest;
+use sui_types::transaction::TransactionData;
 use tracing::{error, info};
 
 pub mod batch_replay;
@@ -37,6 +42,7 @@ pub mod fuzz_mutations;
 mod replay;
 #[cfg(test)]
 mod tests;
+mod trace_stepper;
 pub mod transaction_provider;
 pub mod types;
 
@@ -83,6 +89,60 @@ pub enum ReplayToolCommand {
         profile_output: Option<PathBuf>,
     },
 
+    /// Trace transaction: record the functions called, the instructions executed (with the locals
+    /// and the operand stack), the objects read and written and the events emitted.
+    /// Tracing is only supported by the latest executor, use `--executor-version -1` for older
+    /// transactions.
+    #[command(name = "tr")]
+    TraceTransaction {
+        #[arg(long, short)]
+        tx_digest: String,
+        /// Optional version of the executor to use, if not specified defaults to the one originally used for the transaction.
+        #[arg(long, short, allow_hyphen_values = true)]
+        executor_version: Option<i64>,
+        /// Optional protocol version to use, if not specified defaults to the one originally used for the transaction.
+        #[arg(long, short, allow_hyphen_values = true)]
+        protocol_version: Option<i64>,
+        /// Optional output filepath for the trace, if not specified defaults to `trace_{tx_digest}.json` in the working directory.
+        #[arg(long, short = 'o')]
+        trace_output: Option<PathBuf>,
+        /// Maximum number of events to record, the trace is truncated once it is reached.
+        #[arg(long, default_value_t = DEFAULT_MAX_EVENTS)]
+        max_events: usize,
+    },
+
+    /// Trace a dry run: execute a transaction that has not been executed on chain against the
+    /// latest state of the network, and record a step-level trace like `tr` does.
+    #[command(name = "trd")]
+    TraceDryRun {
+        /// BCS serialized transaction data bytes without its type tag, as base64 encoded string.
+        #[arg(long)]
+        tx_bytes: String,
+        /// Optional version of the executor to use, if not specified defaults to the one of the current protocol version.
+        #[arg(long, short, allow_hyphen_values = true)]
+        executor_version: Option<i64>,
+        /// Optional output filepath for the trace, if not specified defaults to `trace_{tx_digest}.json` in the working directory.
+        #[arg(long, short = 'o')]
+        trace_output: Option<PathBuf>,
+        /// Maximum number of events to record, the trace is truncated once it is reached.
+        #[arg(long, default_value_t = DEFAULT_MAX_EVENTS)]
+        max_events: usize,
+    },
+
+    /// Step through a trace recorded with `tr`
+    #[command(name = "st")]
+    StepTrace {
+        #[arg(long, short)]
+        path: PathBuf,
+        /// Optional path to the Move package that was executed. If it has been built, its source
+        /// maps are used to show the source of each instruction and the names of the locals.
+        #[arg(long)]
+        package_path: Option<PathBuf>,
+        /// Print the whole trace instead of stepping through it
+        #[arg(long)]
+        print: bool,
+    },
+
     /// Replay transaction
     #[command(name = "tx")]
     ReplayTransaction {
@@ -369,6 +429,98 @@ pub async fn execute_replay_command(
             Some((1u64, 1u64))
         }
 
+        ReplayToolCommand::TraceTransaction {
+            tx_digest,
+            executor_version,
+            protocol_version,
+            trace_output,
+            max_events,
+        } => {
+            if cfg!(not(feature = "gas-profiler")) {
+                anyhow::bail!(
+                    "gas-profiler feature is not enabled, rebuild or reinstall with \
+                     --features gas-profiler"
+                );
+            }
+            let tx_digest = TransactionDigest::from_str(&tx_digest)?;
+            let output_path =
+                trace_output.unwrap_or_else(|| PathBuf::from(format!("trace_{}.json", tx_digest)));
+            info!("Executing tx: {}", tx_digest);
+            let mut lx = LocalExec::new_from_fn_url(&get_rpc_url(rpc_url, cfg_path, chain)?)
+                .await?
+                .init_for_execution()
+                .await?;
+            lx.enable_tracer = Some(output_path.clone());
+            lx.max_trace_events = max_events;
+            lx.execute_transaction(
+                &tx_digest,
+                safety,
+                use_authority,
+                executor_version,
+                protocol_version,
+                None,
+            )
+            .await?;
+
+            println!(
+                "Execution finished successfully. Trace written to: {}",
+                output_path.display()
+            );
+            Some((1u64, 1u64))
+        }
+
+        ReplayToolCommand::TraceDryRun {
+            tx_bytes,
+            executor_version,
+            trace_output,
+            max_events,
+        } => {
+            if cfg!(not(feature = "gas-profiler")) {
+                anyhow::bail!(
+                    "gas-profiler feature is not enabled, rebuild or reinstall with \
+                     --features gas-profiler"
+                );
+            }
+            let tx_data: TransactionData = bcs::from_bytes(
+                &Base64::decode(&tx_bytes)
+                    .map_err(|e| anyhow::anyhow!("Invalid base64 transaction bytes: {e}"))?,
+            )?;
+            let tx_digest = TransactionDigest::new(default_hash(&tx_data));
+            let output_path =
+                trace_output.unwrap_or_else(|| PathBuf::from(format!("trace_{}.json", tx_digest)));
+            info!("Dry running tx: {}", tx_digest);
+            let mut lx = LocalExec::new_from_fn_url(&get_rpc_url(rpc_url, cfg_path, chain)?)
+                .await?
+                .init_for_execution()
+                .await?;
+            lx.enable_tracer = Some(output_path.clone());
+            lx.max_trace_events = max_events;
+            let sandbox_state = lx
+                .dry_run_transaction(tx_data, safety, executor_version)
+                .await?;
+
+            match sandbox_state.local_exec_status {
+                Some(Err(err)) => println!("Dry run failed: {err}"),
+                _ => println!("Dry run finished successfully."),
+            }
+            println!("Trace written to: {}", output_path.display());
+            Some((1u64, 1u64))
+        }
+
+        ReplayToolCommand::StepTrace {
+            path,
+            package_path,
+            print,
+        } => {
+            let mut stepper = TraceStepper::new(&path, package_path.as_deref())?;
+            if print {
+                stepper.print_all();
+            } else {
+                stepper.run_interactive()?;
+            }
+            None
+        }
+
         ReplayToolCommand::ReplayTransaction {
             tx_digest,
             show_effects,
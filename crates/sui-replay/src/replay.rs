@@ -20,6 +20,7 @@ use move_core_types::{
     language_storage::{ModuleId, StructTag},
     resolver::{ModuleResolver, ResourceResolver},
 };
+use move_vm_profiler::tracer::DEFAULT_MAX_EVENTS;
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use similar::{ChangeTag, TextDiff};
@@ -34,7 +35,7 @@ use sui_core::authority::NodeStateDump;
 use sui_execution::Executor;
 use sui_framework::BuiltInFramework;
 use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI};
-use sui_protocol_config::{Chain, ProtocolConfig};
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::message_envelope::Message;
@@ -44,6 +45,7 @@ use sui_types::{
     base_types::{ObjectID, ObjectRef, SequenceNumber, VersionNumber},
     committee::EpochId,
     digests::{ObjectDigest, TransactionDigest},
+    effects::TransactionEffects,
     error::{ExecutionError, SuiError, SuiResult},
     executable_transaction::VerifiedExecutableTransaction,
     gas::SuiGasStatus,
@@ -54,7 +56,8 @@ use sui_types::{
     storage::{BackingPackageStore, ChildObjectResolver, ObjectStore, ParentSync},
     transaction::{
         CheckedInputObjects, InputObjectKind, InputObjects, ObjectReadResult, ObjectReadResultKind,
-        SenderSignedData, Transaction, TransactionDataAPI, TransactionKind, VerifiedTransaction,
+        SenderSignedData, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+        VerifiedTransaction,
     },
     DEEPBOOK_PACKAGE_ID,
 };
@@ -235,6 +238,15 @@ pub struct LocalExec {
     // Whether or not to enable the gas profiler, the PathBuf contains either a user specified
     // filepath or the default current directory and name format for the profile output
     pub enable_profiler: Option<PathBuf>,
+    // Whether or not to record a step-level execution trace, the PathBuf is the file the trace is
+    // written to
+    pub enable_tracer: Option<PathBuf>,
+    // Maximum number of events recorded in a trace, after which it is truncated
+    pub max_trace_events: usize,
+    // Whether the transaction being executed is a dry run rather than a replay. Replays load the
+    // objects a transaction reads during execution (e.g. dynamic fields) ahead of time, at the
+    // versions it read, while dry runs download them at their latest versions as they are read.
+    pub dry_run: bool,
     // Retry policies due to RPC errors
     pub num_retries_for_timeout: u32,
     pub sleep_period_for_timeout: std::time::Duration,
@@ -381,6 +393,9 @@ impl LocalExec {
             executor_version: None,
             protocol_version: None,
             enable_profiler: None,
+            enable_tracer: None,
+            max_trace_events: DEFAULT_MAX_EVENTS,
+            dry_run: false,
         })
     }
 
@@ -424,6 +439,9 @@ impl LocalExec {
             executor_version: None,
             protocol_version: None,
             enable_profiler: None,
+            enable_tracer: None,
+            max_trace_events: DEFAULT_MAX_EVENTS,
+            dry_run: false,
         })
     }
 
@@ -659,6 +677,24 @@ impl LocalExec {
         // Initialize the state necessary for execution
         // Get the input objects
         let input_objects = self.initialize_execution_env_state(tx_info).await?;
+        self.execute_with_input_objects(
+            tx_info,
+            input_objects,
+            override_transaction_kind,
+            expensive_safety_check_config,
+        )
+    }
+
+    /// Executes a transaction once its input objects, and the objects they depend on, are in the
+    /// store.
+    fn execute_with_input_objects(
+        &mut self,
+        tx_info: &OnChainTransactionInfo,
+        input_objects: InputObjects,
+        override_transaction_kind: Option<TransactionKind>,
+        expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+    ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        let tx_digest = &tx_info.tx_digest;
         assert_eq!(
             &input_objects.filter_shared_objects().len(),
             &tx_info.shared_object_refs.len()
@@ -685,12 +721,14 @@ impl LocalExec {
         let expensive_checks = true;
         let transaction_kind = override_transaction_kind.unwrap_or(tx_info.kind.clone());
         let certificate_deny_set = HashSet::new();
-        let (inner_store, gas_status, effects, result) = if let Ok(gas_status) = SuiGasStatus::new(
-            tx_info.gas_budget,
-            tx_info.gas_price,
-            tx_info.reference_gas_price,
-            protocol_config,
-        ) {
+        let (inner_store, mut gas_status, effects, result) = if let Ok(mut gas_status) =
+            SuiGasStatus::new(
+                tx_info.gas_budget,
+                tx_info.gas_price,
+                tx_info.reference_gas_price,
+                protocol_config,
+            ) {
+            self.maybe_attach_tracer(&mut gas_status, tx_digest);
             executor.execute_transaction_to_effects(
                 &self,
                 protocol_config,
@@ -709,6 +747,7 @@ impl LocalExec {
         } else {
             unreachable!("Transaction was valid so gas status must be valid");
         };
+        self.finish_tracer(&mut gas_status)?;
 
         if let Err(err) = self.pretty_print_for_tracing(
             &gas_status,
@@ -738,6 +777,44 @@ impl LocalExec {
         })
     }
 
+    /// Attaches an execution tracer to the gas status if tracing is enabled, so that the trace of
+    /// the transaction is written to file once it has been executed
+    fn maybe_attach_tracer(&self, _gas_status: &mut SuiGasStatus, _tx_digest: &TransactionDigest) {
+        #[cfg(feature = "gas-profiler")]
+        if let Some(path) = &self.enable_tracer {
+            use move_vm_profiler::tracer::ExecutionTracer;
+            use move_vm_types::gas::GasMeter;
+            use sui_types::gas::SuiGasStatusAPI;
+
+            let move_gas_status = _gas_status.move_gas_status_mut();
+            let remaining_gas: u64 = move_gas_status.remaining_gas().into();
+            move_gas_status.set_tracer(ExecutionTracer::init(
+                path.clone(),
+                _tx_digest.to_string(),
+                remaining_gas,
+                self.max_trace_events,
+            ));
+        }
+    }
+
+    /// Writes the trace recorded by the tracer attached with `maybe_attach_tracer`, if any
+    fn finish_tracer(&self, _gas_status: &mut SuiGasStatus) -> Result<(), ReplayEngineError> {
+        #[cfg(feature = "gas-profiler")]
+        if let Some(path) = &self.enable_tracer {
+            use sui_types::gas::SuiGasStatusAPI;
+
+            if let Some(tracer) = _gas_status.move_gas_status_mut().take_tracer() {
+                tracer
+                    .finish()
+                    .map_err(|err| ReplayEngineError::UnableToWriteTrace {
+                        path: path.display().to_string(),
+                        err: err.to_string(),
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
     fn pretty_print_for_tracing(
         &self,
         gas_status: &SuiGasStatus,
@@ -951,6 +1028,93 @@ impl LocalExec {
                 .await
         }
     }
+
+    /// Executes a transaction that has not been executed on chain, as a dry run would, against the
+    /// latest state of the network in the current epoch. Objects the transaction reads during
+    /// execution are downloaded as they are read, at their latest versions.
+    pub async fn dry_run_transaction(
+        &mut self,
+        tx_data: TransactionData,
+        expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+        executor_version: Option<i64>,
+    ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        self.executor_version = executor_version;
+        self.dry_run = true;
+
+        let chain = chain_from_chain_id(self.fetcher.get_chain_id().await?.as_str());
+        let system_state = self
+            .fetcher
+            .as_remote()
+            .rpc_client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?;
+
+        let input_objects = tx_data
+            .input_objects()
+            .map_err(|err| ReplayEngineError::UserInputError { err })?;
+        let shared_object_ids: Vec<_> = input_objects
+            .iter()
+            .filter_map(|kind| match kind {
+                InputObjectKind::SharedMoveObject { id, .. } => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let shared_object_refs: Vec<_> = self
+            .multi_download_latest(&shared_object_ids)
+            .await?
+            .iter()
+            .map(|obj| obj.compute_object_reference())
+            .collect();
+        let modified_at_versions = input_objects
+            .iter()
+            .filter_map(|kind| match kind {
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => Some((*id, *version)),
+                _ => None,
+            })
+            .chain(
+                shared_object_refs
+                    .iter()
+                    .map(|(id, version, _)| (*id, *version)),
+            )
+            .chain(tx_data.gas().iter().map(|(id, version, _)| (*id, *version)))
+            .collect();
+
+        let sender_signed_data = SenderSignedData::new(tx_data.clone(), vec![]);
+        let tx_info = OnChainTransactionInfo {
+            tx_digest: sender_signed_data.digest(),
+            sender_signed_data,
+            sender: tx_data.sender(),
+            input_objects,
+            kind: tx_data.kind().clone(),
+            modified_at_versions,
+            shared_object_refs,
+            gas: tx_data.gas().to_vec(),
+            gas_budget: tx_data.gas_budget(),
+            gas_price: tx_data.gas_price(),
+            executed_epoch: system_state.epoch,
+            dependencies: vec![],
+            // The transaction has no effects on chain to compare against
+            effects: SuiTransactionBlockEffects::try_from(TransactionEffects::default())?,
+            protocol_version: ProtocolVersion::new(system_state.protocol_version),
+            epoch_start_timestamp: system_state.epoch_start_timestamp_ms,
+            reference_gas_price: system_state.reference_gas_price,
+            chain,
+        };
+
+        self.current_protocol_version = tx_info.protocol_version.as_u64();
+        self.multi_download_and_store(&tx_info.modified_at_versions)
+            .await?;
+        let input_objects = self
+            .resolve_download_input_objects(&tx_info, vec![])
+            .await?;
+        self.execute_with_input_objects(
+            &tx_info,
+            input_objects,
+            None,
+            expensive_safety_check_config,
+        )
+    }
     fn system_package_ids(protocol_version: u64) -> Vec<ObjectID> {
         let mut ids = BuiltInFramework::all_package_ids();
 
@@ -1011,6 +1175,16 @@ impl LocalExec {
         Ok(Some(o))
     }
 
+    /// Returns an object the transaction reads during execution, rather than as an input. Replays
+    /// load these objects ahead of time, dry runs download them as they are read.
+    fn get_object_read_during_execution(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        if self.dry_run {
+            Ok(self.get_or_download_object(object_id, false)?)
+        } else {
+            Ok(self.get_object(object_id)?)
+        }
+    }
+
     pub fn is_remote_replay(&self) -> bool {
         matches!(self.fetcher, Fetchers::Remote(_))
     }
@@ -1723,7 +1897,7 @@ impl ChildObjectResolver for LocalExec {
             child: &ObjectID,
             child_version_upper_bound: SequenceNumber,
         ) -> SuiResult<Option<Object>> {
-            let child_object = match self_.get_object(child)? {
+            let child_object = match self_.get_object_read_during_execution(child)? {
                 None => return Ok(None),
                 Some(o) => o,
             };
@@ -1772,7 +1946,7 @@ impl ChildObjectResolver for LocalExec {
             receiving_object_id: &ObjectID,
             receive_object_at_version: SequenceNumber,
         ) -> SuiResult<Option<Object>> {
-            let recv_object = match self_.get_object(receiving_object_id)? {
+            let recv_object = match self_.get_object_read_during_execution(receiving_object_id)? {
                 None => return Ok(None),
                 Some(o) => o,
             };
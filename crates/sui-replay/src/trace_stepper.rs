@@ -0,0 +1,509 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Steps through an execution trace recorded with `TraceTransaction`. When the path of a built Move
+//! package is given, the source maps of the package (and of its dependencies) are used to show the
+//! source line of each instruction, and the names of the locals.
+
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_bytecode_source_map::{source_map::SourceMap, utils::source_map_from_file};
+use move_command_line_common::files::FileHash;
+use move_vm_profiler::tracer::{ExecutionTrace, TraceEffect, TraceEvent, TracedFunction};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const SOURCE_MAP_EXTENSION: &str = "mvsm";
+const MOVE_EXTENSION: &str = "move";
+
+const HELP: &str = "\
+Commands:
+    [enter] | s   step to the next event
+    n             step over calls, to the next instruction of the current frame
+    f             finish the current frame
+    c             continue to the end of the trace
+    l             print the locals of the current frame
+    st            print the operand stack
+    q             quit";
+
+/// Source maps and source files of a built package
+#[derive(Default)]
+struct Sources {
+    /// Source maps, by module address (as a hex literal) and name
+    source_maps: BTreeMap<(String, String), SourceMap>,
+    /// Source files, by hash of their contents
+    files: HashMap<FileHash, (PathBuf, String)>,
+}
+
+pub struct TraceStepper {
+    trace: ExecutionTrace,
+    sources: Sources,
+    /// Index of the next event to show
+    next: usize,
+    /// Frames currently open, innermost last
+    frames: Vec<(u64, TracedFunction)>,
+    /// The last instruction shown, with the locals and operand stack before its execution
+    last_instruction: Option<(u16, Vec<String>, Vec<String>)>,
+}
+
+impl TraceStepper {
+    pub fn new(trace_path: &Path, package_path: Option<&Path>) -> anyhow::Result<Self> {
+        let trace: ExecutionTrace =
+            serde_json::from_reader(std::io::BufReader::new(std::fs::File::open(trace_path)?))?;
+        let sources = match package_path {
+            Some(path) => Sources::load(&path.join("build"))?,
+            None => Sources::default(),
+        };
+        Ok(Self::from_trace(trace, sources))
+    }
+
+    fn from_trace(trace: ExecutionTrace, sources: Sources) -> Self {
+        Self {
+            trace,
+            sources,
+            next: 0,
+            frames: vec![],
+            last_instruction: None,
+        }
+    }
+
+    /// Prints the whole trace
+    pub fn print_all(&mut self) {
+        while self.step() {}
+    }
+
+    /// Steps through the trace, reading commands from stdin
+    pub fn run_interactive(&mut self) -> anyhow::Result<()> {
+        let truncated = if self.trace.truncated {
+            ", truncated"
+        } else {
+            ""
+        };
+        println!(
+            "Trace of {} ({} events{truncated})",
+            self.trace.name,
+            self.trace.events.len()
+        );
+        println!("{HELP}");
+        let stdin = std::io::stdin();
+        let mut lines = stdin.lock().lines();
+        while self.next < self.trace.events.len() {
+            print!("> ");
+            std::io::stdout().flush()?;
+            let Some(line) = lines.next() else {
+                break;
+            };
+            match line?.trim() {
+                "" | "s" => {
+                    self.step();
+                }
+                "n" => self.step_over(),
+                "f" => self.finish_frame(),
+                "c" => self.print_all(),
+                "l" => self.print_locals(),
+                "st" => self.print_stack(),
+                "q" => return Ok(()),
+                _ => println!("{HELP}"),
+            }
+        }
+        println!("End of trace");
+        Ok(())
+    }
+
+    /// Shows the next event, returning false if the end of the trace was reached
+    fn step(&mut self) -> bool {
+        let Some(event) = self.trace.events.get(self.next) else {
+            return false;
+        };
+        self.next += 1;
+        let depth = self.frames.len();
+        match event {
+            TraceEvent::OpenFrame {
+                frame,
+                function,
+                args,
+                is_native,
+                gas_left,
+            } => {
+                let native = if *is_native { "native " } else { "" };
+                println!(
+                    "{}-> {native}{}({}) [gas left: {gas_left}]",
+                    indent(depth),
+                    function_name(function),
+                    args.join(", "),
+                );
+                self.frames.push((*frame, function.clone()));
+                self.last_instruction = None;
+            }
+            TraceEvent::Instruction {
+                pc,
+                instruction,
+                gas_left,
+                locals,
+                stack,
+                ..
+            } => {
+                println!(
+                    "{}{pc}: {instruction} [gas left: {gas_left}]",
+                    indent(depth)
+                );
+                if let Some(line) = self.source_line(*pc) {
+                    println!("{}   {line}", indent(depth));
+                }
+                self.last_instruction = Some((*pc, locals.clone(), stack.clone()));
+            }
+            TraceEvent::CloseFrame {
+                return_values,
+                gas_left,
+                ..
+            } => {
+                self.frames.pop();
+                println!(
+                    "{}<- ({}) [gas left: {gas_left}]",
+                    indent(self.frames.len()),
+                    return_values.join(", "),
+                );
+                self.last_instruction = None;
+            }
+            TraceEvent::Effect { effect } => {
+                println!("{}* {}", indent(depth), effect_description(effect));
+            }
+        }
+        true
+    }
+
+    fn step_over(&mut self) {
+        let depth = self.frames.len();
+        while self.step() {
+            let shown_instruction = matches!(
+                self.trace.events[self.next - 1],
+                TraceEvent::Instruction { .. }
+            );
+            if self.frames.len() < depth || (self.frames.len() == depth && shown_instruction) {
+                return;
+            }
+        }
+    }
+
+    fn finish_frame(&mut self) {
+        let depth = self.frames.len();
+        while self.step() {
+            if self.frames.len() < depth {
+                return;
+            }
+        }
+    }
+
+    fn print_locals(&self) {
+        let (Some((_, function)), Some((_, locals, _))) =
+            (self.frames.last(), &self.last_instruction)
+        else {
+            println!("No instruction of the current frame was executed yet");
+            return;
+        };
+        let source_map = self.sources.source_map(function);
+        for (idx, value) in locals.iter().enumerate() {
+            let name = source_map
+                .and_then(|map| {
+                    map.get_parameter_or_local_name(
+                        FunctionDefinitionIndex(function.index),
+                        idx as u64,
+                    )
+                    .ok()
+                })
+                .map(|(name, _)| name)
+                .unwrap_or_else(|| format!("[{idx}]"));
+            println!("    {name}: {value}");
+        }
+    }
+
+    fn print_stack(&self) {
+        let Some((_, _, stack)) = &self.last_instruction else {
+            println!("No instruction of the current frame was executed yet");
+            return;
+        };
+        for (idx, value) in stack.iter().enumerate() {
+            println!("    [{idx}] {value}");
+        }
+    }
+
+    /// Returns the source file and line of the instruction at `pc` in the current frame
+    fn source_line(&self, pc: u16) -> Option<String> {
+        let (_, function) = self.frames.last()?;
+        let loc = self
+            .sources
+            .source_map(function)?
+            .get_code_location(FunctionDefinitionIndex(function.index), pc)
+            .ok()?;
+        let (path, contents) = self.sources.files.get(&loc.file_hash())?;
+        let line_idx = contents.get(..loc.start() as usize)?.matches('\n').count();
+        let line = contents.lines().nth(line_idx)?;
+        Some(format!(
+            "{}:{}: {}",
+            path.display(),
+            line_idx + 1,
+            line.trim()
+        ))
+    }
+}
+
+impl Sources {
+    /// Loads the source maps and sources found in the build directory of a package
+    fn load(build_dir: &Path) -> anyhow::Result<Self> {
+        let mut sources = Sources::default();
+        let mut paths = vec![];
+        collect_files(build_dir, &mut paths)?;
+        for path in paths {
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some(SOURCE_MAP_EXTENSION) => {
+                    let source_map = source_map_from_file(&path)?;
+                    let (address, name) = &source_map.module_name;
+                    sources
+                        .source_maps
+                        .insert((address.to_hex_literal(), name.to_string()), source_map);
+                }
+                Some(MOVE_EXTENSION) => {
+                    let contents = std::fs::read_to_string(&path)?;
+                    sources
+                        .files
+                        .insert(FileHash::new(&contents), (path, contents));
+                }
+                _ => (),
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Returns the source map of the module of a function. Modules are looked up by address and
+    /// name, falling back to the name alone, as the package may have been built with a different
+    /// address than the one it is published at.
+    fn source_map(&self, function: &TracedFunction) -> Option<&SourceMap> {
+        let key = (function.address.clone(), function.module.clone());
+        if let Some(source_map) = self.source_maps.get(&key) {
+            return Some(source_map);
+        }
+        let mut by_name = self
+            .source_maps
+            .iter()
+            .filter(|((_, name), _)| *name == function.module)
+            .map(|(_, source_map)| source_map);
+        match (by_name.next(), by_name.next()) {
+            (Some(source_map), None) => Some(source_map),
+            _ => None,
+        }
+    }
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+fn function_name(function: &TracedFunction) -> String {
+    let TracedFunction {
+        address,
+        module,
+        name,
+        type_args,
+        ..
+    } = function;
+    if type_args.is_empty() {
+        format!("{address}::{module}::{name}")
+    } else {
+        format!("{address}::{module}::{name}<{}>", type_args.join(", "))
+    }
+}
+
+fn effect_description(effect: &TraceEffect) -> String {
+    match effect {
+        TraceEffect::ObjectRead { id, version, type_ } => match type_ {
+            Some(type_) => format!("read {id} at version {version}: {type_}"),
+            None => format!("read {id} at version {version}"),
+        },
+        TraceEffect::ObjectWrite { id, kind, type_ } => match type_ {
+            Some(type_) => format!("{kind:?} {id}: {type_}"),
+            None => format!("{kind:?} {id}"),
+        },
+        TraceEffect::Event { type_, contents } => {
+            format!("emitted {type_} ({} bytes)", contents.len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_vm_profiler::tracer::WriteKind;
+
+    fn function(module: &str, name: &str, type_args: &[&str]) -> TracedFunction {
+        TracedFunction {
+            address: "0x2".to_string(),
+            module: module.to_string(),
+            name: name.to_string(),
+            index: 0,
+            type_args: type_args.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn instruction(frame: u64, pc: u16, instruction: &str) -> TraceEvent {
+        TraceEvent::Instruction {
+            frame,
+            pc,
+            instruction: instruction.to_string(),
+            gas_left: 100,
+            locals: vec![format!("local{pc}")],
+            stack: vec![],
+        }
+    }
+
+    fn open_frame(frame: u64, name: &str) -> TraceEvent {
+        TraceEvent::OpenFrame {
+            frame,
+            function: function("m", name, &[]),
+            args: vec![],
+            is_native: false,
+            gas_left: 100,
+        }
+    }
+
+    fn close_frame(frame: u64) -> TraceEvent {
+        TraceEvent::CloseFrame {
+            frame,
+            return_values: vec![],
+            gas_left: 100,
+        }
+    }
+
+    /// `outer` calls `inner` at pc 1, and returns after pc 2
+    fn stepper() -> TraceStepper {
+        let events = vec![
+            open_frame(0, "outer"),
+            instruction(0, 0, "LdU64(1)"),
+            instruction(0, 1, "Call(inner)"),
+            open_frame(1, "inner"),
+            instruction(1, 0, "LdTrue"),
+            TraceEvent::Effect {
+                effect: TraceEffect::Event {
+                    type_: "0x2::m::E".to_string(),
+                    contents: vec![0; 4],
+                },
+            },
+            instruction(1, 1, "Ret"),
+            close_frame(1),
+            instruction(0, 2, "Ret"),
+            close_frame(0),
+        ];
+        let trace = ExecutionTrace {
+            name: "test".to_string(),
+            start_gas: 100,
+            events,
+            truncated: false,
+        };
+        TraceStepper::from_trace(trace, Sources::default())
+    }
+
+    fn current_pc(stepper: &TraceStepper) -> Option<u16> {
+        stepper.last_instruction.as_ref().map(|(pc, _, _)| *pc)
+    }
+
+    #[test]
+    fn step_tracks_frames_and_instructions() {
+        let mut stepper = stepper();
+        assert!(stepper.step());
+        assert_eq!(stepper.frames.len(), 1);
+        assert_eq!(current_pc(&stepper), None);
+
+        assert!(stepper.step());
+        let (pc, locals, _) = stepper.last_instruction.clone().unwrap();
+        assert_eq!((pc, locals), (0, vec!["local0".to_string()]));
+
+        stepper.step();
+        stepper.step();
+        assert_eq!(stepper.frames.len(), 2);
+        assert_eq!(stepper.frames.last().unwrap().1.name, "inner");
+        // a new frame has no instruction executed yet
+        assert_eq!(current_pc(&stepper), None);
+    }
+
+    #[test]
+    fn step_over_skips_nested_frames() {
+        let mut stepper = stepper();
+        stepper.step();
+        stepper.step();
+        stepper.step_over();
+        assert_eq!(current_pc(&stepper), Some(1));
+        // at `Call(inner)`, stepping over runs the whole call
+        stepper.step_over();
+        assert_eq!(stepper.frames.len(), 1);
+        assert_eq!(current_pc(&stepper), Some(2));
+        assert_eq!(stepper.next, 9);
+
+        // stepping over the last instruction returns from the frame
+        stepper.step_over();
+        assert!(stepper.frames.is_empty());
+        assert!(!stepper.step());
+    }
+
+    #[test]
+    fn finish_frame_returns_to_the_caller() {
+        let mut stepper = stepper();
+        for _ in 0..5 {
+            stepper.step();
+        }
+        assert_eq!(stepper.frames.len(), 2);
+        stepper.finish_frame();
+        assert_eq!(stepper.frames.len(), 1);
+        assert_eq!(stepper.next, 8);
+        assert_eq!(current_pc(&stepper), None);
+    }
+
+    #[test]
+    fn print_all_reaches_the_end() {
+        let mut stepper = stepper();
+        stepper.print_all();
+        assert_eq!(stepper.next, stepper.trace.events.len());
+        assert!(stepper.frames.is_empty());
+        assert!(!stepper.step());
+    }
+
+    #[test]
+    fn formats_functions_and_effects() {
+        assert_eq!(function_name(&function("m", "f", &[])), "0x2::m::f");
+        assert_eq!(
+            function_name(&function("coin", "value", &["0x2::sui::SUI", "u64"])),
+            "0x2::coin::value<0x2::sui::SUI, u64>"
+        );
+
+        let read = TraceEffect::ObjectRead {
+            id: "0x5".to_string(),
+            version: 3,
+            type_: Some("0x2::m::S".to_string()),
+        };
+        assert_eq!(
+            effect_description(&read),
+            "read 0x5 at version 3: 0x2::m::S"
+        );
+        let write = TraceEffect::ObjectWrite {
+            id: "0x5".to_string(),
+            kind: WriteKind::Deleted,
+            type_: None,
+        };
+        assert_eq!(effect_description(&write), "Deleted 0x5");
+        let event = TraceEffect::Event {
+            type_: "0x2::m::E".to_string(),
+            contents: vec![0; 4],
+        };
+        assert_eq!(effect_description(&event), "emitted 0x2::m::E (4 bytes)");
+    }
+}
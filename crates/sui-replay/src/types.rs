@@ -189,6 +189,9 @@ pub enum ReplayEngineError {
     #[error("Unable to write yaml file at {}: {}", path, err)]
     UnableToWriteYamlFile { path: String, err: String },
 
+    #[error("Unable to write execution trace to {}: {}", path, err)]
+    UnableToWriteTrace { path: String, err: String },
+
     #[error("Unable to convert string {} to URL {}", url, err)]
     InvalidUrl { url: String, err: String },
 
@@ -84,7 +84,27 @@ impl ReadApi {
         Ok(self
             .api
             .http
-            .get_owned_objects(address, query, cursor, limit)
+            .get_owned_objects(address, query, cursor, limit, None)
+            .await?)
+    }
+
+    /// Return a paginated response with the objects owned by the given address as of the given
+    /// checkpoint, or an error upon failure.
+    ///
+    /// Unlike [Self::get_owned_objects], pages are consistent with each other when paginating with
+    /// the same `checkpoint`. Only supported by RPC servers backed by an indexer.
+    pub async fn get_owned_objects_at_checkpoint(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<ObjectsPage> {
+        Ok(self
+            .api
+            .http
+            .get_owned_objects(address, query, cursor, limit, Some(checkpoint.into()))
             .await?)
     }
 
@@ -137,7 +157,26 @@ impl ReadApi {
         Ok(self
             .api
             .http
-            .get_dynamic_fields(object_id, cursor, limit)
+            .get_dynamic_fields(object_id, cursor, limit, None)
+            .await?)
+    }
+
+    /// Return a paginated response with the dynamic fields owned by the given [ObjectID] as of the
+    /// given checkpoint, or an error upon failure.
+    ///
+    /// Unlike [Self::get_dynamic_fields], pages are consistent with each other when paginating
+    /// with the same `checkpoint`. Only supported by RPC servers backed by an indexer.
+    pub async fn get_dynamic_fields_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<DynamicFieldPage> {
+        Ok(self
+            .api
+            .http
+            .get_dynamic_fields(object_id, cursor, limit, Some(checkpoint.into()))
             .await?)
     }
 
@@ -154,6 +193,15 @@ impl ReadApi {
             .await?)
     }
 
+    /// Return the address that a SuiNS name (e.g. `example.sui` or `@example`) resolves to, or
+    /// `None` if the name is not registered or does not point to an address.
+    pub async fn resolve_name_service_address(
+        &self,
+        name: String,
+    ) -> SuiRpcResult<Option<SuiAddress>> {
+        Ok(self.api.http.resolve_name_service_address(name).await?)
+    }
+
     /// Return a parsed past object for the provided [ObjectID] and version, or an error upon failure.
     ///
     /// An object's version increases (though it is not guaranteed that it increases always by 1) when
@@ -406,7 +454,23 @@ impl ReadApi {
         Ok(self
             .api
             .http
-            .multi_get_objects(object_ids, Some(options))
+            .multi_get_objects(object_ids, Some(options), None)
+            .await?)
+    }
+
+    /// Return a vector containing the [SuiObjectResponse] of each of the given objects as of the
+    /// given checkpoint, or an error upon failure. Only supported by RPC servers backed by an
+    /// indexer.
+    pub async fn multi_get_object_with_options_at_checkpoint(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<Vec<SuiObjectResponse>> {
+        Ok(self
+            .api
+            .http
+            .multi_get_objects(object_ids, Some(options), Some(checkpoint.into()))
             .await?)
     }
 
@@ -605,7 +669,26 @@ impl ReadApi {
             ));
         };
         let subscription: Subscription<SuiTransactionBlockEffects> =
-            c.subscribe_transaction(filter).await?;
+            c.subscribe_transaction(filter, None).await?;
+        Ok(subscription.map(|item| Ok(item?)))
+    }
+
+    /// Resume a stream of transactions after `cursor`: the matching transactions after it are
+    /// replayed first, followed by new ones, without gaps or duplicates.
+    ///
+    /// This is only available through WebSockets.
+    pub async fn subscribe_transaction_from(
+        &self,
+        filter: TransactionFilter,
+        cursor: TransactionDigest,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockEffects>>> {
+        let Some(c) = &self.api.ws else {
+            return Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            ));
+        };
+        let subscription: Subscription<SuiTransactionBlockEffects> =
+            c.subscribe_transaction(filter, Some(cursor)).await?;
         Ok(subscription.map(|item| Ok(item?)))
     }
 
@@ -750,9 +833,30 @@ impl CoinReadApi {
         Ok(self
             .api
             .http
-            .get_coins(owner, coin_type, cursor, limit)
+            .get_coins(owner, coin_type, cursor, limit, None)
+            .await?)
+    }
+
+    /// Return a paginated response with the coins for the given address as of the given
+    /// checkpoint, or an error upon failure.
+    ///
+    /// Unlike [Self::get_coins], pages are consistent with each other when paginating with the
+    /// same `checkpoint`. Only supported by RPC servers backed by an indexer.
+    pub async fn get_coins_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<CoinPage> {
+        Ok(self
+            .api
+            .http
+            .get_coins(owner, coin_type, cursor, limit, Some(checkpoint.into()))
             .await?)
     }
+
     /// Return a paginated response with all the coins for the given address, or an error upon failure.
     ///
     /// This function includes all coins. If needed to filter by coin type, use the `get_coins` method instead.
@@ -781,7 +885,30 @@ impl CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<CoinPage> {
-        Ok(self.api.http.get_all_coins(owner, cursor, limit).await?)
+        Ok(self
+            .api
+            .http
+            .get_all_coins(owner, cursor, limit, None)
+            .await?)
+    }
+
+    /// Return a paginated response with all the coins for the given address as of the given
+    /// checkpoint, or an error upon failure.
+    ///
+    /// Unlike [Self::get_all_coins], pages are consistent with each other when paginating with
+    /// the same `checkpoint`. Only supported by RPC servers backed by an indexer.
+    pub async fn get_all_coins_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<CoinPage> {
+        Ok(self
+            .api
+            .http
+            .get_all_coins(owner, cursor, limit, Some(checkpoint.into()))
+            .await?)
     }
 
     /// Return the coins for the given address as a stream.
@@ -1043,7 +1170,29 @@ impl EventApi {
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
         match &self.api.ws {
             Some(c) => {
-                let subscription: Subscription<SuiEvent> = c.subscribe_event(filter).await?;
+                let subscription: Subscription<SuiEvent> = c.subscribe_event(filter, None).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
+    /// Resume a stream of events after `cursor`, e.g. the id of the last event received before a
+    /// connection dropped: the matching events after it are replayed first, followed by new ones,
+    /// without gaps or duplicates.
+    ///
+    /// This is only available through WebSockets.
+    pub async fn subscribe_event_from(
+        &self,
+        filter: EventFilter,
+        cursor: EventID,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiEvent> =
+                    c.subscribe_event(filter, Some(cursor)).await?;
                 Ok(subscription.map(|item| Ok(item?)))
             }
             _ => Err(Error::Subscription(
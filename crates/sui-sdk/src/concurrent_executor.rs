@@ -0,0 +1,787 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A transaction executor for submitting many transactions from a single address concurrently.
+//!
+//! [ConcurrentExecutor] owns a pool of SUI coins belonging to its sender, each of which is used to
+//! pay for at most one transaction at a time. It tracks the latest versions of the pool's coins and
+//! of any owned objects that its transactions touch using their effects, rather than reading them
+//! back from the fullnode, and serializes transactions that share owned inputs, so that callers
+//! can submit from many tasks at once without equivocating owned objects.
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use sui_sdk::concurrent_executor::{ConcurrentExecutor, ConcurrentExecutorConfig};
+//! use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+//! # use sui_keys::keystore::Keystore;
+//! # use sui_sdk::types::base_types::SuiAddress;
+//! # async fn example(
+//! #     client: sui_sdk::SuiClient,
+//! #     keystore: Keystore,
+//! #     sender: SuiAddress,
+//! #     recipients: Vec<SuiAddress>,
+//! # ) -> anyhow::Result<()> {
+//! let executor = Arc::new(
+//!     ConcurrentExecutor::new(client, keystore, sender, ConcurrentExecutorConfig::default())
+//!         .await?,
+//! );
+//!
+//! let mut handles = vec![];
+//! for recipient in recipients {
+//!     let executor = executor.clone();
+//!     handles.push(tokio::spawn(async move {
+//!         let mut builder = ProgrammableTransactionBuilder::new();
+//!         builder.pay_sui(vec![recipient], vec![1_000])?;
+//!         executor.execute(builder.finish(), 10_000_000).await
+//!     }));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, RwLock as StdRwLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiObjectDataOptions, SuiRawData, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, Transaction, TransactionData,
+};
+use tokio::sync::{Mutex, Notify, OwnedMutexGuard, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::SuiClient;
+
+/// Maximum number of coins merged or split in a single transaction while rebalancing the pool.
+const MAX_COINS_PER_MERGE: usize = 256;
+
+#[derive(Clone, Debug)]
+pub struct ConcurrentExecutorConfig {
+    /// Number of gas coins to keep in the pool, which bounds the number of transactions that can
+    /// be in flight at once.
+    pub pool_size: usize,
+
+    /// Coins with a balance below this are considered dust: They are merged into other coins when
+    /// the pool is rebalanced, and are not used to pay for gas.
+    pub min_coin_balance: u64,
+
+    /// Balance to give each coin created by splitting while rebalancing the pool. If the sender
+    /// does not have enough SUI, the balance is spread evenly across `pool_size` coins instead.
+    pub target_coin_balance: u64,
+
+    /// How many times to re-submit a transaction whose outcome is unknown (because of a network or
+    /// fullnode error) before giving up on it.
+    pub max_retries: usize,
+
+    /// Delay between re-submissions of a transaction whose outcome is unknown.
+    pub retry_delay: Duration,
+}
+
+/// Submits transactions from a single sender concurrently, paying for gas from a managed pool of
+/// coins. See the module documentation for details.
+pub struct ConcurrentExecutor {
+    client: SuiClient,
+    keystore: Keystore,
+    sender: SuiAddress,
+    gas_price: u64,
+    config: ConcurrentExecutorConfig,
+
+    /// Coins available to pay for gas. Every coin in the pool is either in this list, or checked
+    /// out by exactly one in-flight transaction.
+    coins: StdMutex<Vec<PooledCoin>>,
+
+    /// Number of coins checked out by in-flight transactions.
+    in_flight: AtomicUsize,
+
+    /// Notified whenever a coin is returned to the pool.
+    returned: Notify,
+
+    /// Transactions hold this lock for reading while in flight. Rebalancing holds it for writing,
+    /// so that it has exclusive access to the sender's coins.
+    rebalance: RwLock<()>,
+
+    /// Latest known references for owned objects that this executor's transactions have written.
+    objects: StdRwLock<HashMap<ObjectID, ObjectRef>>,
+
+    /// Per-object locks, held by a transaction for each of its owned inputs while it is in flight.
+    object_locks: StdMutex<HashMap<ObjectID, Arc<Mutex<()>>>>,
+
+    /// Coins that were used by transactions whose outcome could not be determined, and the version
+    /// they were used at. They may be locked by a pending transaction, so are kept out of the pool
+    /// until they are seen at a different version.
+    quarantined: StdMutex<HashMap<ObjectID, SequenceNumber>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PooledCoin {
+    object_ref: ObjectRef,
+    balance: u64,
+}
+
+impl ConcurrentExecutor {
+    /// Create an executor that sends transactions from `sender`, signing them with its key in
+    /// `keystore`. Fills the gas coin pool from the sender's SUI coins, splitting and merging them
+    /// as necessary.
+    pub async fn new(
+        client: SuiClient,
+        keystore: Keystore,
+        sender: SuiAddress,
+        config: ConcurrentExecutorConfig,
+    ) -> anyhow::Result<Self> {
+        if config.pool_size == 0 || config.pool_size > MAX_COINS_PER_MERGE {
+            bail!("Gas coin pool size must be between 1 and {MAX_COINS_PER_MERGE}");
+        }
+
+        let gas_price = client.governance_api().get_reference_gas_price().await?;
+        let executor = Self {
+            client,
+            keystore,
+            sender,
+            gas_price,
+            config,
+            coins: StdMutex::new(vec![]),
+            in_flight: AtomicUsize::new(0),
+            returned: Notify::new(),
+            rebalance: RwLock::new(()),
+            objects: StdRwLock::new(HashMap::new()),
+            object_locks: StdMutex::new(HashMap::new()),
+            quarantined: StdMutex::new(HashMap::new()),
+        };
+
+        executor.rebalance().await?;
+        Ok(executor)
+    }
+
+    pub fn sender(&self) -> SuiAddress {
+        self.sender
+    }
+
+    /// The number of coins currently in the pool (whether or not they are in use).
+    pub fn pool_size(&self) -> usize {
+        self.coins.lock().unwrap().len() + self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// The total balance of the coins that are not currently in use.
+    pub fn available_balance(&self) -> u64 {
+        self.coins.lock().unwrap().iter().map(|c| c.balance).sum()
+    }
+
+    /// The latest version of `id` that this executor knows about, if one of its transactions has
+    /// written it.
+    pub fn object_ref(&self, id: ObjectID) -> Option<ObjectRef> {
+        self.objects.read().unwrap().get(&id).copied()
+    }
+
+    /// Sign and execute `pt`, paying for gas with a coin from the pool, and waiting for one to
+    /// become available if they are all in use.
+    ///
+    /// Owned object inputs are replaced by their latest known versions, and transactions that
+    /// share owned inputs are executed one after the other. The response is returned as long as
+    /// the transaction was executed, even if execution failed -- check its effects for the status.
+    pub async fn execute(
+        &self,
+        pt: ProgrammableTransaction,
+        gas_budget: u64,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let _rebalance = self.rebalance.read().await;
+
+        // Lock owned inputs in a consistent order, to avoid deadlocking with other transactions.
+        let owned_inputs: BTreeSet<_> = pt.inputs.iter().filter_map(owned_input_id).collect();
+        let mut object_guards = Vec::with_capacity(owned_inputs.len());
+        for id in owned_inputs {
+            object_guards.push(self.lock_object(id).await);
+        }
+
+        let pt = self.refresh_inputs(pt);
+        let spends_gas_coin = uses_gas_coin(&pt);
+        let coin = self.checkout(gas_budget).await?;
+
+        let data = TransactionData::new_programmable(
+            self.sender,
+            vec![coin.object_ref],
+            pt,
+            gas_budget,
+            self.gas_price,
+        );
+
+        // If the transaction spends from the gas coin, its balance afterwards is read back from
+        // the fullnode, so wait for the fullnode to execute it.
+        let request_type = if spends_gas_coin {
+            ExecuteTransactionRequestType::WaitForLocalExecution
+        } else {
+            ExecuteTransactionRequestType::WaitForEffectsCert
+        };
+
+        let response = match self.submit(data, request_type).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.quarantine(coin);
+                return Err(e);
+            }
+        };
+
+        self.apply_effects(coin, spends_gas_coin, &response).await;
+        Ok(response)
+    }
+
+    /// Merge the sender's dust coins, and split its coins so that the pool has `pool_size` coins
+    /// in it. Waits for all in-flight transactions to finish, and blocks new ones from starting
+    /// until it is done.
+    pub async fn rebalance(&self) -> anyhow::Result<()> {
+        let _rebalance = self.rebalance.write().await;
+
+        let coins = self.fetch_coins().await?;
+        let total: u64 = coins.iter().map(|c| c.balance).sum();
+        let usable = coins
+            .iter()
+            .filter(|c| c.balance >= self.config.min_coin_balance)
+            .count();
+
+        let has_dust = usable < coins.len();
+        if usable >= self.config.pool_size && !has_dust {
+            self.refill(coins);
+            return Ok(());
+        }
+
+        info!(
+            "Rebalancing gas coin pool for {}: {} coins ({usable} usable), total balance {total}",
+            self.sender,
+            coins.len(),
+        );
+
+        // Pay for rebalancing with the largest coin, merging everything else into it.
+        let mut coins = coins;
+        coins.sort_by_key(|c| std::cmp::Reverse(c.balance));
+        let Some((gas, rest)) = coins.split_first() else {
+            bail!("Address {} has no SUI coins to pay for gas", self.sender);
+        };
+
+        let mut gas = *gas;
+        let mut rest = rest.to_vec();
+        let gas_budget = self.rebalance_gas_budget();
+
+        // Merge in batches, so that each transaction stays within protocol limits.
+        while rest.len() > MAX_COINS_PER_MERGE {
+            let batch: Vec<_> = rest.drain(..MAX_COINS_PER_MERGE).collect();
+            let mut builder = ProgrammableTransactionBuilder::new();
+            merge_into_gas(&mut builder, &batch)?;
+            gas = self
+                .execute_rebalance(builder.finish(), gas, gas_budget)
+                .await?;
+        }
+
+        // Merge whatever is left, and then split the merged coin into `pool_size` coins. The gas
+        // coin itself becomes one of them.
+        let total = gas.balance + rest.iter().map(|c| c.balance).sum::<u64>();
+        let splits = self.config.pool_size - 1;
+        let per_coin = self
+            .config
+            .target_coin_balance
+            .min(total.saturating_sub(gas_budget) / self.config.pool_size as u64);
+
+        if per_coin < self.config.min_coin_balance {
+            bail!(
+                "Insufficient balance to fill gas coin pool for {}: {total} MIST across {} coins, \
+                 need at least {} MIST in each of {} coins",
+                self.sender,
+                rest.len() + 1,
+                self.config.min_coin_balance,
+                self.config.pool_size,
+            );
+        }
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        merge_into_gas(&mut builder, &rest)?;
+        if splits > 0 {
+            let amounts = (0..splits)
+                .map(|_| builder.pure(per_coin))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let split = builder.command(Command::SplitCoins(Argument::GasCoin, amounts));
+            let Argument::Result(split) = split else {
+                unreachable!("Commands return results");
+            };
+
+            let recipient = builder.pure(self.sender)?;
+            builder.command(Command::TransferObjects(
+                (0..splits as u16)
+                    .map(|ix| Argument::NestedResult(split, ix))
+                    .collect(),
+                recipient,
+            ));
+        }
+
+        self.execute_rebalance(builder.finish(), gas, gas_budget)
+            .await?;
+
+        // The rebalancing transactions waited for local execution, so the fullnode's view of the
+        // sender's coins is up-to-date.
+        let coins = self.fetch_coins().await?;
+        self.refill(coins);
+        Ok(())
+    }
+
+    /// Replace the contents of the pool with `coins` (dropping dust and quarantined coins). Only
+    /// called while holding the rebalance lock for writing, so no coins are checked out.
+    fn refill(&self, coins: Vec<PooledCoin>) {
+        let mut pool = self.coins.lock().unwrap();
+        *pool = coins
+            .into_iter()
+            .filter(|c| c.balance >= self.config.min_coin_balance)
+            .collect();
+
+        debug!(
+            "Gas coin pool for {} has {} coins with total balance {}",
+            self.sender,
+            pool.len(),
+            pool.iter().map(|c| c.balance).sum::<u64>(),
+        );
+    }
+
+    /// Take a coin with at least `budget` balance out of the pool, waiting for one to become
+    /// available.
+    async fn checkout(&self, budget: u64) -> anyhow::Result<PooledCoin> {
+        loop {
+            // Register interest in returned coins before inspecting the pool, so that a coin
+            // returned in between is not missed.
+            let returned = self.returned.notified();
+            tokio::pin!(returned);
+            returned.as_mut().enable();
+
+            {
+                let mut pool = self.coins.lock().unwrap();
+
+                // Prefer the smallest coin that covers the budget, to leave bigger coins for
+                // bigger budgets.
+                let smallest = pool
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.balance >= budget)
+                    .min_by_key(|(_, c)| c.balance)
+                    .map(|(ix, _)| ix);
+
+                if let Some(ix) = smallest {
+                    self.in_flight.fetch_add(1, Ordering::Relaxed);
+                    return Ok(pool.swap_remove(ix));
+                }
+
+                // Coins that are currently in use may be big enough once they are returned, but
+                // if none are in use, waiting won't help.
+                if self.in_flight.load(Ordering::Relaxed) == 0 {
+                    bail!(
+                        "No coin in the gas pool for {} can cover a budget of {budget} MIST, \
+                         rebalance the pool or lower the budget",
+                        self.sender,
+                    );
+                }
+            }
+
+            returned.await;
+        }
+    }
+
+    /// Return a coin to the pool, at its new version and balance.
+    fn checkin(&self, coin: PooledCoin) {
+        // The coin is pushed back and `in_flight` is decremented under the same lock, so that
+        // `checkout` never sees the coin as neither in the pool nor in flight.
+        let mut pool = self.coins.lock().unwrap();
+        if coin.balance < self.config.min_coin_balance {
+            warn!(
+                "Gas coin {} has run low ({} MIST), dropping it from the pool",
+                coin.object_ref.0, coin.balance,
+            );
+        } else {
+            pool.push(coin);
+        }
+
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.returned.notify_waiters();
+    }
+
+    /// Remove a checked out coin from the pool for good (until the next rebalance).
+    fn drop_coin(&self) {
+        let _pool = self.coins.lock().unwrap();
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.returned.notify_waiters();
+    }
+
+    /// Remove a coin from circulation, because the transaction it was used for may or may not
+    /// have been executed.
+    fn quarantine(&self, coin: PooledCoin) {
+        let (id, version, _) = coin.object_ref;
+        warn!("Outcome of transaction using gas coin {id} unknown, removing it from the pool");
+        self.quarantined.lock().unwrap().insert(id, version);
+        self.drop_coin();
+    }
+
+    async fn lock_object(&self, id: ObjectID) -> OwnedMutexGuard<()> {
+        let lock = self
+            .object_locks
+            .lock()
+            .unwrap()
+            .entry(id)
+            .or_default()
+            .clone();
+
+        lock.lock_owned().await
+    }
+
+    /// Replace references to owned objects in `pt`'s inputs with the latest versions this executor
+    /// knows about.
+    fn refresh_inputs(&self, mut pt: ProgrammableTransaction) -> ProgrammableTransaction {
+        let objects = self.objects.read().unwrap();
+        for input in &mut pt.inputs {
+            let CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref)) = input else {
+                continue;
+            };
+
+            if let Some(latest) = objects.get(&object_ref.0) {
+                if latest.1 > object_ref.1 {
+                    *object_ref = *latest;
+                }
+            }
+        }
+
+        pt
+    }
+
+    /// Update the versions of the gas coin and any owned objects written by a transaction, and
+    /// return its gas coin to the pool. If the transaction used the gas coin as an argument, its
+    /// new balance is read from the version of the coin that the transaction wrote, otherwise it
+    /// is derived from the transaction's gas cost.
+    async fn apply_effects(
+        &self,
+        mut coin: PooledCoin,
+        spends_gas_coin: bool,
+        response: &SuiTransactionBlockResponse,
+    ) {
+        let Some(effects) = &response.effects else {
+            // The transaction was executed, but without its effects there is no way to know the
+            // gas coin's new version.
+            self.quarantine(coin);
+            return;
+        };
+
+        let mut objects = self.objects.write().unwrap();
+        for obj in effects
+            .created()
+            .iter()
+            .chain(effects.mutated())
+            .chain(effects.unwrapped())
+        {
+            if obj.owner == Owner::AddressOwner(self.sender) {
+                objects.insert(obj.object_id(), obj.reference.to_object_ref());
+            } else {
+                objects.remove(&obj.object_id());
+            }
+        }
+
+        for obj in effects
+            .deleted()
+            .iter()
+            .chain(effects.wrapped())
+            .chain(effects.unwrapped_then_deleted())
+        {
+            objects.remove(&obj.object_id);
+        }
+        drop(objects);
+
+        let gas = effects.gas_object();
+        if gas.owner != Owner::AddressOwner(self.sender) {
+            // The transaction transferred, shared or wrapped the gas coin, so it can no longer be
+            // used to pay for the sender's transactions.
+            warn!(
+                "Gas coin {} is no longer owned by {}, removing it from the pool",
+                gas.object_id(),
+                self.sender,
+            );
+            self.drop_coin();
+            return;
+        }
+
+        let gas = gas.reference.to_object_ref();
+        coin.balance = if spends_gas_coin {
+            match self.gas_coin_balance(gas).await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    // The coin is not locked, so it can be used again once the pool is rebalanced.
+                    warn!(
+                        "Failed to read balance of gas coin {}, removing it from the pool: {e}",
+                        gas.0
+                    );
+                    self.drop_coin();
+                    return;
+                }
+            }
+        } else {
+            balance_after_gas(coin.balance, effects.gas_cost_summary().net_gas_usage())
+        };
+        coin.object_ref = gas;
+
+        self.checkin(coin);
+    }
+
+    /// Read the balance of the gas coin at the version in `gas`.
+    async fn gas_coin_balance(&self, gas: ObjectRef) -> anyhow::Result<u64> {
+        let (id, version, _) = gas;
+        let response = self
+            .client
+            .read_api()
+            .try_get_parsed_past_object(id, version, SuiObjectDataOptions::bcs_lossless())
+            .await?;
+
+        let Some(SuiRawData::MoveObject(object)) = &response.object()?.bcs else {
+            bail!("Missing contents of gas coin {id} at version {version}");
+        };
+
+        Ok(object.deserialize::<GasCoin>()?.value())
+    }
+
+    /// Sign and submit a transaction, re-submitting it if its outcome is unknown. Re-submitting the
+    /// same signed transaction is idempotent.
+    async fn submit(
+        &self,
+        data: TransactionData,
+        request_type: ExecuteTransactionRequestType,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let signature =
+            self.keystore
+                .sign_secure(&self.sender, &data, Intent::sui_transaction())?;
+        let tx = Transaction::from_data(data, vec![signature]);
+
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .quorum_driver_api()
+                .execute_transaction_block(
+                    tx.clone(),
+                    SuiTransactionBlockResponseOptions::new().with_effects(),
+                    Some(request_type.clone()),
+                )
+                .await;
+
+            match result {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt < self.config.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "Failed to execute transaction {} (attempt {attempt}): {e}",
+                        tx.digest(),
+                    );
+                    tokio::time::sleep(self.config.retry_delay).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Execute a transaction that merges or splits the sender's coins, and return the gas coin's
+    /// new state.
+    async fn execute_rebalance(
+        &self,
+        pt: ProgrammableTransaction,
+        gas: PooledCoin,
+        gas_budget: u64,
+    ) -> anyhow::Result<PooledCoin> {
+        let data = TransactionData::new_programmable(
+            self.sender,
+            vec![gas.object_ref],
+            pt,
+            gas_budget,
+            self.gas_price,
+        );
+
+        let response = self
+            .submit(data, ExecuteTransactionRequestType::WaitForLocalExecution)
+            .await?;
+
+        let effects = response
+            .effects
+            .ok_or_else(|| anyhow!("Missing effects for rebalancing transaction"))?;
+
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            bail!("Rebalancing transaction failed: {error}");
+        }
+
+        let new_gas = effects.gas_object().reference.to_object_ref();
+        Ok(PooledCoin {
+            object_ref: new_gas,
+            // Only used to pay for the next rebalancing transaction, and in the final split, which
+            // re-reads balances afterwards, so an estimate is fine.
+            balance: gas.balance.saturating_sub(gas_budget),
+        })
+    }
+
+    /// Gas budget for rebalancing transactions: Enough to merge a full batch of coins.
+    fn rebalance_gas_budget(&self) -> u64 {
+        self.gas_price * 50_000
+    }
+
+    /// Read all the sender's SUI coins from the fullnode, excluding any that are quarantined.
+    async fn fetch_coins(&self) -> anyhow::Result<Vec<PooledCoin>> {
+        let mut coins = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .client
+                .coin_read_api()
+                .get_coins(self.sender, None, cursor, None)
+                .await?;
+
+            coins.extend(page.data.into_iter().map(|c| PooledCoin {
+                object_ref: c.object_ref(),
+                balance: c.balance,
+            }));
+
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        // Coins that have moved past the version they were quarantined at are safe to use again.
+        let mut quarantined = self.quarantined.lock().unwrap();
+        let mut excluded = HashSet::new();
+        quarantined.retain(|id, version| {
+            let stuck = coins
+                .iter()
+                .any(|c| c.object_ref.0 == *id && c.object_ref.1 == *version);
+            if stuck {
+                excluded.insert(*id);
+            }
+            stuck
+        });
+
+        Ok(coins
+            .into_iter()
+            .filter(|c| !excluded.contains(&c.object_ref.0))
+            .collect())
+    }
+}
+
+impl Default for ConcurrentExecutorConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: 32,
+            min_coin_balance: 100_000_000,
+            target_coin_balance: 1_000_000_000,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// The ID of an owned object referred to by `input`, if there is one. Immutable objects can't be
+/// distinguished from owned objects by their input, so they are included as well (and locked
+/// unnecessarily).
+fn owned_input_id(input: &CallArg) -> Option<ObjectID> {
+    match input {
+        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => Some(*id),
+        CallArg::Object(ObjectArg::Receiving((id, _, _))) => Some(*id),
+        CallArg::Object(ObjectArg::SharedObject { .. }) | CallArg::Pure(_) => None,
+    }
+}
+
+/// Whether any of `pt`'s commands take the gas coin as an argument, which means the transaction can
+/// change its balance by more than its gas cost.
+fn uses_gas_coin(pt: &ProgrammableTransaction) -> bool {
+    pt.commands.iter().any(|command| {
+        let mut arguments: Box<dyn Iterator<Item = &Argument>> = match command {
+            Command::MoveCall(call) => Box::new(call.arguments.iter()),
+            Command::TransferObjects(objects, recipient) => {
+                Box::new(objects.iter().chain([recipient]))
+            }
+            Command::SplitCoins(coin, amounts) => Box::new([coin].into_iter().chain(amounts)),
+            Command::MergeCoins(coin, coins) => Box::new([coin].into_iter().chain(coins)),
+            Command::MakeMoveVec(_, elements) => Box::new(elements.iter()),
+            Command::Publish(_, _) => Box::new(std::iter::empty()),
+            Command::Upgrade(_, _, _, ticket) => Box::new([ticket].into_iter()),
+        };
+        arguments.any(|argument| *argument == Argument::GasCoin)
+    })
+}
+
+/// The balance of a coin with `balance` after paying for `net_gas_usage` (which is negative if the
+/// transaction's storage rebate exceeds its costs).
+fn balance_after_gas(balance: u64, net_gas_usage: i64) -> u64 {
+    if net_gas_usage >= 0 {
+        balance.saturating_sub(net_gas_usage as u64)
+    } else {
+        balance.saturating_add(net_gas_usage.unsigned_abs())
+    }
+}
+
+/// Add a command to `builder` that merges `coins` into the gas coin.
+fn merge_into_gas(
+    builder: &mut ProgrammableTransactionBuilder,
+    coins: &[PooledCoin],
+) -> anyhow::Result<()> {
+    if coins.is_empty() {
+        return Ok(());
+    }
+
+    let coins = coins
+        .iter()
+        .map(|c| builder.obj(ObjectArg::ImmOrOwnedObject(c.object_ref)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    builder.command(Command::MergeCoins(Argument::GasCoin, coins));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::random_object_ref;
+
+    use super::*;
+
+    #[test]
+    fn test_uses_gas_coin() {
+        let recipient = SuiAddress::random_for_testing_only();
+
+        // Paying from the gas coin splits it.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_sui(vec![recipient], vec![1_000]).unwrap();
+        assert!(uses_gas_coin(&builder.finish()));
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(recipient, None);
+        assert!(uses_gas_coin(&builder.finish()));
+
+        // Transferring another object only uses the gas coin to pay for gas.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .transfer_object(recipient, random_object_ref())
+            .unwrap();
+        assert!(!uses_gas_coin(&builder.finish()));
+
+        // Merging other coins into the gas coin.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let coin = PooledCoin {
+            object_ref: random_object_ref(),
+            balance: 1_000,
+        };
+        merge_into_gas(&mut builder, &[coin]).unwrap();
+        assert!(uses_gas_coin(&builder.finish()));
+    }
+
+    #[test]
+    fn test_balance_after_gas() {
+        assert_eq!(balance_after_gas(1_000, 300), 700);
+        assert_eq!(balance_after_gas(1_000, -300), 1_300);
+        assert_eq!(balance_after_gas(100, 300), 0);
+    }
+}
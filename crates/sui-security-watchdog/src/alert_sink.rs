@@ -0,0 +1,194 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::pagerduty::{Body, CreateIncident, Incident, Pagerduty, Service};
+use anyhow::anyhow;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::info;
+
+/// An alert raised by a monitoring job.
+#[derive(Clone, Debug, Serialize)]
+pub struct Alert {
+    /// Identifies the condition being alerted on, so that sinks can de-duplicate repeated alerts
+    /// for the same condition.
+    pub key: String,
+    pub title: String,
+    pub details: String,
+}
+
+#[async_trait::async_trait]
+pub trait AlertSink: Send + Sync + 'static {
+    /// Deliver `alert`. Sinks are expected to tolerate being sent the same alert repeatedly, as
+    /// jobs re-raise alerts on every run until the condition clears.
+    async fn send(&self, alert: &Alert) -> anyhow::Result<()>;
+}
+
+/// Raises alerts as incidents on a PagerDuty service, de-duplicated by the alert's key.
+pub struct PagerDutyAlertSink {
+    pagerduty: Pagerduty,
+    service_id: String,
+    from: String,
+}
+
+/// Posts alerts as JSON to an HTTP endpoint (e.g. a Slack or Discord incoming webhook proxy, or an
+/// in-house alert manager).
+pub struct WebhookAlertSink {
+    client: Arc<reqwest::Client>,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    source: &'static str,
+    #[serde(flatten)]
+    alert: &'a Alert,
+}
+
+impl PagerDutyAlertSink {
+    pub fn new(api_key: String, service_id: String, from: String) -> Self {
+        Self {
+            pagerduty: Pagerduty::new(api_key),
+            service_id,
+            from,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSink for PagerDutyAlertSink {
+    async fn send(&self, alert: &Alert) -> anyhow::Result<()> {
+        let service = Service {
+            id: self.service_id.clone(),
+            ..Default::default()
+        };
+        let incident_body = Body {
+            details: alert.details.clone(),
+            ..Default::default()
+        };
+        let incident = Incident {
+            title: alert.title.clone(),
+            service,
+            incident_key: alert.key.clone(),
+            body: incident_body,
+            ..Default::default()
+        };
+        self.pagerduty
+            .create_incident(&self.from, CreateIncident { incident })
+            .await
+    }
+}
+
+impl WebhookAlertSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: Arc::new(reqwest::Client::new()),
+            url,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSink for WebhookAlertSink {
+    async fn send(&self, alert: &Alert) -> anyhow::Result<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&WebhookPayload {
+                source: "sui-security-watchdog",
+                alert,
+            })
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            info!("Sent alert with key: {} to webhook", alert.key);
+            Ok(())
+        } else {
+            let status = response.status();
+            let text = response.text().await?;
+            Err(anyhow!(
+                "Failed to send alert to webhook ({status}): {text}"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use std::net::SocketAddr;
+    use tokio::sync::Mutex;
+
+    /// Serve a webhook on a local port that records the payloads it receives, and responds with
+    /// `status`.
+    fn serve_webhook(status: StatusCode) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let received = Arc::new(Mutex::new(vec![]));
+        let router = Router::new()
+            .route(
+                "/alert",
+                post(
+                    move |State(received): State<Arc<Mutex<Vec<Value>>>>,
+                          Json(payload): Json<Value>| async move {
+                        received.lock().await.push(payload);
+                        (status, "webhook response")
+                    },
+                ),
+            )
+            .with_state(received.clone());
+
+        let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .serve(router.into_make_service());
+        let url = format!("http://{}/alert", server.local_addr());
+        tokio::spawn(async move { server.await.unwrap() });
+        (url, received)
+    }
+
+    fn alert() -> Alert {
+        Alert {
+            key: "wallet-0x1".to_string(),
+            title: "Balance below limit".to_string(),
+            details: "Wallet 0x1 has 10 SUI".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_alert() {
+        let (url, received) = serve_webhook(StatusCode::OK);
+        let sink = WebhookAlertSink::new(url);
+
+        sink.send(&alert()).await.unwrap();
+        sink.send(&alert()).await.unwrap();
+
+        let expected = json!({
+            "source": "sui-security-watchdog",
+            "key": "wallet-0x1",
+            "title": "Balance below limit",
+            "details": "Wallet 0x1 has 10 SUI",
+        });
+        assert_eq!(*received.lock().await, vec![expected.clone(), expected]);
+    }
+
+    #[tokio::test]
+    async fn webhook_reports_failure() {
+        let (url, received) = serve_webhook(StatusCode::INTERNAL_SERVER_ERROR);
+        let sink = WebhookAlertSink::new(url);
+
+        let err = sink.send(&alert()).await.unwrap_err().to_string();
+        assert!(err.contains("500"), "{err}");
+        assert!(err.contains("webhook response"), "{err}");
+        assert_eq!(received.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn webhook_unreachable() {
+        // Nothing is listening on the discard port.
+        let sink = WebhookAlertSink::new("http://127.0.0.1:9/alert".to_string());
+        assert!(sink.send(&alert()).await.is_err());
+    }
+}
@@ -1,13 +1,23 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
+pub mod alert_sink;
 mod metrics;
 mod pagerduty;
-mod query_runner;
+pub mod query_runner;
 pub mod scheduler;
 
+/// The database that monitoring queries are run against.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryBackend {
+    /// Snowflake, configured by the `sf_*` options, with the password in `SF_PASSWORD`.
+    Snowflake,
+    /// Postgres (e.g. a `sui-indexer` database), at `--pg-database-url` or `PG_DATABASE_URL`.
+    Postgres,
+}
+
 #[derive(Parser, Clone, Debug)]
 #[clap(
     name = "Sui Security Watchdog",
@@ -15,10 +25,21 @@ pub mod scheduler;
     rename_all = "kebab-case"
 )]
 pub struct SecurityWatchdogConfig {
+    /// PagerDuty service to raise wallet monitoring incidents on. Requires `PD_API_KEY` and
+    /// `--pd-from-email` to be set.
+    #[clap(long, requires = "pd_from_email")]
+    pub pd_wallet_monitoring_service_id: Option<String>,
+    /// Email address of the PagerDuty user that incidents are raised on behalf of.
     #[clap(long)]
-    pub pd_wallet_monitoring_service_id: String,
+    pub pd_from_email: Option<String>,
+    /// URLs to post wallet monitoring alerts to, as JSON. Can be supplied multiple times, and
+    /// alongside PagerDuty.
+    #[clap(long = "alert-webhook-url")]
+    pub alert_webhook_urls: Vec<String>,
     #[clap(long)]
     pub config: PathBuf,
+    #[clap(long, value_enum, default_value = "snowflake", global = true)]
+    pub query_backend: QueryBackend,
     #[clap(long, default_value = None, global = true)]
     pub sf_account_identifier: Option<String>,
     #[clap(long, default_value = None, global = true)]
@@ -31,6 +52,10 @@ pub struct SecurityWatchdogConfig {
     pub sf_username: Option<String>,
     #[clap(long, default_value = None, global = true)]
     pub sf_role: Option<String>,
+    /// Postgres connection URL. Falls back to the `PG_DATABASE_URL` env var, to keep credentials
+    /// off the command line.
+    #[clap(long, default_value = None, global = true)]
+    pub pg_database_url: Option<String>,
     /// The url of the metrics client to connect to.
     #[clap(long, default_value = "127.0.0.1", global = true)]
     pub client_metric_host: String,
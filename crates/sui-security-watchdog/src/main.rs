@@ -3,11 +3,14 @@
 
 use prometheus::Registry;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::*;
 use std::env;
+use std::sync::Arc;
+use sui_security_watchdog::alert_sink::{AlertSink, PagerDutyAlertSink, WebhookAlertSink};
+use sui_security_watchdog::query_runner::{PostgresQueryRunner, QueryRunner, SnowflakeQueryRunner};
 use sui_security_watchdog::scheduler::SchedulerService;
-use sui_security_watchdog::SecurityWatchdogConfig;
+use sui_security_watchdog::{QueryBackend, SecurityWatchdogConfig};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -16,8 +19,40 @@ async fn main() -> Result<()> {
         .init();
     env_logger::init();
     let config = SecurityWatchdogConfig::parse();
-    let pd_api_key = env::var("PD_API_KEY").expect("PD_API_KEY env var must be set");
-    let sf_password = env::var("SF_PASSWORD").expect("SF_PASSWORD env var must be set");
+
+    let query_runner: Arc<dyn QueryRunner> = match config.query_backend {
+        QueryBackend::Snowflake => {
+            let sf_password = env::var("SF_PASSWORD").expect("SF_PASSWORD env var must be set");
+            Arc::new(SnowflakeQueryRunner::from_config(&config, sf_password)?)
+        }
+        QueryBackend::Postgres => {
+            let url = config
+                .pg_database_url
+                .clone()
+                .or_else(|| env::var("PG_DATABASE_URL").ok())
+                .ok_or_else(|| {
+                    anyhow!("--pg-database-url or PG_DATABASE_URL must be set for Postgres")
+                })?;
+            Arc::new(PostgresQueryRunner::new(&url)?)
+        }
+    };
+
+    let mut alert_sinks: Vec<Arc<dyn AlertSink>> = vec![];
+    if let Some(service_id) = &config.pd_wallet_monitoring_service_id {
+        let pd_api_key = env::var("PD_API_KEY").expect("PD_API_KEY env var must be set");
+        let pd_from_email = config
+            .pd_from_email
+            .clone()
+            .ok_or_else(|| anyhow!("--pd-from-email must be set to raise PagerDuty incidents"))?;
+        alert_sinks.push(Arc::new(PagerDutyAlertSink::new(
+            pd_api_key,
+            service_id.clone(),
+            pd_from_email,
+        )));
+    }
+    for url in &config.alert_webhook_urls {
+        alert_sinks.push(Arc::new(WebhookAlertSink::new(url.clone())));
+    }
 
     let registry_service = mysten_metrics::start_prometheus_server(
         format!(
@@ -29,7 +64,7 @@ async fn main() -> Result<()> {
     );
     let registry: Registry = registry_service.default_registry();
     mysten_metrics::init_metrics(&registry);
-    let service = SchedulerService::new(&config, &registry, pd_api_key, sf_password).await?;
+    let service = SchedulerService::new(&config, &registry, query_runner, alert_sinks).await?;
     service.schedule().await?;
     service.start().await?;
     tokio::signal::ctrl_c().await?;
@@ -0,0 +1,27 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::any::Any;
+use std::collections::HashMap;
+
+mod postgres;
+mod snowflake;
+
+pub use postgres::PostgresQueryRunner;
+pub use snowflake::SnowflakeQueryRunner;
+
+/// A row of query results, keyed by (upper-case) column name. Integer columns are boxed as `i128`
+/// or one of the narrower integer types, floating point columns as `f32` or `f64`, and text
+/// columns as `String`.
+pub type Row = HashMap<String, Box<dyn Any + Send>>;
+
+#[async_trait::async_trait]
+pub trait QueryRunner: Send + Sync + 'static {
+    /// Asynchronously runs the given SQL query and returns the result as a floating-point number.
+    /// Only the first row and first column in returned, so it is important that users of this trait
+    /// use it for a query which returns only a single floating point result
+    async fn run_single_entry(&self, query: &str) -> anyhow::Result<f64>;
+
+    /// Asynchronously runs the given SQL query and returns the result as a vector of rows.
+    async fn run(&self, query: &str) -> anyhow::Result<Vec<Row>>;
+}
@@ -0,0 +1,220 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::query_runner::{QueryRunner, Row};
+use anyhow::anyhow;
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sql_types::Text;
+use diesel::{QueryableByName, RunQueryDsl};
+use serde_json::Value;
+use std::any::Any;
+use tracing::info;
+
+/// Maximum number of connections to keep open to the database. Jobs run on a cron schedule, so
+/// there is rarely more than one query in flight.
+const MAX_POOL_SIZE: u32 = 4;
+
+/// Runs queries against a Postgres database, such as one populated by `sui-indexer`, for operators
+/// who don't have access to Snowflake.
+///
+/// Postgres folds unquoted column names to lower-case, while Snowflake folds them to upper-case.
+/// Column names in returned rows are upper-cased, so that the same monitoring entries (which look
+/// up columns like `WALLET_ID`) work against either backend.
+pub struct PostgresQueryRunner {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+#[derive(QueryableByName)]
+struct JsonRow {
+    #[diesel(sql_type = Text)]
+    row: String,
+}
+
+impl PostgresQueryRunner {
+    /// Creates a new `PostgresQueryRunner` connected to the database at `database_url`.
+    pub fn new(database_url: &str) -> anyhow::Result<Self> {
+        let manager = ConnectionManager::<PgConnection>::new(database_url);
+        let pool = Pool::builder()
+            .max_size(MAX_POOL_SIZE)
+            .build(manager)
+            .map_err(|e| anyhow!("Failed to connect to Postgres: {e}"))?;
+
+        Ok(Self { pool })
+    }
+
+    /// Run `sql` on a blocking thread, with a connection from the pool.
+    async fn with_connection<T, F>(&self, f: F) -> anyhow::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut PgConnection) -> anyhow::Result<T> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+            f(&mut conn)
+        })
+        .await?
+    }
+
+    /// Convert a row, serialized as a JSON object, into a `Row`.
+    fn parse_row(row: &str) -> anyhow::Result<Row> {
+        let Value::Object(columns) = serde_json::from_str(row)? else {
+            return Err(anyhow!("Expected row to be a JSON object: {row}"));
+        };
+
+        let mut parsed = Row::new();
+        for (name, value) in columns {
+            let value: Box<dyn Any + Send> = match value {
+                Value::Null => continue,
+                Value::Bool(b) => Box::new(b),
+                Value::String(s) => Box::new(s),
+                // `row_to_json` writes NUMERIC columns out in full, and numbers are parsed with
+                // arbitrary precision, so their text is exact: Integers too wide for 64 bits (such
+                // as balances summed into a NUMERIC) are recovered as `i128`.
+                Value::Number(n) => {
+                    if let Ok(n) = n.to_string().parse::<i128>() {
+                        Box::new(n)
+                    } else if let Some(n) = n.as_f64() {
+                        Box::new(n)
+                    } else {
+                        return Err(anyhow!("Unsupported number in column {name}: {n}"));
+                    }
+                }
+                value @ (Value::Array(_) | Value::Object(_)) => Box::new(value.to_string()),
+            };
+
+            parsed.insert(name.to_uppercase(), value);
+        }
+
+        Ok(parsed)
+    }
+
+    /// Convert the first column of a row, serialized as a JSON object, into an `f64`.
+    fn parse_first_column(row: &str) -> anyhow::Result<f64> {
+        let Value::Object(columns) = serde_json::from_str(row)? else {
+            return Err(anyhow!("Expected row to be a JSON object: {row}"));
+        };
+
+        // Columns are kept in the order the query returned them.
+        let Some((name, value)) = columns.into_iter().next() else {
+            return Err(anyhow!("No columns found in record"));
+        };
+
+        match value {
+            Value::Number(n) => n
+                .as_f64()
+                .ok_or_else(|| anyhow!("Unsupported number in column {name}: {n}")),
+            Value::String(s) => s
+                .parse()
+                .map_err(|e| anyhow!("Column {name} is not a number ({s:?}): {e}")),
+            Value::Null => Err(anyhow!("No results found for query")),
+            value => Err(anyhow!("Column {name} is not a number: {value}")),
+        }
+    }
+}
+
+/// Strip trailing semi-colons and whitespace, so that `query` can be embedded as a sub-query.
+fn as_subquery(query: &str) -> &str {
+    query.trim().trim_end_matches(';').trim_end()
+}
+
+#[async_trait::async_trait]
+impl QueryRunner for PostgresQueryRunner {
+    async fn run_single_entry(&self, query: &str) -> anyhow::Result<f64> {
+        // Like the Snowflake runner, the result is the first column of the first row, however
+        // many columns the query returns.
+        let sql = format!(
+            "SELECT row_to_json(q)::TEXT AS row FROM ({}) AS q LIMIT 1",
+            as_subquery(query),
+        );
+
+        let rows: Vec<JsonRow> = self
+            .with_connection(move |conn| Ok(diesel::sql_query(sql).load(conn)?))
+            .await?;
+
+        let row = rows
+            .first()
+            .ok_or_else(|| anyhow!("No results found for query"))?;
+
+        Self::parse_first_column(&row.row)
+    }
+
+    async fn run(&self, query: &str) -> anyhow::Result<Vec<Row>> {
+        info!("Running query: {}", query);
+
+        // Serialize each row as JSON, so that queries can return columns of any type, without
+        // declaring them up-front.
+        let sql = format!(
+            "SELECT row_to_json(q)::TEXT AS row FROM ({}) AS q",
+            as_subquery(query),
+        );
+
+        let rows: Vec<JsonRow> = self
+            .with_connection(move |conn| Ok(diesel::sql_query(sql).load(conn)?))
+            .await?;
+
+        let rows = rows
+            .iter()
+            .map(|r| Self::parse_row(&r.row))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        info!("Found {} rows", rows.len());
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_row_upper_cases_columns() {
+        let row = PostgresQueryRunner::parse_row(
+            r#"{"wallet_id": "0x1", "current_balance": 42, "ratio": 0.5, "note": null}"#,
+        )
+        .unwrap();
+
+        assert_eq!(row["WALLET_ID"].downcast_ref::<String>().unwrap(), "0x1");
+        assert_eq!(*row["CURRENT_BALANCE"].downcast_ref::<i128>().unwrap(), 42);
+        assert_eq!(*row["RATIO"].downcast_ref::<f64>().unwrap(), 0.5);
+        assert!(!row.contains_key("NOTE"));
+    }
+
+    #[test]
+    fn parse_row_wide_numeric() {
+        let row = PostgresQueryRunner::parse_row(
+            r#"{"total": 170141183460469231731687303715884105727, "negative": -18446744073709551616, "fraction": 1.25}"#,
+        )
+        .unwrap();
+
+        assert_eq!(*row["TOTAL"].downcast_ref::<i128>().unwrap(), i128::MAX);
+        assert_eq!(
+            *row["NEGATIVE"].downcast_ref::<i128>().unwrap(),
+            -(u64::MAX as i128) - 1
+        );
+        assert_eq!(*row["FRACTION"].downcast_ref::<f64>().unwrap(), 1.25);
+    }
+
+    #[test]
+    fn parse_first_column_of_many() {
+        let value =
+            PostgresQueryRunner::parse_first_column(r#"{"balance": 12.5, "wallet_id": "0x1"}"#)
+                .unwrap();
+        assert_eq!(value, 12.5);
+
+        let value = PostgresQueryRunner::parse_first_column(r#"{"total": "42"}"#).unwrap();
+        assert_eq!(value, 42.0);
+
+        assert!(PostgresQueryRunner::parse_first_column(r#"{"total": null}"#).is_err());
+        assert!(PostgresQueryRunner::parse_first_column(r#"{"id": "0x1"}"#).is_err());
+    }
+
+    #[test]
+    fn subquery_strips_terminator() {
+        assert_eq!(
+            as_subquery("  SELECT 1 FROM objects;\n"),
+            "SELECT 1 FROM objects"
+        );
+    }
+}
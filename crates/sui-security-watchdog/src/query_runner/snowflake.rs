@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::query_runner::{QueryRunner, Row};
 use crate::SecurityWatchdogConfig;
 use anyhow::anyhow;
 use arrow_array::cast::AsArray;
@@ -11,23 +12,9 @@ use arrow_array::types::{
 use arrow_array::{Array, Float32Array, RecordBatch};
 use lexical_util::num::AsPrimitive;
 use snowflake_api::{QueryResult, SnowflakeApi};
-use std::any::Any;
 use std::collections::HashMap;
 use tracing::info;
 
-pub type Row = HashMap<String, Box<dyn Any + Send>>;
-
-#[async_trait::async_trait]
-pub trait QueryRunner: Send + Sync + 'static {
-    /// Asynchronously runs the given SQL query and returns the result as a floating-point number.
-    /// Only the first row and first column in returned, so it is important that users of this trait
-    /// use it for a query which returns only a single floating point result
-    async fn run_single_entry(&self, query: &str) -> anyhow::Result<f64>;
-
-    /// Asynchronously runs the given SQL query and returns the result as a vector of rows.
-    async fn run(&self, query: &str) -> anyhow::Result<Vec<Row>>;
-}
-
 macro_rules! insert_primitive_values {
     ($rows:expr, $column:expr, $name:expr, $type:ty) => {
         if let Some(value) = $column.as_primitive_opt::<$type>() {
@@ -113,19 +100,20 @@ impl SnowflakeQueryRunner {
         config: &SecurityWatchdogConfig,
         sf_password: String,
     ) -> anyhow::Result<Self> {
+        let required = |name: &str, value: &Option<String>| {
+            value
+                .clone()
+                .ok_or_else(|| anyhow!("--{name} is required for the Snowflake query backend"))
+        };
+
         Self::new(
-            config
-                .sf_account_identifier
-                .as_ref()
-                .cloned()
-                .unwrap()
-                .as_str(),
-            config.sf_warehouse.as_ref().cloned().unwrap().as_str(),
-            config.sf_database.as_ref().cloned().unwrap().as_str(),
-            config.sf_schema.as_ref().cloned().unwrap().as_str(),
-            config.sf_username.as_ref().cloned().unwrap().as_str(),
-            config.sf_role.as_ref().cloned().unwrap().as_str(),
-            sf_password.clone().as_str(),
+            &required("sf-account-identifier", &config.sf_account_identifier)?,
+            &required("sf-warehouse", &config.sf_warehouse)?,
+            &required("sf-database", &config.sf_database)?,
+            &required("sf-schema", &config.sf_schema)?,
+            &required("sf-username", &config.sf_username)?,
+            &required("sf-role", &config.sf_role)?,
+            &sf_password,
         )
     }
 
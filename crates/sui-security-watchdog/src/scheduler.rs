@@ -1,9 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::alert_sink::{Alert, AlertSink};
 use crate::metrics::WatchdogMetrics;
-use crate::pagerduty::{Body, CreateIncident, Incident, Pagerduty, Service};
-use crate::query_runner::{QueryRunner, SnowflakeQueryRunner};
+use crate::query_runner::QueryRunner;
 use crate::SecurityWatchdogConfig;
 use anyhow::anyhow;
 use chrono::{DateTime, Utc};
@@ -15,7 +15,7 @@ use std::fs::File;
 use std::io::Read;
 use std::sync::Arc;
 use tokio_cron_scheduler::{Job, JobScheduler};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 const MIST_PER_SUI: i128 = 1_000_000_000;
@@ -43,7 +43,7 @@ pub struct MetricPublishingEntry {
 }
 
 // WalletMonitoringEntry is a struct that represents the configuration of a job which monitors wallet balances.
-// It creates pagerduty incidents based on the given SQL query and cron schedule.
+// It raises alerts on every configured alert sink based on the given SQL query and cron schedule.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct WalletMonitoringEntry {
     name: String,
@@ -56,25 +56,23 @@ pub struct SchedulerService {
     query_runner: Arc<dyn QueryRunner>,
     metrics: Arc<WatchdogMetrics>,
     entries: Vec<MonitoringEntry>,
-    pagerduty: Pagerduty,
-    pd_wallet_monitoring_service_id: String,
+    alert_sinks: Arc<Vec<Arc<dyn AlertSink>>>,
 }
 
 impl SchedulerService {
     pub async fn new(
         config: &SecurityWatchdogConfig,
         registry: &Registry,
-        pd_api_key: String,
-        sf_password: String,
+        query_runner: Arc<dyn QueryRunner>,
+        alert_sinks: Vec<Arc<dyn AlertSink>>,
     ) -> anyhow::Result<Self> {
         let scheduler = JobScheduler::new().await?;
         Ok(Self {
             scheduler,
-            query_runner: Arc::new(SnowflakeQueryRunner::from_config(config, sf_password)?),
+            query_runner,
             metrics: Arc::new(WatchdogMetrics::new(registry)),
             entries: Self::from_config(config)?,
-            pagerduty: Pagerduty::new(pd_api_key.clone()),
-            pd_wallet_monitoring_service_id: config.pd_wallet_monitoring_service_id.clone(),
+            alert_sinks: Arc::new(alert_sinks),
         })
     }
 
@@ -91,13 +89,19 @@ impl SchedulerService {
                     .await?;
                 }
                 MonitoringEntry::WalletMonitoringEntry(entry) => {
+                    if self.alert_sinks.is_empty() {
+                        warn!(
+                            "No alert sinks configured, wallet monitoring job {} will only \
+                             report errors",
+                            entry.name,
+                        );
+                    }
                     self.schedule_wallet_monitoring_job(
                         entry.clone(),
                         self.scheduler.clone(),
                         self.query_runner.clone(),
-                        self.pd_wallet_monitoring_service_id.clone(),
                         self.metrics.clone(),
-                        self.pagerduty.clone(),
+                        self.alert_sinks.clone(),
                     )
                     .await?;
                 }
@@ -124,23 +128,20 @@ impl SchedulerService {
         entry: WalletMonitoringEntry,
         scheduler: JobScheduler,
         query_runner: Arc<dyn QueryRunner>,
-        pd_service_id: String,
         metrics: Arc<WatchdogMetrics>,
-        pagerduty: Pagerduty,
+        alert_sinks: Arc<Vec<Arc<dyn AlertSink>>>,
     ) -> anyhow::Result<Uuid> {
         let name = entry.name.clone();
         let cron_schedule = entry.cron_schedule.clone();
         let job = Job::new_async(cron_schedule.as_str(), move |_uuid, _lock| {
             let entry = entry.clone();
             let query_runner = query_runner.clone();
-            let pd_service_id = pd_service_id.to_string();
-            let pd = pagerduty.clone();
+            let alert_sinks = alert_sinks.clone();
             let metrics = metrics.clone();
             Box::pin(async move {
                 info!("Running wallet monitoring job: {}", entry.name);
                 if let Err(err) =
-                    Self::run_wallet_monitoring_job(&pd, &pd_service_id, &query_runner, &entry)
-                        .await
+                    Self::run_wallet_monitoring_job(&alert_sinks, &query_runner, &entry).await
                 {
                     error!("Failed to run wallet monitoring job with err: {}", err);
                     metrics
@@ -157,8 +158,7 @@ impl SchedulerService {
     }
 
     async fn run_wallet_monitoring_job(
-        pagerduty: &Pagerduty,
-        service_id: &str,
+        alert_sinks: &[Arc<dyn AlertSink>],
         query_runner: &Arc<dyn QueryRunner>,
         entry: &WalletMonitoringEntry,
     ) -> anyhow::Result<()> {
@@ -181,49 +181,50 @@ impl SchedulerService {
                     .ok_or_else(|| anyhow!("Missing lower_bound"))?,
             )
             .ok_or(anyhow!("Failed to downcast lower_bound"))?;
-            Self::create_wallet_monitoring_incident(
-                pagerduty,
+            Self::raise_wallet_monitoring_alert(
+                alert_sinks,
                 &wallet_id,
                 current_balance,
                 lower_bound,
-                service_id,
             )
             .await?;
         }
         Ok(())
     }
 
-    async fn create_wallet_monitoring_incident(
-        pagerduty: &Pagerduty,
+    async fn raise_wallet_monitoring_alert(
+        alert_sinks: &[Arc<dyn AlertSink>],
         wallet_id: &str,
         current_balance: i128,
         lower_bound: i128,
-        service_id: &str,
     ) -> anyhow::Result<()> {
-        let service = Service {
-            id: service_id.to_string(),
-            ..Default::default()
-        };
-        let incident_body = Body {
+        let alert = Alert {
+            key: wallet_id.to_string(),
+            title: format!("Wallet: {} is out of compliance", wallet_id),
             details: format!(
                 "Current balance: {} SUI, Lower bound: {} SUI",
                 current_balance / MIST_PER_SUI,
                 lower_bound / MIST_PER_SUI
             ),
-            ..Default::default()
         };
-        let incident = Incident {
-            title: format!("Wallet: {} is out of compliance", wallet_id),
-            service,
-            incident_key: wallet_id.to_string(),
-            body: incident_body,
-            ..Default::default()
-        };
-        let create_incident = CreateIncident { incident };
-        pagerduty
-            .create_incident("sadhan@mystenlabs.com", create_incident)
-            .await?;
-        Ok(())
+
+        // Deliver to every sink, even if some of them fail, so that one misbehaving sink does not
+        // silence the others.
+        let mut failures = vec![];
+        for sink in alert_sinks {
+            if let Err(err) = sink.send(&alert).await {
+                failures.push(err.to_string());
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to deliver alert for wallet {wallet_id}: {}",
+                failures.join("; ")
+            ))
+        }
     }
 
     async fn schedule_metric_publish_job(
@@ -292,6 +293,8 @@ impl SchedulerService {
     fn extract_i128(value: &Box<dyn Any + Send>) -> Option<i128> {
         if let Some(value) = value.downcast_ref::<i128>() {
             Some(*value)
+        } else if let Some(value) = value.downcast_ref::<u64>() {
+            Some(*value as i128)
         } else if let Some(value) = value.downcast_ref::<u32>() {
             Some(*value as i128)
         } else if let Some(value) = value.downcast_ref::<u16>() {
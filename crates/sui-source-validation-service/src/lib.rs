@@ -5,19 +5,23 @@ use axum::middleware::{self, Next};
 use std::collections::BTreeMap;
 use std::fmt;
 use std::net::TcpListener;
-use std::path::PathBuf;
+use std::path::{Component, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{ffi::OsString, fs, path::Path, process::Command};
 use tokio::sync::oneshot::Sender;
+use tokio::sync::Semaphore;
 
 use anyhow::{anyhow, bail};
 use axum::extract::{Query, State};
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, IntoMakeService};
+use axum::routing::{get, post, IntoMakeService};
 use axum::Extension;
 use axum::{Json, Router, Server};
-use hyper::http::{HeaderName, HeaderValue, Method};
+use hyper::http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    HeaderName, HeaderValue, Method,
+};
 use hyper::server::conn::AddrIncoming;
 use hyper::{HeaderMap, StatusCode};
 use jsonrpsee::core::client::{Subscription, SubscriptionClientT};
@@ -34,7 +38,7 @@ use move_core_types::account_address::AccountAddress;
 use move_package::{BuildConfig as MoveBuildConfig, LintFlag};
 use move_symbol_pool::Symbol;
 use sui_move::build::resolve_lock_file_path;
-use sui_move_build::{BuildConfig, SuiPackageHooks};
+use sui_move_build::{BuildConfig, CompiledPackage, SuiPackageHooks};
 use sui_sdk::rpc_types::{SuiTransactionBlockEffects, TransactionFilter};
 use sui_sdk::types::base_types::ObjectID;
 use sui_sdk::SuiClientBuilder;
@@ -59,6 +63,17 @@ pub const WS_PING_INTERVAL: Duration = Duration::from_millis(20_000);
 pub const METRICS_ROUTE: &str = "/metrics";
 pub const METRICS_HOST_PORT: &str = "0.0.0.0:9184";
 
+/// Files accepted in a submitted package, other than the `.move` files under `SOURCES_DIR`.
+pub const MANIFEST: &str = "Move.toml";
+pub const LOCK_FILE: &str = "Move.lock";
+pub const SOURCES_DIR: &str = "sources";
+/// Name of the file mapping module names to source paths, in the directory of a verified submission.
+pub const SUBMISSION_INDEX: &str = "sources.json";
+/// Environment variable holding the token that authorizes source submissions.
+pub const SUBMISSION_TOKEN_ENV: &str = "SUBMISSION_TOKEN";
+/// Maximum number of submitted packages that are built and verified at the same time.
+pub const MAX_CONCURRENT_SUBMISSIONS: usize = 2;
+
 pub fn host_port() -> String {
     match option_env!("HOST_PORT") {
         Some(v) => v.to_string(),
@@ -69,6 +84,9 @@ pub fn host_port() -> String {
 #[derive(Clone, Deserialize, Debug)]
 pub struct Config {
     pub packages: Vec<PackageSource>,
+    /// Directory where packages verified through `/api/submit` are kept. Submissions are disabled
+    /// if it is not set.
+    pub submissions_dir: Option<PathBuf>,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -149,15 +167,19 @@ pub type AddressLookup = BTreeMap<AccountAddress, SourceLookup>;
 /// Top-level lookup that maps network to sources for corresponding on-chain networks.
 pub type NetworkLookup = BTreeMap<Network, AddressLookup>;
 
-pub async fn verify_package(
-    network: &Network,
-    package_path: impl AsRef<Path>,
-) -> anyhow::Result<(Network, AddressLookup)> {
+fn network_url(network: &Network) -> &'static str {
+    match network {
+        Network::Mainnet => MAINNET_URL,
+        Network::Testnet => TESTNET_URL,
+        Network::Devnet => DEVNET_URL,
+        Network::Localnet => LOCALNET_URL,
+    }
+}
+
+fn build_package(package_path: &Path) -> anyhow::Result<CompiledPackage> {
     move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
-    let mut config = resolve_lock_file_path(
-        MoveBuildConfig::default(),
-        Some(package_path.as_ref().to_path_buf()),
-    )?;
+    let mut config =
+        resolve_lock_file_path(MoveBuildConfig::default(), Some(package_path.to_path_buf()))?;
     config.lint_flag = LintFlag::LEVEL_NONE;
     config.silence_warnings = true;
     let build_config = BuildConfig {
@@ -165,15 +187,28 @@ pub async fn verify_package(
         run_bytecode_verifier: false, /* no need to run verifier if code is on-chain */
         print_diags_to_stderr: false,
     };
-    let compiled_package = build_config.build(package_path.as_ref().to_path_buf())?;
+    build_config.build(package_path.to_path_buf())
+}
 
-    let network_url = match network {
-        Network::Mainnet => MAINNET_URL,
-        Network::Testnet => TESTNET_URL,
-        Network::Devnet => DEVNET_URL,
-        Network::Localnet => LOCALNET_URL,
-    };
-    let client = SuiClientBuilder::default().build(network_url).await?;
+/// Returns the sources of the modules of the root package.
+fn root_sources(compiled_package: &CompiledPackage) -> anyhow::Result<SourceLookup> {
+    let mut source_lookup = SourceLookup::new();
+    for v in &compiled_package.package.root_compiled_units {
+        let path = v.source_path.to_path_buf();
+        let source = Some(fs::read_to_string(path.as_path())?);
+        source_lookup.insert(v.unit.name, SourceInfo { path, source });
+    }
+    Ok(source_lookup)
+}
+
+pub async fn verify_package(
+    network: &Network,
+    package_path: impl AsRef<Path>,
+) -> anyhow::Result<(Network, AddressLookup)> {
+    let compiled_package = build_package(package_path.as_ref())?;
+    let client = SuiClientBuilder::default()
+        .build(network_url(network))
+        .await?;
     BytecodeSourceVerifier::new(client.read_api())
         .verify_package(
             &compiled_package,
@@ -190,19 +225,159 @@ pub async fn verify_package(
         .map(|id| **id)
         .map_err(|_| anyhow!("could not resolve published-at field in package manifest"))?;
     info!("verifying {} at {address}", package_path.as_ref().display());
-    for v in &compiled_package.package.root_compiled_units {
-        let path = v.source_path.to_path_buf();
-        let source = Some(fs::read_to_string(path.as_path())?);
-        let name = v.unit.name;
-        if let Some(existing) = address_map.get_mut(&address) {
-            existing.insert(name, SourceInfo { path, source });
-        } else {
-            let mut source_map = SourceLookup::new();
-            source_map.insert(name, SourceInfo { path, source });
-            address_map.insert(address, source_map);
+    address_map.insert(address, root_sources(&compiled_package)?);
+    Ok((network.clone(), address_map))
+}
+
+/// Writes the files of a submitted package to `dir`, and verifies them against the package at
+/// `address` on `network`. A package whose manifest has no `published-at` field is verified as if
+/// it was published at `address`. Verified packages are kept at `<dir>/<network>/<address>`,
+/// replacing any earlier submission for the same package, along with an index of their modules so
+/// that they can be loaded with `load_submissions`. Rejected packages are discarded.
+pub async fn submit_package(
+    dir: &Path,
+    network: &Network,
+    address: AccountAddress,
+    files: &BTreeMap<String, String>,
+) -> anyhow::Result<SourceLookup> {
+    let network_dir = dir.join(network.to_string());
+    fs::create_dir_all(&network_dir)?;
+    let bundle = tempfile::tempdir_in(&network_dir)?;
+
+    // Building resolves (and may fetch) dependencies, so it is kept off the async runtime.
+    let bundle_path = bundle.path().to_path_buf();
+    let files = files.clone();
+    let compiled_package = tokio::task::spawn_blocking(move || {
+        write_submitted_files(&bundle_path, &files)?;
+        build_package(&bundle_path)
+    })
+    .await??;
+    let source_mode = match &compiled_package.published_at {
+        Ok(published_at) if **published_at == address => SourceMode::Verify,
+        Ok(published_at) => bail!(
+            "Package manifest is published at {published_at}, but sources were submitted for {}",
+            address.to_hex_literal()
+        ),
+        Err(_) => SourceMode::VerifyAt(address),
+    };
+    let client = SuiClientBuilder::default()
+        .build(network_url(network))
+        .await?;
+    BytecodeSourceVerifier::new(client.read_api())
+        .verify_package(&compiled_package, /* verify_deps */ false, source_mode)
+        .await
+        .map_err(|e| anyhow!("Network {network}: {e}"))?;
+    info!(
+        "verified submitted sources for {} on {network}",
+        address.to_hex_literal()
+    );
+
+    // Move the package to its final location, and point the sources there.
+    let bundle_path = fs::canonicalize(bundle.path())?;
+    let dest = network_dir.join(address.to_hex_literal());
+    let mut index = BTreeMap::new();
+    let mut source_lookup = SourceLookup::new();
+    for (name, SourceInfo { path, source }) in root_sources(&compiled_package)? {
+        let relative_path = fs::canonicalize(&path)?
+            .strip_prefix(&bundle_path)
+            .map_err(|_| anyhow!("Module {name} is not in the submitted package"))?
+            .to_path_buf();
+        let path = dest.join(&relative_path);
+        index.insert(name, relative_path);
+        source_lookup.insert(name, SourceInfo { path, source });
+    }
+    let _ = fs::remove_dir_all(bundle.path().join("build"));
+    fs::write(
+        bundle.path().join(SUBMISSION_INDEX),
+        serde_json::to_string_pretty(&index)?,
+    )?;
+    if dest.exists() {
+        fs::remove_dir_all(&dest)?;
+    }
+    fs::rename(bundle.into_path(), &dest)?;
+    Ok(source_lookup)
+}
+
+/// Writes the files of a submitted package to `dir`. Only the manifest, the lock file and `.move`
+/// files under the sources directory are accepted, and dependencies on local packages are rejected
+/// as they could refer to any directory of the host.
+fn write_submitted_files(dir: &Path, files: &BTreeMap<String, String>) -> anyhow::Result<()> {
+    let Some(manifest) = files.get(MANIFEST) else {
+        bail!("Missing {MANIFEST} in submitted package");
+    };
+    let manifest: toml::Value = toml::from_str(manifest)?;
+    for section in ["dependencies", "dev-dependencies"] {
+        let Some(deps) = manifest.get(section).and_then(|deps| deps.as_table()) else {
+            continue;
+        };
+        for (name, dep) in deps {
+            if dep.get("local").is_some() {
+                bail!("Local dependency {name} is not supported in submitted packages");
+            }
         }
     }
-    Ok((network.clone(), address_map))
+
+    for (name, contents) in files {
+        let path = Path::new(name);
+        let is_source = path.starts_with(SOURCES_DIR)
+            && path.extension().and_then(|ext| ext.to_str()) == Some("move")
+            && path.components().all(|c| matches!(c, Component::Normal(_)));
+        if name != MANIFEST && name != LOCK_FILE && !is_source {
+            bail!(
+                "Unexpected file {name} in submitted package. Only {MANIFEST}, {LOCK_FILE} and \
+                 .move files under {SOURCES_DIR}/ are accepted"
+            );
+        }
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Loads the sources of the packages verified through `submit_package` into `dir`.
+pub fn load_submissions(dir: &Path) -> anyhow::Result<NetworkLookup> {
+    let mut lookup = NetworkLookup::new();
+    for network in [
+        Network::Mainnet,
+        Network::Testnet,
+        Network::Devnet,
+        Network::Localnet,
+    ] {
+        let network_dir = dir.join(network.to_string());
+        if !network_dir.is_dir() {
+            continue;
+        }
+        let mut address_map = AddressLookup::new();
+        for entry in fs::read_dir(&network_dir)? {
+            let package_dir = entry?.path();
+            // Skips packages that are still being verified
+            let Some(Ok(address)) = package_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(AccountAddress::from_hex_literal)
+            else {
+                continue;
+            };
+            let index: BTreeMap<Symbol, PathBuf> =
+                serde_json::from_str(&fs::read_to_string(package_dir.join(SUBMISSION_INDEX))?)?;
+            let mut source_lookup = SourceLookup::new();
+            for (name, relative_path) in index {
+                let path = package_dir.join(relative_path);
+                let source = Some(fs::read_to_string(&path)?);
+                source_lookup.insert(name, SourceInfo { path, source });
+            }
+            address_map.insert(address, source_lookup);
+        }
+        info!(
+            "loaded {} submitted package(s) for {network}",
+            address_map.len()
+        );
+        lookup.insert(network, address_map);
+    }
+    Ok(lookup)
 }
 
 pub fn parse_config(config_path: impl AsRef<Path>) -> anyhow::Result<Config> {
@@ -343,7 +518,12 @@ pub async fn initialize(
         }
     }
     clone_repositories(repos, dir).await?;
-    let sources = verify_packages(config, dir).await?;
+    let mut sources = verify_packages(config, dir).await?;
+    if let Some(submissions_dir) = &config.submissions_dir {
+        for (network, address_map) in load_submissions(submissions_dir)? {
+            sources.entry(network).or_default().extend(address_map);
+        }
+    }
     let sources_list = sources_list(&sources).await;
     Ok((sources, sources_list))
 }
@@ -353,23 +533,28 @@ pub async fn sources_list(sources: &NetworkLookup) -> NetworkLookup {
     for (network, addresses) in sources {
         let mut address_map = AddressLookup::new();
         for (address, symbols) in addresses {
-            let mut symbol_map = SourceLookup::new();
-            for (symbol, source_info) in symbols {
-                symbol_map.insert(
-                    *symbol,
-                    SourceInfo {
-                        path: source_info.path.file_name().unwrap().into(),
-                        source: None,
-                    },
-                );
-            }
-            address_map.insert(*address, symbol_map);
+            address_map.insert(*address, listed_sources(symbols));
         }
         sources_list.insert(network.clone(), address_map);
     }
     sources_list
 }
 
+/// Returns the sources of a package as listed by `/api/list`, i.e., file names without contents.
+fn listed_sources(symbols: &SourceLookup) -> SourceLookup {
+    let mut symbol_map = SourceLookup::new();
+    for (symbol, source_info) in symbols {
+        symbol_map.insert(
+            *symbol,
+            SourceInfo {
+                path: source_info.path.file_name().unwrap().into(),
+                source: None,
+            },
+        );
+    }
+    symbol_map
+}
+
 pub async fn verify_packages(config: &Config, dir: &Path) -> anyhow::Result<NetworkLookup> {
     let mut tasks = vec![];
     for p in &config.packages {
@@ -515,25 +700,57 @@ pub struct AppState {
     pub sources: NetworkLookup,
     pub metrics: Option<SourceServiceMetrics>,
     pub sources_list: NetworkLookup,
+    /// Set if source submissions through `/api/submit` are enabled.
+    pub submissions: Option<Submissions>,
+}
+
+/// Settings of source submissions through `/api/submit`.
+pub struct Submissions {
+    /// Directory where verified packages are kept
+    pub dir: PathBuf,
+    /// Token that submitters must present in the `Authorization` header, as a bearer token
+    pub token: String,
+    /// Bounds the number of submissions built at the same time. Submissions beyond the bound are
+    /// rejected rather than queued.
+    pub builds: Arc<Semaphore>,
+}
+
+impl Submissions {
+    pub fn new(dir: PathBuf, token: String, max_concurrent_builds: usize) -> Self {
+        Self {
+            dir,
+            token,
+            builds: Arc::new(Semaphore::new(max_concurrent_builds)),
+        }
+    }
 }
 
 pub fn serve(
     app_state: Arc<RwLock<AppState>>,
+) -> anyhow::Result<Server<AddrIncoming, IntoMakeService<Router>>> {
+    serve_on(app_state, TcpListener::bind(host_port())?)
+}
+
+/// Serves on a listener that is already bound, e.g. to a port picked by the OS.
+pub fn serve_on(
+    app_state: Arc<RwLock<AppState>>,
+    listener: TcpListener,
 ) -> anyhow::Result<Server<AddrIncoming, IntoMakeService<Router>>> {
     let app = Router::new()
         .route("/api", get(api_route))
         .route("/api/list", get(list_route))
+        .route("/api/submit", post(submit_route))
         .layer(
             ServiceBuilder::new()
                 .layer(
                     tower_http::cors::CorsLayer::new()
-                        .allow_methods([Method::GET])
+                        .allow_methods([Method::GET, Method::POST])
+                        .allow_headers([AUTHORIZATION, CONTENT_TYPE])
                         .allow_origin(tower_http::cors::Any),
                 )
                 .layer(middleware::from_fn(check_version_header)),
         )
         .with_state(app_state);
-    let listener = TcpListener::bind(host_port())?;
     Ok(Server::from_tcp(listener)?.serve(app.into_make_service()))
 }
 
@@ -555,6 +772,23 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct SubmitRequest {
+    #[serde(default)]
+    pub network: Network,
+    /// ID of the on-chain package the sources are submitted for
+    pub address: String,
+    /// Contents of the files of the package, by path relative to the package root: `Move.toml`,
+    /// optionally `Move.lock`, and the `.move` files under `sources/`.
+    pub files: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmitResponse {
+    /// Names of the modules whose sources were verified
+    pub modules: Vec<String>,
+}
+
 async fn api_route(
     State(app_state): State<Arc<RwLock<AppState>>>,
     Query(Request {
@@ -607,6 +841,85 @@ async fn api_route(
     }
 }
 
+async fn submit_route(
+    State(app_state): State<Arc<RwLock<AppState>>>,
+    headers: HeaderMap,
+    Json(SubmitRequest {
+        network,
+        address,
+        files,
+    }): Json<SubmitRequest>,
+) -> impl IntoResponse {
+    debug!("submission network={network}&address={address}");
+    let submissions = app_state
+        .read()
+        .unwrap()
+        .submissions
+        .as_ref()
+        .map(|s| (s.dir.clone(), s.token.clone(), s.builds.clone()));
+    let Some((submissions_dir, token, builds)) = submissions else {
+        let error = "Source submissions are not enabled on this server".to_string();
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse { error }).into_response(),
+        );
+    };
+    let bearer = headers
+        .get(AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "));
+    if bearer != Some(token.as_str()) {
+        let error = "Missing or invalid submission token".to_string();
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(ErrorResponse { error }).into_response(),
+        );
+    }
+    let Ok(address) = AccountAddress::from_hex_literal(&address) else {
+        let error = format!("Invalid hex address {address}");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse { error }).into_response(),
+        );
+    };
+    let Ok(_permit) = builds.try_acquire_owned() else {
+        let error = "Too many submissions are being verified, try again later".to_string();
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse { error }).into_response(),
+        );
+    };
+
+    let source_lookup = match submit_package(&submissions_dir, &network, address, &files).await {
+        Ok(source_lookup) => source_lookup,
+        Err(e) => {
+            let error = format!("Failed to verify submitted sources: {e}");
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse { error }).into_response(),
+            );
+        }
+    };
+    let modules = source_lookup.keys().map(|m| m.to_string()).collect();
+    {
+        let mut app_state = app_state.write().unwrap();
+        app_state
+            .sources_list
+            .entry(network.clone())
+            .or_default()
+            .insert(address, listed_sources(&source_lookup));
+        app_state
+            .sources
+            .entry(network)
+            .or_default()
+            .insert(address, source_lookup);
+    }
+    (
+        StatusCode::OK,
+        Json(SubmitResponse { modules }).into_response(),
+    )
+}
+
 async fn check_version_header<B>(
     headers: HeaderMap,
     req: hyper::Request<B>,
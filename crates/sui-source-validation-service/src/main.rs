@@ -12,7 +12,7 @@ use telemetry_subscribers::TelemetryConfig;
 use sui_source_validation_service::{
     host_port, initialize, parse_config, serve, start_prometheus_server, watch_for_upgrades,
     AppState, DirectorySource, Network, PackageSource, RepositorySource, SourceServiceMetrics,
-    METRICS_HOST_PORT,
+    Submissions, MAX_CONCURRENT_SUBMISSIONS, METRICS_HOST_PORT, SUBMISSION_TOKEN_ENV,
 };
 
 #[derive(Parser, Debug)]
@@ -28,6 +28,19 @@ pub async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let _logging_guard = TelemetryConfig::new().with_env().init();
     let package_config = parse_config(args.config_path)?;
+    let submissions = match &package_config.submissions_dir {
+        Some(dir) => {
+            let token = std::env::var(SUBMISSION_TOKEN_ENV).map_err(|_| {
+                anyhow::anyhow!("{SUBMISSION_TOKEN_ENV} must be set to enable source submissions")
+            })?;
+            Some(Submissions::new(
+                dir.clone(),
+                token,
+                MAX_CONCURRENT_SUBMISSIONS,
+            ))
+        }
+        None => None,
+    };
     let tmp_dir = tempfile::tempdir()?;
     let start = tokio::time::Instant::now();
     let (sources, sources_list) = initialize(&package_config, tmp_dir.path()).await?;
@@ -42,6 +55,7 @@ pub async fn main() -> anyhow::Result<()> {
         sources,
         metrics: Some(metrics),
         sources_list,
+        submissions,
     }));
     let mut threads = vec![];
     let networks_to_watch = vec![
@@ -3,8 +3,10 @@
 
 use expect_test::expect;
 use reqwest::Client;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Read;
+use std::net::TcpListener;
 use std::os::unix::fs::FileExt;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
@@ -23,10 +25,12 @@ use tokio::sync::oneshot;
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::Symbol;
 use sui_source_validation_service::{
-    host_port, initialize, serve, start_prometheus_server, verify_packages, watch_for_upgrades,
-    AddressLookup, AppState, Branch, CloneCommand, Config, DirectorySource, ErrorResponse, Network,
-    NetworkLookup, Package, PackageSource, RepositorySource, SourceInfo, SourceLookup,
-    SourceResponse, SourceServiceMetrics, METRICS_HOST_PORT, SUI_SOURCE_VALIDATION_VERSION_HEADER,
+    host_port, initialize, load_submissions, serve, serve_on, start_prometheus_server,
+    submit_package, verify_packages, watch_for_upgrades, AddressLookup, AppState, Branch,
+    CloneCommand, Config, DirectorySource, ErrorResponse, Network, NetworkLookup, Package,
+    PackageSource, RepositorySource, SourceInfo, SourceLookup, SourceResponse,
+    SourceServiceMetrics, Submissions, SubmitRequest, SubmitResponse, METRICS_HOST_PORT,
+    SUI_SOURCE_VALIDATION_VERSION_HEADER,
 };
 use test_cluster::TestClusterBuilder;
 
@@ -86,6 +90,7 @@ async fn test_end_to_end() -> anyhow::Result<()> {
             }],
             network: Some(Network::Localnet),
         })],
+        submissions_dir: None,
     };
     // Start watching for upgrades.
     let mut sources = NetworkLookup::new();
@@ -97,6 +102,7 @@ async fn test_end_to_end() -> anyhow::Result<()> {
         sources,
         metrics: None,
         sources_list,
+        submissions: None,
     }));
     let app_state_ref = app_state.clone();
     let (tx, rx) = oneshot::channel();
@@ -126,6 +132,107 @@ async fn test_end_to_end() -> anyhow::Result<()> {
     let app_state_ref = app_state_ref.read().unwrap();
     assert!(app_state_ref.sources.is_empty());
 
+    ///////////////////////////
+    // Test submit_package
+    //////////////////////////
+    let submissions_dir = tempfile::tempdir()?;
+    let files = BTreeMap::from([
+        (
+            "Move.toml".to_string(),
+            fs::read_to_string(package_path.join("Move.toml"))?,
+        ),
+        (
+            "sources/custom.move".to_string(),
+            fs::read_to_string(package_path.join("sources/custom.move"))?,
+        ),
+    ]);
+    let source_lookup = submit_package(
+        submissions_dir.path(),
+        &Network::Localnet,
+        *package_id,
+        &files,
+    )
+    .await?;
+    let modules: Vec<_> = source_lookup.keys().map(|m| m.as_str()).collect();
+    assert_eq!(modules, vec!["foo"]);
+    // Verified sources are persisted, and loaded back on restart.
+    let submissions = load_submissions(submissions_dir.path())?;
+    let source = submissions
+        .get(&Network::Localnet)
+        .and_then(|n| n.get(&*package_id))
+        .and_then(|a| a.get(&Symbol::from("foo")))
+        .and_then(|s| s.source.clone());
+    assert_eq!(source, Some(files["sources/custom.move"].clone()));
+
+    ///////////////////////////
+    // Test /api/submit
+    //////////////////////////
+    let submissions_dir = tempfile::tempdir()?;
+    let mut sources = NetworkLookup::new();
+    sources.insert(Network::Localnet, AddressLookup::new());
+    let app_state = Arc::new(RwLock::new(AppState {
+        sources,
+        metrics: None,
+        sources_list: NetworkLookup::new(),
+        submissions: Some(Submissions::new(
+            submissions_dir.path().to_path_buf(),
+            "token".to_string(),
+            1,
+        )),
+    }));
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let server_address = listener.local_addr()?;
+    tokio::spawn(serve_on(app_state, listener).expect("Cannot start service."));
+    let http_client = Client::new();
+
+    // sources that do not match the on-chain package are rejected
+    let mut mismatched_files = files.clone();
+    mismatched_files.insert(
+        "sources/custom.move".to_string(),
+        files["sources/custom.move"].replace("        0\n", "        1\n"),
+    );
+    let response = http_client
+        .post(format!("http://{server_address}/api/submit"))
+        .bearer_auth("token")
+        .json(&SubmitRequest {
+            network: Network::Localnet,
+            address: package_id.to_string(),
+            files: mismatched_files,
+        })
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let error = response.json::<ErrorResponse>().await?.error;
+    assert!(
+        error.starts_with("Failed to verify submitted sources"),
+        "{error}"
+    );
+
+    let response = http_client
+        .post(format!("http://{server_address}/api/submit"))
+        .bearer_auth("token")
+        .json(&SubmitRequest {
+            network: Network::Localnet,
+            address: package_id.to_string(),
+            files: files.clone(),
+        })
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let SubmitResponse { modules } = response.json().await?;
+    assert_eq!(modules, vec!["foo".to_string()]);
+
+    // verified sources are served right away
+    let json = http_client
+        .get(format!(
+            "http://{server_address}/api?address={package_id}&module=foo&network=localnet"
+        ))
+        .send()
+        .await?
+        .json::<SourceResponse>()
+        .await?;
+    assert_eq!(json.source, files["sources/custom.move"]);
+
     ///////////////////////////
     // Test verify_packages
     //////////////////////////
@@ -141,6 +248,7 @@ async fn test_end_to_end() -> anyhow::Result<()> {
             }],
             network: Some(Network::Localnet),
         })],
+        submissions_dir: None,
     };
 
     let fixtures = tempfile::tempdir()?;
@@ -208,6 +316,7 @@ async fn run_upgrade(
         build_config,
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
+        skip_compatibility_check: false,
         opts: OptsWithGas::for_testing(Some(gas_obj_id), rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
     }
     .execute(context)
@@ -260,7 +369,10 @@ fn copy_with_published_at_manifest(
 
 #[tokio::test]
 async fn test_api_route() -> anyhow::Result<()> {
-    let config = Config { packages: vec![] };
+    let config = Config {
+        packages: vec![],
+        submissions_dir: None,
+    };
     let tmp_dir = tempfile::tempdir()?;
     initialize(&config, tmp_dir.path()).await?;
 
@@ -297,6 +409,7 @@ async fn test_api_route() -> anyhow::Result<()> {
         sources,
         metrics: None,
         sources_list,
+        submissions: None,
     }));
     tokio::spawn(serve(app_state).expect("Cannot start service."));
 
@@ -348,6 +461,99 @@ async fn test_api_route() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_submit_rejects_unexpected_files() -> anyhow::Result<()> {
+    let submissions_dir = tempfile::tempdir()?;
+    let manifest = fs::read_to_string(PathBuf::from(TEST_FIXTURES_DIR).join("custom/Move.toml"))?;
+    let files = BTreeMap::from([
+        ("Move.toml".to_string(), manifest.clone()),
+        ("sources/../../escape.move".to_string(), "".to_string()),
+    ]);
+    let error = submit_package(
+        submissions_dir.path(),
+        &Network::Localnet,
+        AccountAddress::from_hex_literal("0x42")?,
+        &files,
+    )
+    .await
+    .unwrap_err();
+    let expected = expect!["Unexpected file sources/../../escape.move in submitted package. Only Move.toml, Move.lock and .move files under sources/ are accepted"];
+    expected.assert_eq(&error.to_string());
+
+    let files = BTreeMap::from([(
+        "Move.toml".to_string(),
+        format!("{manifest}\n[dependencies]\nLocal = {{ local = \"/home/user\" }}\n"),
+    )]);
+    let error = submit_package(
+        submissions_dir.path(),
+        &Network::Localnet,
+        AccountAddress::from_hex_literal("0x42")?,
+        &files,
+    )
+    .await
+    .unwrap_err();
+    let expected = expect!["Local dependency Local is not supported in submitted packages"];
+    expected.assert_eq(&error.to_string());
+
+    // Rejected submissions are not kept.
+    assert!(load_submissions(submissions_dir.path())?[&Network::Localnet].is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submit_route_rejections() -> anyhow::Result<()> {
+    let submissions_dir = tempfile::tempdir()?;
+    let app_state = Arc::new(RwLock::new(AppState {
+        sources: NetworkLookup::new(),
+        metrics: None,
+        sources_list: NetworkLookup::new(),
+        // no submission can be built, so that valid requests are turned away
+        submissions: Some(Submissions::new(
+            submissions_dir.path().to_path_buf(),
+            "token".to_string(),
+            0,
+        )),
+    }));
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let server_address = listener.local_addr()?;
+    tokio::spawn(serve_on(app_state, listener).expect("Cannot start service."));
+
+    let client = Client::new();
+    let submit = |token: Option<&str>, address: &str| {
+        let request = client
+            .post(format!("http://{server_address}/api/submit"))
+            .json(&SubmitRequest {
+                network: Network::Localnet,
+                address: address.to_string(),
+                files: BTreeMap::new(),
+            });
+        let request = match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        request.send()
+    };
+
+    let response = submit(None, "0x42").await?;
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let response = submit(Some("bogus"), "0x42").await?;
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let expected = expect!["Missing or invalid submission token"];
+    expected.assert_eq(&response.json::<ErrorResponse>().await?.error);
+
+    let response = submit(Some("token"), "not an address").await?;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let response = submit(Some("token"), "0x42").await?;
+    assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    let expected = expect!["Too many submissions are being verified, try again later"];
+    expected.assert_eq(&response.json::<ErrorResponse>().await?.error);
+
+    // Nothing was kept.
+    assert!(fs::read_dir(submissions_dir.path())?.next().is_none());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_metrics_route() -> anyhow::Result<()> {
     // Start metrics server
@@ -461,6 +667,7 @@ paths = [
                     },
                 ),
             ],
+            submissions_dir: None,
         }"#]];
     expect.assert_eq(&format!("{:#?}", config));
     Ok(())
@@ -18,7 +18,10 @@ use std::fs::File;
 use std::io::{self, Seek};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Debug,
+};
 use sui_move_build::CompiledPackage;
 use sui_types::error::SuiObjectResponseError;
 use tar::Archive;
@@ -200,6 +203,9 @@ impl<'a> BytecodeSourceVerifier<'a> {
     /// at the address specified on the Sui network we are publishing to.  If `verify_deps` is true,
     /// the dependencies are verified.  If `root_on_chain_address` is specified, the root is
     /// verified against a package at `root_on_chain_address`.
+    ///
+    /// Dependencies that were resolved from on-chain dependencies are not verified: their sources
+    /// are interface stubs generated from the on-chain bytecode, so they cannot match it.
     pub async fn verify_package(
         &self,
         compiled_package: &CompiledPackage,
@@ -219,17 +225,24 @@ impl<'a> BytecodeSourceVerifier<'a> {
             }
         };
 
+        let skipped_deps = &compiled_package.dependency_ids.on_chain;
         if verify_deps {
             on_chain_pkgs.extend(
                 compiled_package
                     .dependency_ids
                     .published
-                    .values()
-                    .map(|id| **id),
+                    .iter()
+                    .filter(|(name, _)| !skipped_deps.contains(name))
+                    .map(|(_, id)| **id),
             );
         }
 
-        let local_modules = local_modules(&compiled_package.package, verify_deps, source_mode)?;
+        let local_modules = local_modules(
+            &compiled_package.package,
+            verify_deps,
+            skipped_deps,
+            source_mode,
+        )?;
         let mut on_chain_modules = self.on_chain_modules(on_chain_pkgs.into_iter()).await?;
 
         let mut errors = Vec::new();
@@ -358,6 +371,7 @@ fn substitute_root_address(
 fn local_modules(
     compiled_package: &MoveCompiledPackage,
     include_deps: bool,
+    skipped_deps: &BTreeSet<Symbol>,
     source_mode: SourceMode,
 ) -> Result<LocalModules, SourceVerificationError> {
     let mut map = LocalModules::new();
@@ -374,7 +388,7 @@ fn local_modules(
             let m = &local_unit.unit;
             let module = m.name;
             let address = m.address.into_inner();
-            if address == AccountAddress::ZERO {
+            if address == AccountAddress::ZERO || skipped_deps.contains(&package) {
                 continue;
             }
 
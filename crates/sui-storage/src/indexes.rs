@@ -14,6 +14,7 @@ use itertools::Itertools;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
 use prometheus::{register_int_counter_with_registry, IntCounter, Registry};
+use rocksdb::CompactionDecision;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::BTreeMap;
 use sui_types::execution::DynamicallyLoadedObjectMetadata;
@@ -37,7 +38,8 @@ use sui_types::parse_sui_struct_tag;
 use tokio::task::spawn_blocking;
 use tracing::{debug, trace};
 use typed_store::rocks::{
-    default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf,
+    default_db_options, read_size_from_env, DBBatch, DBMap, DBMapTableConfigMap, DBOptions,
+    MetricConf,
 };
 use typed_store::traits::Map;
 use typed_store::traits::{TableSummary, TypedStoreDebug};
@@ -214,6 +216,10 @@ pub struct IndexStoreTables {
     event_by_sender: DBMap<(SuiAddress, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
     event_by_time: DBMap<(u64, EventId), EventIndex>,
+
+    /// The lowest transaction sequence number whose index entries are retained. Entries below it
+    /// have been pruned, or are waiting to be dropped by compaction.
+    pruning_watermark: DBMap<(), TxSequenceNumber>,
 }
 
 impl IndexStoreTables {
@@ -228,6 +234,7 @@ impl IndexStoreTables {
 
 pub struct IndexStore {
     next_sequence_number: AtomicU64,
+    lowest_retained_sequence_number: Arc<AtomicU64>,
     tables: IndexStoreTables,
     caches: IndexStoreCaches,
     metrics: Arc<IndexStoreMetrics>,
@@ -276,10 +283,126 @@ fn coin_index_table_default_config() -> DBOptions {
         )
 }
 
+/// Drop entries of a pruned transaction when they are compacted, for tables whose keys end with
+/// the transaction sequence number, followed by `trailing_bytes` bytes. These entries are keyed by
+/// something else first, so they can't be deleted by range when the transactions are pruned.
+fn with_pruning_compaction_filter(
+    mut options: DBOptions,
+    lowest_retained_sequence_number: &Arc<AtomicU64>,
+    trailing_bytes: usize,
+) -> DBOptions {
+    let lowest_retained = lowest_retained_sequence_number.clone();
+    options.options.set_compaction_filter(
+        "index_pruning_filter",
+        move |_level: u32, key: &[u8], _value: &[u8]| {
+            let end = key.len().saturating_sub(trailing_bytes);
+            let sequence_number = end
+                .checked_sub(8)
+                .and_then(|start| key[start..end].try_into().ok())
+                .map(TxSequenceNumber::from_be_bytes);
+            match sequence_number {
+                Some(seq) if seq < lowest_retained.load(Ordering::Relaxed) => {
+                    CompactionDecision::Remove
+                }
+                _ => CompactionDecision::Keep,
+            }
+        },
+    );
+    options
+}
+
+fn index_tables_config(lowest_retained_sequence_number: &Arc<AtomicU64>) -> DBMapTableConfigMap {
+    // Keys are serialized with fixed-width big-endian integers, and event ids are a transaction
+    // sequence number followed by the (8 byte) index of the event in the transaction.
+    let tx_keyed =
+        |options| with_pruning_compaction_filter(options, lowest_retained_sequence_number, 0);
+    let event_keyed =
+        |options| with_pruning_compaction_filter(options, lowest_retained_sequence_number, 8);
+    DBMapTableConfigMap::new(BTreeMap::from([
+        (
+            "transactions_from_addr".to_string(),
+            tx_keyed(transactions_from_addr_table_default_config()),
+        ),
+        (
+            "transactions_to_addr".to_string(),
+            tx_keyed(transactions_to_addr_table_default_config()),
+        ),
+        (
+            "transactions_by_input_object_id".to_string(),
+            tx_keyed(transactions_by_input_object_id_table_default_config()),
+        ),
+        (
+            "transactions_by_mutated_object_id".to_string(),
+            tx_keyed(transactions_by_mutated_object_id_table_default_config()),
+        ),
+        (
+            "transactions_by_move_function".to_string(),
+            tx_keyed(transactions_by_move_function_table_default_config()),
+        ),
+        ("timestamps".to_string(), timestamps_table_default_config()),
+        (
+            "transaction_order".to_string(),
+            transactions_order_table_default_config(),
+        ),
+        (
+            "transactions_seq".to_string(),
+            transactions_seq_table_default_config(),
+        ),
+        (
+            "owner_index".to_string(),
+            owner_index_table_default_config(),
+        ),
+        ("coin_index".to_string(), coin_index_table_default_config()),
+        (
+            "dynamic_field_index".to_string(),
+            dynamic_field_index_table_default_config(),
+        ),
+        (
+            "loaded_child_object_versions".to_string(),
+            default_db_options(),
+        ),
+        ("event_order".to_string(), index_table_default_config()),
+        (
+            "event_by_move_module".to_string(),
+            event_keyed(index_table_default_config()),
+        ),
+        (
+            "event_by_move_event".to_string(),
+            event_keyed(index_table_default_config()),
+        ),
+        (
+            "event_by_event_module".to_string(),
+            event_keyed(index_table_default_config()),
+        ),
+        (
+            "event_by_sender".to_string(),
+            event_keyed(index_table_default_config()),
+        ),
+        (
+            "event_by_time".to_string(),
+            event_keyed(index_table_default_config()),
+        ),
+        ("pruning_watermark".to_string(), default_db_options()),
+    ]))
+}
+
 impl IndexStore {
     pub fn new(path: PathBuf, registry: &Registry, max_type_length: Option<u64>) -> Self {
-        let tables =
-            IndexStoreTables::open_tables_read_write(path, MetricConf::new("index"), None, None);
+        let lowest_retained_sequence_number = Arc::new(AtomicU64::new(0));
+        let tables = IndexStoreTables::open_tables_read_write(
+            path,
+            MetricConf::new("index"),
+            None,
+            Some(index_tables_config(&lowest_retained_sequence_number)),
+        );
+        lowest_retained_sequence_number.store(
+            tables
+                .pruning_watermark
+                .get(&())
+                .expect("Failed to read index pruning watermark")
+                .unwrap_or_default(),
+            Ordering::SeqCst,
+        );
         let metrics = IndexStoreMetrics::new(registry);
         let caches = IndexStoreCaches {
             per_coin_type_balance: ShardedLruCache::new(1_000_000, 1000),
@@ -298,6 +421,7 @@ impl IndexStore {
         Self {
             tables,
             next_sequence_number,
+            lowest_retained_sequence_number,
             caches,
             metrics: Arc::new(metrics),
             max_type_length: max_type_length.unwrap_or(128),
@@ -651,6 +775,68 @@ impl IndexStore {
         self.next_sequence_number.load(Ordering::SeqCst) + 1
     }
 
+    /// The lowest transaction sequence number that hasn't been pruned from the indexes.
+    pub fn lowest_retained_sequence_number(&self) -> TxSequenceNumber {
+        self.lowest_retained_sequence_number.load(Ordering::SeqCst)
+    }
+
+    /// Prune the index entries of every transaction with a sequence number below `cutoff`, and of
+    /// their events.
+    ///
+    /// Tables keyed by sequence number or by transaction digest are pruned immediately. The other
+    /// tables are keyed by address, object, function, or event type first, so their pruned entries
+    /// are dropped as they get compacted, and skipped by reads until then.
+    pub fn prune(&self, cutoff: TxSequenceNumber) -> SuiResult {
+        let lowest_retained = self.lowest_retained_sequence_number();
+        if cutoff <= lowest_retained {
+            return Ok(());
+        }
+
+        let digests: Vec<_> = self
+            .tables
+            .transaction_order
+            .unbounded_iter()
+            .skip_to(&lowest_retained)?
+            .take_while(|(seq, _)| *seq < cutoff)
+            .map(|(_, digest)| digest)
+            .collect();
+
+        let mut batch = self.tables.transaction_order.batch();
+        batch.schedule_delete_range(&self.tables.transaction_order, &lowest_retained, &cutoff)?;
+        batch.schedule_delete_range(
+            &self.tables.event_order,
+            &(lowest_retained, 0),
+            &(cutoff, 0),
+        )?;
+        batch.delete_batch(&self.tables.transactions_seq, digests.iter())?;
+        batch.delete_batch(&self.tables.loaded_child_object_versions, digests.iter())?;
+        batch.delete_batch(&self.tables.timestamps, digests.iter())?;
+        batch.insert_batch(&self.tables.pruning_watermark, [((), cutoff)])?;
+        batch.write()?;
+
+        self.lowest_retained_sequence_number
+            .store(cutoff, Ordering::SeqCst);
+        debug!(
+            "Pruned indexes of {} transactions, up to sequence number {cutoff}",
+            digests.len()
+        );
+        Ok(())
+    }
+
+    /// The error for a cursor that isn't indexed: if the indexes have been pruned, it may point to
+    /// a transaction that exists, but whose index entries are gone.
+    pub fn cursor_not_found(&self, cursor: TransactionDigest) -> SuiError {
+        match self.lowest_retained_sequence_number() {
+            0 => SuiError::TransactionNotFound { digest: cursor },
+            lowest_available_tx_seq => SuiError::UserInputError {
+                error: UserInputError::IndexCursorPruned {
+                    cursor,
+                    lowest_available_tx_seq,
+                },
+            },
+        }
+    }
+
     pub fn get_transactions(
         &self,
         filter: Option<TransactionFilter>,
@@ -662,7 +848,7 @@ impl IndexStore {
         let cursor = if let Some(cursor) = cursor {
             Some(
                 self.get_transaction_seq(&cursor)?
-                    .ok_or(SuiError::TransactionNotFound { digest: cursor })?,
+                    .ok_or_else(|| self.cursor_not_found(cursor))?,
             )
         } else {
             None
@@ -693,6 +879,7 @@ impl IndexStore {
                 error: UserInputError::Unsupported(format!("{:?}", filter)),
             }),
             None => {
+                let lowest_retained = self.lowest_retained_sequence_number();
                 let iter = self.tables.transaction_order.unbounded_iter();
 
                 if reverse {
@@ -700,6 +887,7 @@ impl IndexStore {
                         .skip_prior_to(&cursor.unwrap_or(TxSequenceNumber::MAX))?
                         .reverse()
                         .skip(usize::from(cursor.is_some()))
+                        .take_while(|(seq, _)| *seq >= lowest_retained)
                         .map(|(_, digest)| digest);
                     if let Some(limit) = limit {
                         Ok(iter.take(limit).collect())
@@ -708,7 +896,7 @@ impl IndexStore {
                     }
                 } else {
                     let iter = iter
-                        .skip_to(&cursor.unwrap_or(TxSequenceNumber::MIN))?
+                        .skip_to(&cursor.unwrap_or(lowest_retained))?
                         .skip(usize::from(cursor.is_some()))
                         .map(|(_, digest)| digest);
                     if let Some(limit) = limit {
@@ -735,6 +923,7 @@ impl IndexStore {
     fn get_transactions_from_index<KeyT: Clone + Serialize + DeserializeOwned + PartialEq>(
         index: &DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
         key: KeyT,
+        lowest_retained: TxSequenceNumber,
         cursor: Option<TxSequenceNumber>,
         limit: Option<usize>,
         reverse: bool,
@@ -747,6 +936,8 @@ impl IndexStore {
                 // skip one more if exclusive cursor is Some
                 .skip(usize::from(cursor.is_some()))
                 .take_while(|((id, _), _)| *id == key)
+                // entries of pruned transactions remain until they are compacted away
+                .filter(|((_, seq), _)| *seq >= lowest_retained)
                 .map(|(_, digest)| digest);
             if let Some(limit) = limit {
                 iter.take(limit).collect()
@@ -760,6 +951,8 @@ impl IndexStore {
                 // skip one more if exclusive cursor is Some
                 .skip(usize::from(cursor.is_some()))
                 .take_while(|((id, _), _)| *id == key)
+                // entries of pruned transactions remain until they are compacted away
+                .filter(|((_, seq), _)| *seq >= lowest_retained)
                 .map(|(_, digest)| digest);
             if let Some(limit) = limit {
                 iter.take(limit).collect()
@@ -779,6 +972,7 @@ impl IndexStore {
         Self::get_transactions_from_index(
             &self.tables.transactions_by_input_object_id,
             input_object,
+            self.lowest_retained_sequence_number(),
             cursor,
             limit,
             reverse,
@@ -795,6 +989,7 @@ impl IndexStore {
         Self::get_transactions_from_index(
             &self.tables.transactions_by_mutated_object_id,
             mutated_object,
+            self.lowest_retained_sequence_number(),
             cursor,
             limit,
             reverse,
@@ -811,6 +1006,7 @@ impl IndexStore {
         Self::get_transactions_from_index(
             &self.tables.transactions_from_addr,
             addr,
+            self.lowest_retained_sequence_number(),
             cursor,
             limit,
             reverse,
@@ -863,6 +1059,7 @@ impl IndexStore {
                 .unwrap_or(if reverse { max_string } else { "".to_string() });
 
         let key = (package, module_val, function_val, cursor_val);
+        let lowest_retained = self.lowest_retained_sequence_number();
         let iter = self.tables.transactions_by_move_function.unbounded_iter();
         Ok(if reverse {
             let iter = iter
@@ -875,6 +1072,7 @@ impl IndexStore {
                         && module.as_ref().map(|x| x == m).unwrap_or(true)
                         && function.as_ref().map(|x| x == f).unwrap_or(true)
                 })
+                .filter(|((_, _, _, seq), _)| *seq >= lowest_retained)
                 .map(|(_, digest)| digest);
             if let Some(limit) = limit {
                 iter.take(limit).collect()
@@ -891,6 +1089,7 @@ impl IndexStore {
                         && module.as_ref().map(|x| x == m).unwrap_or(true)
                         && function.as_ref().map(|x| x == f).unwrap_or(true)
                 })
+                .filter(|((_, _, _, seq), _)| *seq >= lowest_retained)
                 .map(|(_, digest)| digest);
             if let Some(limit) = limit {
                 iter.take(limit).collect()
@@ -910,6 +1109,7 @@ impl IndexStore {
         Self::get_transactions_from_index(
             &self.tables.transactions_to_addr,
             addr,
+            self.lowest_retained_sequence_number(),
             cursor,
             limit,
             reverse,
@@ -930,12 +1130,14 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        let lowest_retained = self.lowest_retained_sequence_number();
         Ok(if descending {
             self.tables
                 .event_order
                 .unbounded_iter()
                 .skip_prior_to(&(tx_seq, event_seq))?
                 .reverse()
+                .take_while(|((seq, _), _)| *seq >= lowest_retained)
                 .take(limit)
                 .map(|((_, event_seq), (digest, tx_digest, time))| {
                     (digest, tx_digest, event_seq, time)
@@ -945,7 +1147,7 @@ impl IndexStore {
             self.tables
                 .event_order
                 .unbounded_iter()
-                .skip_to(&(tx_seq, event_seq))?
+                .skip_to(&(max(tx_seq, lowest_retained), event_seq))?
                 .take(limit)
                 .map(|((_, event_seq), (digest, tx_digest, time))| {
                     (digest, tx_digest, event_seq, time)
@@ -994,6 +1196,7 @@ impl IndexStore {
     fn get_event_from_index<KeyT: Clone + PartialEq + Serialize + DeserializeOwned>(
         index: &DBMap<(KeyT, EventId), (TransactionEventsDigest, TransactionDigest, u64)>,
         key: &KeyT,
+        lowest_retained: TxSequenceNumber,
         tx_seq: TxSequenceNumber,
         event_seq: usize,
         limit: usize,
@@ -1005,6 +1208,8 @@ impl IndexStore {
                 .skip_prior_to(&(key.clone(), (tx_seq, event_seq)))?
                 .reverse()
                 .take_while(|((m, _), _)| m == key)
+                // entries of pruned transactions remain until they are compacted away
+                .filter(|((_, (seq, _)), _)| *seq >= lowest_retained)
                 .take(limit)
                 .map(|((_, (_, event_seq)), (digest, tx_digest, time))| {
                     (digest, tx_digest, event_seq, time)
@@ -1015,6 +1220,8 @@ impl IndexStore {
                 .unbounded_iter()
                 .skip_to(&(key.clone(), (tx_seq, event_seq)))?
                 .take_while(|((m, _), _)| m == key)
+                // entries of pruned transactions remain until they are compacted away
+                .filter(|((_, (seq, _)), _)| *seq >= lowest_retained)
                 .take(limit)
                 .map(|((_, (_, event_seq)), (digest, tx_digest, time))| {
                     (digest, tx_digest, event_seq, time)
@@ -1034,6 +1241,7 @@ impl IndexStore {
         Self::get_event_from_index(
             &self.tables.event_by_move_module,
             module,
+            self.lowest_retained_sequence_number(),
             tx_seq,
             event_seq,
             limit,
@@ -1052,6 +1260,7 @@ impl IndexStore {
         Self::get_event_from_index(
             &self.tables.event_by_move_event,
             struct_name,
+            self.lowest_retained_sequence_number(),
             tx_seq,
             event_seq,
             limit,
@@ -1070,6 +1279,7 @@ impl IndexStore {
         Self::get_event_from_index(
             &self.tables.event_by_event_module,
             module_id,
+            self.lowest_retained_sequence_number(),
             tx_seq,
             event_seq,
             limit,
@@ -1088,6 +1298,7 @@ impl IndexStore {
         Self::get_event_from_index(
             &self.tables.event_by_sender,
             sender,
+            self.lowest_retained_sequence_number(),
             tx_seq,
             event_seq,
             limit,
@@ -1104,6 +1315,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        let lowest_retained = self.lowest_retained_sequence_number();
         Ok(if descending {
             self.tables
                 .event_by_time
@@ -1111,6 +1323,7 @@ impl IndexStore {
                 .skip_prior_to(&(end_time, (tx_seq, event_seq)))?
                 .reverse()
                 .take_while(|((m, _), _)| m >= &start_time)
+                .filter(|((_, (seq, _)), _)| *seq >= lowest_retained)
                 .take(limit)
                 .map(|((_, (_, event_seq)), (digest, tx_digest, time))| {
                     (digest, tx_digest, event_seq, time)
@@ -1122,6 +1335,7 @@ impl IndexStore {
                 .unbounded_iter()
                 .skip_to(&(start_time, (tx_seq, event_seq)))?
                 .take_while(|((m, _), _)| m <= &end_time)
+                .filter(|((_, (seq, _)), _)| *seq >= lowest_retained)
                 .take(limit)
                 .map(|((_, (_, event_seq)), (digest, tx_digest, time))| {
                     (digest, tx_digest, event_seq, time)
@@ -1590,12 +1804,15 @@ mod tests {
     use prometheus::Registry;
     use std::collections::BTreeMap;
     use std::env::temp_dir;
+    use sui_json_rpc_types::TransactionFilter;
     use sui_types::base_types::{ObjectInfo, ObjectType, SuiAddress};
     use sui_types::digests::TransactionDigest;
     use sui_types::effects::TransactionEvents;
+    use sui_types::error::{SuiError, UserInputError};
     use sui_types::gas_coin::GAS;
     use sui_types::object;
     use sui_types::object::Owner;
+    use typed_store::Map;
 
     #[tokio::test]
     async fn test_index_cache() -> anyhow::Result<()> {
@@ -1723,4 +1940,79 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_prune_indexes() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        let address: SuiAddress = AccountAddress::random().into();
+
+        let mut digests = vec![];
+        for _ in 0..5 {
+            let digest = TransactionDigest::random();
+            index_store
+                .index_tx(
+                    address,
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    &TransactionEvents { data: vec![] },
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                    },
+                    &digest,
+                    1234,
+                    None,
+                    &BTreeMap::new(),
+                )
+                .await?;
+            index_store.tables.timestamps.insert(&digest, &1234)?;
+            digests.push(digest);
+        }
+
+        let cutoff = index_store.get_transaction_seq(&digests[2])?.unwrap();
+        index_store.prune(cutoff)?;
+        assert_eq!(index_store.lowest_retained_sequence_number(), cutoff);
+        assert_eq!(index_store.get_transaction_seq(&digests[1])?, None);
+        for digest in &digests[..2] {
+            assert_eq!(index_store.tables.timestamps.get(digest)?, None);
+        }
+        for digest in &digests[2..] {
+            assert_eq!(index_store.tables.timestamps.get(digest)?, Some(1234));
+        }
+
+        let filter = Some(TransactionFilter::FromAddress(address));
+        assert_eq!(
+            index_store.get_transactions(filter.clone(), None, None, false)?,
+            digests[2..]
+        );
+        assert_eq!(
+            index_store.get_transactions(filter.clone(), None, None, true)?,
+            digests[2..].iter().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            index_store.get_transactions(None, None, None, false)?,
+            digests[2..]
+        );
+        assert_eq!(
+            index_store.get_transactions(filter.clone(), Some(digests[2]), None, false)?,
+            digests[3..]
+        );
+        assert!(matches!(
+            index_store.get_transactions(filter, Some(digests[0]), None, false),
+            Err(SuiError::UserInputError {
+                error: UserInputError::IndexCursorPruned { lowest_available_tx_seq, .. },
+            }) if lowest_available_tx_seq == cutoff
+        ));
+
+        // The watermark survives a restart.
+        drop(index_store);
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        assert_eq!(index_store.lowest_retained_sequence_number(), cutoff);
+
+        Ok(())
+    }
 }
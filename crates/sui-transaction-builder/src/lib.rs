@@ -123,8 +123,9 @@ impl TransactionBuilder {
     }
 
     /// Construct the transaction data from a transaction kind, and other parameters.
-    /// If the gas_payment list is empty, it will pick the first gas coin that has at least
-    /// the required gas budget that is not in the input coins.
+    /// If the gas_payment list is empty, it will pick the first gas coin of the gas owner (the
+    /// sponsor if there is one, or the sender otherwise) that has at least the required gas budget
+    /// that is not in the input coins.
     pub async fn tx_data(
         &self,
         sender: SuiAddress,
@@ -144,8 +145,14 @@ impl TransactionBuilder {
                 })
                 .collect();
             vec![
-                self.select_gas(sender, None, gas_budget, input_objs, gas_price)
-                    .await?,
+                self.select_gas(
+                    gas_sponsor.unwrap_or(sender),
+                    None,
+                    gas_budget,
+                    input_objs,
+                    gas_price,
+                )
+                .await?,
             ]
         } else {
             self.input_refs(&gas_payment).await?
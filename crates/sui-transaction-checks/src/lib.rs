@@ -10,7 +10,7 @@ mod checked {
     use std::collections::{BTreeMap, HashSet};
     use std::sync::Arc;
     use sui_protocol_config::ProtocolConfig;
-    use sui_types::base_types::{ObjectID, ObjectRef};
+    use sui_types::base_types::{EpochId, ObjectID, ObjectRef};
     use sui_types::error::{UserInputError, UserInputResult};
     use sui_types::executable_transaction::VerifiedExecutableTransaction;
     use sui_types::metrics::BytecodeVerifierMetrics;
@@ -139,6 +139,38 @@ mod checked {
         Ok((gas_status, input_objects.into_checked()))
     }
 
+    /// Check that `transaction`'s expiration allows it to be processed in `epoch`, at
+    /// `timestamp_ms` (milliseconds since the Unix epoch).
+    ///
+    /// Called when signing a transaction (with the validator's local time), and when scheduling a
+    /// certificate that uses shared objects (with the consensus commit timestamp, so that every
+    /// validator reaches the same decision), which is cancelled if it has expired. Certificates
+    /// that only use owned objects are not re-checked, so a transaction may still execute after
+    /// its expiration if it was certified before it.
+    pub fn check_transaction_expiration(
+        protocol_config: &ProtocolConfig,
+        transaction: &TransactionData,
+        epoch: EpochId,
+        timestamp_ms: u64,
+    ) -> SuiResult<()> {
+        let expiration = transaction.expiration();
+        if !expiration.is_window() {
+            // Epoch expirations are covered by `SenderSignedData::validity_check`.
+            return Ok(());
+        }
+
+        fp_ensure!(
+            protocol_config.transaction_expiration_window(),
+            SuiError::UnsupportedFeatureError {
+                error: "timestamp and window expirations are not enabled on this network"
+                    .to_string(),
+            }
+        );
+
+        expiration.check_epoch(epoch)?;
+        expiration.check_timestamp(timestamp_ms)
+    }
+
     /// WARNING! This should only be used for the dev-inspect transaction. This transaction type
     /// bypasses many of the normal object checks
     pub fn check_dev_inspect_input(
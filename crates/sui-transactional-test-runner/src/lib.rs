@@ -180,6 +180,7 @@ impl TransactionalAdapter for ValidatorWithFullnode {
                 None,
                 None,
                 None,
+                None,
             )
             .await
     }
@@ -44,6 +44,7 @@ use anyhow::anyhow;
 use fastcrypto::encoding::decode_bytes_hex;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::hash::HashFunction;
+use fastcrypto::secp256r1::Secp256r1PublicKey;
 use fastcrypto::traits::AllowedRng;
 use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
 use move_binary_format::file_format::SignatureToken;
@@ -616,6 +617,16 @@ impl SuiAddress {
         hasher.update(inputs.get_address_seed().unpadded());
         Ok(SuiAddress(hasher.finalize().digest))
     }
+
+    /// Sui address for a passkey is defined as the blake2b hash of
+    /// [passkey_flag || secp256r1_compressed_pk_bytes]. It is distinct from the address of the
+    /// same key used as a plain secp256r1 key.
+    pub fn from_passkey_pk(pk: &Secp256r1PublicKey) -> Self {
+        let mut hasher = DefaultHash::default();
+        hasher.update([SignatureScheme::PasskeyAuthenticator.flag()]);
+        hasher.update(pk);
+        SuiAddress(hasher.finalize().digest)
+    }
 }
 
 impl From<ObjectID> for SuiAddress {
@@ -739,6 +750,9 @@ impl TryFrom<&GenericSignature> for SuiAddress {
             GenericSignature::ZkLoginAuthenticator(zklogin) => {
                 SuiAddress::try_from_unpadded(&zklogin.inputs)
             }
+            GenericSignature::PasskeyAuthenticator(passkey) => {
+                Ok(SuiAddress::from_passkey_pk(&passkey.get_pk()?))
+            }
         }
     }
 }
@@ -1007,6 +1021,7 @@ impl SequenceNumber {
     pub const MAX: SequenceNumber = SequenceNumber(0x7fff_ffff_ffff_ffff);
     pub const CANCELLED_READ: SequenceNumber = SequenceNumber(SequenceNumber::MAX.value() + 1);
     pub const CONGESTED: SequenceNumber = SequenceNumber(SequenceNumber::MAX.value() + 2);
+    pub const EXPIRED: SequenceNumber = SequenceNumber(SequenceNumber::MAX.value() + 3);
 
     pub const fn new() -> Self {
         SequenceNumber(0)
@@ -1056,7 +1071,9 @@ impl SequenceNumber {
     }
 
     pub fn is_cancelled(&self) -> bool {
-        self == &SequenceNumber::CANCELLED_READ || self == &SequenceNumber::CONGESTED
+        self == &SequenceNumber::CANCELLED_READ
+            || self == &SequenceNumber::CONGESTED
+            || self == &SequenceNumber::EXPIRED
     }
 
     pub fn is_valid(&self) -> bool {
@@ -257,6 +257,15 @@ pub enum UserInputError {
 
     #[error("Commands following a command with Random can only be TransferObjects or MergeCoins")]
     PostRandomCommandRestrictions,
+
+    #[error(
+        "Cursor {cursor} points to data pruned from this node's indexes, which only retain \
+         transactions from sequence number {lowest_available_tx_seq}"
+    )]
+    IndexCursorPruned {
+        cursor: TransactionDigest,
+        lowest_available_tx_seq: u64,
+    },
 }
 
 #[derive(
@@ -566,6 +575,9 @@ pub enum SuiError {
     #[error("Transaction Expired")]
     TransactionExpired,
 
+    #[error("Transaction is not yet valid: {error}")]
+    TransactionNotYetValid { error: String },
+
     // These are errors that occur when an RPC fails and is simply the utf8 message sent in a
     // Tonic::Status
     #[error("{1} - {0}")]
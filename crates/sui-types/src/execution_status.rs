@@ -203,6 +203,9 @@ pub enum ExecutionFailureStatus {
 
     #[error("Certificate is cancelled due to congestion on shared objects: {congested_objects}")]
     ExecutionCancelledDueToSharedObjectCongestion { congested_objects: CongestedObjects },
+
+    #[error("Certificate is cancelled because it was sequenced outside of its expiration window")]
+    ExecutionCancelledDueToExpiration,
     // NOTE: if you want to add a new enum,
     // please add it at the end for Rust SDK backward compatibility.
 }
@@ -9,7 +9,7 @@ use move_core_types::gas_algebra::{AbstractMemorySize, InternalGas, NumArgs, Num
 use move_core_types::language_storage::ModuleId;
 
 use move_core_types::vm_status::StatusCode;
-use move_vm_profiler::GasProfiler;
+use move_vm_profiler::{tracer::ExecutionTracer, GasProfiler};
 use move_vm_types::gas::{GasMeter, SimpleInstruction};
 use move_vm_types::loaded_data::runtime_types::Type;
 use move_vm_types::views::{TypeView, ValueView};
@@ -76,6 +76,7 @@ pub struct GasStatus {
     instructions_current_tier_mult: u64,
 
     profiler: Option<GasProfiler>,
+    tracer: Option<ExecutionTracer>,
 }
 
 impl GasStatus {
@@ -113,6 +114,7 @@ impl GasStatus {
             stack_size_next_tier_start,
             instructions_next_tier_start,
             profiler: None,
+            tracer: None,
         }
     }
 
@@ -140,9 +142,16 @@ impl GasStatus {
             stack_size_next_tier_start: None,
             instructions_next_tier_start: None,
             profiler: None,
+            tracer: None,
         }
     }
 
+    /// Takes the execution tracer attached to this gas status, if any, to write out its trace
+    /// once execution is done.
+    pub fn take_tracer(&mut self) -> Option<ExecutionTracer> {
+        self.tracer.take()
+    }
+
     const INTERNAL_UNIT_MULTIPLIER: u64 = 1000;
 
     fn to_internal_units(val: u64) -> InternalGas {
@@ -677,6 +686,14 @@ impl GasMeter for GasStatus {
     fn set_profiler(&mut self, profiler: GasProfiler) {
         self.profiler = Some(profiler);
     }
+
+    fn get_tracer_mut(&mut self) -> Option<&mut ExecutionTracer> {
+        self.tracer.as_mut()
+    }
+
+    fn set_tracer(&mut self, tracer: ExecutionTracer) {
+        self.tracer = Some(tracer);
+    }
 }
 
 pub fn zero_cost_schedule() -> CostTable {
@@ -67,6 +67,7 @@ pub mod move_package;
 pub mod multisig;
 pub mod multisig_legacy;
 pub mod object;
+pub mod passkey_authenticator;
 pub mod programmable_transaction_builder;
 pub mod quorum_driver_types;
 pub mod randomness_state;
@@ -0,0 +1,310 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::signature_verification::VerifiedDigestCache;
+use crate::{
+    base_types::{EpochId, SuiAddress},
+    crypto::{DefaultHash, Signature, SignatureScheme, SuiSignature},
+    digests::ZKLoginInputsDigest,
+    error::{SuiError, SuiResult},
+    signature::{AuthenticatorTrait, VerifyParams},
+};
+use fastcrypto::hash::{HashFunction, Sha256};
+use fastcrypto::secp256r1::{Secp256r1PublicKey, Secp256r1Signature};
+use fastcrypto::traits::VerifyingKey;
+use fastcrypto::{error::FastCryptoError, traits::ToFromBytes};
+use once_cell::sync::OnceCell;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::IntentMessage;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+use url::{Host, Url};
+#[cfg(test)]
+#[path = "unit_tests/passkey_authenticator_test.rs"]
+mod passkey_authenticator_test;
+
+/// The only WebAuthn ceremony type accepted, see
+/// <https://www.w3.org/TR/webauthn-2/#dom-collectedclientdata-type>.
+const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+
+/// Length of `rpIdHash || flags || signCount`, the fixed-size prefix of `authenticatorData`, see
+/// <https://www.w3.org/TR/webauthn-2/#sctn-authenticator-data>.
+const AUTHENTICATOR_DATA_MIN_LENGTH: usize = 37;
+
+/// Offset of the flags byte in `authenticatorData`, after the 32-byte `rpIdHash`.
+const AUTHENTICATOR_DATA_FLAGS_OFFSET: usize = 32;
+
+/// The user present (UP) flag, set if the user interacted with the authenticator.
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// A passkey (WebAuthn) authenticator, as produced by a platform or roaming authenticator in a
+/// `navigator.credentials.get()` ceremony, whose challenge is the digest of the intent message.
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyAuthenticator {
+    /// `authenticatorData` as returned by the authenticator, see
+    /// <https://www.w3.org/TR/webauthn-2/#authenticator-data>.
+    authenticator_data: Vec<u8>,
+
+    /// `clientDataJSON` as returned by the client, see
+    /// <https://www.w3.org/TR/webauthn-2/#dictdef-collectedclientdata>.
+    client_data_json: String,
+
+    /// A secp256r1 signature `flag || sig || pk` over `authenticatorData ||
+    /// sha256(clientDataJSON)`. The signature must be in its compact (r, s) form, with s
+    /// normalized to the lower half of the curve order.
+    user_signature: Signature,
+
+    #[serde(skip)]
+    pub bytes: OnceCell<Vec<u8>>,
+}
+
+/// The fields of `clientDataJSON` that are checked during verification.
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    type_: String,
+    challenge: String,
+    origin: String,
+}
+
+impl PasskeyAuthenticator {
+    /// Create a new [struct PasskeyAuthenticator] with necessary fields. Fails if
+    /// `user_signature` is not a secp256r1 signature.
+    pub fn new(
+        authenticator_data: Vec<u8>,
+        client_data_json: String,
+        user_signature: Signature,
+    ) -> SuiResult<Self> {
+        if !matches!(user_signature.scheme(), SignatureScheme::Secp256r1) {
+            return Err(SuiError::InvalidSignature {
+                error: "Passkey signature must be secp256r1".to_string(),
+            });
+        }
+
+        Ok(Self {
+            authenticator_data,
+            client_data_json,
+            user_signature,
+            bytes: OnceCell::new(),
+        })
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &str {
+        &self.client_data_json
+    }
+
+    pub fn get_pk(&self) -> SuiResult<Secp256r1PublicKey> {
+        Secp256r1PublicKey::from_bytes(self.user_signature.public_key_bytes())
+            .map_err(|_| SuiError::KeyConversionError("Cannot parse secp256r1 pk".to_string()))
+    }
+
+    /// The challenge that the passkey must sign over for `intent_msg`: the Blake2b256 digest of
+    /// its BCS bytes, which is the same message signed by other signature schemes.
+    pub fn challenge<T: Serialize>(intent_msg: &IntentMessage<T>) -> [u8; 32] {
+        let mut hasher = DefaultHash::default();
+        hasher.update(bcs::to_bytes(intent_msg).expect("Message serialization should not fail"));
+        hasher.finalize().digest
+    }
+
+    /// The bytes covered by the signature: `authenticatorData || sha256(clientDataJSON)`.
+    fn signed_bytes(&self) -> Vec<u8> {
+        let client_data_hash = Sha256::digest(self.client_data_json.as_bytes()).digest;
+        let mut bytes = Vec::with_capacity(self.authenticator_data.len() + client_data_hash.len());
+        bytes.extend_from_slice(&self.authenticator_data);
+        bytes.extend_from_slice(&client_data_hash);
+        bytes
+    }
+
+    /// Check that the user was present, and that the credential was scoped to a relying party
+    /// that `origin` belongs to: `rpIdHash` must be the SHA-256 digest of the origin's host, or of
+    /// one of the explicitly configured `rp_ids` that the host is, or is a subdomain of.
+    fn verify_authenticator_data(&self, origin: &str, rp_ids: &[String]) -> SuiResult {
+        if self.authenticator_data.len() < AUTHENTICATOR_DATA_MIN_LENGTH {
+            return Err(SuiError::InvalidSignature {
+                error: "Invalid authenticatorData, too short".to_string(),
+            });
+        }
+
+        let flags = self.authenticator_data[AUTHENTICATOR_DATA_FLAGS_OFFSET];
+        if flags & USER_PRESENT_FLAG == 0 {
+            return Err(SuiError::InvalidSignature {
+                error: "Invalid authenticatorData, user present flag is not set".to_string(),
+            });
+        }
+
+        let host = origin_host(origin).ok_or_else(|| SuiError::InvalidSignature {
+            error: format!("Invalid clientDataJSON origin {origin}"),
+        })?;
+
+        let rp_id_hash = &self.authenticator_data[..AUTHENTICATOR_DATA_FLAGS_OFFSET];
+        if !rp_id_candidates(&host, rp_ids)
+            .any(|rp_id| Sha256::digest(rp_id.as_bytes()).digest == rp_id_hash)
+        {
+            return Err(SuiError::InvalidSignature {
+                error: format!(
+                    "Invalid authenticatorData, rpIdHash does not match origin {origin}"
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The host of a WebAuthn origin (`scheme://host[:port]`), which must be served over HTTPS, or
+/// over HTTP from localhost. The origin must be in its serialized form, as browsers report it.
+fn origin_host(origin: &str) -> Option<Host> {
+    let url = Url::parse(origin).ok()?;
+    if url.origin().ascii_serialization() != origin {
+        return None;
+    }
+
+    let host = url.host()?.to_owned();
+    let localhost = matches!(&host, Host::Domain(domain) if domain == "localhost");
+    let secure = url.scheme() == "https" || (url.scheme() == "http" && localhost);
+    secure.then_some(host)
+}
+
+/// The relying party IDs that may be used from `host`: the host itself (serialized, so IPv6
+/// addresses are bracketed), and any of `rp_ids` that it is a subdomain of.
+///
+/// Parent domains of the host are not accepted implicitly, because without a public suffix list
+/// there is no telling a registrable domain (`sui.io`) from a public suffix (`co.uk`), which
+/// anyone can register subdomains of.
+fn rp_id_candidates<'a>(host: &Host, rp_ids: &'a [String]) -> impl Iterator<Item = String> + 'a {
+    let domain = match host {
+        Host::Domain(domain) => Some(domain.clone()),
+        Host::Ipv4(_) | Host::Ipv6(_) => None,
+    };
+
+    std::iter::once(host.to_string()).chain(rp_ids.iter().filter_map(move |rp_id| {
+        let domain = domain.as_ref()?;
+        let is_subdomain = domain
+            .strip_suffix(rp_id.as_str())
+            .is_some_and(|prefix| prefix.ends_with('.'));
+        is_subdomain.then(|| rp_id.clone())
+    }))
+}
+
+/// Necessary trait for [struct SenderSignedData].
+impl PartialEq for PasskeyAuthenticator {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+/// Necessary trait for [struct SenderSignedData].
+impl Eq for PasskeyAuthenticator {}
+
+/// Necessary trait for [struct SenderSignedData].
+impl Hash for PasskeyAuthenticator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl AuthenticatorTrait for PasskeyAuthenticator {
+    fn verify_user_authenticator_epoch(
+        &self,
+        _epoch: EpochId,
+        _max_epoch_upper_bound_delta: Option<u64>,
+    ) -> SuiResult {
+        // Passkey signatures do not expire.
+        Ok(())
+    }
+
+    /// Verify an intent message of a transaction with a passkey authenticator.
+    fn verify_claims<T>(
+        &self,
+        intent_msg: &IntentMessage<T>,
+        author: SuiAddress,
+        aux_verify_data: &VerifyParams,
+        _zklogin_inputs_cache: Arc<VerifiedDigestCache<ZKLoginInputsDigest>>,
+    ) -> SuiResult
+    where
+        T: Serialize,
+    {
+        let pk = self.get_pk()?;
+        if author != SuiAddress::from_passkey_pk(&pk) {
+            return Err(SuiError::IncorrectSigner {
+                error: format!("Passkey public key does not match signer {author}"),
+            });
+        }
+
+        let client_data: CollectedClientData = serde_json::from_str(&self.client_data_json)
+            .map_err(|e| SuiError::InvalidSignature {
+                error: format!("Invalid clientDataJSON: {e}"),
+            })?;
+
+        if client_data.type_ != WEBAUTHN_GET_TYPE {
+            return Err(SuiError::InvalidSignature {
+                error: format!(
+                    "Invalid clientDataJSON type, expected {WEBAUTHN_GET_TYPE}, got {}",
+                    client_data.type_
+                ),
+            });
+        }
+
+        self.verify_authenticator_data(&client_data.origin, &aux_verify_data.passkey_rp_ids)?;
+
+        // The challenge binds the passkey signature to this intent message.
+        let challenge =
+            base64_url::decode(&client_data.challenge).map_err(|_| SuiError::InvalidSignature {
+                error: "Invalid base64url challenge in clientDataJSON".to_string(),
+            })?;
+
+        if challenge != Self::challenge(intent_msg) {
+            return Err(SuiError::InvalidSignature {
+                error: "Passkey challenge does not match intent message".to_string(),
+            });
+        }
+
+        let sig = Secp256r1Signature::from_bytes(self.user_signature.signature_bytes()).map_err(
+            |_| SuiError::InvalidSignature {
+                error: "Cannot parse secp256r1 sig".to_string(),
+            },
+        )?;
+
+        pk.verify(&self.signed_bytes(), &sig)
+            .map_err(|e| SuiError::InvalidSignature {
+                error: format!("Fail to verify passkey sig {}", e),
+            })
+    }
+}
+
+impl ToFromBytes for PasskeyAuthenticator {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        // The first byte matches the flag of PasskeyAuthenticator.
+        if bytes.first().ok_or(FastCryptoError::InvalidInput)?
+            != &SignatureScheme::PasskeyAuthenticator.flag()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let passkey: PasskeyAuthenticator =
+            bcs::from_bytes(&bytes[1..]).map_err(|_| FastCryptoError::InvalidSignature)?;
+        if !matches!(passkey.user_signature.scheme(), SignatureScheme::Secp256r1) {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        Ok(passkey)
+    }
+}
+
+impl AsRef<[u8]> for PasskeyAuthenticator {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+            .get_or_try_init::<_, eyre::Report>(|| {
+                let as_bytes = bcs::to_bytes(self).expect("BCS serialization should not fail");
+                let mut bytes = Vec::with_capacity(1 + as_bytes.len());
+                bytes.push(SignatureScheme::PasskeyAuthenticator.flag());
+                bytes.extend_from_slice(as_bytes.as_slice());
+                Ok(bytes)
+            })
+            .expect("OnceCell invariant violated")
+    }
+}
@@ -8,6 +8,7 @@ use crate::crypto::{
 use crate::digests::ZKLoginInputsDigest;
 use crate::error::SuiError;
 use crate::multisig_legacy::MultiSigLegacy;
+use crate::passkey_authenticator::PasskeyAuthenticator;
 use crate::signature_verification::VerifiedDigestCache;
 use crate::zk_login_authenticator::ZkLoginAuthenticator;
 use crate::{base_types::SuiAddress, crypto::Signature, error::SuiResult, multisig::MultiSig};
@@ -36,6 +37,10 @@ pub struct VerifyParams {
     pub verify_legacy_zklogin_address: bool,
     pub accept_zklogin_in_multisig: bool,
     pub zklogin_max_epoch_upper_bound_delta: Option<u64>,
+    /// Relying party IDs that passkeys may be scoped to, in addition to the exact host of the
+    /// origin they were used from. A passkey used from a subdomain of one of these may be scoped
+    /// to it.
+    pub passkey_rp_ids: Vec<String>,
 }
 
 impl VerifyParams {
@@ -54,8 +59,14 @@ impl VerifyParams {
             verify_legacy_zklogin_address,
             accept_zklogin_in_multisig,
             zklogin_max_epoch_upper_bound_delta,
+            passkey_rp_ids: vec![],
         }
     }
+
+    pub fn with_passkey_rp_ids(mut self, passkey_rp_ids: Vec<String>) -> Self {
+        self.passkey_rp_ids = passkey_rp_ids;
+        self
+    }
 }
 
 /// A lightweight trait that all members of [enum GenericSignature] implement.
@@ -89,6 +100,7 @@ pub enum GenericSignature {
     MultiSigLegacy,
     Signature,
     ZkLoginAuthenticator,
+    PasskeyAuthenticator,
 }
 
 impl GenericSignature {
@@ -96,6 +108,10 @@ impl GenericSignature {
         matches!(self, GenericSignature::ZkLoginAuthenticator(_))
     }
 
+    pub fn is_passkey(&self) -> bool {
+        matches!(self, GenericSignature::PasskeyAuthenticator(_))
+    }
+
     pub fn is_upgraded_multisig(&self) -> bool {
         matches!(self, GenericSignature::MultiSig(_))
     }
@@ -206,6 +222,8 @@ impl GenericSignature {
 /// of [struct MultiSigLegacy] i.e. `flag || bcs_bytes(MultiSigLegacy)`.
 /// [struct Multisig] is encodede as the MultiSig flag (0x03) concat with the bcs serializedbytes
 /// of [struct Multisig] i.e. `flag || bcs_bytes(Multisig)`.
+/// [struct PasskeyAuthenticator] is encoded as the Passkey flag (0x06) concat with the bcs
+/// serialized bytes of [struct PasskeyAuthenticator] i.e. `flag || bcs_bytes(PasskeyAuthenticator)`.
 impl ToFromBytes for GenericSignature {
     fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
         match SignatureScheme::from_flag_byte(
@@ -228,6 +246,10 @@ impl ToFromBytes for GenericSignature {
                     let zk_login = ZkLoginAuthenticator::from_bytes(bytes)?;
                     Ok(GenericSignature::ZkLoginAuthenticator(zk_login))
                 }
+                SignatureScheme::PasskeyAuthenticator => {
+                    let passkey = PasskeyAuthenticator::from_bytes(bytes)?;
+                    Ok(GenericSignature::PasskeyAuthenticator(passkey))
+                }
                 _ => Err(FastCryptoError::InvalidInput),
             },
             Err(_) => Err(FastCryptoError::InvalidInput),
@@ -243,6 +265,7 @@ impl AsRef<[u8]> for GenericSignature {
             GenericSignature::MultiSigLegacy(s) => s.as_ref(),
             GenericSignature::Signature(s) => s.as_ref(),
             GenericSignature::ZkLoginAuthenticator(s) => s.as_ref(),
+            GenericSignature::PasskeyAuthenticator(s) => s.as_ref(),
         }
     }
 }
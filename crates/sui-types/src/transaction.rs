@@ -1526,6 +1526,86 @@ pub enum TransactionExpiration {
     /// Validators wont sign a transaction unless the expiration Epoch
     /// is greater than or equal to the current epoch
     Epoch(EpochId),
+    /// Validators wont sign a transaction, and consensus wont schedule it, unless the expiration
+    /// timestamp (in milliseconds since the Unix epoch) is greater than or equal to the current
+    /// time.
+    ///
+    /// Only transactions with shared object inputs go through consensus, so the timestamp is not
+    /// re-checked when executing a certificate for a transaction that only uses owned objects: It
+    /// was signed before the deadline, but may be executed after it.
+    Timestamp(u64),
+    /// Validators wont sign a transaction, and consensus wont schedule it, unless the current
+    /// epoch and time fall within these bounds. All bounds are inclusive, and absent bounds are
+    /// unconstrained.
+    ///
+    /// As with [TransactionExpiration::Timestamp], the time bounds of a transaction that only
+    /// uses owned objects are enforced when it is signed, but not when its certificate is
+    /// executed. Certificates are only valid in the epoch they were formed in, so the epoch bounds
+    /// always hold.
+    ValidDuring {
+        min_epoch: Option<EpochId>,
+        max_epoch: Option<EpochId>,
+        min_timestamp_ms: Option<u64>,
+        max_timestamp_ms: Option<u64>,
+    },
+}
+
+impl TransactionExpiration {
+    /// Whether this expiration depends on the time, or on a window of epochs. These forms of
+    /// expiration are only supported behind the `transaction_expiration_window` feature flag.
+    pub fn is_window(&self) -> bool {
+        matches!(self, Self::Timestamp(_) | Self::ValidDuring { .. })
+    }
+
+    /// Check that a transaction with this expiration is valid in `epoch`.
+    pub fn check_epoch(&self, epoch: EpochId) -> SuiResult {
+        let (min, max) = match self {
+            Self::None | Self::Timestamp(_) => (None, None),
+            Self::Epoch(max) => (None, Some(*max)),
+            Self::ValidDuring {
+                min_epoch,
+                max_epoch,
+                ..
+            } => (*min_epoch, *max_epoch),
+        };
+
+        check_expiration_bounds("epoch", epoch, min, max)
+    }
+
+    /// Check that a transaction with this expiration is valid at `timestamp_ms` (milliseconds
+    /// since the Unix epoch).
+    pub fn check_timestamp(&self, timestamp_ms: u64) -> SuiResult {
+        let (min, max) = match self {
+            Self::None | Self::Epoch(_) => (None, None),
+            Self::Timestamp(max) => (None, Some(*max)),
+            Self::ValidDuring {
+                min_timestamp_ms,
+                max_timestamp_ms,
+                ..
+            } => (*min_timestamp_ms, *max_timestamp_ms),
+        };
+
+        check_expiration_bounds("timestamp", timestamp_ms, min, max)
+    }
+}
+
+fn check_expiration_bounds(
+    name: &str,
+    current: u64,
+    min: Option<u64>,
+    max: Option<u64>,
+) -> SuiResult {
+    if max.is_some_and(|max| current > max) {
+        return Err(SuiError::TransactionExpired);
+    }
+
+    if let Some(min) = min.filter(|min| current < *min) {
+        return Err(SuiError::TransactionNotYetValid {
+            error: format!("current {name} {current} is before {min}"),
+        });
+    }
+
+    Ok(())
 }
 
 #[enum_dispatch(TransactionDataAPI)]
@@ -1565,6 +1645,15 @@ impl VersionedProtocolMessage for TransactionData {
         // Now check interior versioned data
         self.kind().check_version_supported(protocol_config)?;
 
+        if self.expiration().is_window() && !protocol_config.transaction_expiration_window() {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: format!(
+                    "timestamp and window expirations are not supported at {:?}",
+                    protocol_config.version
+                ),
+            });
+        }
+
         Ok(())
     }
 }
@@ -1941,6 +2030,15 @@ impl TransactionData {
         )
     }
 
+    /// Replace this transaction's expiration. Transactions are created without an expiration by
+    /// default.
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        match &mut self {
+            TransactionData::V1(data) => data.expiration = expiration,
+        }
+        self
+    }
+
     pub fn execution_parts(&self) -> (TransactionKind, SuiAddress, Vec<ObjectRef>) {
         (
             self.kind().clone(),
@@ -2300,6 +2398,10 @@ impl SenderSignedData {
         self.tx_signatures().iter().any(|sig| sig.is_zklogin())
     }
 
+    pub fn has_passkey_sig(&self) -> bool {
+        self.tx_signatures().iter().any(|sig| sig.is_passkey())
+    }
+
     pub fn has_upgraded_multisig(&self) -> bool {
         self.tx_signatures()
             .iter()
@@ -2350,13 +2452,9 @@ impl SenderSignedData {
             }
         );
 
-        // Checks to see if the transaction has expired
-        if match &tx_data.expiration() {
-            TransactionExpiration::None => false,
-            TransactionExpiration::Epoch(exp_poch) => *exp_poch < epoch,
-        } {
-            return Err(SuiError::TransactionExpired);
-        }
+        // Checks to see if the transaction has expired, or is not yet valid. Timestamps are
+        // checked separately, by `sui_transaction_checks::check_transaction_expiration`.
+        tx_data.expiration().check_epoch(epoch)?;
 
         // Enforce overall transaction size limit.
         let tx_size = self.serialized_size()?;
@@ -2408,6 +2506,13 @@ impl VersionedProtocolMessage for SenderSignedData {
                         });
                     }
                 }
+                GenericSignature::PasskeyAuthenticator(_) => {
+                    if !protocol_config.passkey_auth() {
+                        return Err(SuiError::UnsupportedFeatureError {
+                            error: "passkey is not enabled on this network".to_string(),
+                        });
+                    }
+                }
                 GenericSignature::Signature(_)
                 | GenericSignature::MultiSigLegacy(_)
                 | GenericSignature::ZkLoginAuthenticator(_) => (),
@@ -3075,14 +3180,26 @@ impl InputObjects {
             .any(|obj| obj.is_deleted_shared_object())
     }
 
+    /// Whether the transaction was cancelled because consensus sequenced it outside of its
+    /// expiration window.
+    pub fn is_expired(&self) -> bool {
+        self.objects.iter().any(|obj| {
+            matches!(
+                obj.object,
+                ObjectReadResultKind::CancelledTransactionSharedObject(SequenceNumber::EXPIRED)
+            )
+        })
+    }
+
     pub fn get_congested_objects(&self) -> Option<Vec<ObjectID>> {
         let mut contains_cancelled_read = false;
         let mut congested_objects = Vec::new();
         for obj in &self.objects {
             if let ObjectReadResultKind::CancelledTransactionSharedObject(version) = obj.object {
-                contains_cancelled_read = true;
                 if version == SequenceNumber::CONGESTED {
                     congested_objects.push(obj.id());
+                } else if version == SequenceNumber::CANCELLED_READ {
+                    contains_cancelled_read = true;
                 }
             }
         }
@@ -3225,6 +3342,10 @@ impl InputObjects {
         self.objects.iter()
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ObjectReadResult> {
+        self.objects.iter_mut()
+    }
+
     pub fn iter_objects(&self) -> impl Iterator<Item = &Object> {
         self.objects.iter().filter_map(|o| o.as_object())
     }
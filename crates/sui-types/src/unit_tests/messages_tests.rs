@@ -1387,3 +1387,57 @@ fn check_approx_effects_components_size() {
         "Update APPROX_SIZE_OF_EXECUTION_STATUS constant"
     );
 }
+
+#[test]
+fn test_transaction_expiration_bounds() {
+    use TransactionExpiration as E;
+
+    assert!(E::None.check_epoch(u64::MAX).is_ok());
+    assert!(E::None.check_timestamp(u64::MAX).is_ok());
+
+    // Epoch expirations are inclusive, and ignore the time.
+    assert!(E::Epoch(5).check_epoch(5).is_ok());
+    assert!(matches!(
+        E::Epoch(5).check_epoch(6),
+        Err(SuiError::TransactionExpired)
+    ));
+    assert!(E::Epoch(5).check_timestamp(u64::MAX).is_ok());
+
+    // Timestamp expirations are inclusive, and ignore the epoch.
+    assert!(E::Timestamp(1000).check_timestamp(1000).is_ok());
+    assert!(matches!(
+        E::Timestamp(1000).check_timestamp(1001),
+        Err(SuiError::TransactionExpired)
+    ));
+    assert!(E::Timestamp(1000).check_epoch(u64::MAX).is_ok());
+
+    let window = E::ValidDuring {
+        min_epoch: Some(2),
+        max_epoch: Some(3),
+        min_timestamp_ms: Some(1000),
+        max_timestamp_ms: None,
+    };
+
+    assert!(matches!(
+        window.check_epoch(1),
+        Err(SuiError::TransactionNotYetValid { .. })
+    ));
+    assert!(window.check_epoch(2).is_ok());
+    assert!(window.check_epoch(3).is_ok());
+    assert!(matches!(
+        window.check_epoch(4),
+        Err(SuiError::TransactionExpired)
+    ));
+
+    assert!(matches!(
+        window.check_timestamp(999),
+        Err(SuiError::TransactionNotYetValid { .. })
+    ));
+    assert!(window.check_timestamp(1000).is_ok());
+    assert!(window.check_timestamp(u64::MAX).is_ok());
+
+    assert!(!E::None.is_window());
+    assert!(!E::Epoch(5).is_window());
+    assert!(E::Timestamp(1000).is_window());
+    assert!(window.is_window());
+}
@@ -0,0 +1,295 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use super::PasskeyAuthenticator;
+use crate::base_types::SuiAddress;
+use crate::crypto::{get_key_pair, Signature, SignatureScheme, SuiKeyPair};
+use crate::signature::{GenericSignature, VerifyParams};
+use crate::signature_verification::VerifiedDigestCache;
+use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::hash::{HashFunction, Sha256};
+use fastcrypto::secp256r1::Secp256r1KeyPair;
+use fastcrypto::traits::{KeyPair, Signer, ToFromBytes};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+
+/// `rpIdHash || flags || signCount`, with the user present and user verified flags set.
+fn authenticator_data() -> Vec<u8> {
+    authenticator_data_with("www.sui.io", 0x05)
+}
+
+fn authenticator_data_with(rp_id: &str, flags: u8) -> Vec<u8> {
+    let mut data = Sha256::digest(rp_id.as_bytes()).digest.to_vec();
+    data.push(flags);
+    data.extend([0, 0, 0, 1]);
+    data
+}
+
+fn client_data_json(type_: &str, challenge: &[u8]) -> String {
+    client_data_json_with(type_, challenge, "https://www.sui.io")
+}
+
+fn client_data_json_with(type_: &str, challenge: &[u8], origin: &str) -> String {
+    format!(
+        r#"{{"type":"{type_}","challenge":"{}","origin":"{origin}","crossOrigin":false}}"#,
+        base64_url::encode(challenge),
+    )
+}
+
+/// Sign as a passkey would: over `authenticatorData || sha256(clientDataJSON)`.
+fn sign(kp: &Secp256r1KeyPair, authenticator_data: &[u8], client_data_json: &str) -> Signature {
+    let mut message = authenticator_data.to_vec();
+    message.extend(Sha256::digest(client_data_json.as_bytes()).digest);
+    Signer::<Signature>::sign(kp, &message)
+}
+
+fn make_passkey(
+    kp: &Secp256r1KeyPair,
+    intent_msg: &IntentMessage<PersonalMessage>,
+) -> PasskeyAuthenticator {
+    let authenticator_data = authenticator_data();
+    let client_data_json =
+        client_data_json("webauthn.get", &PasskeyAuthenticator::challenge(intent_msg));
+    let sig = sign(kp, &authenticator_data, &client_data_json);
+    PasskeyAuthenticator::new(authenticator_data, client_data_json, sig).unwrap()
+}
+
+fn personal_message(message: &[u8]) -> IntentMessage<PersonalMessage> {
+    IntentMessage::new(
+        Intent::personal_message(),
+        PersonalMessage {
+            message: message.to_vec(),
+        },
+    )
+}
+
+fn verify(
+    sig: &GenericSignature,
+    intent_msg: &IntentMessage<PersonalMessage>,
+    author: SuiAddress,
+) -> crate::error::SuiResult {
+    sig.verify_authenticator(
+        intent_msg,
+        author,
+        0,
+        &VerifyParams::default(),
+        Arc::new(VerifiedDigestCache::new_empty()),
+    )
+}
+
+#[test]
+fn test_serde_passkey_signature() {
+    let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+    let passkey = make_passkey(&kp, &personal_message(b"Hello"));
+
+    let serialized = passkey.as_ref();
+    assert_eq!(serialized[0], SignatureScheme::PasskeyAuthenticator.flag());
+
+    let deserialized = GenericSignature::from_bytes(serialized).unwrap();
+    assert_eq!(
+        deserialized,
+        GenericSignature::PasskeyAuthenticator(passkey)
+    );
+
+    let addr: SuiAddress = (&deserialized).try_into().unwrap();
+    assert_eq!(addr, SuiAddress::from_passkey_pk(kp.public()));
+
+    // A passkey's address is distinct from the address of the same key used directly.
+    assert_ne!(addr, SuiAddress::from(kp.public()));
+}
+
+#[test]
+fn test_passkey_verify() {
+    let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+    let author = SuiAddress::from_passkey_pk(kp.public());
+    let intent_msg = personal_message(b"Hello");
+    let sig = GenericSignature::PasskeyAuthenticator(make_passkey(&kp, &intent_msg));
+
+    assert!(verify(&sig, &intent_msg, author).is_ok());
+
+    // Signature is bound to the intent message through the challenge.
+    assert!(verify(&sig, &personal_message(b"Goodbye"), author).is_err());
+
+    // Signature is bound to the passkey's address.
+    let (other, _): (_, Secp256r1KeyPair) = get_key_pair();
+    assert!(verify(&sig, &intent_msg, other).is_err());
+}
+
+#[test]
+fn test_passkey_invalid_client_data() {
+    let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+    let author = SuiAddress::from_passkey_pk(kp.public());
+    let intent_msg = personal_message(b"Hello");
+    let challenge = PasskeyAuthenticator::challenge(&intent_msg);
+    let authenticator_data = authenticator_data();
+
+    // Registration ceremonies are not accepted.
+    let create_json = client_data_json("webauthn.create", &challenge);
+    let sig = sign(&kp, &authenticator_data, &create_json);
+    let passkey = PasskeyAuthenticator::new(authenticator_data.clone(), create_json, sig).unwrap();
+    let sig = GenericSignature::PasskeyAuthenticator(passkey);
+    assert!(verify(&sig, &intent_msg, author).is_err());
+
+    // The signature must cover the client data that was supplied.
+    let get_json = client_data_json("webauthn.get", &challenge);
+    let sig = sign(&kp, &authenticator_data, "{}");
+    let passkey = PasskeyAuthenticator::new(authenticator_data, get_json, sig).unwrap();
+    let sig = GenericSignature::PasskeyAuthenticator(passkey);
+    assert!(verify(&sig, &intent_msg, author).is_err());
+}
+
+#[test]
+fn test_passkey_requires_secp256r1() {
+    let (_, kp): (_, Ed25519KeyPair) = get_key_pair();
+    let kp = SuiKeyPair::Ed25519(kp);
+    let intent_msg = personal_message(b"Hello");
+    let sig = Signature::new_secure(&intent_msg, &kp);
+
+    assert!(PasskeyAuthenticator::new(
+        authenticator_data(),
+        client_data_json(
+            "webauthn.get",
+            &PasskeyAuthenticator::challenge(&intent_msg)
+        ),
+        sig,
+    )
+    .is_err());
+}
+
+/// Verify a passkey signature for `intent_msg`, made with `authenticator_data` from `origin`.
+fn verify_with(
+    intent_msg: &IntentMessage<PersonalMessage>,
+    authenticator_data: Vec<u8>,
+    origin: &str,
+) -> crate::error::SuiResult {
+    verify_with_rp_ids(intent_msg, authenticator_data, origin, &[])
+}
+
+/// Like [verify_with], accepting passkeys scoped to the relying parties in `rp_ids`.
+fn verify_with_rp_ids(
+    intent_msg: &IntentMessage<PersonalMessage>,
+    authenticator_data: Vec<u8>,
+    origin: &str,
+    rp_ids: &[&str],
+) -> crate::error::SuiResult {
+    let (_, kp): (_, Secp256r1KeyPair) = get_key_pair();
+    let author = SuiAddress::from_passkey_pk(kp.public());
+    let client_data_json = client_data_json_with(
+        "webauthn.get",
+        &PasskeyAuthenticator::challenge(intent_msg),
+        origin,
+    );
+    let sig = sign(&kp, &authenticator_data, &client_data_json);
+    let passkey = PasskeyAuthenticator::new(authenticator_data, client_data_json, sig).unwrap();
+    let params =
+        VerifyParams::default().with_passkey_rp_ids(rp_ids.iter().map(|s| s.to_string()).collect());
+    GenericSignature::PasskeyAuthenticator(passkey).verify_authenticator(
+        intent_msg,
+        author,
+        0,
+        &params,
+        Arc::new(VerifiedDigestCache::new_empty()),
+    )
+}
+
+#[test]
+fn test_passkey_user_present() {
+    let intent_msg = personal_message(b"Hello");
+    let origin = "https://www.sui.io";
+
+    // User present, without user verification.
+    assert!(verify_with(
+        &intent_msg,
+        authenticator_data_with("www.sui.io", 0x01),
+        origin
+    )
+    .is_ok());
+
+    // User verified, but not present.
+    let err = verify_with(
+        &intent_msg,
+        authenticator_data_with("www.sui.io", 0x04),
+        origin,
+    );
+    assert!(err.unwrap_err().to_string().contains("user present"));
+
+    // Missing flags and sign count.
+    let truncated = Sha256::digest(b"www.sui.io").digest.to_vec();
+    assert!(verify_with(&intent_msg, truncated, origin).is_err());
+}
+
+#[test]
+fn test_passkey_rp_id_hash() {
+    let intent_msg = personal_message(b"Hello");
+    let data = |rp_id| authenticator_data_with(rp_id, 0x05);
+
+    // The relying party is the origin's host.
+    assert!(verify_with(&intent_msg, data("www.sui.io"), "https://www.sui.io").is_ok());
+    assert!(verify_with(&intent_msg, data("sui.io"), "https://sui.io:8443").is_ok());
+    assert!(verify_with(&intent_msg, data("localhost"), "http://localhost:3000").is_ok());
+
+    // A credential from another relying party.
+    let err = verify_with(&intent_msg, data("evil.io"), "https://www.sui.io");
+    assert!(err.unwrap_err().to_string().contains("rpIdHash"));
+
+    // Neither a subdomain of the origin, nor a parent domain that has not been configured, is a
+    // valid relying party.
+    assert!(verify_with(&intent_msg, data("wallet.www.sui.io"), "https://www.sui.io").is_err());
+    assert!(verify_with(&intent_msg, data("sui.io"), "https://www.sui.io").is_err());
+    assert!(verify_with(&intent_msg, data("io"), "https://www.sui.io").is_err());
+
+    // Origins must be secure, and serialized.
+    assert!(verify_with(&intent_msg, data("www.sui.io"), "http://www.sui.io").is_err());
+    assert!(verify_with(&intent_msg, data("www.sui.io"), "www.sui.io").is_err());
+    assert!(verify_with(&intent_msg, data("www.sui.io"), "https://www.sui.io/login").is_err());
+    assert!(verify_with(&intent_msg, data("www.sui.io"), "https://user@www.sui.io").is_err());
+}
+
+#[test]
+fn test_passkey_configured_rp_id() {
+    let intent_msg = personal_message(b"Hello");
+    let data = |rp_id| authenticator_data_with(rp_id, 0x05);
+    let rp_ids = ["sui.io"];
+
+    // A configured relying party may be used from its subdomains, and from itself.
+    assert!(verify_with_rp_ids(&intent_msg, data("sui.io"), "https://www.sui.io", &rp_ids).is_ok());
+    assert!(verify_with_rp_ids(&intent_msg, data("sui.io"), "https://sui.io", &rp_ids).is_ok());
+
+    // Only from its subdomains, not from domains that merely end with it.
+    assert!(verify_with_rp_ids(&intent_msg, data("sui.io"), "https://notsui.io", &rp_ids).is_err());
+
+    // Configuring a relying party does not make other parent domains valid.
+    assert!(verify_with_rp_ids(&intent_msg, data("io"), "https://www.sui.io", &rp_ids).is_err());
+}
+
+#[test]
+fn test_passkey_public_suffix() {
+    let intent_msg = personal_message(b"Hello");
+    let data = |rp_id| authenticator_data_with(rp_id, 0x05);
+
+    // A public suffix is never inferred as the relying party of the domains registered under it.
+    let err = verify_with(&intent_msg, data("co.uk"), "https://foo.co.uk");
+    assert!(err.unwrap_err().to_string().contains("rpIdHash"));
+    assert!(verify_with(&intent_msg, data("foo.co.uk"), "https://foo.co.uk").is_ok());
+}
+
+#[test]
+fn test_passkey_ipv6_origin() {
+    let intent_msg = personal_message(b"Hello");
+    let data = |rp_id| authenticator_data_with(rp_id, 0x05);
+
+    // IPv6 hosts contain colons, which must not be confused with the port separator.
+    let origin = "https://[2001:db8::1]:8443";
+    assert!(verify_with(&intent_msg, data("[2001:db8::1]"), origin).is_ok());
+    assert!(verify_with(&intent_msg, data("[2001"), origin).is_err());
+    assert!(verify_with(&intent_msg, data("2001:db8::1"), origin).is_err());
+
+    // Addresses must be in their canonical form, as browsers serialize them.
+    assert!(verify_with(
+        &intent_msg,
+        data("[2001:db8::1]"),
+        "https://[2001:DB8:0::1]"
+    )
+    .is_err());
+}
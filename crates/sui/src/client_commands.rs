@@ -5,6 +5,7 @@ use crate::{
     client_ptb::ptb::PTB,
     displays::Pretty,
     key_identity::{get_identity_address, KeyIdentity},
+    upgrade_compatibility::check_upgrade_compatibility,
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
 use std::{
@@ -29,6 +30,7 @@ use move_binary_format::CompiledModule;
 use move_bytecode_verifier_meter::Scope;
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig as MoveBuildConfig;
+use move_vm_profiler::tracer::DEFAULT_MAX_EVENTS;
 use prometheus::Registry;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -58,8 +60,8 @@ use sui_sdk::{
     SuiClient, SUI_COIN_TYPE, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_TESTNET_URL,
 };
 use sui_types::{
-    base_types::{ObjectID, SequenceNumber, SuiAddress},
-    crypto::{EmptySignInfo, SignatureScheme},
+    base_types::{EpochId, ObjectID, SequenceNumber, SuiAddress},
+    crypto::{EmptySignInfo, PublicKey, SignatureScheme},
     digests::TransactionDigest,
     dynamic_field::DynamicFieldInfo,
     error::SuiError,
@@ -67,11 +69,13 @@ use sui_types::{
     message_envelope::Envelope,
     metrics::BytecodeVerifierMetrics,
     move_package::UpgradeCap,
+    multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit},
     object::Owner,
     parse_sui_type_tag,
     signature::GenericSignature,
     transaction::{
-        SenderSignedData, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+        SenderSignedData, Transaction, TransactionData, TransactionDataAPI, TransactionExpiration,
+        TransactionKind,
     },
 };
 
@@ -89,6 +93,10 @@ use tabled::{
 
 use tracing::info;
 
+#[path = "unit_tests/client_commands_tests.rs"]
+#[cfg(test)]
+mod client_commands_tests;
+
 #[path = "unit_tests/profiler_tests.rs"]
 #[cfg(test)]
 mod profiler_tests;
@@ -189,6 +197,34 @@ pub enum SuiClientCommands {
         #[clap(long)]
         signatures: Vec<String>,
     },
+    /// Execute a transaction that needs the signatures of several parties, such as a sponsored
+    /// transaction, whose gas is paid by an address other than its sender. Each signature is
+    /// matched to the signer (sender or gas owner) it is from, and the partial signatures of the
+    /// members of a multisig signer are combined first.
+    ExecuteMultiPartyTx {
+        /// BCS serialized transaction data bytes without its type tag, as base64 encoded string. This is the output of sui client command using --serialize-unsigned-transaction.
+        #[clap(long)]
+        tx_bytes: String,
+
+        /// A list of Base64 encoded signatures `flag || signature || pubkey` from the signers of
+        /// the transaction, or from members of a multisig signer, in any order.
+        #[clap(long, num_args(1..))]
+        signatures: Vec<String>,
+
+        /// The public keys of the members of a multisig signer, whose partial signatures are
+        /// combined.
+        #[clap(long, num_args(1..), requires_all = ["multisig_weights", "multisig_threshold"])]
+        multisig_pks: Vec<PublicKey>,
+
+        /// The weights of the members of the multisig signer, in the same order as their public
+        /// keys.
+        #[clap(long, num_args(1..))]
+        multisig_weights: Vec<WeightUnit>,
+
+        /// The threshold of the multisig signer.
+        #[clap(long)]
+        multisig_threshold: Option<ThresholdUnit>,
+    },
     /// Execute a combined serialized SenderSignedData string.
     ExecuteCombinedSignedTx {
         /// BCS serialized sender signed data, as base64 encoded string. This is the output of sui client command using --serialize-signed-transaction.
@@ -284,7 +320,7 @@ pub enum SuiClientCommands {
         input_coins: Vec<ObjectID>,
 
         /// The recipient addresses, must be of same length as amounts.
-        /// Aliases of addresses are also accepted as input.
+        /// Aliases of addresses and SuiNS names are also accepted as input.
         #[clap(long, num_args(1..))]
         recipients: Vec<KeyIdentity>,
 
@@ -303,7 +339,7 @@ pub enum SuiClientCommands {
         #[clap(long, num_args(1..))]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient address (or its alias if it's an address in the keystore).
+        /// The recipient address, its alias if it's an address in the keystore, or its SuiNS name.
         #[clap(long)]
         recipient: KeyIdentity,
 
@@ -320,7 +356,7 @@ pub enum SuiClientCommands {
         input_coins: Vec<ObjectID>,
 
         /// The recipient addresses, must be of same length as amounts.
-        /// Aliases of addresses are also accepted as input.
+        /// Aliases of addresses and SuiNS names are also accepted as input.
         #[clap(long, num_args(1..))]
         recipients: Vec<KeyIdentity>,
 
@@ -400,7 +436,7 @@ pub enum SuiClientCommands {
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address (or its alias if it's an address in the keystore)
+        /// Recipient address, its alias if it's an address in the keystore, or its SuiNS name
         #[clap(long)]
         to: KeyIdentity,
 
@@ -417,7 +453,7 @@ pub enum SuiClientCommands {
     /// is transferred.
     #[clap(name = "transfer-sui")]
     TransferSui {
-        /// Recipient address (or its alias if it's an address in the keystore)
+        /// Recipient address, its alias if it's an address in the keystore, or its SuiNS name
         #[clap(long)]
         to: KeyIdentity,
 
@@ -459,6 +495,11 @@ pub enum SuiClientCommands {
         /// Also publish transitive dependencies that have not already been published.
         #[clap(long)]
         with_unpublished_dependencies: bool,
+
+        /// Send the upgrade without first checking locally that it is compatible with the
+        /// published package. Validators still reject incompatible upgrades.
+        #[clap(long)]
+        skip_compatibility_check: bool,
     },
 
     /// Run the bytecode verifier on the package
@@ -522,6 +563,43 @@ pub enum SuiClientCommands {
         profile_output: Option<PathBuf>,
     },
 
+    /// Record a step-level execution trace of a transaction: the functions called, the instructions
+    /// executed with the locals and operand stack, the objects read and written and the events
+    /// emitted. Use `step-trace` to step through it.
+    #[clap(name = "trace-transaction")]
+    TraceTransaction {
+        /// The digest of the transaction to trace
+        #[arg(long, short)]
+        tx_digest: String,
+
+        /// If specified, overrides the filepath of the output trace.
+        /// If an output filepath is not specified, it will output a file `trace_{tx_digest}.json` to the working directory
+        #[arg(long, short = 'o')]
+        trace_output: Option<PathBuf>,
+
+        /// The maximum number of events to record. Execution continues past the limit, but the
+        /// trace is marked as truncated.
+        #[arg(long, default_value_t = DEFAULT_MAX_EVENTS)]
+        max_events: usize,
+    },
+
+    /// Step through an execution trace recorded with `trace-transaction`.
+    #[clap(name = "step-trace")]
+    StepTrace {
+        /// The path of the trace
+        #[arg(long, short)]
+        path: PathBuf,
+
+        /// The path of the Move package that was executed. If it has been built, its source maps
+        /// are used to show the source of each instruction and the names of the locals.
+        #[arg(long)]
+        package_path: Option<PathBuf>,
+
+        /// Print the whole trace instead of stepping through it
+        #[arg(long)]
+        print: bool,
+    },
+
     /// Replay a given transaction to view transaction effects. Set environment variable MOVE_VM_STEP=1 to debug.
     #[clap(name = "replay-transaction")]
     ReplayTransaction {
@@ -587,6 +665,10 @@ pub struct Opts {
     /// Perform a dry run of the transaction, without executing it.
     #[arg(long)]
     pub dry_run: bool,
+    /// Also record a step-level execution trace of the dry run to this path, by executing the
+    /// transaction locally against the latest network state. Requires the gas-profiler feature.
+    #[arg(long, requires = "dry_run")]
+    pub trace: Option<PathBuf>,
     /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
     /// (TransactionData) using base64 encoding, and print out the string <TX_BYTES>. The string can
     /// be used to execute transaction with `sui client execute-signed-tx --tx-bytes <TX_BYTES>`.
@@ -598,6 +680,27 @@ pub struct Opts {
     /// `sui client execute-combined-signed-tx --signed-tx-bytes <SIGNED_TX_BYTES>`.
     #[arg(long, required = false)]
     pub serialize_signed_transaction: bool,
+    #[clap(flatten)]
+    pub expiration: ExpirationOpts,
+}
+
+/// Options restricting when a transaction can be executed. Validators will not sign the
+/// transaction, and consensus will not schedule it, outside of these (inclusive) bounds. By
+/// default, a transaction does not expire.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ExpirationOpts {
+    /// The first epoch in which the transaction is valid.
+    #[arg(long)]
+    pub valid_from_epoch: Option<EpochId>,
+    /// The last epoch in which the transaction is valid.
+    #[arg(long)]
+    pub valid_until_epoch: Option<EpochId>,
+    /// The earliest time at which the transaction is valid, in milliseconds since the Unix epoch.
+    #[arg(long)]
+    pub valid_from_ms: Option<u64>,
+    /// The latest time at which the transaction is valid, in milliseconds since the Unix epoch.
+    #[arg(long)]
+    pub valid_until_ms: Option<u64>,
 }
 
 /// Global options with gas
@@ -617,8 +720,10 @@ impl Opts {
         Self {
             gas_budget: Some(gas_budget),
             dry_run: false,
+            trace: None,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            expiration: ExpirationOpts::default(),
         }
     }
     /// Uses the passed gas_budget for the gas budget variable, sets dry run to true,
@@ -627,9 +732,63 @@ impl Opts {
         Self {
             gas_budget: Some(gas_budget),
             dry_run: true,
+            trace: None,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            expiration: ExpirationOpts::default(),
+        }
+    }
+}
+
+impl ExpirationOpts {
+    /// The narrowest form of expiration that expresses these bounds. Fails if a lower bound is
+    /// greater than its upper bound, as no transaction could ever be valid.
+    pub fn to_expiration(&self) -> Result<TransactionExpiration, anyhow::Error> {
+        if let (Some(from), Some(until)) = (self.valid_from_epoch, self.valid_until_epoch) {
+            ensure!(
+                from <= until,
+                "--valid-from-epoch ({from}) must not be greater than --valid-until-epoch ({until})"
+            );
+        }
+
+        if let (Some(from), Some(until)) = (self.valid_from_ms, self.valid_until_ms) {
+            ensure!(
+                from <= until,
+                "--valid-from-ms ({from}) must not be greater than --valid-until-ms ({until})"
+            );
         }
+
+        Ok(match *self {
+            Self {
+                valid_from_epoch: None,
+                valid_until_epoch: None,
+                valid_from_ms: None,
+                valid_until_ms: None,
+            } => TransactionExpiration::None,
+            Self {
+                valid_from_epoch: None,
+                valid_until_epoch: Some(epoch),
+                valid_from_ms: None,
+                valid_until_ms: None,
+            } => TransactionExpiration::Epoch(epoch),
+            Self {
+                valid_from_epoch: None,
+                valid_until_epoch: None,
+                valid_from_ms: None,
+                valid_until_ms: Some(timestamp_ms),
+            } => TransactionExpiration::Timestamp(timestamp_ms),
+            Self {
+                valid_from_epoch,
+                valid_until_epoch,
+                valid_from_ms,
+                valid_until_ms,
+            } => TransactionExpiration::ValidDuring {
+                min_epoch: valid_from_epoch,
+                max_epoch: valid_until_epoch,
+                min_timestamp_ms: valid_from_ms,
+                max_timestamp_ms: valid_until_ms,
+            },
+        })
     }
 }
 
@@ -687,6 +846,45 @@ impl SuiClientCommands {
                 // this will be displayed via trace info, so no output is needed here
                 SuiClientCommandResult::NoOutput
             }
+            SuiClientCommands::TraceTransaction {
+                tx_digest,
+                trace_output,
+                max_events,
+            } => {
+                move_vm_profiler::gas_profiler_feature_disabled! {
+                    bail!(
+                        "gas-profiler feature is not enabled, rebuild or reinstall with \
+                         --features gas-profiler"
+                    );
+                };
+
+                let cmd = ReplayToolCommand::TraceTransaction {
+                    tx_digest,
+                    executor_version: None,
+                    protocol_version: None,
+                    trace_output,
+                    max_events,
+                };
+                let rpc = context.config.get_active_env()?.rpc.clone();
+                let _command_result =
+                    sui_replay::execute_replay_command(Some(rpc), false, false, None, None, cmd)
+                        .await?;
+                SuiClientCommandResult::NoOutput
+            }
+            SuiClientCommands::StepTrace {
+                path,
+                package_path,
+                print,
+            } => {
+                let cmd = ReplayToolCommand::StepTrace {
+                    path,
+                    package_path,
+                    print,
+                };
+                let _command_result =
+                    sui_replay::execute_replay_command(None, false, false, None, None, cmd).await?;
+                SuiClientCommandResult::NoOutput
+            }
             SuiClientCommands::ReplayTransaction {
                 tx_digest,
                 gas_info: _,
@@ -768,7 +966,7 @@ impl SuiClientCommands {
                 coin_type,
                 with_coins,
             } => {
-                let address = get_identity_address(address, context)?;
+                let address = get_identity_address(address, context).await?;
                 let client = context.get_client().await?;
 
                 let mut objects: Vec<Coin> = Vec::new();
@@ -853,6 +1051,7 @@ impl SuiClientCommands {
                 build_config,
                 skip_dependency_verification,
                 with_unpublished_dependencies,
+                skip_compatibility_check,
                 opts,
             } => {
                 let sender = context.try_get_object_owner(&opts.gas).await?;
@@ -872,6 +1071,7 @@ impl SuiClientCommands {
                         upgrade_capability,
                         with_unpublished_dependencies,
                         skip_dependency_verification,
+                        skip_compatibility_check,
                     )
                     .await?;
                 let tx_kind = client
@@ -887,7 +1087,7 @@ impl SuiClientCommands {
                     .await?;
 
                 let result = dry_run_or_execute_or_serialize(
-                    sender, tx_kind, context, None, None, opts.gas, opts.rest,
+                    sender, None, tx_kind, context, None, None, opts.gas, opts.rest,
                 )
                 .await?;
 
@@ -961,7 +1161,7 @@ impl SuiClientCommands {
                     )
                     .await?;
                 let result = dry_run_or_execute_or_serialize(
-                    sender, tx_kind, context, None, None, opts.gas, opts.rest,
+                    sender, None, tx_kind, context, None, None, opts.gas, opts.rest,
                 )
                 .await?;
 
@@ -1145,7 +1345,7 @@ impl SuiClientCommands {
                 };
 
                 dry_run_or_execute_or_serialize(
-                    sender, tx_kind, context, None, gas_price, opts.gas, opts.rest,
+                    sender, None, tx_kind, context, None, gas_price, opts.gas, opts.rest,
                 )
                 .await?
             }
@@ -1156,14 +1356,14 @@ impl SuiClientCommands {
                 opts,
             } => {
                 let signer = context.get_object_owner(&object_id).await?;
-                let to = get_identity_address(Some(to), context)?;
+                let to = get_identity_address(Some(to), context).await?;
                 let client = context.get_client().await?;
                 let tx_kind = client
                     .transaction_builder()
                     .transfer_object_tx_kind(object_id, to)
                     .await?;
                 dry_run_or_execute_or_serialize(
-                    signer, tx_kind, context, None, None, opts.gas, opts.rest,
+                    signer, None, tx_kind, context, None, None, opts.gas, opts.rest,
                 )
                 .await?
             }
@@ -1175,13 +1375,14 @@ impl SuiClientCommands {
                 opts,
             } => {
                 let signer = context.get_object_owner(&object_id).await?;
-                let to = get_identity_address(Some(to), context)?;
+                let to = get_identity_address(Some(to), context).await?;
                 let client = context.get_client().await?;
                 let tx_kind = client
                     .transaction_builder()
                     .transfer_sui_tx_kind(to, amount);
                 dry_run_or_execute_or_serialize(
                     signer,
+                    None,
                     tx_kind,
                     context,
                     None,
@@ -1214,11 +1415,11 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
-                let recipients = recipients
-                    .into_iter()
-                    .map(|x| get_identity_address(Some(x), context))
-                    .collect::<Result<Vec<SuiAddress>, anyhow::Error>>()
-                    .map_err(|e| anyhow!("{e}"))?;
+                let mut recipient_addresses = Vec::with_capacity(recipients.len());
+                for recipient in recipients {
+                    recipient_addresses.push(get_identity_address(Some(recipient), context).await?);
+                }
+                let recipients = recipient_addresses;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
                 let tx_kind = client
@@ -1233,7 +1434,7 @@ impl SuiClientCommands {
                 }
 
                 dry_run_or_execute_or_serialize(
-                    signer, tx_kind, context, None, None, opts.gas, opts.rest,
+                    signer, None, tx_kind, context, None, None, opts.gas, opts.rest,
                 )
                 .await?
             }
@@ -1260,11 +1461,11 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
-                let recipients = recipients
-                    .into_iter()
-                    .map(|x| get_identity_address(Some(x), context))
-                    .collect::<Result<Vec<SuiAddress>, anyhow::Error>>()
-                    .map_err(|e| anyhow!("{e}"))?;
+                let mut recipient_addresses = Vec::with_capacity(recipients.len());
+                for recipient in recipients {
+                    recipient_addresses.push(get_identity_address(Some(recipient), context).await?);
+                }
+                let recipients = recipient_addresses;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
                 let tx_kind = client
@@ -1273,6 +1474,7 @@ impl SuiClientCommands {
 
                 dry_run_or_execute_or_serialize(
                     signer,
+                    None,
                     tx_kind,
                     context,
                     Some(input_coins),
@@ -1292,12 +1494,13 @@ impl SuiClientCommands {
                     !input_coins.is_empty(),
                     "PayAllSui transaction requires a non-empty list of input coins"
                 );
-                let recipient = get_identity_address(Some(recipient), context)?;
+                let recipient = get_identity_address(Some(recipient), context).await?;
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
                 let tx_kind = client.transaction_builder().pay_all_sui_tx_kind(recipient);
                 dry_run_or_execute_or_serialize(
                     signer,
+                    None,
                     tx_kind,
                     context,
                     Some(input_coins),
@@ -1309,7 +1512,7 @@ impl SuiClientCommands {
             }
 
             SuiClientCommands::Objects { address } => {
-                let address = get_identity_address(address, context)?;
+                let address = get_identity_address(address, context).await?;
                 let client = context.get_client().await?;
                 let mut objects: Vec<SuiObjectResponse> = Vec::new();
                 let mut cursor = None;
@@ -1362,7 +1565,7 @@ impl SuiClientCommands {
                 })
             }
             SuiClientCommands::Gas { address } => {
-                let address = get_identity_address(address, context)?;
+                let address = get_identity_address(address, context).await?;
                 let coins = context
                     .gas_objects(address)
                     .await?
@@ -1373,7 +1576,7 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Gas(coins)
             }
             SuiClientCommands::Faucet { address, url } => {
-                let address = get_identity_address(address, context)?;
+                let address = get_identity_address(address, context).await?;
                 let url = if let Some(url) = url {
                     url
                 } else {
@@ -1423,7 +1626,7 @@ impl SuiClientCommands {
                     .await?;
                 let signer = context.get_object_owner(&coin_id).await?;
                 dry_run_or_execute_or_serialize(
-                    signer, tx_kind, context, None, None, opts.gas, opts.rest,
+                    signer, None, tx_kind, context, None, None, opts.gas, opts.rest,
                 )
                 .await?
             }
@@ -1440,7 +1643,7 @@ impl SuiClientCommands {
                     .await?;
 
                 dry_run_or_execute_or_serialize(
-                    signer, tx_kind, context, None, None, opts.gas, opts.rest,
+                    signer, None, tx_kind, context, None, None, opts.gas, opts.rest,
                 )
                 .await?
             }
@@ -1454,7 +1657,7 @@ impl SuiClientCommands {
                 }
 
                 if let Some(address) = address {
-                    let address = get_identity_address(Some(address), context)?;
+                    let address = get_identity_address(Some(address), context).await?;
                     if !context.config.keystore.addresses().contains(&address) {
                         return Err(anyhow!("Address {} not managed by wallet", address));
                     }
@@ -1500,6 +1703,44 @@ impl SuiClientCommands {
                 let response = context.execute_transaction_may_fail(transaction).await?;
                 SuiClientCommandResult::TransactionBlock(response)
             }
+            SuiClientCommands::ExecuteMultiPartyTx {
+                tx_bytes,
+                signatures,
+                multisig_pks,
+                multisig_weights,
+                multisig_threshold,
+            } => {
+                let data = bcs::from_bytes(
+                    &Base64::try_from(tx_bytes)
+                    .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+                    .to_vec()
+                    .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+                ).map_err(|_| anyhow!("Failed to parse tx bytes, check if it matches the output of sui client commands with --serialize-unsigned-transaction"))?;
+
+                let mut sigs = Vec::new();
+                for sig in signatures {
+                    sigs.push(
+                        GenericSignature::from_bytes(
+                            &Base64::try_from(sig)
+                                .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+                                .to_vec()
+                                .map_err(|e| anyhow!(e))?,
+                        )
+                        .map_err(|_| anyhow!("Invalid generic signature"))?,
+                    );
+                }
+
+                let multisig_pk = multisig_threshold
+                    .map(|threshold| {
+                        MultiSigPublicKey::new(multisig_pks, multisig_weights, threshold)
+                    })
+                    .transpose()?;
+                let sigs = collect_signatures(&data, sigs, multisig_pk)?;
+                let transaction = Transaction::from_generic_sig_data(data, sigs);
+
+                let response = context.execute_transaction_may_fail(transaction).await?;
+                SuiClientCommandResult::TransactionBlock(response)
+            }
             SuiClientCommands::ExecuteCombinedSignedTx { signed_tx_bytes } => {
                 let data: SenderSignedData = bcs::from_bytes(
                     &Base64::try_from(signed_tx_bytes)
@@ -1622,6 +1863,7 @@ pub(crate) async fn upgrade_package(
     upgrade_capability: ObjectID,
     with_unpublished_dependencies: bool,
     skip_dependency_verification: bool,
+    skip_compatibility_check: bool,
 ) -> Result<(ObjectID, Vec<Vec<u8>>, PackageDependencies, [u8; 32], u8), anyhow::Error> {
     let (dependencies, compiled_modules, compiled_package, package_id) = compile_package(
         read_api,
@@ -1665,6 +1907,20 @@ pub(crate) async fn upgrade_package(
     // policy at the moment. To change the policy you can call a Move function in the
     // `package` module to change this policy.
     let upgrade_policy = upgrade_cap.policy;
+
+    // Check compatibility locally, so that incompatible upgrades fail before any gas is spent.
+    if !skip_compatibility_check {
+        let report = check_upgrade_compatibility(
+            read_api,
+            upgrade_cap.package.bytes,
+            &compiled_package,
+            upgrade_policy,
+        )
+        .await?;
+        if !report.is_compatible() {
+            bail!("{report}\nTo send the upgrade anyway, use the --skip-compatibility-check flag.");
+        }
+    }
     let package_digest = compiled_package.get_package_digest(with_unpublished_dependencies);
 
     Ok((
@@ -2578,6 +2834,54 @@ pub async fn execute_dry_run(
     Ok(SuiClientCommandResult::DryRun(response))
 }
 
+/// Execute the transaction locally against the latest network state, recording a step-level
+/// trace of its execution to `trace_output`. Unlike a dry run, the gas payment is selected from the
+/// signer's (or sponsor's) coins rather than mocked.
+async fn trace_dry_run(
+    context: &WalletContext,
+    client: &SuiClient,
+    signer: SuiAddress,
+    kind: TransactionKind,
+    gas_budget: Option<u64>,
+    gas_price: u64,
+    gas_payment: Option<Vec<ObjectID>>,
+    sponsor: Option<SuiAddress>,
+    trace_output: PathBuf,
+) -> Result<(), anyhow::Error> {
+    move_vm_profiler::gas_profiler_feature_disabled! {
+        bail!(
+            "gas-profiler feature is not enabled, rebuild or reinstall with \
+             --features gas-profiler"
+        );
+    };
+
+    let gas_budget = match gas_budget {
+        Some(gas_budget) => gas_budget,
+        None => max_gas_budget(client).await?,
+    };
+    let tx_data = client
+        .transaction_builder()
+        .tx_data(
+            signer,
+            kind,
+            gas_budget,
+            gas_price,
+            gas_payment.unwrap_or_default(),
+            sponsor,
+        )
+        .await?;
+
+    let cmd = ReplayToolCommand::TraceDryRun {
+        tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+        executor_version: None,
+        trace_output: Some(trace_output),
+        max_events: DEFAULT_MAX_EVENTS,
+    };
+    let rpc = context.config.get_active_env()?.rpc.clone();
+    sui_replay::execute_replay_command(Some(rpc), false, false, None, None, cmd).await?;
+    Ok(())
+}
+
 /// Call a dry run with the transaction data to estimate the gas budget.
 /// The estimated gas budget is computed as following:
 /// * the maximum between A and B, where:
@@ -2609,6 +2913,75 @@ pub async fn estimate_gas_budget(
     Ok(computation_cost_with_overhead.max(if gas_usage < 0 { 0 } else { gas_usage as u64 }))
 }
 
+/// Matches each of `signatures` to the signer of `tx_data` it is from, after combining the partial
+/// signatures of the members of `multisig_pk` into a multisig signature, and checks that every
+/// signer has signed. Returns the signatures in the order of the transaction's signers.
+pub fn collect_signatures(
+    tx_data: &TransactionData,
+    signatures: Vec<GenericSignature>,
+    multisig_pk: Option<MultiSigPublicKey>,
+) -> Result<Vec<GenericSignature>, anyhow::Error> {
+    let signers = tx_data.signers();
+    let mut collected = BTreeMap::new();
+    let mut partials = vec![];
+
+    for signature in signatures {
+        let address =
+            SuiAddress::try_from(&signature).map_err(|e| anyhow!("Invalid signature: {e}"))?;
+        if signers.contains(&address) {
+            if collected.insert(address, signature).is_some() {
+                bail!("Found more than one signature from {address}");
+            }
+            continue;
+        }
+
+        let member_index = match (&multisig_pk, &signature) {
+            (Some(multisig_pk), GenericSignature::Signature(_)) => multisig_pk.get_index(
+                &signature
+                    .to_public_key()
+                    .map_err(|e| anyhow!("Invalid signature: {e}"))?,
+            ),
+            _ => None,
+        };
+        let Some(index) = member_index else {
+            bail!("Signature from {address} is not from a signer of the transaction");
+        };
+        partials.push((index, signature));
+    }
+
+    if let Some(multisig_pk) = multisig_pk {
+        let address = SuiAddress::from(&multisig_pk);
+        if !signers.contains(&address) {
+            bail!("Multisig address {address} is not a signer of the transaction");
+        }
+        if !partials.is_empty() {
+            if collected.contains_key(&address) {
+                bail!("Found both a signature and partial signatures from multisig {address}");
+            }
+            // Partial signatures must be combined in the order of the multisig's public keys.
+            partials.sort_by_key(|(index, _)| *index);
+            let sigs = partials.into_iter().map(|(_, sig)| sig).collect();
+            let multisig = MultiSig::combine(sigs, multisig_pk)
+                .map_err(|e| anyhow!("Cannot combine partial signatures of {address}: {e}"))?;
+            collected.insert(address, multisig.into());
+        }
+    }
+
+    let missing: Vec<_> = signers
+        .iter()
+        .filter(|signer| !collected.contains_key(signer))
+        .map(|signer| signer.to_string())
+        .collect();
+    if !missing.is_empty() {
+        bail!("Missing signatures from: {}", missing.join(", "));
+    }
+
+    Ok(signers
+        .into_iter()
+        .filter_map(|signer| collected.remove(&signer))
+        .collect())
+}
+
 /// Queries the protocol config for the maximum gas allowed in a transaction.
 pub async fn max_gas_budget(client: &SuiClient) -> Result<u64, anyhow::Error> {
     let cfg = client.read_api().get_protocol_config(None).await?;
@@ -2625,9 +2998,13 @@ pub async fn max_gas_budget(client: &SuiClient) -> Result<u64, anyhow::Error> {
 ///
 /// This basically extracts the logical code for each command that deals with dry run, executing,
 /// or serializing a transaction and puts it in a function to reduce code duplication.
+///
+/// If there is a `sponsor`, it owns the gas coins and pays for gas, and signing the transaction
+/// requires the keys of both the signer and the sponsor.
 // TODO (stefan): Add gas_price option for all commands and remove it from this function
 pub(crate) async fn dry_run_or_execute_or_serialize(
     signer: SuiAddress,
+    sponsor: Option<SuiAddress>,
     tx_kind: TransactionKind,
     context: &mut WalletContext,
     gas_payment: Option<Vec<ObjectID>>,
@@ -2645,6 +3022,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         !serialize_unsigned_transaction || !serialize_signed_transaction,
         "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
     );
+    let expiration = opts.expiration.to_expiration()?;
     let gas_price = if let Some(gas_price) = gas_price {
         gas_price
     } else {
@@ -2658,6 +3036,20 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
 
     let client = context.get_client().await?;
     if dry_run {
+        if let Some(trace_output) = opts.trace {
+            trace_dry_run(
+                context,
+                &client,
+                signer,
+                tx_kind.clone(),
+                gas_budget,
+                gas_price,
+                gas.clone(),
+                sponsor,
+                trace_output,
+            )
+            .await?;
+        }
         return execute_dry_run(
             &client,
             signer,
@@ -2665,7 +3057,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas.clone(),
-            None,
+            sponsor,
         )
         .await;
     }
@@ -2679,7 +3071,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
                 tx_kind.clone(),
                 gas_price,
                 gas.clone(),
-                None,
+                sponsor,
             )
             .await?
         }
@@ -2693,21 +3085,37 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas.unwrap_or_default(),
-            None,
+            sponsor,
         )
-        .await?;
+        .await?
+        .with_expiration(expiration);
 
     if serialize_unsigned_transaction {
         Ok(SuiClientCommandResult::SerializedUnsignedTransaction(
             tx_data,
         ))
     } else {
-        let signature = context.config.keystore.sign_secure(
-            &tx_data.sender(),
-            &tx_data,
-            Intent::sui_transaction(),
-        )?;
-        let sender_signed_data = SenderSignedData::new_from_sender_signature(tx_data, signature);
+        let mut signatures = vec![];
+        for address in tx_data.signers() {
+            let signature =
+                context
+                    .config
+                    .keystore
+                    .sign_secure(&address, &tx_data, Intent::sui_transaction());
+            let signature = if address == tx_data.sender() {
+                signature?
+            } else {
+                signature.map_err(|e| {
+                    anyhow!(
+                        "Cannot sign as gas sponsor {address}: {e}\nSerialize the transaction with \
+                         --serialize-unsigned-transaction, and execute it with the signatures of \
+                         all its signers using `sui client execute-multi-party-tx`."
+                    )
+                })?
+            };
+            signatures.push(signature.into());
+        }
+        let sender_signed_data = SenderSignedData::new(tx_data, signatures);
         if serialize_signed_transaction {
             Ok(SuiClientCommandResult::SerializedSignedTransaction(
                 sender_signed_data,
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use move_command_line_common::{
     address::{NumericalAddress, ParsedAddress},
@@ -9,11 +9,13 @@ use move_command_line_common::{
 };
 use move_core_types::runtime_value::MoveValue;
 use sui_types::{
-    base_types::{ObjectID, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR},
+    base_types::{
+        ObjectID, SuiAddress, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR,
+    },
     Identifier, TypeTag,
 };
 
-use crate::{err, error, sp};
+use crate::{err, error, key_identity::is_name_service_name, sp};
 
 use super::error::{PTBResult, Span, Spanned};
 
@@ -33,10 +35,15 @@ pub const WARN_SHADOWS: &str = "warn-shadows";
 pub const GAS_BUDGET: &str = "gas-budget";
 pub const SUMMARY: &str = "summary";
 pub const GAS_COIN: &str = "gas-coin";
+pub const SPONSOR: &str = "sponsor";
 pub const JSON: &str = "json";
 pub const DRY_RUN: &str = "dry-run";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
+pub const VALID_FROM_EPOCH: &str = "valid-from-epoch";
+pub const VALID_UNTIL_EPOCH: &str = "valid-until-epoch";
+pub const VALID_FROM_MS: &str = "valid-from-ms";
+pub const VALID_UNTIL_MS: &str = "valid-until-ms";
 
 // Types
 pub const U8: &str = "u8";
@@ -72,10 +79,15 @@ pub const COMMANDS: &[&str] = &[
     GAS_BUDGET,
     SUMMARY,
     GAS_COIN,
+    SPONSOR,
     JSON,
     DRY_RUN,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
+    VALID_FROM_EPOCH,
+    VALID_UNTIL_EPOCH,
+    VALID_FROM_MS,
+    VALID_UNTIL_MS,
 ];
 
 pub fn is_keyword(s: &str) -> bool {
@@ -100,6 +112,38 @@ pub struct Program {
     pub warn_shadows_set: bool,
 }
 
+impl Program {
+    /// The SuiNS names (`example.sui` or `@example`) used as arguments in the program, with the
+    /// location of their first use.
+    pub fn name_service_names(&self) -> BTreeMap<String, Span> {
+        let mut names = BTreeMap::new();
+        for sp!(_, command) in &self.commands {
+            let args: Vec<&Spanned<Argument>> = match command {
+                ParsedPTBCommand::TransferObjects(objs, to) => {
+                    objs.value.iter().chain(std::iter::once(to)).collect()
+                }
+                ParsedPTBCommand::SplitCoins(arg, args)
+                | ParsedPTBCommand::MergeCoins(arg, args) => {
+                    std::iter::once(arg).chain(args.value.iter()).collect()
+                }
+                ParsedPTBCommand::MakeMoveVec(_, args) => args.value.iter().collect(),
+                ParsedPTBCommand::MoveCall(_, _, args) => args.iter().collect(),
+                ParsedPTBCommand::Assign(_, Some(arg)) | ParsedPTBCommand::Upgrade(_, arg) => {
+                    vec![arg]
+                }
+                ParsedPTBCommand::Assign(_, None)
+                | ParsedPTBCommand::Publish(_)
+                | ParsedPTBCommand::WarnShadows
+                | ParsedPTBCommand::Preview => vec![],
+            };
+            for sp!(loc, arg) in args {
+                arg.collect_name_service_names(*loc, &mut names);
+            }
+        }
+        names
+    }
+}
+
 /// The `ProgramMetadata` struct holds metadata about a PTB program, such as whether the preview
 /// flag was set, json output was set, etc.
 #[derive(Debug, Clone)]
@@ -109,9 +153,31 @@ pub struct ProgramMetadata {
     pub serialize_unsigned_set: bool,
     pub serialize_signed_set: bool,
     pub gas_object_id: Option<Spanned<ObjectID>>,
+    pub sponsor: Option<Spanned<AddressOrName>>,
     pub json_set: bool,
     pub dry_run_set: bool,
     pub gas_budget: Option<Spanned<u64>>,
+    pub valid_from_epoch: Option<Spanned<u64>>,
+    pub valid_until_epoch: Option<Spanned<u64>>,
+    pub valid_from_ms: Option<Spanned<u64>>,
+    pub valid_until_ms: Option<Spanned<u64>>,
+}
+
+/// An address given as a literal, or as a SuiNS name (`example.sui` or `@example`) that is
+/// resolved before the PTB is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressOrName {
+    Address(SuiAddress),
+    Name(String),
+}
+
+impl fmt::Display for AddressOrName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressOrName::Address(address) => write!(f, "{address}"),
+            AddressOrName::Name(name) => write!(f, "{name}"),
+        }
+    }
 }
 
 /// A parsed module access consisting of the address, module name, and function name.
@@ -298,6 +364,34 @@ impl Argument {
     }
 }
 
+impl Argument {
+    /// The SuiNS name this argument refers to, if it has the shape of one.
+    pub fn as_name_service_name(&self) -> Option<String> {
+        let name = match self {
+            Argument::Identifier(i) => i.clone(),
+            Argument::VariableAccess(..) => self.to_string(),
+            _ => return None,
+        };
+        is_name_service_name(&name).then_some(name)
+    }
+
+    fn collect_name_service_names(&self, loc: Span, names: &mut BTreeMap<String, Span>) {
+        match self {
+            Argument::Vector(vs) => {
+                for sp!(loc, v) in vs {
+                    v.collect_name_service_names(*loc, names);
+                }
+            }
+            Argument::Option(sp!(loc, Some(v))) => v.collect_name_service_names(*loc, names),
+            _ => {
+                if let Some(name) = self.as_name_service_name() {
+                    names.entry(name).or_insert(loc);
+                }
+            }
+        }
+    }
+}
+
 impl fmt::Display for Argument {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
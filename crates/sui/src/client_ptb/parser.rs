@@ -8,7 +8,10 @@ use move_command_line_common::{
     parser::{parse_u128, parse_u16, parse_u256, parse_u32, parse_u64, parse_u8},
     types::{ParsedFqName, ParsedModuleId, ParsedStructType, ParsedType},
 };
-use sui_types::{base_types::ObjectID, Identifier};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    Identifier,
+};
 
 use crate::{
     client_ptb::{
@@ -19,7 +22,10 @@ use crate::{
 };
 
 use super::{
-    ast::{self as A, is_keyword, Argument, ModuleAccess, ParsedPTBCommand, ParsedProgram},
+    ast::{
+        self as A, is_keyword, AddressOrName, Argument, ModuleAccess, ParsedPTBCommand,
+        ParsedProgram,
+    },
     error::{PTBError, PTBResult, Span, Spanned},
     lexer::Lexer,
     token::{Lexeme, Token},
@@ -42,7 +48,12 @@ struct ProgramParsingState {
     json_set: bool,
     dry_run_set: bool,
     gas_object_id: Option<Spanned<ObjectID>>,
+    sponsor: Option<Spanned<AddressOrName>>,
     gas_budget: Option<Spanned<u64>>,
+    valid_from_epoch: Option<Spanned<u64>>,
+    valid_until_epoch: Option<Spanned<u64>>,
+    valid_from_ms: Option<Spanned<u64>>,
+    valid_until_ms: Option<Spanned<u64>>,
 }
 
 impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
@@ -64,7 +75,12 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 json_set: false,
                 dry_run_set: false,
                 gas_object_id: None,
+                sponsor: None,
                 gas_budget: None,
+                valid_from_epoch: None,
+                valid_until_epoch: None,
+                valid_from_ms: None,
+                valid_until_ms: None,
             },
         })
     }
@@ -104,6 +120,21 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 }};
             }
 
+            macro_rules! bound {
+                ($bound:ident, $name:literal) => {{
+                    let value = try_!(self.parse_u64()).widen_span(sp);
+                    if let Some(other) = self.state.$bound.replace(value) {
+                        self.state.errors.extend([
+                            err!(other.span, "Multiple {} found. First set here.", $name),
+                            err!(value.span => help: {
+                                "PTBs can have at most one {}.", $name
+                            }, "Set again here."),
+                        ]);
+                        self.fast_forward_to_next_command();
+                    }
+                }};
+            }
+
             match lexeme {
                 L(T::Command, A::SERIALIZE_UNSIGNED) => flag!(serialize_unsigned_set),
                 L(T::Command, A::SERIALIZE_SIGNED) => flag!(serialize_signed_set),
@@ -116,8 +147,32 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     let specifier = try_!(self.parse_gas_specifier());
                     self.state.gas_object_id = Some(specifier);
                 }
+                L(T::Command, A::SPONSOR) => {
+                    let sponsor = try_!(self.parse_sponsor()).widen_span(sp);
+                    let sponsor_span = sponsor.span;
+                    if let Some(other) = self.state.sponsor.replace(sponsor) {
+                        self.state.errors.extend([
+                            err!(
+                                other.span,
+                                "Multiple sponsors found. Sponsor first set here.",
+                            ),
+                            err!(sponsor_span => help: {
+                                "PTBs can have at most one sponsor."
+                            },"Sponsor set again here."),
+                        ]);
+                        self.fast_forward_to_next_command();
+                    }
+                }
+                L(T::Command, A::VALID_FROM_EPOCH) => {
+                    bound!(valid_from_epoch, "--valid-from-epoch")
+                }
+                L(T::Command, A::VALID_UNTIL_EPOCH) => {
+                    bound!(valid_until_epoch, "--valid-until-epoch")
+                }
+                L(T::Command, A::VALID_FROM_MS) => bound!(valid_from_ms, "--valid-from-ms"),
+                L(T::Command, A::VALID_UNTIL_MS) => bound!(valid_until_ms, "--valid-until-ms"),
                 L(T::Command, A::GAS_BUDGET) => {
-                    let budget = try_!(self.parse_gas_budget()).widen_span(sp);
+                    let budget = try_!(self.parse_u64()).widen_span(sp);
                     if let Some(other) = self.state.gas_budget.replace(budget) {
                         self.state.errors.extend([
                             err!(
@@ -205,9 +260,14 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     serialize_unsigned_set: self.state.serialize_unsigned_set,
                     serialize_signed_set: self.state.serialize_signed_set,
                     gas_object_id: self.state.gas_object_id,
+                    sponsor: self.state.sponsor,
                     json_set: self.state.json_set,
                     dry_run_set: self.state.dry_run_set,
                     gas_budget: self.state.gas_budget,
+                    valid_from_epoch: self.state.valid_from_epoch,
+                    valid_until_epoch: self.state.valid_until_epoch,
+                    valid_from_ms: self.state.valid_from_ms,
+                    valid_until_ms: self.state.valid_until_ms,
                 },
             ))
         } else {
@@ -355,9 +415,9 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         Ok(sp.wrap(ParsedPTBCommand::MoveCall(function, ty_args, args)))
     }
 
-    /// Parse a gas-budget command.
+    /// Parse the argument of a command that takes a u64, e.g. a gas-budget command.
     /// The expected format is: `--gas-budget <u64>`
-    fn parse_gas_budget(&mut self) -> PTBResult<Spanned<u64>> {
+    fn parse_u64(&mut self) -> PTBResult<Spanned<u64>> {
         Ok(match self.parse_argument()? {
             sp!(sp, Argument::U64(u)) => sp.wrap(u),
             sp!(sp, Argument::InferredNum(n)) => {
@@ -374,6 +434,21 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
             .parse_address_literal()?
             .map(|a| ObjectID::from(a.into_inner())))
     }
+
+    /// Parse a sponsor.
+    /// The expected format is: `--sponsor <address>`, where the address is a literal, or a SuiNS
+    /// name (`@example` or `example.sui`).
+    fn parse_sponsor(&mut self) -> PTBResult<Spanned<AddressOrName>> {
+        Ok(match self.parse_argument()? {
+            sp!(sp, Argument::Address(a)) => {
+                sp.wrap(AddressOrName::Address(SuiAddress::from(a.into_inner())))
+            }
+            sp!(sp, arg) => match arg.as_name_service_name() {
+                Some(name) => sp.wrap(AddressOrName::Name(name)),
+                None => error!(sp, "Expected an address or a SuiNS name"),
+            },
+        })
+    }
 }
 
 /// Methods for parsing arguments and types in commands
@@ -412,7 +487,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 self.parse_number(sp.wrap(&number))?
             }
 
-            L(T::At, _) => self.parse_address_literal()?.map(V::Address),
+            L(T::At, _) => self.parse_address_or_name()?,
 
             L(T::Ident, A::NONE) => {
                 self.bump();
@@ -695,6 +770,16 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         })
     }
 
+    /// Parse an address literal, or a SuiNS name given as `@name`. Names are kept as identifiers,
+    /// which are bound to the addresses they resolve to before the PTB is built.
+    fn parse_address_or_name(&mut self) -> PTBResult<Spanned<Argument>> {
+        let sp!(sp, _) = self.expect(Token::At)?;
+        Ok(match self.parse_address()?.widen_span(sp) {
+            sp!(sp, ParsedAddress::Numerical(n)) => sp.wrap(Argument::Address(n)),
+            sp!(sp, ParsedAddress::Named(n)) => sp.wrap(Argument::Identifier(format!("@{n}"))),
+        })
+    }
+
     // Parse an array of arguments. Each element of the array is separated by a comma.
     fn parse_array(&mut self) -> PTBResult<Spanned<Vec<Spanned<Argument>>>> {
         use Lexeme as L;
@@ -809,6 +894,8 @@ mod tests {
             "foo.bar.baz.qux",
             "foo.0",
             "foo.0.1",
+            // SuiNS names
+            "@example",
         ];
         let mut parsed = Vec::new();
         for input in inputs {
@@ -827,8 +914,7 @@ mod tests {
     fn test_parse_args_invalid() {
         let inputs = vec![
             // Integers
-            "0xfffu8", // addresses
-            "@n",      // options
+            "0xfffu8", // options
             "some",
             "some(",
             "some(1",
@@ -1019,4 +1105,105 @@ mod tests {
         }
         insta::assert_debug_snapshot!(parsed);
     }
+
+    #[test]
+    fn test_parse_expiration() {
+        let parse = |input: &str| {
+            let x = shlex::split(input).unwrap();
+            ProgramParser::new(x.iter().map(|x| x.as_str()))
+                .unwrap()
+                .parse()
+        };
+
+        let (_, metadata) = parse(
+            "--valid-from-epoch 1 --valid-until-epoch 2u64 \
+             --valid-from-ms 1000 --valid-until-ms 2000 --gas-budget 1",
+        )
+        .unwrap();
+        assert_eq!(metadata.valid_from_epoch.map(|x| x.value), Some(1));
+        assert_eq!(metadata.valid_until_epoch.map(|x| x.value), Some(2));
+        assert_eq!(metadata.valid_from_ms.map(|x| x.value), Some(1000));
+        assert_eq!(metadata.valid_until_ms.map(|x| x.value), Some(2000));
+
+        let (_, metadata) = parse("--gas-budget 1").unwrap();
+        assert!(metadata.valid_from_epoch.is_none());
+        assert!(metadata.valid_until_ms.is_none());
+
+        for input in [
+            "--valid-until-epoch",
+            "--valid-until-epoch woah",
+            "--valid-until-ms -1",
+            "--valid-from-ms @0x1",
+            "--valid-from-epoch 1 --valid-from-epoch 2",
+        ] {
+            assert!(parse(input).is_err(), "Parsed {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_sponsor() {
+        let parse = |input: &str| {
+            let x = shlex::split(input).unwrap();
+            ProgramParser::new(x.iter().map(|x| x.as_str()))
+                .unwrap()
+                .parse()
+        };
+
+        let sponsor = |input: &str| parse(input).unwrap().1.sponsor.map(|x| x.value);
+        assert_eq!(
+            sponsor("--sponsor @0x1 --gas-budget 1"),
+            Some(AddressOrName::Address(SuiAddress::from(
+                ObjectID::from_single_byte(1)
+            )))
+        );
+        assert_eq!(
+            sponsor("--sponsor @example --gas-budget 1"),
+            Some(AddressOrName::Name("@example".to_string()))
+        );
+        assert_eq!(
+            sponsor("--sponsor example.sui --gas-budget 1"),
+            Some(AddressOrName::Name("example.sui".to_string()))
+        );
+
+        for input in [
+            "--sponsor",
+            "--sponsor 1",
+            "--sponsor example",
+            "--sponsor @0x1 --sponsor @0x2",
+        ] {
+            assert!(parse(input).is_err(), "Parsed {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_name_service_names() {
+        let input = "--split-coins gas [1, 2] --assign coins \
+             --transfer-objects [coins.0] example.sui \
+             --transfer-objects [coins.1] @example \
+             --move-call 0x2::pay::split_and_transfer <0x2::sui::SUI> gas 1 sub.example.sui \
+             --make-move-vec <address> [@0x1, other.sui, some(nested.sui)] \
+             --transfer-objects [gas] coins.0 \
+             --assign a example.sui \
+             --gas-budget 1";
+        let x = shlex::split(input).unwrap();
+        let (program, _) = ProgramParser::new(x.iter().map(|x| x.as_str()))
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        // Each name is reported once, at its first use. Variables, including accesses into them,
+        // and address literals are not names.
+        let names = program.name_service_names();
+        assert_eq!(
+            names.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec![
+                "@example",
+                "example.sui",
+                "nested.sui",
+                "other.sui",
+                "sub.example.sui",
+            ],
+        );
+        assert!(names["example.sui"].start < names["@example"].start);
+    }
 }
@@ -2,29 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client_commands::{dry_run_or_execute_or_serialize, Opts, OptsWithGas, SuiClientCommandResult},
+    client_commands::{
+        dry_run_or_execute_or_serialize, ExpirationOpts, Opts, OptsWithGas, SuiClientCommandResult,
+    },
     client_ptb::{
-        ast::{ParsedProgram, Program},
+        ast::{AddressOrName, ParsedProgram, Program},
         builder::PTBBuilder,
         error::{build_error_reports, PTBError},
         token::{Lexeme, Token},
     },
     displays::Pretty,
-    sp,
+    err, sp,
 };
 
 use super::{ast::ProgramMetadata, lexer::Lexer, parser::ProgramParser};
 use anyhow::{anyhow, ensure, Error};
 use clap::{arg, Args, ValueHint};
+use fastcrypto::encoding::{Base64, Encoding};
 use move_core_types::account_address::AccountAddress;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::{wallet_context::WalletContext, SuiClient};
 use sui_types::{
+    base_types::SuiAddress,
     digests::TransactionDigest,
     gas::GasCostSummary,
-    transaction::{ProgrammableTransaction, TransactionKind},
+    transaction::{ProgrammableTransaction, TransactionDataAPI, TransactionKind},
 };
 
 #[derive(Clone, Debug, Args)]
@@ -37,6 +42,17 @@ pub struct PTB {
 pub struct PTBPreview<'a> {
     pub program: &'a Program,
     pub program_metadata: &'a ProgramMetadata,
+    /// The addresses that the SuiNS names used in the program resolve to
+    pub names: &'a BTreeMap<String, SuiAddress>,
+}
+
+/// The unsigned bytes of a sponsored transaction, and the addresses that need to sign them.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsoredTransaction {
+    pub tx_bytes: String,
+    pub sender: SuiAddress,
+    pub sponsor: SuiAddress,
 }
 
 #[derive(Serialize)]
@@ -88,12 +104,26 @@ impl PTB {
             "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
         );
 
+        let names = match Self::resolve_names(&program, &program_metadata, context).await {
+            Err(errors) => {
+                let suffix = if errors.len() > 1 { "s" } else { "" };
+                let rendered = build_error_reports(&source_string, errors);
+                eprintln!("Encountered error{suffix} when resolving SuiNS names:");
+                for e in rendered.iter() {
+                    eprintln!("{:?}", e);
+                }
+                anyhow::bail!("Could not build PTB due to previous error{suffix}");
+            }
+            Ok(names) => names,
+        };
+
         if program_metadata.preview_set {
             println!(
                 "{}",
                 PTBPreview {
                     program: &program,
-                    program_metadata: &program_metadata
+                    program_metadata: &program_metadata,
+                    names: &names,
                 }
             );
             return Ok(());
@@ -101,7 +131,7 @@ impl PTB {
 
         let client = context.get_client().await?;
 
-        let (res, warnings) = Self::build_ptb(program, context, client).await;
+        let (res, warnings) = Self::build_ptb(program, &names, context, client).await;
 
         // Render warnings
         if !warnings.is_empty() {
@@ -127,14 +157,33 @@ impl PTB {
 
         // get all the metadata needed for executing the PTB: sender, gas, signing tx
         let gas = program_metadata.gas_object_id.map(|x| x.value);
+        let sponsor = match &program_metadata.sponsor {
+            None => None,
+            Some(sp!(_, AddressOrName::Address(address))) => Some(*address),
+            // Names that are also keystore aliases are not resolved through SuiNS.
+            Some(sp!(_, AddressOrName::Name(name))) => Some(match names.get(name) {
+                Some(address) => *address,
+                None => *context.config.keystore.get_address_by_alias(name.clone())?,
+            }),
+        };
 
-        // the sender is the gas object if gas is provided, otherwise the active address
-        let sender = match gas {
-            Some(gas) => context
-                .get_object_owner(&gas)
-                .await
-                .map_err(|_| anyhow!("Could not find owner for gas object ID"))?,
-            None => context
+        // the sender is the owner of the gas object if gas is provided and the PTB is not
+        // sponsored, otherwise the active address. A sponsor must own the gas object.
+        let gas_owner = match gas {
+            Some(gas) => Some(
+                context
+                    .get_object_owner(&gas)
+                    .await
+                    .map_err(|_| anyhow!("Could not find owner for gas object ID"))?,
+            ),
+            None => None,
+        };
+        let sender = match (gas_owner, sponsor) {
+            (Some(owner), Some(sponsor)) if owner != sponsor => {
+                anyhow::bail!("Gas object is owned by {owner}, not by the sponsor {sponsor}")
+            }
+            (Some(owner), None) => owner,
+            _ => context
                 .config
                 .active_address
                 .ok_or_else(|| anyhow!("No active address, cannot execute PTB"))?,
@@ -150,18 +199,50 @@ impl PTB {
             gas: program_metadata.gas_object_id.map(|x| x.value),
             rest: Opts {
                 dry_run: program_metadata.dry_run_set,
+                trace: None,
                 gas_budget: program_metadata.gas_budget.map(|x| x.value),
                 serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
                 serialize_signed_transaction: program_metadata.serialize_signed_set,
+                expiration: ExpirationOpts {
+                    valid_from_epoch: program_metadata.valid_from_epoch.map(|x| x.value),
+                    valid_until_epoch: program_metadata.valid_until_epoch.map(|x| x.value),
+                    valid_from_ms: program_metadata.valid_from_ms.map(|x| x.value),
+                    valid_until_ms: program_metadata.valid_until_ms.map(|x| x.value),
+                },
             },
         };
 
         let transaction_response = dry_run_or_execute_or_serialize(
-            sender, tx_kind, context, None, None, opts.gas, opts.rest,
+            sender, sponsor, tx_kind, context, None, None, opts.gas, opts.rest,
         )
         .await?;
 
         let transaction_response = match transaction_response {
+            SuiClientCommandResult::SerializedUnsignedTransaction(ref tx_data)
+                if tx_data.sender() != tx_data.gas_owner() =>
+            {
+                let sponsored = SponsoredTransaction {
+                    tx_bytes: Base64::encode(bcs::to_bytes(tx_data)?),
+                    sender: tx_data.sender(),
+                    sponsor: tx_data.gas_owner(),
+                };
+                if program_metadata.json_set {
+                    let json_string =
+                        serde_json::to_string_pretty(&serde_json::json!(sponsored))
+                            .map_err(|_| anyhow!("Cannot serialize PTB result to json"))?;
+                    println!("{}", json_string);
+                } else {
+                    println!("{}", sponsored.tx_bytes);
+                    eprintln!(
+                        "The transaction must be signed by its sender {} and its sponsor {}. \
+                         Sign it with `sui keytool sign --address <ADDRESS> --data <TX_BYTES>`, \
+                         and execute it with `sui client execute-multi-party-tx --tx-bytes \
+                         <TX_BYTES> --signatures <SIGNATURES>...`.",
+                        sponsored.sender, sponsored.sponsor,
+                    );
+                }
+                return Ok(());
+            }
             SuiClientCommandResult::DryRun(_) => {
                 println!("{}", transaction_response);
                 return Ok(());
@@ -214,9 +295,11 @@ impl PTB {
         Ok(())
     }
 
-    /// Exposed for testing
+    /// Exposed for testing. `names` holds the addresses that the SuiNS names used in the program
+    /// resolve to, see [PTB::resolve_names].
     pub async fn build_ptb(
         program: Program,
+        names: &BTreeMap<String, SuiAddress>,
         context: &WalletContext,
         client: SuiClient,
     ) -> (
@@ -229,11 +312,61 @@ impl PTB {
             .addresses_with_alias()
             .into_iter()
             .map(|(sa, alias)| (alias.alias.clone(), AccountAddress::from(*sa)))
+            .chain(
+                names
+                    .iter()
+                    .map(|(name, sa)| (name.clone(), AccountAddress::from(*sa))),
+            )
             .collect();
         let builder = PTBBuilder::new(starting_addresses, client.read_api());
         builder.build(program).await
     }
 
+    /// Resolve the SuiNS names used in the program, and as its sponsor, through the name service
+    /// of the active environment. Keystore aliases take precedence over names, and are not
+    /// resolved.
+    pub async fn resolve_names(
+        program: &Program,
+        program_metadata: &ProgramMetadata,
+        context: &WalletContext,
+    ) -> Result<BTreeMap<String, SuiAddress>, Vec<PTBError>> {
+        let mut names = program.name_service_names();
+        if let Some(sp!(loc, AddressOrName::Name(name))) = &program_metadata.sponsor {
+            names.entry(name.clone()).or_insert(*loc);
+        }
+
+        let mut resolved = BTreeMap::new();
+        let mut errors = vec![];
+        for (name, loc) in names {
+            if context.config.keystore.alias_exists(&name) {
+                continue;
+            }
+            let client = context
+                .get_client()
+                .await
+                .map_err(|e| vec![err!(loc, "{e}")])?;
+            match client
+                .read_api()
+                .resolve_name_service_address(name.clone())
+                .await
+            {
+                Ok(Some(address)) => {
+                    resolved.insert(name, address);
+                }
+                Ok(None) => errors.push(err!(
+                    loc,
+                    "SuiNS name '{name}' does not resolve to an address"
+                )),
+                Err(e) => errors.push(err!(loc, "Failed to resolve SuiNS name '{name}': {e}")),
+            }
+        }
+        if errors.is_empty() {
+            Ok(resolved)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Exposed for testing
     pub fn parse_ptb_commands(args: Vec<String>) -> Result<ParsedProgram, Vec<PTBError>> {
         ProgramParser::new(args.iter().map(|s| s.as_str()))
@@ -300,6 +433,22 @@ pub fn ptb_description() -> clap::Command {
             "The object ID of the gas coin to use. If not specified, it will try to use the first \
             gas coin that it finds that has at least the requested gas-budget balance."
         ))
+        .arg(arg!(
+            --"sponsor" <ADDRESS>
+            "The address or SuiNS name that pays for gas, if it is not the sender. The gas coin (if specified) \
+            must be owned by the sponsor, and the active address is the sender."
+        )
+        .long_help(
+            "The address or SuiNS name that pays for gas, if it is not the sender. The gas coin (if specified) \
+            must be owned by the sponsor, and the active address is the sender.\
+            \n If the keys of both the sender and the sponsor are in the keystore, the transaction \
+            is signed by both and executed. Otherwise, use --serialize-unsigned-transaction to get \
+            the bytes that each of them signs, and execute the transaction with \
+            `sui client execute-multi-party-tx`.\
+            \n\nExamples:\
+            \n --sponsor @sponsor_address \
+            --serialize-unsigned-transaction"
+        ))
         .arg(arg!(
             --"gas-budget" <MIST>
             "An optional gas budget for this PTB (in MIST). If gas budget is not provided, the \
@@ -307,6 +456,24 @@ pub fn ptb_description() -> clap::Command {
             the transaction. Please note that this incurs a small cost in performance due to the \
             additional dry run call."
         ))
+        .arg(arg!(
+            --"valid-from-epoch" <EPOCH>
+            "The first epoch in which the transaction is valid."
+        ))
+        .arg(arg!(
+            --"valid-until-epoch" <EPOCH>
+            "The last epoch in which the transaction is valid."
+        ))
+        .arg(arg!(
+            --"valid-from-ms" <TIMESTAMP_MS>
+            "The earliest time at which the transaction is valid, in milliseconds since the Unix \
+            epoch."
+        ))
+        .arg(arg!(
+            --"valid-until-ms" <TIMESTAMP_MS>
+            "The latest time at which the transaction is valid, in milliseconds since the Unix \
+            epoch."
+        ))
         .arg(arg!(
             --"make-move-vec" <MAKE_MOVE_VEC>
             "Given n-values of the same type, it constructs a vector. For non objects or an empty \
@@ -365,7 +532,8 @@ pub fn ptb_description() -> clap::Command {
             \n --transfer-objects [obj1, obj2, obj3] @address 
             \n --split-coins gas [1000, 5000, 75000]\
             \n --assign new_coins # bound new_coins to result of split-coins to use next\
-            \n --transfer-objects [new_coins.0, new_coins.1, new_coins.2] @to_address"
+            \n --transfer-objects [new_coins.0, new_coins.1, new_coins.2] @to_address\
+            \n --transfer-objects [obj1] example.sui # or @example, resolved through SuiNS"
         )
         .value_names(["[OBJECTS]", "TO"]))
         .arg(arg!(
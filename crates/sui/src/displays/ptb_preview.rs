@@ -3,7 +3,7 @@
 
 use crate::{
     client_ptb::{
-        ast::{GAS_BUDGET, GAS_COIN, JSON, SUMMARY, WARN_SHADOWS},
+        ast::{GAS_BUDGET, GAS_COIN, JSON, SPONSOR, SUMMARY, WARN_SHADOWS},
         ptb::PTBPreview,
     },
     sp,
@@ -24,12 +24,18 @@ impl<'a> Display for PTBPreview<'a> {
                 builder.push_record([command, vals]);
             }
         }
+        for (name, address) in self.names {
+            builder.push_record([name.as_str(), address.to_string().as_str()]);
+        }
         if let Some(gas_budget) = self.program_metadata.gas_budget {
             builder.push_record([GAS_BUDGET, gas_budget.value.to_string().as_str()]);
         }
         if let Some(gas_coin_id) = self.program_metadata.gas_object_id {
             builder.push_record([GAS_COIN, gas_coin_id.value.to_string().as_str()]);
         }
+        if let Some(sponsor) = &self.program_metadata.sponsor {
+            builder.push_record([SPONSOR, sponsor.value.to_string().as_str()]);
+        }
         if self.program_metadata.json_set {
             builder.push_record([JSON, "true"]);
         }
@@ -3,10 +3,10 @@
 
 use std::{fmt::Display, str::FromStr};
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use serde::Serialize;
 use sui_keys::keystore::{AccountKeystore, Keystore};
-use sui_sdk::wallet_context::WalletContext;
+use sui_sdk::{wallet_context::WalletContext, SuiClient};
 use sui_types::base_types::SuiAddress;
 
 /// An address or an alias associated with a key in the wallet
@@ -40,18 +40,43 @@ impl Display for KeyIdentity {
 }
 
 /// Get the SuiAddress corresponding to this key identity.
-/// If no string is provided, then the current active address is returned.
-pub fn get_identity_address(
+/// If no string is provided, then the current active address is returned. Aliases that are not in
+/// the keystore but look like SuiNS names (`example.sui` or `@example`) are resolved through the
+/// name service of the active environment.
+pub async fn get_identity_address(
     input: Option<KeyIdentity>,
     ctx: &mut WalletContext,
 ) -> Result<SuiAddress, Error> {
-    if let Some(addr) = input {
-        get_identity_address_from_keystore(addr, &ctx.config.keystore)
-    } else {
-        Ok(ctx.active_address()?)
+    match input {
+        Some(KeyIdentity::Alias(alias))
+            if is_name_service_name(&alias) && !ctx.config.keystore.alias_exists(&alias) =>
+        {
+            let client = ctx.get_client().await?;
+            resolve_name_service_name(&client, &alias).await
+        }
+        Some(addr) => get_identity_address_from_keystore(addr, &ctx.config.keystore),
+        None => Ok(ctx.active_address()?),
     }
 }
 
+/// Whether `name` has the shape of a SuiNS name, i.e. `example.sui`, `sub.example.sui`,
+/// `@example` or `sub@example`.
+pub fn is_name_service_name(name: &str) -> bool {
+    name.ends_with(".sui") || name.contains('@')
+}
+
+/// Resolve a SuiNS name to the address it points to, erroring if it does not point to one.
+pub async fn resolve_name_service_name(
+    client: &SuiClient,
+    name: &str,
+) -> Result<SuiAddress, Error> {
+    client
+        .read_api()
+        .resolve_name_service_address(name.to_string())
+        .await?
+        .ok_or_else(|| anyhow!("SuiNS name '{name}' does not resolve to an address"))
+}
+
 pub fn get_identity_address_from_keystore(
     input: KeyIdentity,
     keystore: &Keystore,
@@ -187,6 +187,23 @@ pub enum KeyToolCommand {
         threshold: ThresholdUnit,
     },
 
+    /// Given a passkey signature, parse it if valid. If `bytes` provided, parse it as either as
+    /// TransactionData or PersonalMessage based on `intent_scope`, and verify the signature
+    /// against it, including that the challenge in its clientDataJSON commits to the intent
+    /// message.
+    /// Example request: sui keytool passkey-sig-verify --sig $SERIALIZED_PASSKEY_SIG --bytes $BYTES --intent-scope 0
+    PasskeySigVerify {
+        /// The Base64 of the serialized passkey signature.
+        #[clap(long)]
+        sig: String,
+        /// The Base64 of the BCS encoded TransactionData or PersonalMessage.
+        #[clap(long)]
+        bytes: Option<String>,
+        /// Either 0 for TransactionData or 3 for PersonalMessage.
+        #[clap(long)]
+        intent_scope: u8,
+    },
+
     /// Read the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
     /// (Base64 encoded `privkey`). It prints its Base64 encoded public key and the key scheme flag.
@@ -440,6 +457,15 @@ pub struct ZkLoginSigVerifyResponse {
     res: Option<SuiResult>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeySigVerifyResponse {
+    address: SuiAddress,
+    data: Option<String>,
+    parsed: String,
+    res: Option<SuiResult>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZkLoginInsecureSignPersonalMessage {
@@ -464,6 +490,7 @@ pub enum CommandOutput {
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
+    PasskeySigVerify(PasskeySigVerifyResponse),
     PrivateKeyBase64(PrivateKeyBase64),
     Show(Key),
     Sign(SignData),
@@ -770,6 +797,77 @@ impl KeyToolCommand {
                 )
             }
 
+            KeyToolCommand::PasskeySigVerify {
+                sig,
+                bytes,
+                intent_scope,
+            } => {
+                match GenericSignature::from_bytes(
+                    &Base64::decode(&sig).map_err(|e| anyhow!("Invalid base64 sig: {:?}", e))?,
+                )? {
+                    GenericSignature::PasskeyAuthenticator(passkey) => {
+                        let address = SuiAddress::from_passkey_pk(&passkey.get_pk()?);
+                        let parsed = serde_json::to_string(&passkey)?;
+                        let Some(bytes) = bytes else {
+                            return Ok(CommandOutput::PasskeySigVerify(PasskeySigVerifyResponse {
+                                address,
+                                data: None,
+                                parsed,
+                                res: None,
+                            }));
+                        };
+
+                        // Passkey signatures do not expire, and do not depend on any
+                        // network-specific parameters, so the epoch and verify params are unused.
+                        let verify_params = VerifyParams::default();
+                        let sig = GenericSignature::PasskeyAuthenticator(passkey);
+                        let (serialized, res) = match IntentScope::try_from(intent_scope)
+                            .map_err(|_| anyhow!("Invalid scope"))?
+                        {
+                            IntentScope::TransactionData => {
+                                let tx_data: TransactionData = bcs::from_bytes(
+                                    &Base64::decode(&bytes)
+                                        .map_err(|e| anyhow!("Invalid base64 tx data: {:?}", e))?,
+                                )?;
+
+                                let res = sig.verify_authenticator(
+                                    &IntentMessage::new(Intent::sui_transaction(), tx_data.clone()),
+                                    tx_data.execution_parts().1,
+                                    0,
+                                    &verify_params,
+                                    Arc::new(VerifiedDigestCache::new_empty()),
+                                );
+                                (serde_json::to_string(&tx_data)?, res)
+                            }
+                            IntentScope::PersonalMessage => {
+                                let data = PersonalMessage {
+                                    message: Base64::decode(&bytes).map_err(|e| {
+                                        anyhow!("Invalid base64 personal message data: {:?}", e)
+                                    })?,
+                                };
+
+                                let res = sig.verify_authenticator(
+                                    &IntentMessage::new(Intent::personal_message(), data.clone()),
+                                    address,
+                                    0,
+                                    &verify_params,
+                                    Arc::new(VerifiedDigestCache::new_empty()),
+                                );
+                                (serde_json::to_string(&data)?, res)
+                            }
+                            _ => return Err(anyhow!("Invalid intent scope")),
+                        };
+                        CommandOutput::PasskeySigVerify(PasskeySigVerifyResponse {
+                            address,
+                            data: Some(serialized),
+                            parsed,
+                            res: Some(res),
+                        })
+                    }
+                    _ => CommandOutput::Error("Not a passkey signature".to_string()),
+                }
+            }
+
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
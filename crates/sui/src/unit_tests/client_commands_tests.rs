@@ -0,0 +1,114 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use shared_crypto::intent::IntentMessage;
+use sui_types::{
+    base_types::random_object_ref,
+    crypto::{get_key_pair, AccountKeyPair, Signature, SuiKeyPair},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+};
+
+fn key_pair() -> (SuiAddress, SuiKeyPair) {
+    let (_, kp): (_, AccountKeyPair) = get_key_pair();
+    let kp = SuiKeyPair::Ed25519(kp);
+    (SuiAddress::from(&kp.public()), kp)
+}
+
+fn sponsored_tx_data(sender: SuiAddress, sponsor: SuiAddress) -> TransactionData {
+    TransactionData::new_with_gas_coins_allow_sponsor(
+        TransactionKind::programmable(ProgrammableTransactionBuilder::new().finish()),
+        sender,
+        vec![random_object_ref()],
+        1_000_000,
+        1_000,
+        sponsor,
+    )
+}
+
+fn sign(tx_data: &TransactionData, kp: &SuiKeyPair) -> GenericSignature {
+    Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), tx_data.clone()),
+        kp,
+    )
+    .into()
+}
+
+#[test]
+fn test_collect_signatures() {
+    let (sender, sender_kp) = key_pair();
+    let (sponsor, sponsor_kp) = key_pair();
+    let tx_data = sponsored_tx_data(sender, sponsor);
+    let sender_sig = sign(&tx_data, &sender_kp);
+    let sponsor_sig = sign(&tx_data, &sponsor_kp);
+
+    // Signatures are returned in the order of the transaction's signers
+    let sigs = collect_signatures(
+        &tx_data,
+        vec![sponsor_sig.clone(), sender_sig.clone()],
+        None,
+    )
+    .unwrap();
+    assert_eq!(sigs, vec![sender_sig.clone(), sponsor_sig.clone()]);
+
+    let err = collect_signatures(&tx_data, vec![sender_sig.clone()], None).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Missing signatures from: {sponsor}")
+    );
+
+    let err = collect_signatures(
+        &tx_data,
+        vec![sender_sig.clone(), sender_sig.clone(), sponsor_sig.clone()],
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Found more than one signature from {sender}")
+    );
+
+    let (other, other_kp) = key_pair();
+    let err = collect_signatures(
+        &tx_data,
+        vec![sender_sig, sponsor_sig, sign(&tx_data, &other_kp)],
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Signature from {other} is not from a signer of the transaction")
+    );
+}
+
+#[test]
+fn test_collect_multisig_partial_signatures() {
+    let members: Vec<_> = (0..3).map(|_| key_pair().1).collect();
+    let multisig_pk = MultiSigPublicKey::new(
+        members.iter().map(|kp| kp.public()).collect(),
+        vec![1, 1, 1],
+        2,
+    )
+    .unwrap();
+    let sender = SuiAddress::from(&multisig_pk);
+    let (sponsor, sponsor_kp) = key_pair();
+    let tx_data = sponsored_tx_data(sender, sponsor);
+
+    // Partial signatures can be given in any order
+    let partials = vec![sign(&tx_data, &members[2]), sign(&tx_data, &members[0])];
+    let sponsor_sig = sign(&tx_data, &sponsor_kp);
+    let mut signatures = partials.clone();
+    signatures.push(sponsor_sig.clone());
+
+    let sigs = collect_signatures(&tx_data, signatures.clone(), Some(multisig_pk.clone())).unwrap();
+    let multisig = MultiSig::combine(partials.into_iter().rev().collect(), multisig_pk).unwrap();
+    assert_eq!(sigs, vec![multisig.into(), sponsor_sig]);
+
+    // Partial signatures are only recognized with the multisig's public keys
+    let member = SuiAddress::from(&members[2].public());
+    let err = collect_signatures(&tx_data, signatures, None).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Signature from {member} is not from a signer of the transaction")
+    );
+}
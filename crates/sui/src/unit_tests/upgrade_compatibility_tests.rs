@@ -0,0 +1,184 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use expect_test::expect;
+use move_binary_format::file_format::{Ability, StructTypeParameter};
+use move_core_types::{account_address::AccountAddress, ident_str};
+
+fn module(
+    structs: Vec<(&str, normalized::Struct)>,
+    functions: Vec<(&str, normalized::Function)>,
+) -> BTreeMap<String, normalized::Module> {
+    let module = normalized::Module {
+        file_format_version: 6,
+        address: AccountAddress::from_hex_literal("0x42").unwrap(),
+        name: ident_str!("m").to_owned(),
+        dependencies: vec![],
+        friends: vec![],
+        structs: structs
+            .into_iter()
+            .map(|(name, s)| (Identifier::new(name).unwrap(), s))
+            .collect(),
+        functions: functions
+            .into_iter()
+            .map(|(name, f)| (Identifier::new(name).unwrap(), f))
+            .collect(),
+        constants: vec![],
+    };
+    BTreeMap::from([("m".to_string(), module)])
+}
+
+fn struct_(
+    abilities: AbilitySet,
+    phantom: bool,
+    fields: Vec<normalized::Type>,
+) -> normalized::Struct {
+    normalized::Struct {
+        abilities,
+        type_parameters: vec![StructTypeParameter {
+            constraints: AbilitySet::EMPTY,
+            is_phantom: phantom,
+        }],
+        fields: fields
+            .into_iter()
+            .enumerate()
+            .map(|(i, type_)| normalized::Field {
+                name: Identifier::new(format!("f{i}")).unwrap(),
+                type_,
+            })
+            .collect(),
+    }
+}
+
+fn function(visibility: Visibility, parameters: Vec<normalized::Type>) -> normalized::Function {
+    normalized::Function {
+        visibility,
+        is_entry: false,
+        type_parameters: vec![],
+        parameters,
+        return_: vec![],
+        code: vec![],
+    }
+}
+
+fn report(
+    policy: UpgradePolicy,
+    existing: &BTreeMap<String, normalized::Module>,
+    upgraded: &BTreeMap<String, normalized::Module>,
+) -> String {
+    compatibility_report(policy, existing, upgraded).to_string()
+}
+
+#[test]
+fn test_compatible_policy() {
+    use normalized::Type::{U64, U8};
+    let key_store = AbilitySet::EMPTY | Ability::Key | Ability::Store;
+    let existing = module(
+        vec![
+            ("S", struct_(key_store, false, vec![U64])),
+            ("T", struct_(AbilitySet::EMPTY, false, vec![U64])),
+            ("Removed", struct_(AbilitySet::EMPTY, false, vec![])),
+        ],
+        vec![
+            ("public_fun", function(Visibility::Public, vec![U64])),
+            ("demoted_fun", function(Visibility::Public, vec![])),
+            ("removed_fun", function(Visibility::Public, vec![])),
+            ("friend_fun", function(Visibility::Friend, vec![U64])),
+            ("private_fun", function(Visibility::Private, vec![])),
+        ],
+    );
+    let upgraded = module(
+        vec![
+            ("S", struct_(key_store, true, vec![U64, U8])),
+            (
+                "T",
+                struct_(AbilitySet::EMPTY | Ability::Drop, false, vec![U64]),
+            ),
+        ],
+        vec![
+            ("public_fun", function(Visibility::Public, vec![U8])),
+            ("demoted_fun", function(Visibility::Friend, vec![])),
+            // Friend and private functions can change
+            ("friend_fun", function(Visibility::Friend, vec![U8])),
+        ],
+    );
+    expect![[r#"
+        Upgrade is not compatible with the published package under the COMPATIBLE policy:
+          module m:
+            - struct `Removed` was removed
+            - type parameters of struct `S` changed (constraints or phantom declarations)
+            - fields of struct `S` changed
+            - abilities of struct `T` changed from (no abilities) to (drop)
+            - visibility of function `demoted_fun` changed from public to friend
+            - signature of public function `public_fun` changed
+            - public function `removed_fun` was removed"#]]
+    .assert_eq(&report(UpgradePolicy::Compatible, &existing, &upgraded));
+
+    // Adding structs, functions and modules is compatible
+    let mut extended = module(
+        vec![
+            ("S", struct_(key_store, false, vec![U64])),
+            ("T", struct_(AbilitySet::EMPTY, false, vec![U64])),
+            ("Removed", struct_(AbilitySet::EMPTY, false, vec![])),
+            ("New", struct_(AbilitySet::EMPTY, false, vec![])),
+        ],
+        vec![
+            ("public_fun", function(Visibility::Public, vec![U64])),
+            ("demoted_fun", function(Visibility::Public, vec![])),
+            ("removed_fun", function(Visibility::Public, vec![])),
+            ("new_fun", function(Visibility::Public, vec![])),
+        ],
+    );
+    let mut new_module = extended["m"].clone();
+    new_module.name = ident_str!("n").to_owned();
+    extended.insert("n".to_string(), new_module);
+    expect!["Upgrade is compatible with the published package under the COMPATIBLE policy"]
+        .assert_eq(&report(UpgradePolicy::Compatible, &existing, &extended));
+}
+
+#[test]
+fn test_additive_and_dep_only_policies() {
+    use normalized::Type::U64;
+    let existing = module(
+        vec![("S", struct_(AbilitySet::EMPTY, false, vec![U64]))],
+        vec![
+            ("public_fun", function(Visibility::Public, vec![U64])),
+            ("private_fun", function(Visibility::Private, vec![])),
+        ],
+    );
+    let upgraded = module(
+        vec![
+            ("S", struct_(AbilitySet::EMPTY, false, vec![U64])),
+            ("New", struct_(AbilitySet::EMPTY, false, vec![])),
+        ],
+        vec![
+            ("public_fun", function(Visibility::Public, vec![U64])),
+            ("private_fun", function(Visibility::Private, vec![U64])),
+            ("new_fun", function(Visibility::Public, vec![])),
+        ],
+    );
+    expect![[r#"
+        Upgrade is not compatible with the published package under the ADDITIVE policy:
+          module m:
+            - function `private_fun` changed (signature, visibility or code)"#]]
+    .assert_eq(&report(UpgradePolicy::Additive, &existing, &upgraded));
+    expect![[r#"
+        Upgrade is not compatible with the published package under the DEP_ONLY policy:
+          module m:
+            - function `private_fun` changed (signature, visibility or code)
+            - struct `New` was added
+            - function `new_fun` was added"#]]
+    .assert_eq(&report(UpgradePolicy::DepOnly, &existing, &upgraded));
+
+    // Removing a module breaks any policy
+    expect![[r#"
+        Upgrade is not compatible with the published package under the ADDITIVE policy:
+          module m:
+            - module was removed"#]]
+    .assert_eq(&report(
+        UpgradePolicy::Additive,
+        &existing,
+        &BTreeMap::new(),
+    ));
+}
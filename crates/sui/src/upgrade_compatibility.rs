@@ -0,0 +1,381 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that an upgraded package is compatible with the package currently published on-chain,
+//! under the policy of its `UpgradeCap`, before the upgrade transaction is sent. Whether a module is
+//! compatible is decided by the same checks validators run when executing the upgrade. For modules
+//! that fail them, the differences between the two versions are then used to report every breaking
+//! change, rather than only the first one.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use anyhow::anyhow;
+use move_binary_format::{
+    binary_config::BinaryConfig,
+    compatibility::{Compatibility, InclusionCheck},
+    errors::PartialVMResult,
+    file_format::{AbilitySet, Visibility},
+    normalized,
+};
+use move_core_types::identifier::Identifier;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiRawData};
+use sui_move_build::CompiledPackage;
+use sui_sdk::apis::ReadApi;
+use sui_types::{
+    base_types::ObjectID,
+    move_package::{normalize_deserialized_modules, normalize_modules, UpgradePolicy},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/upgrade_compatibility_tests.rs"]
+mod upgrade_compatibility_tests;
+
+/// A change to a module that the upgrade policy does not allow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakingChange {
+    ModuleRemoved,
+    ModuleIdentityChanged,
+    FileFormatVersionDowngraded {
+        old: u32,
+        new: u32,
+    },
+    FriendsChanged,
+    StructRemoved(Identifier),
+    StructAdded(Identifier),
+    StructChanged(Identifier),
+    StructAbilitiesChanged {
+        name: Identifier,
+        old: AbilitySet,
+        new: AbilitySet,
+    },
+    StructTypeParametersChanged(Identifier),
+    StructLayoutChanged(Identifier),
+    FunctionRemoved {
+        name: Identifier,
+        visibility: Visibility,
+    },
+    FunctionAdded(Identifier),
+    FunctionChanged(Identifier),
+    FunctionVisibilityReduced {
+        name: Identifier,
+        old: Visibility,
+        new: Visibility,
+    },
+    FunctionSignatureChanged(Identifier),
+    /// The module fails the compatibility check, but none of the changes above explains why
+    Incompatible(String),
+}
+
+/// The breaking changes found in each module of an upgraded package.
+#[derive(Debug)]
+pub struct CompatibilityReport {
+    pub policy: UpgradePolicy,
+    /// Breaking changes by module name, only for modules with breaking changes
+    pub modules: BTreeMap<String, Vec<BreakingChange>>,
+}
+
+impl CompatibilityReport {
+    pub fn is_compatible(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+
+/// Fetches the package at `package_id`, and compares its modules to the ones of `compiled_package`
+/// under `policy`.
+pub async fn check_upgrade_compatibility(
+    read_api: &ReadApi,
+    package_id: ObjectID,
+    compiled_package: &CompiledPackage,
+    policy: u8,
+) -> anyhow::Result<CompatibilityReport> {
+    let policy = UpgradePolicy::try_from(policy)
+        .map_err(|_| anyhow!("Unknown upgrade policy {policy} in upgrade capability"))?;
+
+    let resp = read_api
+        .get_object_with_options(package_id, SuiObjectDataOptions::default().with_bcs())
+        .await?;
+    let Some(SuiRawData::Package(package)) = resp.data.and_then(|data| data.bcs) else {
+        return Err(anyhow!("Could not find package {package_id} to upgrade"));
+    };
+    let existing = normalize_modules(package.module_map.values(), &BinaryConfig::standard())?;
+    let upgraded = normalize_deserialized_modules(compiled_package.get_modules());
+    Ok(compatibility_report(policy, &existing, &upgraded))
+}
+
+/// Compares the modules of an upgraded package to those of the existing package, under `policy`.
+pub fn compatibility_report(
+    policy: UpgradePolicy,
+    existing: &BTreeMap<String, normalized::Module>,
+    upgraded: &BTreeMap<String, normalized::Module>,
+) -> CompatibilityReport {
+    let mut modules = BTreeMap::new();
+    for (name, old_module) in existing {
+        let Some(new_module) = upgraded.get(name) else {
+            modules.insert(name.clone(), vec![BreakingChange::ModuleRemoved]);
+            continue;
+        };
+        let Err(error) = check_module_compatibility(policy, old_module, new_module) else {
+            continue;
+        };
+        let mut changes = match policy {
+            UpgradePolicy::Compatible => compatible_changes(old_module, new_module),
+            UpgradePolicy::Additive => inclusion_changes(old_module, new_module, false),
+            UpgradePolicy::DepOnly => inclusion_changes(old_module, new_module, true),
+        };
+        if changes.is_empty() {
+            let message = match error.message() {
+                Some(message) => format!("{:?}: {message}", error.major_status()),
+                None => format!("{:?}", error.major_status()),
+            };
+            changes.push(BreakingChange::Incompatible(message));
+        }
+        modules.insert(name.clone(), changes);
+    }
+    CompatibilityReport { policy, modules }
+}
+
+/// The check validators run on each module of an upgraded package, under `policy`.
+fn check_module_compatibility(
+    policy: UpgradePolicy,
+    old_module: &normalized::Module,
+    new_module: &normalized::Module,
+) -> PartialVMResult<()> {
+    match policy {
+        UpgradePolicy::Additive => InclusionCheck::Subset.check(old_module, new_module),
+        UpgradePolicy::DepOnly => InclusionCheck::Equal.check(old_module, new_module),
+        UpgradePolicy::Compatible => Compatibility {
+            check_struct_and_pub_function_linking: true,
+            check_struct_layout: true,
+            check_friend_linking: false,
+            check_private_entry_linking: false,
+            disallowed_new_abilities: AbilitySet::ALL,
+            disallow_change_struct_type_params: true,
+        }
+        .check(old_module, new_module),
+    }
+}
+
+/// Breaking changes under the `COMPATIBLE` policy: public functions and structs must keep linking,
+/// and struct layouts must not change. Friend and private functions (entry or not) can change.
+fn compatible_changes(
+    old_module: &normalized::Module,
+    new_module: &normalized::Module,
+) -> Vec<BreakingChange> {
+    let mut changes = vec![];
+    if old_module.address != new_module.address || old_module.name != new_module.name {
+        changes.push(BreakingChange::ModuleIdentityChanged);
+    }
+
+    for (name, old_struct) in &old_module.structs {
+        let Some(new_struct) = new_module.structs.get(name) else {
+            changes.push(BreakingChange::StructRemoved(name.clone()));
+            continue;
+        };
+        // No abilities can be added to or removed from an existing struct
+        if old_struct.abilities != new_struct.abilities {
+            changes.push(BreakingChange::StructAbilitiesChanged {
+                name: name.clone(),
+                old: old_struct.abilities,
+                new: new_struct.abilities,
+            });
+        }
+        if old_struct.type_parameters != new_struct.type_parameters {
+            changes.push(BreakingChange::StructTypeParametersChanged(name.clone()));
+        }
+        if old_struct.fields != new_struct.fields {
+            changes.push(BreakingChange::StructLayoutChanged(name.clone()));
+        }
+    }
+
+    for (name, old_func) in &old_module.functions {
+        if old_func.visibility != Visibility::Public {
+            continue;
+        }
+        let Some(new_func) = new_module.functions.get(name) else {
+            changes.push(BreakingChange::FunctionRemoved {
+                name: name.clone(),
+                visibility: old_func.visibility,
+            });
+            continue;
+        };
+        if new_func.visibility != Visibility::Public {
+            changes.push(BreakingChange::FunctionVisibilityReduced {
+                name: name.clone(),
+                old: old_func.visibility,
+                new: new_func.visibility,
+            });
+        }
+        // Type parameters can only drop constraints
+        let type_parameters_compatible = old_func.type_parameters.len()
+            == new_func.type_parameters.len()
+            && old_func
+                .type_parameters
+                .iter()
+                .zip(&new_func.type_parameters)
+                .all(|(old, new)| new.is_subset(*old));
+        if old_func.parameters != new_func.parameters
+            || old_func.return_ != new_func.return_
+            || !type_parameters_compatible
+        {
+            changes.push(BreakingChange::FunctionSignatureChanged(name.clone()));
+        }
+    }
+    changes
+}
+
+/// Breaking changes under the `ADDITIVE` policy (`exact` is false), where existing structs and
+/// functions must be unchanged, and under the `DEP_ONLY` policy (`exact` is true), where nothing
+/// can be added either.
+fn inclusion_changes(
+    old_module: &normalized::Module,
+    new_module: &normalized::Module,
+    exact: bool,
+) -> Vec<BreakingChange> {
+    let mut changes = vec![];
+    if old_module.address != new_module.address || old_module.name != new_module.name {
+        changes.push(BreakingChange::ModuleIdentityChanged);
+    }
+    if old_module.file_format_version > new_module.file_format_version {
+        changes.push(BreakingChange::FileFormatVersionDowngraded {
+            old: old_module.file_format_version,
+            new: new_module.file_format_version,
+        });
+    }
+
+    for (name, old_struct) in &old_module.structs {
+        match new_module.structs.get(name) {
+            None => changes.push(BreakingChange::StructRemoved(name.clone())),
+            Some(new_struct) if new_struct != old_struct => {
+                changes.push(BreakingChange::StructChanged(name.clone()))
+            }
+            Some(_) => (),
+        }
+    }
+    for (name, old_func) in &old_module.functions {
+        match new_module.functions.get(name) {
+            None => changes.push(BreakingChange::FunctionRemoved {
+                name: name.clone(),
+                visibility: old_func.visibility,
+            }),
+            Some(new_func) if new_func != old_func => {
+                changes.push(BreakingChange::FunctionChanged(name.clone()))
+            }
+            Some(_) => (),
+        }
+    }
+
+    if exact {
+        for name in new_module.structs.keys() {
+            if !old_module.structs.contains_key(name) {
+                changes.push(BreakingChange::StructAdded(name.clone()));
+            }
+        }
+        for name in new_module.functions.keys() {
+            if !old_module.functions.contains_key(name) {
+                changes.push(BreakingChange::FunctionAdded(name.clone()));
+            }
+        }
+        if old_module.friends.len() != new_module.friends.len() {
+            changes.push(BreakingChange::FriendsChanged);
+        }
+    }
+    changes
+}
+
+fn visibility_str(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Private => "private",
+        Visibility::Public => "public",
+        Visibility::Friend => "friend",
+    }
+}
+
+fn abilities_str(abilities: &AbilitySet) -> String {
+    let abilities: Vec<_> = abilities
+        .into_iter()
+        .map(|ability| format!("{ability:?}").to_lowercase())
+        .collect();
+    if abilities.is_empty() {
+        "no abilities".to_string()
+    } else {
+        abilities.join(", ")
+    }
+}
+
+impl Display for BreakingChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakingChange::ModuleRemoved => write!(f, "module was removed"),
+            BreakingChange::ModuleIdentityChanged => {
+                write!(f, "module address or name changed")
+            }
+            BreakingChange::FileFormatVersionDowngraded { old, new } => {
+                write!(f, "bytecode version was downgraded from {old} to {new}")
+            }
+            BreakingChange::FriendsChanged => write!(f, "friend declarations changed"),
+            BreakingChange::StructRemoved(name) => write!(f, "struct `{name}` was removed"),
+            BreakingChange::StructAdded(name) => write!(f, "struct `{name}` was added"),
+            BreakingChange::StructChanged(name) => write!(f, "struct `{name}` changed"),
+            BreakingChange::StructAbilitiesChanged { name, old, new } => write!(
+                f,
+                "abilities of struct `{name}` changed from ({}) to ({})",
+                abilities_str(old),
+                abilities_str(new),
+            ),
+            BreakingChange::StructTypeParametersChanged(name) => write!(
+                f,
+                "type parameters of struct `{name}` changed (constraints or phantom declarations)"
+            ),
+            BreakingChange::StructLayoutChanged(name) => {
+                write!(f, "fields of struct `{name}` changed")
+            }
+            BreakingChange::FunctionRemoved { name, visibility } => write!(
+                f,
+                "{} function `{name}` was removed",
+                visibility_str(visibility)
+            ),
+            BreakingChange::FunctionAdded(name) => write!(f, "function `{name}` was added"),
+            BreakingChange::FunctionChanged(name) => write!(
+                f,
+                "function `{name}` changed (signature, visibility or code)"
+            ),
+            BreakingChange::FunctionVisibilityReduced { name, old, new } => write!(
+                f,
+                "visibility of function `{name}` changed from {} to {}",
+                visibility_str(old),
+                visibility_str(new),
+            ),
+            BreakingChange::FunctionSignatureChanged(name) => {
+                write!(f, "signature of public function `{name}` changed")
+            }
+            BreakingChange::Incompatible(error) => write!(f, "incompatible change ({error})"),
+        }
+    }
+}
+
+impl Display for CompatibilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_compatible() {
+            return write!(
+                f,
+                "Upgrade is compatible with the published package under the {} policy",
+                self.policy
+            );
+        }
+        write!(
+            f,
+            "Upgrade is not compatible with the published package under the {} policy:",
+            self.policy
+        )?;
+        for (module, changes) in &self.modules {
+            write!(f, "\n  module {module}:")?;
+            for change in changes {
+                write!(f, "\n    - {change}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
 use std::str::FromStr;
 
 use expect_test::expect;
+use move_core_types::ident_str;
 use move_package::{lock_file::schema::ManagedPackage, BuildConfig as MoveBuildConfig};
 use serde_json::json;
 use sui::client_ptb::ptb::PTB;
@@ -24,10 +25,11 @@ use sui_types::transaction::{
 };
 use tokio::time::sleep;
 
+use shared_crypto::intent::Intent;
 use sui::{
     client_commands::{
-        estimate_gas_budget, Opts, OptsWithGas, SuiClientCommandResult, SuiClientCommands,
-        SwitchResponse,
+        estimate_gas_budget, ExpirationOpts, Opts, OptsWithGas, SuiClientCommandResult,
+        SuiClientCommands, SwitchResponse,
     },
     sui_commands::SuiCommand,
 };
@@ -53,6 +55,12 @@ use sui_types::crypto::{
     Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
 use sui_types::error::SuiObjectResponseError;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{
+    Transaction, TransactionDataAPI, TransactionExpiration, TransactionKind,
+};
+use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
 use test_cluster::{TestCluster, TestClusterBuilder};
 
@@ -1534,6 +1542,90 @@ async fn test_package_publish_command_non_zero_unpublished_dep_fails() -> Result
     Ok(())
 }
 
+// On-chain dependencies are fetched over the network with a blocking client, which the simulator
+// does not support.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_package_publish_command_with_on_chain_dependency() -> Result<(), anyhow::Error> {
+    move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let rpc_url = test_cluster.rpc_url().to_string();
+    let context = &mut test_cluster.wallet;
+
+    // Publish the package to depend on.
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config: BuildConfig::new_for_testing().config,
+        opts: OptsWithGas::for_testing(None, rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::TransactionBlock(response) = resp else {
+        unreachable!("Invalid response");
+    };
+    let (package_id, _, _) = get_new_package_obj_from_response(&response)
+        .ok_or_else(|| anyhow::anyhow!("No package object response"))?;
+
+    // Depend on it from chain, rather than from source.
+    let framework_path = std::fs::canonicalize("../sui-framework/packages/sui-framework")?;
+    let tmp_dir = tempfile::tempdir()?;
+    let package_path = tmp_dir.path().join("dependent");
+    std::fs::create_dir_all(package_path.join("sources"))?;
+    std::fs::write(
+        package_path.join("Move.toml"),
+        format!(
+            r#"[package]
+name = "Dependent"
+edition = "2024.beta"
+
+[dependencies]
+Sui = {{ local = "{}" }}
+Examples = {{ id = "{package_id}", network = "{rpc_url}" }}
+
+[addresses]
+dependent = "0x0"
+"#,
+            framework_path.display(),
+        ),
+    )?;
+    std::fs::write(
+        package_path.join("sources").join("dependent.move"),
+        r#"module dependent::dependent {
+    use examples::trusted_coin::{Self, TRUSTED_COIN};
+    use sui::coin::TreasuryCap;
+    use sui::tx_context::TxContext;
+
+    public fun mint_twice(cap: &mut TreasuryCap<TRUSTED_COIN>, amount: u64, ctx: &mut TxContext) {
+        trusted_coin::mint(cap, amount, ctx);
+        trusted_coin::mint(cap, amount, ctx);
+    }
+}
+"#,
+    )?;
+
+    // The dependency's sources are generated from its bytecode, so dependency verification
+    // must not compare them against it.
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config: BuildConfig::new_for_testing().config,
+        opts: OptsWithGas::for_testing(None, rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::TransactionBlock(response) = resp else {
+        unreachable!("Invalid response");
+    };
+    assert!(response.status_ok().unwrap());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_package_publish_command_failure_invalid() -> Result<(), anyhow::Error> {
     let with_unpublished_dependencies = true; // Invalid packages should fail to publish, even if we allow unpublished dependencies.
@@ -1763,6 +1855,51 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
     let new = lines.join("\n");
     move_toml.write_at(new.as_bytes(), 0).unwrap();
 
+    // An upgrade that removes a public function is rejected before it is sent
+    let source_path = upgrade_pkg_path.join("sources").join("trusted_coin.move");
+    let source = std::fs::read_to_string(&source_path)?;
+    std::fs::write(
+        &source_path,
+        source.replace("public fun f()", "public fun g()"),
+    )?;
+    let err = SuiClientCommands::Upgrade {
+        package_path: upgrade_pkg_path.clone(),
+        upgrade_capability: cap.reference.object_id,
+        build_config: BuildConfig::new_for_testing().config,
+        opts: OptsWithGas::for_testing(Some(gas_obj_id), rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+        skip_compatibility_check: false,
+    }
+    .execute(context)
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("public function `f` was removed"),
+        "{err}"
+    );
+
+    // ...unless the local check is skipped, in which case validators reject it
+    let resp = SuiClientCommands::Upgrade {
+        package_path: upgrade_pkg_path.clone(),
+        upgrade_capability: cap.reference.object_id,
+        build_config: BuildConfig::new_for_testing().config,
+        opts: OptsWithGas::for_testing_dry_run(
+            Some(gas_obj_id),
+            rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        ),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+        skip_compatibility_check: true,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        unreachable!("Invalid dry run response");
+    };
+    assert!(response.effects.status().is_err());
+    std::fs::write(&source_path, source)?;
+
     // Now run the upgrade
     let build_config = BuildConfig::new_for_testing().config;
     let resp = SuiClientCommands::Upgrade {
@@ -1772,6 +1909,7 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         opts: OptsWithGas::for_testing(Some(gas_obj_id), rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
+        skip_compatibility_check: false,
     }
     .execute(context)
     .await?;
@@ -1914,6 +2052,7 @@ async fn test_package_management_on_upgrade_command() -> Result<(), anyhow::Erro
         opts: OptsWithGas::for_testing(Some(gas_obj_id), rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
+        skip_compatibility_check: false,
     }
     .execute(context)
     .await?;
@@ -2769,8 +2908,10 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
         opts: Opts {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
             dry_run: false,
+            trace: None,
             serialize_unsigned_transaction: true,
             serialize_signed_transaction: false,
+            expiration: ExpirationOpts::default(),
         },
     }
     .execute(context)
@@ -2783,8 +2924,10 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
         opts: Opts {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
             dry_run: false,
+            trace: None,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: true,
+            expiration: ExpirationOpts::default(),
         },
     }
     .execute(context)
@@ -2798,8 +2941,10 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
         opts: Opts {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
             dry_run: false,
+            trace: None,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: true,
+            expiration: ExpirationOpts::default(),
         },
     }
     .execute(context)
@@ -2829,6 +2974,106 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_sponsored_transactions() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let sender_balance = total_balance(&client, sender).await?;
+    let sponsor_balance = total_balance(&client, sponsor).await?;
+
+    // The keystore holds the keys of both the sender and the sponsor, so the PTB is signed by
+    // both and executed, with the sponsor paying for gas.
+    let args = vec![
+        "--move-call".to_string(),
+        "sui::tx_context::sender".to_string(),
+        "--gas-budget".to_string(),
+        (rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC).to_string(),
+        "--sponsor".to_string(),
+        format!("@{sponsor}"),
+    ];
+    SuiClientCommands::PTB(PTB { args })
+        .execute(context)
+        .await?;
+
+    assert_eq!(total_balance(&client, sender).await?, sender_balance);
+    let sponsor_balance_after_ptb = total_balance(&client, sponsor).await?;
+    assert!(sponsor_balance_after_ptb < sponsor_balance);
+
+    // Collect the signatures of the sender and the sponsor separately, and execute the
+    // transaction with them.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        ident_str!("tx_context").to_owned(),
+        ident_str!("sender").to_owned(),
+        vec![],
+        vec![],
+    )?;
+    let tx_data = client
+        .transaction_builder()
+        .tx_data(
+            sender,
+            TransactionKind::programmable(builder.finish()),
+            rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+            rgp,
+            vec![],
+            Some(sponsor),
+        )
+        .await?;
+    assert_eq!(tx_data.gas_owner(), sponsor);
+
+    let mut signatures = vec![];
+    for address in [sponsor, sender] {
+        let signature =
+            context
+                .config
+                .keystore
+                .sign_secure(&address, &tx_data, Intent::sui_transaction())?;
+        signatures.push(GenericSignature::from(signature));
+    }
+    let (tx_bytes, signatures) =
+        Transaction::from_generic_sig_data(tx_data, signatures).to_tx_bytes_and_signatures();
+
+    // A signature from the sponsor alone is not enough.
+    let err = SuiClientCommands::ExecuteMultiPartyTx {
+        tx_bytes: tx_bytes.encoded(),
+        signatures: vec![signatures[0].encoded()],
+        multisig_pks: vec![],
+        multisig_weights: vec![],
+        multisig_threshold: None,
+    }
+    .execute(context)
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Missing signatures from"));
+
+    SuiClientCommands::ExecuteMultiPartyTx {
+        tx_bytes: tx_bytes.encoded(),
+        signatures: signatures.iter().map(|s| s.encoded()).collect(),
+        multisig_pks: vec![],
+        multisig_weights: vec![],
+        multisig_threshold: None,
+    }
+    .execute(context)
+    .await?;
+
+    assert_eq!(total_balance(&client, sender).await?, sender_balance);
+    assert!(total_balance(&client, sponsor).await? < sponsor_balance_after_ptb);
+    Ok(())
+}
+
+async fn total_balance(client: &SuiClient, address: SuiAddress) -> Result<u128, anyhow::Error> {
+    Ok(client
+        .coin_read_api()
+        .get_balance(address, None)
+        .await?
+        .total_balance)
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -3061,20 +3306,35 @@ async fn key_identity_test() {
     // by alias
     assert_eq!(
         address,
-        get_identity_address(Some(KeyIdentity::Alias(alias)), context).unwrap()
+        get_identity_address(Some(KeyIdentity::Alias(alias)), context)
+            .await
+            .unwrap()
     );
     // by address
     assert_eq!(
         address,
-        get_identity_address(Some(KeyIdentity::Address(address)), context).unwrap()
+        get_identity_address(Some(KeyIdentity::Address(address)), context)
+            .await
+            .unwrap()
     );
     // alias does not exist
-    assert!(get_identity_address(Some(KeyIdentity::Alias("alias".to_string())), context).is_err());
+    assert!(
+        get_identity_address(Some(KeyIdentity::Alias("alias".to_string())), context)
+            .await
+            .is_err()
+    );
+    // SuiNS names that are not registered do not resolve
+    assert!(get_identity_address(
+        Some(KeyIdentity::Alias("unregistered.sui".to_string())),
+        context
+    )
+    .await
+    .is_err());
 
     // get active address instead when no alias/address is given
     assert_eq!(
         context.active_address().unwrap(),
-        get_identity_address(None, context).unwrap()
+        get_identity_address(None, context).await.unwrap()
     );
 }
 
@@ -3644,8 +3904,10 @@ async fn test_gas_estimation() -> Result<(), anyhow::Error> {
         opts: Opts {
             gas_budget: None,
             dry_run: false,
+            trace: None,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            expiration: ExpirationOpts::default(),
         },
     }
     .execute(context)
@@ -3669,3 +3931,44 @@ async fn test_gas_estimation() -> Result<(), anyhow::Error> {
     }
     Ok(())
 }
+
+#[test]
+fn test_expiration_opts() {
+    let opts = |valid_from_epoch, valid_until_epoch, valid_from_ms, valid_until_ms| {
+        ExpirationOpts {
+            valid_from_epoch,
+            valid_until_epoch,
+            valid_from_ms,
+            valid_until_ms,
+        }
+        .to_expiration()
+    };
+
+    assert_eq!(
+        opts(None, None, None, None).unwrap(),
+        TransactionExpiration::None
+    );
+    assert_eq!(
+        opts(None, Some(5), None, None).unwrap(),
+        TransactionExpiration::Epoch(5)
+    );
+    assert_eq!(
+        opts(None, None, None, Some(1_000)).unwrap(),
+        TransactionExpiration::Timestamp(1_000)
+    );
+    assert_eq!(
+        opts(Some(5), Some(5), Some(1_000), Some(1_000)).unwrap(),
+        TransactionExpiration::ValidDuring {
+            min_epoch: Some(5),
+            max_epoch: Some(5),
+            min_timestamp_ms: Some(1_000),
+            max_timestamp_ms: Some(1_000),
+        }
+    );
+
+    // Windows that no transaction could ever be valid in.
+    let err = opts(Some(6), Some(5), None, None).unwrap_err();
+    assert!(err.to_string().contains("--valid-from-epoch"), "{err}");
+    let err = opts(None, None, Some(2_000), Some(1_000)).unwrap_err();
+    assert!(err.to_string().contains("--valid-from-ms"), "{err}");
+}
@@ -4,6 +4,8 @@
 #[cfg(not(msim))]
 use std::path::Path;
 #[cfg(not(msim))]
+use sui_types::base_types::{ObjectID, SuiAddress};
+#[cfg(not(msim))]
 use sui_types::transaction::{CallArg, ObjectArg};
 
 #[cfg(not(msim))]
@@ -12,6 +14,8 @@ const TEST_DIR: &str = "tests";
 #[cfg(not(msim))]
 #[tokio::main]
 async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
+    use std::collections::BTreeMap;
+    use sui::client_ptb::ast::AddressOrName;
     use sui::client_ptb::ptb::{to_source_string, PTB};
     use sui::client_ptb::{error::build_error_reports, ptb::PTBPreview};
     use test_cluster::TestClusterBuilder;
@@ -46,6 +50,21 @@ async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
         }
     };
 
+    // SuiNS is not set up on the test cluster, so names resolve to fixed addresses instead.
+    let mut names: Vec<String> = program.name_service_names().into_keys().collect();
+    if let Some(sponsor) = &program_meta.sponsor {
+        if let AddressOrName::Name(name) = &sponsor.value {
+            names.push(name.clone());
+        }
+    }
+    let names: BTreeMap<String, SuiAddress> = names
+        .into_iter()
+        .filter_map(|name| {
+            let address = test_name_service_address(&name)?;
+            Some((name, address))
+        })
+        .collect();
+
     // Preview (This is based on the parsed commands).
     let mut results = vec![];
     results.push(" === PREVIEW === ".to_string());
@@ -53,7 +72,8 @@ async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
         "{}",
         PTBPreview {
             program: &program,
-            program_metadata: &program_meta
+            program_metadata: &program_meta,
+            names: &names,
         }
     ));
 
@@ -63,7 +83,7 @@ async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
     let context = &test_cluster.wallet;
     let client = context.get_client().await?;
 
-    let (built_ptb, warnings) = PTB::build_ptb(program, context, client).await;
+    let (built_ptb, warnings) = PTB::build_ptb(program, &names, context, client).await;
 
     if !warnings.is_empty() {
         let rendered = build_error_reports(&file_contents, warnings);
@@ -99,6 +119,17 @@ async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
     Ok(())
 }
 
+/// The address that a SuiNS name used in a test resolves to, if any.
+#[cfg(not(msim))]
+fn test_name_service_address(name: &str) -> Option<SuiAddress> {
+    let byte = match name {
+        "example.sui" => 0xa,
+        "@example" => 0xb,
+        _ => return None,
+    };
+    Some(SuiAddress::from(ObjectID::from_single_byte(byte)))
+}
+
 #[cfg(not(msim))]
 fn stable_call_arg_display(ca: &CallArg) -> String {
     match ca {
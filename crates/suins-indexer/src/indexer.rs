@@ -6,16 +6,21 @@ use std::{
     str::FromStr,
 };
 
+use anyhow::Result;
+use diesel::{dsl::sql, pg::PgConnection, BoolExpressionMethods, ExpressionMethods, RunQueryDsl};
 use move_core_types::language_storage::StructTag;
 use sui_json_rpc::name_service::{Domain, NameRecord, SubDomainRegistration};
+use sui_package_indexer::{
+    sinks::PostgresHandler, Batch, Filter, IndexedObject, ObjectChangeKind, TypeFilter,
+};
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
     dynamic_field::Field,
-    full_checkpoint_content::{CheckpointData, CheckpointTransaction},
+    full_checkpoint_content::CheckpointData,
     object::Object,
 };
 
-use crate::models::VerifiedDomain;
+use crate::{models::VerifiedDomain, schema::domains};
 
 /// We default to mainnet for both.
 const REGISTRY_TABLE_ID: &str =
@@ -79,6 +84,14 @@ impl SuinsIndexer {
                 .is_some_and(|tag| tag == self.name_record_type)
     }
 
+    /// The types of the objects the indexer processes: name records and subdomain wrappers.
+    pub fn object_filters(&self) -> Vec<TypeFilter> {
+        vec![
+            self.name_record_type.clone().into(),
+            self.subdomain_wrapper_type.clone().into(),
+        ]
+    }
+
     /// Processes a checkpoint and produces a list of `updates` and a list of `removals`
     ///
     /// We can then use these to execute our DB bulk insertions + bulk deletions.
@@ -87,19 +100,21 @@ impl SuinsIndexer {
     /// - `Vec<VerifiedDomain>`: A list of NameRecord updates for the database (including sequence number)
     /// - `Vec<String>`: A list of IDs to be deleted from the database (`field_id` is the matching column)
     pub fn process_checkpoint(&self, data: &CheckpointData) -> (Vec<VerifiedDomain>, Vec<String>) {
-        let mut checkpoint = SuinsIndexerCheckpoint::new(data.checkpoint_summary.sequence_number);
+        let filter = Filter {
+            events: vec![],
+            objects: self.object_filters(),
+        };
+        self.process_batch(&filter.select(data))
+    }
 
-        // loop through all the transactions in the checkpoint
-        // Since the transactions are sequenced inside the checkpoint, we can safely assume
+    /// Processes the object changes of a batch of checkpoints, like `process_checkpoint`.
+    pub fn process_batch(&self, batch: &Batch) -> (Vec<VerifiedDomain>, Vec<String>) {
+        let mut checkpoint = SuinsIndexerCheckpoint::default();
+
+        // Object changes are ordered by checkpoint and transaction, so we can safely assume
         // that we have the latest data for each name record in the end of the loop.
-        for transaction in &data.transactions {
-            // Add all name record changes to the name_records HashMap.
-            // Remove any removals that got re-created.
-            checkpoint.parse_record_changes(self, &transaction.output_objects);
-
-            // Gather all removals from the transaction,
-            // and delete any name records from the name_records if it got deleted.
-            checkpoint.parse_record_deletions(self, transaction);
+        for change in &batch.objects {
+            checkpoint.parse_object_change(self, change);
         }
 
         (
@@ -114,35 +129,31 @@ impl SuinsIndexer {
     }
 }
 
+impl PostgresHandler for SuinsIndexer {
+    fn commit(&self, conn: &mut PgConnection, batch: &Batch) -> Result<()> {
+        let (updates, removals) = self.process_batch(batch);
+        commit_to_db(conn, &updates, &removals, batch.watermark)
+    }
+}
+
+#[derive(Default)]
 pub struct SuinsIndexerCheckpoint {
-    /// A list of name records that have been updated in the checkpoint.
-    name_records: HashMap<ObjectID, NameRecordChange>,
-    /// A list of subdomain wrappers that have been created in the checkpoint.
+    /// A list of name records that have been updated in the checkpoints, with the checkpoint of
+    /// their last update.
+    name_records: HashMap<ObjectID, (NameRecordChange, u64)>,
+    /// A list of subdomain wrappers that have been created in the checkpoints.
     subdomain_wrappers: HashMap<String, String>,
-    /// A list of name records that have been deleted in the checkpoint.
+    /// A list of name records that have been deleted in the checkpoints.
     removals: HashSet<ObjectID>,
-    /// The sequence number of the checkpoint.
-    checkpoint_sequence_number: u64,
 }
 
 impl SuinsIndexerCheckpoint {
-    pub fn new(checkpoint_sequence_number: u64) -> Self {
-        Self {
-            name_records: HashMap::new(),
-            subdomain_wrappers: HashMap::new(),
-            removals: HashSet::new(),
-            checkpoint_sequence_number,
-        }
-    }
-
-    /// Parses the name record changes + subdomain wraps.
-    /// and pushes them into the supplied vector + hashmap.
-    ///
-    /// It is implemented in a way to do just a single iteration over the objects.
-    pub fn parse_record_changes(&mut self, config: &SuinsIndexer, objects: &[Object]) {
-        for object in objects {
+    /// Parses a name record change or subdomain wrap, and records it in the updates or removals.
+    pub fn parse_object_change(&mut self, config: &SuinsIndexer, change: &IndexedObject) {
+        let object = &change.object;
+        match change.kind {
             // Parse all the changes to a `NameRecord`
-            if config.is_name_record(object) {
+            ObjectChangeKind::Written if config.is_name_record(object) => {
                 let name_record: Field<Domain, NameRecord> = object
                     .to_rust()
                     .unwrap_or_else(|| panic!("Failed to parse name record for {:?}", object));
@@ -151,50 +162,33 @@ impl SuinsIndexerCheckpoint {
 
                 // Remove from the removals list if it's there.
                 // The reason it might have been there is that the same name record might have been
-                // deleted in a previous transaction in the same checkpoint, and now it got re-created.
+                // deleted in a previous transaction, and now it got re-created.
                 self.removals.remove(&id);
 
-                self.name_records.insert(id, NameRecordChange(name_record));
+                self.name_records
+                    .insert(id, (NameRecordChange(name_record), change.checkpoint));
             }
             // Parse subdomain wrappers and save them in our hashmap.
             // Later, we'll save the id of the wrapper in the name record.
             // NameRecords & their equivalent SubdomainWrappers are always created in the same PTB, so we can safely assume
             // that the wrapper will be created on the same checkpoint as the name record and vice versa.
-            if config.is_subdomain_wrapper(object) {
+            ObjectChangeKind::Written if config.is_subdomain_wrapper(object) => {
                 let sub_domain: SubDomainRegistration = object.to_rust().unwrap();
                 self.subdomain_wrappers.insert(
                     sub_domain.nft.domain_name,
                     sub_domain.id.id.bytes.to_string(),
                 );
-            };
-        }
-    }
-
-    /// Parses a list of the deletions in the checkpoint and adds them to the removals list.
-    /// Also removes any name records from the updates, if they ended up being deleted in the same checkpoint.
-    pub fn parse_record_deletions(
-        &mut self,
-        config: &SuinsIndexer,
-        transaction: &CheckpointTransaction,
-    ) {
-        // a list of all the deleted objects in the transaction.
-        let deleted_objects: HashSet<_> = transaction
-            .effects
-            .all_tombstones()
-            .into_iter()
-            .map(|(id, _)| id)
-            .collect();
-
-        for input in transaction.input_objects.iter() {
-            if config.is_name_record(input) && deleted_objects.contains(&input.id()) {
+            }
+            ObjectChangeKind::Removed if config.is_name_record(object) => {
                 // since this record was deleted, we need to remove it from the name_records hashmap.
-                // that catches a case where a name record was edited on a previous transaction in the checkpoint
-                // and deleted from a different tx later in the checkpoint.
-                self.name_records.remove(&input.id());
+                // that catches a case where a name record was edited on a previous transaction
+                // and deleted from a different tx later.
+                self.name_records.remove(&object.id());
 
                 // add it in the list of removals
-                self.removals.insert(input.id());
+                self.removals.insert(object.id());
             }
+            _ => {}
         }
     }
 
@@ -203,7 +197,7 @@ impl SuinsIndexerCheckpoint {
     pub fn prepare_db_updates(&self) -> Vec<VerifiedDomain> {
         let mut updates: Vec<VerifiedDomain> = vec![];
 
-        for (field_id, name_record_change) in self.name_records.iter() {
+        for (field_id, (name_record_change, checkpoint)) in self.name_records.iter() {
             let name_record = &name_record_change.0;
 
             let parent = name_record.name.parent().to_string();
@@ -224,7 +218,7 @@ impl SuinsIndexerCheckpoint {
                 },
                 // unwrapping must be safe as `value.data` is an on-chain value with VecMap<String,String> type.
                 data: serde_json::to_value(&name_record.value.data).unwrap(),
-                last_checkpoint_updated: self.checkpoint_sequence_number as i64,
+                last_checkpoint_updated: *checkpoint as i64,
                 subdomain_wrapper_id: self
                     .subdomain_wrappers
                     .get(&name_record.name.to_string())
@@ -236,6 +230,60 @@ impl SuinsIndexerCheckpoint {
     }
 }
 
+/// Upserts the given name record updates, and deletes the given name record deletions, in the
+/// transaction of `conn`.
+///
+/// This is done using 1 or 2 queries, depending on whether there are any deletions/updates.
+///
+/// - The first query is a bulk insert of all updates, with an upsert on conflict.
+/// - The second query is a bulk delete of all deletions.
+///
+/// You can safely call this with empty updates/deletions as it will return Ok.
+pub fn commit_to_db(
+    conn: &mut PgConnection,
+    updates: &[VerifiedDomain],
+    removals: &[String],
+    checkpoint_seq_num: u64,
+) -> Result<()> {
+    if !updates.is_empty() {
+        // Bulk insert all updates and override with data.
+        diesel::insert_into(domains::table)
+            .values(updates)
+            .on_conflict(domains::name)
+            .do_update()
+            .set((
+                domains::expiration_timestamp_ms
+                    .eq(sql(&format_update_field_query("expiration_timestamp_ms"))),
+                domains::nft_id.eq(sql(&format_update_field_query("nft_id"))),
+                domains::target_address.eq(sql(&format_update_field_query("target_address"))),
+                domains::data.eq(sql(&format_update_field_query("data"))),
+                domains::last_checkpoint_updated
+                    .eq(sql(&format_update_field_query("last_checkpoint_updated"))),
+                domains::field_id.eq(sql(&format_update_field_query("field_id"))),
+                // We always want to respect the subdomain_wrapper re-assignment, even if the checkpoint is older.
+                // That prevents a scenario where we first process a later checkpoint that did an update to the name record (e..g change target address),
+                // without first executing the checkpoint that created the subdomain wrapper.
+                // Since wrapper re-assignment can only happen every 2 days, we can't write invalid data here.
+                domains::subdomain_wrapper_id.eq(sql(&format_update_subdomain_wrapper_query())),
+            ))
+            .execute(conn)?;
+    }
+
+    if !removals.is_empty() {
+        // We want to remove from the database all name records that were removed in the checkpoints
+        // but only if the checkpoint is newer than the last time the name record was updated.
+        diesel::delete(domains::table)
+            .filter(
+                domains::field_id
+                    .eq_any(removals)
+                    .and(domains::last_checkpoint_updated.le(checkpoint_seq_num as i64)),
+            )
+            .execute(conn)?;
+    }
+
+    Ok(())
+}
+
 /// Allows us to format a SuiNS specific query for updating the DB entries
 /// only if the checkpoint is newer than the last checkpoint we have in the DB.
 /// Doing that, we do not care about the order of execution and we can use multiple threads
@@ -31,6 +31,23 @@ pub fn gen_transaction_expiration() -> impl Strategy<Value = TransactionExpirati
     prop_oneof![
         Just(TransactionExpiration::None),
         (0u64..=u64::MAX).prop_map(TransactionExpiration::Epoch),
+        any::<u64>().prop_map(TransactionExpiration::Timestamp),
+        (
+            any::<Option<u64>>(),
+            any::<Option<u64>>(),
+            any::<Option<u64>>(),
+            any::<Option<u64>>(),
+        )
+            .prop_map(
+                |(min_epoch, max_epoch, min_timestamp_ms, max_timestamp_ms)| {
+                    TransactionExpiration::ValidDuring {
+                        min_epoch,
+                        max_epoch,
+                        min_timestamp_ms,
+                        max_timestamp_ms,
+                    }
+                }
+            ),
     ]
 }
 
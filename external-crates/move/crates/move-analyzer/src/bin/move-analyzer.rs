@@ -7,10 +7,12 @@ use clap::Parser;
 use crossbeam::channel::{bounded, select};
 use lsp_server::{Connection, Message, Notification, Request, Response};
 use lsp_types::{
-    notification::Notification as _, request::Request as _, CompletionOptions, Diagnostic,
-    HoverProviderCapability, InlayHintOptions, InlayHintServerCapabilities, OneOf, SaveOptions,
-    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
-    TypeDefinitionProviderCapability, WorkDoneProgressOptions,
+    notification::Notification as _, request::Request as _, CodeActionKind, CodeActionOptions,
+    CodeActionProviderCapability, CompletionOptions, Diagnostic, HoverProviderCapability,
+    InlayHintOptions, InlayHintServerCapabilities, OneOf, RenameOptions, SaveOptions,
+    SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensServerCapabilities,
+    SignatureHelpOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TypeDefinitionProviderCapability, WorkDoneProgressOptions,
 };
 use move_compiler::linters::LintLevel;
 use std::{
@@ -20,8 +22,8 @@ use std::{
 };
 
 use move_analyzer::{
-    completion::on_completion_request, context::Context, inlay_hints, symbols,
-    vfs::on_text_document_sync_notification,
+    code_action, completion::on_completion_request, context::Context, inlay_hints, rename,
+    semantic_tokens, signature_help, symbols, vfs::on_text_document_sync_notification,
 };
 use url::Url;
 use vfs::{impls::memory::MemoryFS, VfsPath};
@@ -116,6 +118,38 @@ fn main() {
                 resolve_provider: None,
             },
         ))),
+        // The server checks whether a symbol can be renamed before the user enters a new name.
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+            resolve_provider: None,
+        })),
+        // The server shows the parameters of a function as a call to it is being typed.
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                work_done_progress_options: WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                legend: semantic_tokens::legend(),
+                range: None,
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+            },
+        )),
         ..Default::default()
     })
     .expect("could not serialize server capabilities");
@@ -318,6 +352,40 @@ fn on_request(
         lsp_types::request::InlayHintRequest::METHOD => {
             inlay_hints::on_inlay_hint_request(context, request, &context.symbols.lock().unwrap());
         }
+        lsp_types::request::PrepareRenameRequest::METHOD => {
+            rename::on_prepare_rename_request(context, request, &context.symbols.lock().unwrap());
+        }
+        lsp_types::request::Rename::METHOD => {
+            rename::on_rename_request(
+                context,
+                request,
+                &context.symbols.lock().unwrap(),
+                &ide_files_root,
+            );
+        }
+        lsp_types::request::SignatureHelpRequest::METHOD => {
+            signature_help::on_signature_help_request(
+                context,
+                request,
+                &context.symbols.lock().unwrap(),
+                &ide_files_root,
+            );
+        }
+        lsp_types::request::SemanticTokensFullRequest::METHOD => {
+            semantic_tokens::on_semantic_tokens_full_request(
+                context,
+                request,
+                &context.symbols.lock().unwrap(),
+            );
+        }
+        lsp_types::request::CodeActionRequest::METHOD => {
+            code_action::on_code_action_request(
+                context,
+                request,
+                &context.symbols.lock().unwrap(),
+                &ide_files_root,
+            );
+        }
         lsp_types::request::Shutdown::METHOD => {
             eprintln!("Shutdown request received");
             let response =
@@ -0,0 +1,498 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quick fixes for compiler diagnostics. Diagnostics are identified by the compiler code they are
+//! published with (see `diagnostics::lsp_diagnostics`), and each fix is computed from the
+//! diagnostic's range and message, and the contents of the file it was reported for.
+
+use crate::{
+    context::Context,
+    symbols::{mod_ident_to_ide_string, Symbols},
+    utils::{byte_offset, file_contents},
+};
+use lsp_server::Request;
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Diagnostic, NumberOrString,
+    Position, Range, TextEdit, WorkspaceEdit,
+};
+use move_compiler::diagnostics::codes::{self, Category};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+use url::Url;
+use vfs::VfsPath;
+
+/// Handles code action request of the language server
+pub fn on_code_action_request(
+    context: &Context,
+    request: &Request,
+    symbols: &Symbols,
+    ide_files_root: &VfsPath,
+) {
+    let parameters = serde_json::from_value::<CodeActionParams>(request.params.clone())
+        .expect("could not deserialize code action request");
+
+    let uri = parameters.text_document.uri;
+    let fpath = uri.to_file_path().unwrap();
+    let contents = file_contents(ide_files_root, &fpath).unwrap_or_default();
+    let actions: Vec<CodeActionOrCommand> = parameters
+        .context
+        .diagnostics
+        .iter()
+        .flat_map(|diag| quick_fixes(symbols, &fpath, &contents, diag))
+        .map(|(title, diag, edits)| {
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diag.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri.clone(), edits)])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    let response = lsp_server::Response::new_ok(request.id.clone(), actions);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send code action response: {:?}", err);
+    }
+}
+
+/// Computes the quick fixes for a diagnostic, each given as its title and its edits.
+fn quick_fixes<'a>(
+    symbols: &Symbols,
+    fpath: &Path,
+    contents: &str,
+    diag: &'a Diagnostic,
+) -> Vec<(String, &'a Diagnostic, Vec<TextEdit>)> {
+    const UNUSED_ITEM: u8 = Category::UnusedItem as u8;
+    const UNUSED_VARIABLE: u8 = codes::UnusedItem::Variable as u8;
+    const NAME_RESOLUTION: u8 = Category::NameResolution as u8;
+    const UNBOUND_MODULE: u8 = codes::NameResolution::UnboundModule as u8;
+    const MIGRATION: u8 = Category::Migration as u8;
+    const NEEDS_MUT: u8 = codes::Migration::NeedsLetMut as u8;
+    const NEEDS_PUBLIC: u8 = codes::Migration::NeedsPublic as u8;
+    const NEEDS_BACKTICKS: u8 = codes::Migration::NeedsRestrictedIdentifier as u8;
+    const NEEDS_GLOBAL_QUAL: u8 = codes::Migration::NeedsGlobalQualification as u8;
+    const REMOVE_FRIEND: u8 = codes::Migration::RemoveFriend as u8;
+    const MAKE_PUB_PACKAGE: u8 = codes::Migration::MakePubPackage as u8;
+    const ADDRESS_REMOVE: u8 = codes::Migration::AddressRemove as u8;
+    const ADDRESS_ADD: u8 = codes::Migration::AddressAdd as u8;
+
+    let Some(code) = compiler_code(diag) else {
+        return vec![];
+    };
+    let range = diag.range;
+    let text = range_text(contents, &range);
+    if text.is_empty() {
+        // the file is out of sync with the diagnostic
+        return vec![];
+    }
+    let insert = |new_text: &str| {
+        vec![TextEdit::new(
+            Range::new(range.start, range.start),
+            new_text.to_string(),
+        )]
+    };
+    let replace = |new_text: String| vec![TextEdit::new(range, new_text)];
+    let fix = |title: String, edits: Vec<TextEdit>| vec![(title, diag, edits)];
+
+    match code {
+        (UNUSED_ITEM, UNUSED_VARIABLE) => {
+            // macro parameters keep their `$` prefix
+            let new_name = match text.strip_prefix('$') {
+                Some(name) => format!("$_{name}"),
+                None => format!("_{text}"),
+            };
+            fix(format!("Rename to '{new_name}'"), replace(new_name))
+        }
+        (NAME_RESOLUTION, UNBOUND_MODULE) if diag.message.starts_with("Unbound module alias") => {
+            let Some(insert_pos) = use_insertion_position(symbols, fpath, contents, &range) else {
+                return vec![];
+            };
+            let (indent, separator) = match insert_pos.1 {
+                '{' => ("    ", "\n"),
+                _ => ("", "\n\n"),
+            };
+            let module_names: BTreeSet<String> = symbols
+                .file_mods()
+                .values()
+                .flatten()
+                .filter(|mod_defs| mod_defs.ident().module.value().as_str() == text)
+                .map(|mod_defs| mod_ident_to_ide_string(mod_defs.ident()))
+                .collect();
+            module_names
+                .into_iter()
+                .map(|module_name| {
+                    let edit = TextEdit::new(
+                        Range::new(insert_pos.0, insert_pos.0),
+                        format!("{separator}{indent}use {module_name};"),
+                    );
+                    (format!("Import '{module_name}'"), diag, vec![edit])
+                })
+                .collect()
+        }
+        (MIGRATION, NEEDS_MUT) => fix("Add 'mut'".to_string(), insert("mut ")),
+        (MIGRATION, NEEDS_PUBLIC) => fix("Add 'public'".to_string(), insert("public ")),
+        (MIGRATION, NEEDS_BACKTICKS) => fix(
+            format!("Escape '{}' with backticks", diag.message),
+            replace(format!("`{}`", diag.message)),
+        ),
+        (MIGRATION, NEEDS_GLOBAL_QUAL) => fix("Add '::'".to_string(), insert("::")),
+        (MIGRATION, REMOVE_FRIEND) => fix(
+            "Comment out 'friend' declaration".to_string(),
+            vec![comment_out(contents, &range, &text)],
+        ),
+        (MIGRATION, MAKE_PUB_PACKAGE) => fix(
+            "Replace with 'public(package)'".to_string(),
+            replace("public(package)".to_string()),
+        ),
+        (MIGRATION, ADDRESS_REMOVE) => fix(
+            "Comment out address block".to_string(),
+            vec![comment_out(contents, &range, &text)],
+        ),
+        (MIGRATION, ADDRESS_ADD) => fix(
+            format!("Add '{}'", diag.message),
+            insert(diag.message.as_str()),
+        ),
+        _ => vec![],
+    }
+}
+
+/// Returns the category and code of a diagnostic reported by the compiler itself (i.e., not by an
+/// external tool such as a linter), whose codes are rendered as a severity prefix followed by two
+/// digits of category and three digits of code (e.g., `W09002`).
+fn compiler_code(diag: &Diagnostic) -> Option<(u8, u8)> {
+    let Some(NumberOrString::String(code)) = &diag.code else {
+        return None;
+    };
+    let digits = code.strip_prefix(['E', 'W'])?;
+    if digits.len() != 5 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((digits[..2].parse().ok()?, digits[2..].parse().ok()?))
+}
+
+/// Returns the text of the file within the given range.
+fn range_text(contents: &str, range: &Range) -> String {
+    let start = byte_offset(contents, &range.start);
+    let end = byte_offset(contents, &range.end);
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => contents[start..end].to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Comments out the text within the given range, like the migration tool does, but with a line
+/// comment when it is the only code on its line (e.g., a `friend` declaration, or the closing brace
+/// of an `address` block).
+fn comment_out(contents: &str, range: &Range, text: &str) -> TextEdit {
+    let line = contents
+        .lines()
+        .nth(range.start.line as usize)
+        .unwrap_or_default();
+    let code = |s: &str| s.trim().trim_end_matches(';').trim_end().to_string();
+    if range.start.line == range.end.line && code(line) == code(text) {
+        let indent = line.chars().take_while(|c| c.is_whitespace()).count() as u32;
+        let start = Position::new(range.start.line, indent);
+        TextEdit::new(Range::new(start, start), "// ".to_string())
+    } else {
+        TextEdit::new(*range, format!("/* {text} */"))
+    }
+}
+
+/// Returns the position after the opening of the module enclosing the given range (i.e., after
+/// either its `{` or its `;`), where a `use` declaration can be inserted, along with the
+/// character opening the module.
+fn use_insertion_position(
+    symbols: &Symbols,
+    fpath: &Path,
+    contents: &str,
+    range: &Range,
+) -> Option<(Position, char)> {
+    let mod_start = symbols
+        .file_mods()
+        .get(fpath)?
+        .iter()
+        .map(|mod_defs| mod_defs.start())
+        .filter(|start| *start <= range.start)
+        .max()?;
+    let mod_offset = byte_offset(contents, &mod_start)?;
+    let (opening_offset, opening) = contents[mod_offset..]
+        .char_indices()
+        .find(|(_, c)| *c == '{' || *c == ';')?;
+    let prefix = &contents[..mod_offset + opening_offset];
+    let line = prefix.matches('\n').count() as u32;
+    let line_start = prefix.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let character = prefix[line_start..].chars().count() as u32 + 1;
+    Some((Position { line, character }, opening))
+}
+
+#[test]
+fn code_action_compiler_code_test() {
+    let diag = |code: &str| Diagnostic {
+        code: Some(NumberOrString::String(code.to_string())),
+        ..Default::default()
+    };
+    assert_eq!(compiler_code(&diag("W09002")), Some((9, 2)));
+    assert_eq!(compiler_code(&diag("E03002")), Some((3, 2)));
+    assert_eq!(compiler_code(&diag("Lint W01001")), None);
+    assert_eq!(compiler_code(&diag("ICE01001")), None);
+}
+
+#[test]
+fn code_action_range_text_test() {
+    let contents = "module a::m {\n    friend a::n;\n}\n";
+    let range = Range::new(Position::new(1, 4), Position::new(1, 16));
+    assert_eq!(range_text(contents, &range), "friend a::n;");
+    let range = Range::new(Position::new(0, 7), Position::new(1, 10));
+    assert_eq!(range_text(contents, &range), "a::m {\n    friend");
+}
+
+#[cfg(test)]
+/// Applies the quick fixes for a diagnostic with the given compiler code, range, and message,
+/// returning the title and the fixed contents for each of them.
+fn apply_quick_fixes(
+    symbols: &Symbols,
+    fpath: &Path,
+    contents: &str,
+    code: (Category, u8),
+    range: Range,
+    message: &str,
+) -> Vec<(String, String)> {
+    let diag = Diagnostic {
+        range,
+        code: Some(NumberOrString::String(format!(
+            "W{:02}{:03}",
+            code.0 as u8, code.1
+        ))),
+        message: message.to_string(),
+        ..Default::default()
+    };
+    quick_fixes(symbols, fpath, contents, &diag)
+        .into_iter()
+        .map(|(title, _, edits)| {
+            let mut fixed = contents.to_string();
+            for edit in edits.iter().rev() {
+                let start = byte_offset(&fixed, &edit.range.start).unwrap();
+                let end = byte_offset(&fixed, &edit.range.end).unwrap();
+                fixed.replace_range(start..end, &edit.new_text);
+            }
+            (title, fixed)
+        })
+        .collect()
+}
+
+#[test]
+fn code_action_quick_fixes_test() {
+    use crate::symbols::empty_symbols;
+    use codes::Migration;
+
+    let symbols = empty_symbols();
+    let fix = |contents: &str, code: (Category, u8), range: (u32, u32, u32, u32), message| {
+        let range = Range::new(
+            Position::new(range.0, range.1),
+            Position::new(range.2, range.3),
+        );
+        let fixes = apply_quick_fixes(
+            &symbols,
+            Path::new("m.move"),
+            contents,
+            code,
+            range,
+            message,
+        );
+        assert_eq!(fixes.len(), 1, "{fixes:?}");
+        fixes.into_iter().next().unwrap()
+    };
+    let unused_var = (Category::UnusedItem, codes::UnusedItem::Variable as u8);
+    let migration = |code: Migration| (Category::Migration, code as u8);
+
+    assert_eq!(
+        fix("let x = 1;", unused_var, (0, 4, 0, 5), "unused"),
+        ("Rename to '_x'".to_string(), "let _x = 1;".to_string())
+    );
+    assert_eq!(
+        fix(
+            "macro fun m($f: u64) {}",
+            unused_var,
+            (0, 12, 0, 14),
+            "unused"
+        ),
+        (
+            "Rename to '$_f'".to_string(),
+            "macro fun m($_f: u64) {}".to_string()
+        )
+    );
+    assert_eq!(
+        fix(
+            "let x = 1;",
+            migration(Migration::NeedsLetMut),
+            (0, 4, 0, 5),
+            "mut"
+        ),
+        ("Add 'mut'".to_string(), "let mut x = 1;".to_string())
+    );
+    assert_eq!(
+        fix(
+            "struct S {}",
+            migration(Migration::NeedsPublic),
+            (0, 0, 0, 6),
+            "public"
+        ),
+        ("Add 'public'".to_string(), "public struct S {}".to_string())
+    );
+    assert_eq!(
+        fix(
+            "fun enum() {}",
+            migration(Migration::NeedsRestrictedIdentifier),
+            (0, 4, 0, 8),
+            "enum"
+        ),
+        (
+            "Escape 'enum' with backticks".to_string(),
+            "fun `enum`() {}".to_string()
+        )
+    );
+    assert_eq!(
+        fix(
+            "a::m::f()",
+            migration(Migration::NeedsGlobalQualification),
+            (0, 0, 0, 1),
+            "global"
+        ),
+        ("Add '::'".to_string(), "::a::m::f()".to_string())
+    );
+    assert_eq!(
+        fix(
+            "public(friend) fun f() {}",
+            migration(Migration::MakePubPackage),
+            (0, 0, 0, 14),
+            "package"
+        ),
+        (
+            "Replace with 'public(package)'".to_string(),
+            "public(package) fun f() {}".to_string()
+        )
+    );
+    assert_eq!(
+        fix(
+            "module m {}",
+            migration(Migration::AddressAdd),
+            (0, 7, 0, 8),
+            "a::"
+        ),
+        ("Add 'a::'".to_string(), "module a::m {}".to_string())
+    );
+
+    // declarations are commented out rather than removed, with a line comment when possible
+    let friend = migration(Migration::RemoveFriend);
+    assert_eq!(
+        fix(
+            "module a::m {\n    friend a::n;\n}\n",
+            friend,
+            (1, 4, 1, 16),
+            "friend"
+        ),
+        (
+            "Comment out 'friend' declaration".to_string(),
+            "module a::m {\n    // friend a::n;\n}\n".to_string()
+        )
+    );
+    assert_eq!(
+        fix(
+            "module a::m { friend a::n; }",
+            friend,
+            (0, 14, 0, 26),
+            "friend"
+        )
+        .1,
+        "module a::m { /* friend a::n; */ }"
+    );
+    let address = migration(Migration::AddressRemove);
+    let contents = "address a {\nmodule m {}\n}\n";
+    assert_eq!(
+        fix(contents, address, (0, 0, 0, 11), "address decl"),
+        (
+            "Comment out address block".to_string(),
+            "// address a {\nmodule m {}\n}\n".to_string()
+        )
+    );
+    assert_eq!(
+        fix(contents, address, (2, 0, 2, 1), "close lbrace").1,
+        "address a {\nmodule m {}\n// }\n"
+    );
+
+    // diagnostics out of sync with the file, or with other codes, have no fixes
+    let diag = Diagnostic {
+        range: Range::new(Position::new(3, 0), Position::new(3, 1)),
+        code: Some(NumberOrString::String("W09002".to_string())),
+        ..Default::default()
+    };
+    assert!(quick_fixes(&symbols, Path::new("m.move"), "let x = 1;", &diag).is_empty());
+    let diag = Diagnostic {
+        range: Range::new(Position::new(0, 4), Position::new(0, 5)),
+        code: Some(NumberOrString::String("Lint W01001".to_string())),
+        ..Default::default()
+    };
+    assert!(quick_fixes(&symbols, Path::new("m.move"), "let x = 1;", &diag).is_empty());
+}
+
+#[test]
+fn code_action_import_test() {
+    use crate::symbols::get_symbols;
+    use move_compiler::linters::LintLevel;
+    use std::{
+        collections::BTreeMap,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+    use vfs::impls::memory::MemoryFS;
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/rename");
+
+    let (symbols_opt, _) = get_symbols(
+        Arc::new(Mutex::new(BTreeMap::new())),
+        MemoryFS::new().into(),
+        path.as_path(),
+        LintLevel::None,
+    )
+    .unwrap();
+    let symbols = symbols_opt.unwrap();
+    let fpath = dunce::canonicalize(path.join("sources/m2.move")).unwrap();
+    let contents = std::fs::read_to_string(&fpath).unwrap();
+
+    // pretend that the `m1` alias used in the signature of `twice` is unbound
+    let fixes = apply_quick_fixes(
+        &symbols,
+        &fpath,
+        &contents,
+        (
+            Category::NameResolution,
+            codes::NameResolution::UnboundModule as u8,
+        ),
+        Range::new(Position::new(3, 35), Position::new(3, 37)),
+        "Unbound module alias 'm1'",
+    );
+    assert_eq!(fixes.len(), 1, "{fixes:?}");
+    let (title, fixed) = &fixes[0];
+    assert!(
+        title.starts_with("Import '") && title.ends_with("::m1'"),
+        "{title}"
+    );
+    let module_name = &title["Import '".len()..title.len() - 1];
+    assert!(
+        fixed.starts_with(&format!(
+            "module Rename::m2 {{\n    use {module_name};\n    use Rename::m1::"
+        )),
+        "{fixed}"
+    );
+}
@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::utils::get_loc;
-use codespan_reporting::{diagnostic::Severity, files::SimpleFiles};
-use lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range};
+use codespan_reporting::files::SimpleFiles;
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Range,
+};
 use move_command_line_common::files::FileHash;
-use move_ir_types::location::Loc;
+use move_compiler::diagnostics::{codes::Severity, Diagnostic as CompilerDiagnostic};
 use move_symbol_pool::Symbol;
 use std::{
     collections::{BTreeMap, HashMap},
@@ -13,21 +15,19 @@ use std::{
 };
 use url::Url;
 
-/// Converts diagnostics from the codespan format to the format understood by the language server.
+/// Converts diagnostics from the compiler's format to the format understood by the language server.
+/// Each diagnostic carries its compiler code (e.g., `W09002`), which code actions use to identify
+/// the diagnostics they can offer fixes for.
 pub fn lsp_diagnostics(
-    diagnostics: &Vec<(
-        codespan_reporting::diagnostic::Severity,
-        &'static str,
-        (Loc, String),
-        Vec<(Loc, String)>,
-        Vec<String>,
-    )>,
+    diagnostics: &[CompilerDiagnostic],
     files: &SimpleFiles<Symbol, String>,
     file_id_mapping: &HashMap<FileHash, usize>,
     file_name_mapping: &BTreeMap<FileHash, PathBuf>,
 ) -> BTreeMap<PathBuf, Vec<Diagnostic>> {
     let mut lsp_diagnostics = BTreeMap::new();
-    for (s, _, (loc, msg), labels, _) in diagnostics {
+    for diag in diagnostics {
+        let loc = diag.primary_loc();
+        let labels = diag.secondary_labels();
         let fpath = file_name_mapping.get(&loc.file_hash()).unwrap();
         if let Some(start) = get_loc(&loc.file_hash(), loc.start(), files, file_id_mapping) {
             if let Some(end) = get_loc(&loc.file_hash(), loc.end(), files, file_id_mapping) {
@@ -65,10 +65,10 @@ pub fn lsp_diagnostics(
                     .or_insert_with(Vec::new)
                     .push(Diagnostic::new(
                         range,
-                        Some(severity(*s)),
-                        None,
+                        Some(severity(diag.info().severity())),
+                        Some(NumberOrString::String(diag.info().clone().render().0)),
                         None,
-                        msg.to_string(),
+                        diag.primary_msg().to_string(),
                         related_info_opt,
                         None,
                     ));
@@ -90,14 +90,12 @@ pub fn lsp_empty_diagnostics(
     lsp_diagnostics
 }
 
-/// Converts diagnostic severity level from the codespan format to the format understood by the
+/// Converts diagnostic severity level from the compiler's format to the format understood by the
 /// language server.
 fn severity(s: Severity) -> DiagnosticSeverity {
     match s {
         Severity::Bug => DiagnosticSeverity::ERROR,
-        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::BlockingError | Severity::NonblockingError => DiagnosticSeverity::ERROR,
         Severity::Warning => DiagnosticSeverity::WARNING,
-        Severity::Note => DiagnosticSeverity::INFORMATION,
-        Severity::Help => DiagnosticSeverity::HINT,
     }
 }
@@ -5,10 +5,14 @@
 #[macro_use(sp)]
 extern crate move_ir_types;
 
+pub mod code_action;
 pub mod completion;
 pub mod context;
 pub mod diagnostics;
 pub mod inlay_hints;
+pub mod rename;
+pub mod semantic_tokens;
+pub mod signature_help;
 pub mod symbols;
 pub mod utils;
 pub mod vfs;
@@ -0,0 +1,377 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Renaming of symbols across a package, built on the use/def information computed by the
+//! symbolicator. Functions, structs, fields, locals, and constants can be renamed as long as they
+//! are defined in the package itself (and not in one of its dependencies).
+
+use crate::{
+    context::Context,
+    symbols::{DefInfo, DefLoc, SymbolicatorRunner, Symbols, UseDef},
+    utils::file_contents,
+};
+use lsp_server::{ErrorCode, Request, RequestId, Response};
+use lsp_types::{
+    Position, PrepareRenameResponse, Range, RenameParams, TextDocumentPositionParams, TextEdit,
+    WorkspaceEdit,
+};
+use move_compiler::parser::keywords::KEYWORDS;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+use url::Url;
+use vfs::VfsPath;
+
+/// Kinds of definitions that can be renamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameKind {
+    Function,
+    Struct,
+    Field,
+    Local,
+    Const,
+}
+
+/// Handles prepare rename request of the language server
+pub fn on_prepare_rename_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let parameters = serde_json::from_value::<TextDocumentPositionParams>(request.params.clone())
+        .expect("could not deserialize prepare rename request");
+
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+    let response = match renamed_def(symbols, &fpath, &parameters) {
+        Ok((use_def, _, _)) => {
+            let range = Range {
+                start: Position {
+                    line: parameters.position.line,
+                    character: use_def.col_start(),
+                },
+                end: Position {
+                    line: parameters.position.line,
+                    character: use_def.col_end(),
+                },
+            };
+            Response::new_ok(request.id.clone(), PrepareRenameResponse::Range(range))
+        }
+        Err(msg) => rename_error(request.id.clone(), ErrorCode::InvalidRequest, msg),
+    };
+    send_response(context, response);
+}
+
+/// Handles rename request of the language server
+pub fn on_rename_request(
+    context: &Context,
+    request: &Request,
+    symbols: &Symbols,
+    ide_files_root: &VfsPath,
+) {
+    let parameters = serde_json::from_value::<RenameParams>(request.params.clone())
+        .expect("could not deserialize rename request");
+
+    let position = &parameters.text_document_position;
+    let fpath = position.text_document.uri.to_file_path().unwrap();
+    let response = match renamed_def(symbols, &fpath, position) {
+        Ok((use_def, kind, old_name)) => {
+            let new_name = parameters.new_name.as_str();
+            if let Err(msg) = check_new_name(kind, &old_name, new_name) {
+                rename_error(request.id.clone(), ErrorCode::InvalidParams, msg)
+            } else {
+                let edit = rename_edit(
+                    symbols,
+                    ide_files_root,
+                    &use_def.def_loc(),
+                    kind,
+                    &old_name,
+                    new_name,
+                );
+                Response::new_ok(request.id.clone(), edit)
+            }
+        }
+        Err(msg) => rename_error(request.id.clone(), ErrorCode::InvalidRequest, msg),
+    };
+    send_response(context, response);
+}
+
+/// Finds the definition used at the given position and checks that it can be renamed, returning
+/// the use, the kind of the definition, and its current name.
+fn renamed_def(
+    symbols: &Symbols,
+    fpath: &Path,
+    position: &TextDocumentPositionParams,
+) -> Result<(UseDef, RenameKind, String), String> {
+    let line = position.position.line;
+    let col = position.position.character;
+    let Some(use_def) = symbols
+        .line_uses(fpath, line)
+        .into_iter()
+        .find(|u| col >= u.col_start() && col <= u.col_end())
+    else {
+        return Err("No symbol to rename at this position".to_string());
+    };
+
+    let def_loc = use_def.def_loc();
+    let (kind, name) = match symbols.def_info(&def_loc) {
+        Some(DefInfo::Function(_, _, _, name, _, _, _, _)) => (RenameKind::Function, name),
+        Some(DefInfo::Struct(_, name, _, _, _, _, _)) => (RenameKind::Struct, name),
+        Some(DefInfo::Field(_, _, name, _)) => (RenameKind::Field, name),
+        Some(DefInfo::Local(name, _, _, _)) => (RenameKind::Local, name),
+        Some(DefInfo::Const(_, name, _, _)) => (RenameKind::Const, name),
+        Some(DefInfo::Type(_)) | Some(DefInfo::Module(_)) | None => {
+            return Err(
+                "Only functions, structs, fields, locals, and constants can be renamed".to_string(),
+            )
+        }
+    };
+
+    // definitions outside of the package being edited (i.e., in its dependencies) cannot be
+    // renamed as we cannot (and should not) update their other uses
+    let pkg_root = SymbolicatorRunner::root_dir(fpath);
+    let def_pkg_root = symbols
+        .file_path(&def_loc.fhash())
+        .and_then(|def_path| SymbolicatorRunner::root_dir(def_path));
+    if pkg_root.is_none() || pkg_root != def_pkg_root {
+        return Err(format!(
+            "Cannot rename '{name}' as it is not defined in this package"
+        ));
+    }
+    Ok((use_def, kind, name.to_string()))
+}
+
+/// Checks that the new name is valid for the kind of definition being renamed.
+fn check_new_name(kind: RenameKind, old_name: &str, new_name: &str) -> Result<(), String> {
+    // macro parameters keep their `$` prefix
+    let name = if kind == RenameKind::Local && old_name.starts_with('$') {
+        let Some(name) = new_name.strip_prefix('$') else {
+            return Err(format!(
+                "Invalid name '{new_name}'. Macro parameter names must start with '$'"
+            ));
+        };
+        name
+    } else {
+        new_name
+    };
+    let mut chars = name.chars();
+    let valid_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name);
+    if !valid_identifier {
+        return Err(format!("Invalid name '{new_name}'. Expected an identifier"));
+    }
+    let first = name.chars().next().unwrap();
+    match kind {
+        RenameKind::Struct | RenameKind::Const if !first.is_ascii_uppercase() => Err(format!(
+            "Invalid name '{new_name}'. Struct and constant names must start with 'A'..'Z'"
+        )),
+        RenameKind::Function if first == '_' => Err(format!(
+            "Invalid name '{new_name}'. Function names cannot start with '_'"
+        )),
+        RenameKind::Local if first.is_ascii_uppercase() => Err(format!(
+            "Invalid name '{new_name}'. Local variable names must start with 'a'..'z' or '_'"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Computes the edits renaming all uses of the definition at `def_loc`.
+fn rename_edit(
+    symbols: &Symbols,
+    ide_files_root: &VfsPath,
+    def_loc: &DefLoc,
+    kind: RenameKind,
+    old_name: &str,
+    new_name: &str,
+) -> WorkspaceEdit {
+    let mut file_lines: BTreeMap<_, Vec<String>> = BTreeMap::new();
+    let mut edits: BTreeMap<_, BTreeMap<(u32, u32), TextEdit>> = BTreeMap::new();
+    for (path, range) in symbols.references(def_loc) {
+        let lines = file_lines.entry(path.clone()).or_insert_with(|| {
+            file_contents(ide_files_root, &path)
+                .map(|contents| contents.lines().map(str::to_string).collect())
+                .unwrap_or_default()
+        });
+        // uses through an alias (e.g., `use a::m::foo as bar`) refer to the definition under a
+        // different name, and they should stay as they are
+        let Some(line) = lines.get(range.start.line as usize) else {
+            continue;
+        };
+        let text: String = line
+            .chars()
+            .skip(range.start.character as usize)
+            .take((range.end.character - range.start.character) as usize)
+            .collect();
+        if text != old_name {
+            continue;
+        }
+        // in field punning (e.g., `S { x }`), the same identifier is both a field and a local, so
+        // the shorthand has to be expanded to rename only one of them
+        let punned = symbols
+            .line_uses(&path, range.start.line)
+            .iter()
+            .any(|u| u.col_start() == range.start.character && u.def_loc() != *def_loc);
+        let new_text = match kind {
+            RenameKind::Field if punned => format!("{new_name}: {old_name}"),
+            RenameKind::Local if punned => format!("{old_name}: {new_name}"),
+            _ => new_name.to_string(),
+        };
+        edits.entry(path).or_default().insert(
+            (range.start.line, range.start.character),
+            TextEdit { range, new_text },
+        );
+    }
+
+    let changes: HashMap<Url, Vec<TextEdit>> = edits
+        .into_iter()
+        .map(|(path, file_edits)| {
+            (
+                Url::from_file_path(path).unwrap(),
+                file_edits.into_values().collect(),
+            )
+        })
+        .collect();
+    WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }
+}
+
+fn rename_error(id: RequestId, code: ErrorCode, msg: String) -> Response {
+    Response::new_err(id, code as i32, msg)
+}
+
+fn send_response(context: &Context, response: Response) {
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send rename response: {:?}", err);
+    }
+}
+
+#[test]
+fn rename_new_name_test() {
+    assert!(check_new_name(RenameKind::Function, "foo", "bar_2").is_ok());
+    assert!(check_new_name(RenameKind::Function, "foo", "_bar").is_err());
+    assert!(check_new_name(RenameKind::Function, "foo", "2bar").is_err());
+    assert!(check_new_name(RenameKind::Function, "foo", "fun").is_err());
+    assert!(check_new_name(RenameKind::Struct, "S", "Coin").is_ok());
+    assert!(check_new_name(RenameKind::Struct, "S", "coin").is_err());
+    assert!(check_new_name(RenameKind::Const, "E_FOO", "EBar").is_ok());
+    assert!(check_new_name(RenameKind::Const, "E_FOO", "e_bar").is_err());
+    assert!(check_new_name(RenameKind::Local, "x", "_y").is_ok());
+    assert!(check_new_name(RenameKind::Local, "x", "Y").is_err());
+    assert!(check_new_name(RenameKind::Local, "$f", "$g").is_ok());
+    assert!(check_new_name(RenameKind::Local, "$f", "g").is_err());
+    assert!(check_new_name(RenameKind::Field, "x", "value").is_ok());
+    assert!(check_new_name(RenameKind::Field, "x", "a-b").is_err());
+}
+
+#[test]
+fn rename_across_modules_test() {
+    use crate::symbols::get_symbols;
+    use lsp_types::TextDocumentIdentifier;
+    use move_compiler::linters::LintLevel;
+    use std::{
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+    use vfs::impls::memory::MemoryFS;
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/rename");
+
+    let ide_files_root: VfsPath = MemoryFS::new().into();
+    let (symbols_opt, _) = get_symbols(
+        Arc::new(Mutex::new(BTreeMap::new())),
+        ide_files_root.clone(),
+        path.as_path(),
+        LintLevel::None,
+    )
+    .unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    // renames the symbol used at the given position, returning the edits as their file name,
+    // line, column, and new text
+    let rename = |file: &str, line, character, new_name: &str| {
+        let fpath = dunce::canonicalize(path.join("sources").join(file)).unwrap();
+        let position = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier::new(Url::from_file_path(&fpath).unwrap()),
+            position: Position { line, character },
+        };
+        let (use_def, kind, old_name) = renamed_def(&symbols, &fpath, &position)?;
+        check_new_name(kind, &old_name, new_name)?;
+        let edit = rename_edit(
+            &symbols,
+            &ide_files_root,
+            &use_def.def_loc(),
+            kind,
+            &old_name,
+            new_name,
+        );
+        let mut edits: Vec<_> = edit
+            .changes
+            .unwrap()
+            .into_iter()
+            .flat_map(|(uri, file_edits)| {
+                let path = uri.to_file_path().unwrap();
+                let file = path.file_name().unwrap().to_string_lossy().to_string();
+                file_edits.into_iter().map(move |e| {
+                    (
+                        file.clone(),
+                        e.range.start.line,
+                        e.range.start.character,
+                        e.new_text,
+                    )
+                })
+            })
+            .collect();
+        edits.sort();
+        Ok::<_, String>(edits)
+    };
+    let edit = |file: &str, line: u32, col: u32, new_text: &str| {
+        (file.to_string(), line, col, new_text.to_string())
+    };
+
+    // the definition in m1 and the uses in m2 are renamed, except where the function is used
+    // through an alias
+    assert_eq!(
+        rename("m2.move", 4, 24, "create").unwrap(),
+        vec![
+            edit("m1.move", 3, 15, "create"),
+            edit("m2.move", 1, 27, "create"),
+            edit("m2.move", 4, 24, "create"),
+        ]
+    );
+    assert_eq!(
+        rename("m2.move", 3, 39, "Token").unwrap(),
+        vec![
+            edit("m1.move", 1, 18, "Token"),
+            edit("m1.move", 3, 33, "Token"),
+            edit("m1.move", 4, 8, "Token"),
+            edit("m2.move", 3, 39, "Token"),
+            edit("m2.move", 3, 49, "Token"),
+        ]
+    );
+    assert!(rename("m2.move", 3, 39, "token").is_err());
+    // renaming either a field or a local used in field punning expands the shorthand
+    assert_eq!(
+        rename("m1.move", 1, 34, "amount").unwrap(),
+        vec![
+            edit("m1.move", 1, 34, "amount"),
+            edit("m1.move", 4, 15, "amount: value"),
+        ]
+    );
+    assert_eq!(
+        rename("m1.move", 3, 20, "amount").unwrap(),
+        vec![
+            edit("m1.move", 3, 20, "amount"),
+            edit("m1.move", 4, 15, "value: amount"),
+        ]
+    );
+    // definitions in dependencies cannot be renamed
+    let err = rename("m2.move", 9, 21, "len").unwrap_err();
+    assert!(err.contains("not defined in this package"), "{err}");
+    assert!(rename("m2.move", 2, 0, "foo").is_err());
+}
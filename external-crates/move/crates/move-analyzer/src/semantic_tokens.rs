@@ -0,0 +1,169 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Semantic tokens, classifying identifiers based on what they refer to (as opposed to the purely
+//! syntactic highlighting done by the editor itself), computed from the symbolication information.
+
+use crate::{
+    context::Context,
+    symbols::{DefInfo, Symbols},
+};
+use lsp_server::Request;
+use lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensLegend,
+    SemanticTokensParams, SemanticTokensResult,
+};
+use move_compiler::naming::ast::Type_;
+
+/// Token types, in the order of their indices in the legend
+const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::VARIABLE,
+];
+
+const NAMESPACE: u32 = 0;
+const STRUCT: u32 = 1;
+const TYPE: u32 = 2;
+const TYPE_PARAMETER: u32 = 3;
+const FUNCTION: u32 = 4;
+const PROPERTY: u32 = 5;
+const VARIABLE: u32 = 6;
+
+/// Token modifiers, in the order of their bits in the legend
+fn token_modifiers() -> Vec<SemanticTokenModifier> {
+    vec![
+        SemanticTokenModifier::DECLARATION,
+        SemanticTokenModifier::READONLY,
+        SemanticTokenModifier::new("mutable"),
+    ]
+}
+
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const MUTABLE: u32 = 1 << 2;
+
+/// Returns the legend of the semantic tokens computed by the language server
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: token_modifiers(),
+    }
+}
+
+/// Handles semantic tokens request of the language server
+pub fn on_semantic_tokens_full_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let parameters = serde_json::from_value::<SemanticTokensParams>(request.params.clone())
+        .expect("could not deserialize semantic tokens request");
+
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+    let mut tokens = vec![];
+    for (line, u) in symbols.file_uses(&fpath) {
+        let def_loc = u.def_loc();
+        let Some(def_info) = symbols.def_info(&def_loc) else {
+            continue;
+        };
+        let (token_type, mut modifiers) = match def_info {
+            DefInfo::Module(_) => (NAMESPACE, 0),
+            DefInfo::Struct(..) => (STRUCT, 0),
+            DefInfo::Type(sp!(_, Type_::Param(_))) => (TYPE_PARAMETER, 0),
+            DefInfo::Type(_) => (TYPE, 0),
+            DefInfo::Function(..) => (FUNCTION, 0),
+            DefInfo::Field(..) => (PROPERTY, 0),
+            DefInfo::Local(_, _, _, mutable) => (VARIABLE, if *mutable { MUTABLE } else { 0 }),
+            DefInfo::Const(..) => (VARIABLE, READONLY),
+        };
+        if def_loc.start().line == line
+            && def_loc.start().character == u.col_start()
+            && symbols.file_path(&def_loc.fhash()) == Some(&fpath)
+        {
+            modifiers |= DECLARATION;
+        }
+        tokens.push((
+            line,
+            u.col_start(),
+            u.col_end() - u.col_start(),
+            token_type,
+            modifiers,
+        ));
+    }
+
+    let result = SemanticTokensResult::Tokens(SemanticTokens {
+        result_id: None,
+        data: encode(tokens),
+    });
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send semantic tokens response: {:?}", err);
+    }
+}
+
+/// Encodes tokens, given as their line, start column, length, type and modifiers, in the relative
+/// format of the protocol. Tokens cannot overlap, so of the tokens starting at the same location
+/// only the first one is kept.
+fn encode(mut tokens: Vec<(u32, u32, u32, u32, u32)>) -> Vec<SemanticToken> {
+    tokens.sort_by_key(|(line, start, _, _, _)| (*line, *start));
+    let mut data = vec![];
+    let mut prev: Option<(u32, u32, u32)> = None;
+    for (line, start, length, token_type, token_modifiers_bitset) in tokens {
+        let (delta_line, delta_start) = match prev {
+            Some((prev_line, prev_start, prev_length)) if prev_line == line => {
+                if start < prev_start + prev_length {
+                    continue;
+                }
+                (0, start - prev_start)
+            }
+            Some((prev_line, _, _)) => (line - prev_line, start),
+            None => (line, start),
+        };
+        data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset,
+        });
+        prev = Some((line, start, length));
+    }
+    data
+}
+
+#[test]
+fn semantic_tokens_encode_test() {
+    let tokens = vec![
+        (2, 8, 1, VARIABLE, 0),
+        (0, 7, 4, NAMESPACE, DECLARATION),
+        (2, 4, 3, FUNCTION, 0),
+        (2, 8, 1, PROPERTY, 0),
+        (5, 2, 3, STRUCT, 0),
+    ];
+    let encoded: Vec<_> = encode(tokens)
+        .into_iter()
+        .map(|t| {
+            (
+                t.delta_line,
+                t.delta_start,
+                t.length,
+                t.token_type,
+                t.token_modifiers_bitset,
+            )
+        })
+        .collect();
+    assert_eq!(
+        encoded,
+        vec![
+            (0, 7, 4, NAMESPACE, DECLARATION),
+            (2, 4, 3, FUNCTION, 0),
+            (0, 4, 1, VARIABLE, 0),
+            (3, 2, 3, STRUCT, 0),
+        ]
+    );
+}
@@ -0,0 +1,279 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signature help for function calls. The call surrounding the cursor is found in the source text
+//! (which is likely not to compile while the call is being typed), and the called function is
+//! resolved using the symbolication information computed for the typed AST.
+
+use crate::{
+    context::Context,
+    symbols::{
+        mod_ident_to_ide_string, type_args_to_ide_string, type_to_ide_string, DefInfo, Symbols,
+    },
+    utils::{byte_offset, file_contents},
+};
+use lsp_server::Request;
+use lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, Position, SignatureHelp,
+    SignatureHelpParams, SignatureInformation,
+};
+use move_compiler::naming::ast::Type_;
+use move_symbol_pool::Symbol;
+use std::path::Path;
+use vfs::VfsPath;
+
+/// A call whose arguments are being typed
+#[derive(Debug, PartialEq, Eq)]
+struct CallSite {
+    /// Name of the called function
+    name: String,
+    /// Location of the called function's name
+    name_start: Position,
+    /// Name of the module qualifying the function name (as in `m::f(...)`), if any
+    module: Option<String>,
+    /// Is this a method call (as in `x.f(...)`)?
+    is_method: bool,
+    /// Index of the argument the cursor is in
+    arg_idx: u32,
+}
+
+/// Handles signature help request of the language server
+pub fn on_signature_help_request(
+    context: &Context,
+    request: &Request,
+    symbols: &Symbols,
+    ide_files_root: &VfsPath,
+) {
+    let parameters = serde_json::from_value::<SignatureHelpParams>(request.params.clone())
+        .expect("could not deserialize signature help request");
+
+    let position = parameters.text_document_position_params.position;
+    let fpath = parameters
+        .text_document_position_params
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let help = file_contents(ide_files_root, &fpath)
+        .and_then(|contents| call_site(&contents, &position))
+        .and_then(|call| signature_help(symbols, &fpath, &call));
+
+    let response = lsp_server::Response::new_ok(request.id.clone(), help);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send signature help response: {:?}", err);
+    }
+}
+
+/// Finds the call whose argument list contains the cursor.
+fn call_site(contents: &str, position: &Position) -> Option<CallSite> {
+    let cursor = byte_offset(contents, position)?;
+    let prefix = &contents[..cursor];
+
+    // find the opening parenthesis of the argument list, counting the arguments preceding the
+    // cursor on the way
+    let mut depth = 0;
+    let mut arg_idx = 0;
+    let mut open_paren = None;
+    for (idx, c) in prefix.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' if depth == 0 => {
+                open_paren = Some(idx);
+                break;
+            }
+            // the cursor is not within an argument list
+            '[' | '{' | ';' if depth == 0 => return None,
+            '(' | '[' | '{' => depth -= 1,
+            ',' if depth == 0 => arg_idx += 1,
+            _ => (),
+        }
+    }
+
+    // skip explicit type arguments (as in `f<u64>(...)`)
+    let mut name_end = prefix[..open_paren?].trim_end();
+    if name_end.ends_with('>') {
+        let mut depth = 0;
+        let (lt_idx, _) = name_end.char_indices().rev().find(|(_, c)| {
+            match c {
+                '>' => depth += 1,
+                '<' => depth -= 1,
+                _ => (),
+            }
+            depth == 0
+        })?;
+        name_end = name_end[..lt_idx].trim_end();
+    }
+
+    let name = trailing_identifier(name_end)?;
+    let before_name = &name_end[..name_end.len() - name.len()];
+    let (module, is_method) = if let Some(qualified) = before_name.strip_suffix("::") {
+        (trailing_identifier(qualified).map(str::to_string), false)
+    } else {
+        (None, before_name.trim_end().ends_with('.'))
+    };
+
+    let line = before_name.matches('\n').count() as u32;
+    let line_start = before_name.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    let character = before_name[line_start..].chars().count() as u32;
+    Some(CallSite {
+        name: name.to_string(),
+        name_start: Position { line, character },
+        module,
+        is_method,
+        arg_idx,
+    })
+}
+
+/// Returns the identifier the given string ends with, if any.
+fn trailing_identifier(s: &str) -> Option<&str> {
+    let start = s
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_' || *c == '$')
+        .last()?
+        .0;
+    let ident = &s[start..];
+    ident
+        .starts_with(|c: char| !c.is_ascii_digit())
+        .then_some(ident)
+}
+
+/// Computes signature help for the function called at the given call site.
+fn signature_help(symbols: &Symbols, fpath: &Path, call: &CallSite) -> Option<SignatureHelp> {
+    // the function name has been resolved if the call was present the last time the package was
+    // compiled successfully, and otherwise the function has to be looked up by name
+    let resolved = symbols
+        .line_uses(fpath, call.name_start.line)
+        .into_iter()
+        .find(|u| u.col_start() == call.name_start.character)
+        .and_then(|u| {
+            let info = symbols.def_info(&u.def_loc())?;
+            matches!(info, DefInfo::Function(..)).then(|| (info, u.doc_string().cloned()))
+        });
+    let (info, doc_string) = match resolved {
+        Some(resolved) => resolved,
+        None => (lookup_function(symbols, fpath, call)?, None),
+    };
+    let DefInfo::Function(mod_ident, _, _, name, type_args, arg_names, arg_types, ret) = info
+    else {
+        return None;
+    };
+
+    let mut label = format!(
+        "fun {}::{}{}(",
+        mod_ident_to_ide_string(mod_ident),
+        name,
+        type_args_to_ide_string(type_args)
+    );
+    let mut parameters = vec![];
+    for (idx, (arg_name, arg_type)) in arg_names.iter().zip(arg_types).enumerate() {
+        if idx > 0 {
+            label.push_str(", ");
+        }
+        let start = label.chars().count() as u32;
+        label.push_str(&format!("{}: {}", arg_name, type_to_ide_string(arg_type)));
+        let end = label.chars().count() as u32;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+    label.push(')');
+    if !matches!(ret.value, Type_::Unit) {
+        label.push_str(&format!(": {}", type_to_ide_string(ret)));
+    }
+
+    // the receiver of a method call is the function's first argument
+    let active_parameter = call.arg_idx + u32::from(call.is_method);
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: doc_string.map(Documentation::String),
+            parameters: Some(parameters),
+            active_parameter: Some(active_parameter),
+        }],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+/// Looks up a function by the name it is called with: in the module it is qualified with, or in
+/// the modules of the current file for unqualified calls. Method calls can only be looked up if
+/// there is a single function with the given name, as the type of the receiver is not known.
+fn lookup_function<'a>(symbols: &'a Symbols, fpath: &Path, call: &CallSite) -> Option<&'a DefInfo> {
+    let name = Symbol::from(call.name.as_str());
+    let mut candidates: Vec<&DefInfo> = match (&call.module, call.is_method) {
+        (Some(module), _) => symbols
+            .file_mods()
+            .values()
+            .flatten()
+            .filter(|mod_defs| mod_defs.ident().module.value().as_str() == module)
+            .filter_map(|mod_defs| symbols.function_info(mod_defs, &name))
+            .collect(),
+        (None, false) => symbols
+            .file_mods()
+            .get(fpath)?
+            .iter()
+            .filter_map(|mod_defs| symbols.function_info(mod_defs, &name))
+            .collect(),
+        (None, true) => symbols
+            .file_mods()
+            .values()
+            .flatten()
+            .filter_map(|mod_defs| symbols.function_info(mod_defs, &name))
+            .collect(),
+    };
+    if candidates.len() == 1 {
+        candidates.pop()
+    } else {
+        None
+    }
+}
+
+#[test]
+fn signature_help_call_site_test() {
+    let call = |contents: &str, line, character| call_site(contents, &Position { line, character });
+
+    let contents = "fun foo() {\n    let x = m::bar(1, vector[2, 3], ";
+    assert_eq!(
+        call(contents, 1, 36),
+        Some(CallSite {
+            name: "bar".to_string(),
+            name_start: Position {
+                line: 1,
+                character: 15
+            },
+            module: Some("m".to_string()),
+            is_method: false,
+            arg_idx: 2,
+        })
+    );
+
+    let contents = "    v.push_back<u64>(baz(1), ";
+    assert_eq!(
+        call(contents, 0, 29),
+        Some(CallSite {
+            name: "push_back".to_string(),
+            name_start: Position {
+                line: 0,
+                character: 6
+            },
+            module: None,
+            is_method: true,
+            arg_idx: 1,
+        })
+    );
+
+    // inside of a nested call
+    assert_eq!(
+        call(contents, 0, 26).map(|c| c.name),
+        Some("baz".to_string())
+    );
+    // outside of any call
+    assert_eq!(call("fun foo() { x + 1", 0, 16), None);
+    assert_eq!(call("    let x = (1 + ", 0, 17), None);
+}
@@ -423,6 +423,14 @@ impl ModuleDefs {
         self.fhash
     }
 
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn ident(&self) -> &ModuleIdent_ {
+        &self.ident
+    }
+
     pub fn untyped_defs(&self) -> &BTreeSet<DefLoc> {
         &self.untyped_defs
     }
@@ -567,7 +575,7 @@ fn visibility_to_ide_string(visibility: &Visibility) -> String {
     visibility_str
 }
 
-fn type_args_to_ide_string(type_args: &Vec<Type>) -> String {
+pub fn type_args_to_ide_string(type_args: &Vec<Type>) -> String {
     let mut type_args_str = "".to_string();
     if !type_args.is_empty() {
         type_args_str.push('<');
@@ -764,7 +772,7 @@ fn ast_value_to_ide_string(sp!(_, val): &Value) -> String {
     }
 }
 
-fn mod_ident_to_ide_string(mod_ident: &E::ModuleIdent_) -> String {
+pub fn mod_ident_to_ide_string(mod_ident: &E::ModuleIdent_) -> String {
     use E::Address as A;
     match mod_ident.address {
         A::Numerical {
@@ -1030,6 +1038,10 @@ impl UseDef {
     pub fn def_loc(&self) -> DefLoc {
         self.def_loc
     }
+
+    pub fn doc_string(&self) -> Option<&String> {
+        self.doc_string.as_ref()
+    }
 }
 
 impl Ord for UseDef {
@@ -1096,10 +1108,57 @@ impl Symbols {
         file_symbols.get(use_line).unwrap_or_else(BTreeSet::new)
     }
 
+    /// Returns all uses in a file along with the lines they are on, sorted by their location.
+    pub fn file_uses(&self, use_fpath: &Path) -> Vec<(u32, &UseDef)> {
+        let Some(file_symbols) = self.file_use_defs.get(use_fpath) else {
+            return vec![];
+        };
+        file_symbols
+            .0
+            .iter()
+            .flat_map(|(line, uses)| uses.iter().map(move |u| (*line, u)))
+            .collect()
+    }
+
     pub fn def_info(&self, def_loc: &DefLoc) -> Option<&DefInfo> {
         self.def_info.get(def_loc)
     }
 
+    /// Returns information about a function defined in the given module.
+    pub fn function_info(&self, mod_defs: &ModuleDefs, name: &Symbol) -> Option<&DefInfo> {
+        let fun_def = mod_defs.functions.get(name)?;
+        self.def_info(&DefLoc {
+            fhash: mod_defs.fhash,
+            start: fun_def.start,
+        })
+    }
+
+    /// Returns the locations of all uses of the definition at `def_loc`, including the
+    /// definition itself.
+    pub fn references(&self, def_loc: &DefLoc) -> Vec<(PathBuf, Range)> {
+        let Some(ref_locs) = self.references.get(def_loc) else {
+            return vec![];
+        };
+        ref_locs
+            .iter()
+            .filter_map(|ref_loc| {
+                let path = self.file_name_mapping.get(&ref_loc.fhash)?;
+                let range = Range {
+                    start: ref_loc.start,
+                    end: Position {
+                        line: ref_loc.start.line,
+                        character: ref_loc.col_end,
+                    },
+                };
+                Some((path.clone(), range))
+            })
+            .collect()
+    }
+
+    pub fn file_path(&self, fhash: &FileHash) -> Option<&PathBuf> {
+        self.file_name_mapping.get(fhash)
+    }
+
     pub fn mod_defs(&self, fhash: &FileHash, mod_ident: ModuleIdent_) -> Option<&ModuleDefs> {
         let Some(fpath) = self.file_name_mapping.get(fhash) else {
             return None;
@@ -1301,7 +1360,7 @@ pub fn get_symbols(
     let mut ide_diagnostics = lsp_empty_diagnostics(&file_name_mapping);
     if let Some((compiler_diagnostics, failure)) = diagnostics {
         let lsp_diagnostics = lsp_diagnostics(
-            &compiler_diagnostics.into_codespan_format(),
+            &compiler_diagnostics.into_vec(),
             &files,
             &file_id_mapping,
             &file_name_mapping,
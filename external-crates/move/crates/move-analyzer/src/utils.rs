@@ -6,7 +6,8 @@ use lsp_types::Position;
 use move_command_line_common::files::FileHash;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Read, path::Path};
+use vfs::VfsPath;
 
 /// Converts a location from the byte index format to the line/character (Position) format, where
 /// line/character are 0-based.
@@ -29,3 +30,34 @@ pub fn get_loc(
         Err(_) => None,
     }
 }
+
+/// Returns the contents of a file as the user sees them: the contents of the editor buffer if the
+/// file is open in the IDE, and the contents of the file on disk otherwise.
+pub fn file_contents(ide_files_root: &VfsPath, path: &Path) -> Option<String> {
+    if let Ok(mut f) = ide_files_root
+        .join(path.to_string_lossy())
+        .and_then(|p| p.open_file())
+    {
+        let mut buffer = String::new();
+        if f.read_to_string(&mut buffer).is_ok() {
+            return Some(buffer);
+        }
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+/// Converts a line/character (Position) location in the given file contents to a byte offset.
+/// Characters past the end of a line are clamped to the end of the line.
+pub fn byte_offset(contents: &str, pos: &Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..pos.line {
+        line_start += contents[line_start..].find('\n')? + 1;
+    }
+    let line = &contents[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let col = match line.char_indices().nth(pos.character as usize) {
+        Some((col, _)) => col,
+        None => line.len(),
+    };
+    Some(line_start + col)
+}
@@ -0,0 +1,7 @@
+module Rename::m1 {
+    public struct Coin has drop { value: u64 }
+
+    public fun mint(value: u64): Coin {
+        Coin { value }
+    }
+}
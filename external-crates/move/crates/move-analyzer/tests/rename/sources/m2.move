@@ -0,0 +1,12 @@
+module Rename::m2 {
+    use Rename::m1::{Self, mint as make};
+
+    public fun twice(value: u64): (m1::Coin, m1::Coin) {
+        let first = m1::mint(value);
+        (first, make(value))
+    }
+
+    public fun length(v: &vector<u64>): u64 {
+        std::vector::length(v)
+    }
+}
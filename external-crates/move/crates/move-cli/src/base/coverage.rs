@@ -5,12 +5,12 @@ use super::reroot_path;
 use clap::*;
 use move_compiler::compiled_unit::NamedCompiledModule;
 use move_coverage::{
-    coverage_map::CoverageMap, format_csv_summary, format_human_summary,
+    coverage_map::CoverageMap, format_csv_summary, format_human_summary, report::CoverageReport,
     source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
 use move_package::BuildConfig;
-use std::path::PathBuf;
+use std::{fs::File, io::Write, path::PathBuf};
 
 #[derive(Parser)]
 pub enum CoverageSummaryOptions {
@@ -36,6 +36,25 @@ pub enum CoverageSummaryOptions {
         #[clap(long = "module")]
         module_name: String,
     },
+    /// Export per-line, per-function and per-branch coverage information for all modules in this
+    /// package, for use with other coverage tools
+    #[clap(name = "export")]
+    Export {
+        /// The format to export coverage information in
+        #[clap(long = "format", value_enum, default_value = "lcov")]
+        format: CoverageExportFormat,
+        /// Write coverage information to this file instead of stdout
+        #[clap(long = "output", short = 'o')]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CoverageExportFormat {
+    /// An LCOV tracefile
+    Lcov,
+    /// Coverage information and summaries for each module, as JSON
+    Json,
 }
 
 /// Inspect test coverage for this package. A previous test run with the `--coverage` flag must
@@ -95,6 +114,27 @@ impl Coverage {
                 disassembler.add_coverage_map(coverage_map.to_unified_exec_map());
                 println!("{}", disassembler.disassemble()?);
             }
+            CoverageSummaryOptions::Export { format, output } => {
+                let coverage_map = coverage_map.to_unified_exec_map();
+                let report = CoverageReport::new(
+                    package.root_modules().map(|unit| {
+                        (
+                            &unit.unit.module,
+                            &unit.unit.source_map,
+                            unit.source_path.as_path(),
+                        )
+                    }),
+                    &coverage_map,
+                )?;
+                let mut writer: Box<dyn Write> = match output {
+                    Some(output) => Box::new(File::create(output)?),
+                    None => Box::new(std::io::stdout()),
+                };
+                match format {
+                    CoverageExportFormat::Lcov => report.write_lcov(&mut writer)?,
+                    CoverageExportFormat::Json => report.write_json(&mut writer)?,
+                }
+            }
         }
         Ok(())
     }
@@ -66,6 +66,13 @@ pub struct Test {
     /// Collect coverage information for later use with the various `move coverage` subcommands. Currently supported only in debug builds.
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
+    /// Number of times to run each `#[random_test]`, each time with newly generated arguments.
+    #[clap(long = "rand-num-iters", value_parser = clap::value_parser!(u64).range(1..))]
+    pub rand_num_iters: Option<u64>,
+    /// Seed for the arguments generated for `#[random_test]`s, e.g. the one reported by a failing
+    /// random test to reproduce its failure.
+    #[clap(long = "seed")]
+    pub seed: Option<u64>,
 }
 
 impl Test {
@@ -104,6 +111,8 @@ impl Test {
             report_statistics,
             verbose_mode,
             compute_coverage: _,
+            rand_num_iters,
+            seed,
         } = self;
         UnitTestingConfig {
             gas_limit,
@@ -112,6 +121,8 @@ impl Test {
             num_threads,
             report_statistics,
             verbose: verbose_mode,
+            rand_num_iters,
+            seed,
             ..UnitTestingConfig::default_with_bound(None)
         }
     }
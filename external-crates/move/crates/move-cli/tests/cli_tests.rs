@@ -2,7 +2,8 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use move_cli::sandbox::commands::test;
+use clap::Parser;
+use move_cli::{base::test::Test, sandbox::commands::test};
 use std::{env, path::PathBuf};
 
 pub const CLI_METATEST_PATH: [&str; 3] = ["tests", "metatests", "args.txt"];
@@ -33,3 +34,13 @@ fn run_metatest() {
     // temp workspace + without coverage
     assert!(test::run_all(&path_metatest, &path_cli_binary, true, false).is_ok());
 }
+
+#[test]
+fn rand_num_iters_must_be_positive() {
+    let parse = |iters: &str| Test::try_parse_from(["test", "--rand-num-iters", iters]);
+
+    assert_eq!(parse("5").unwrap().rand_num_iters, Some(5));
+
+    // Running a `#[random_test]` zero times would let it pass without ever running.
+    assert!(parse("0").is_err());
+}
@@ -767,6 +767,10 @@ impl Diagnostic {
         self.primary_label.0
     }
 
+    pub fn secondary_labels(&self) -> &[(Loc, String)] {
+        &self.secondary_labels
+    }
+
     pub fn is_migration(&self) -> bool {
         const MIGRATION_CATEGORY: u8 = codes::Category::Migration as u8;
         self.info.category() == MIGRATION_CATEGORY
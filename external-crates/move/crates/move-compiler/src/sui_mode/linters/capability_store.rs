@@ -0,0 +1,161 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags capability objects (i.e., objects whose type name ends with `Cap`) that have
+//! the `store` ability without it being needed. Such capabilities can be transferred freely by
+//! their owners, whereas without `store` their transfers are controlled by the defining module.
+//! The `store` ability is considered needed if the capability is used with one of the `public_*`
+//! functions of `sui::transfer`, with a dynamic field, or in a field of another struct.
+
+use std::collections::BTreeSet;
+
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    parser::ast::Ability_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    sui_mode::SUI_ADDR_NAME,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    LinterDiagnosticCategory, LinterDiagnosticCode, DYNAMIC_FIELD_MOD_NAME,
+    DYNAMIC_OBJECT_FIELD_MOD_NAME, LINT_WARNING_PREFIX, PUBLIC_FREEZE_FUN, PUBLIC_RECEIVE_FUN,
+    PUBLIC_SHARE_FUN, PUBLIC_TRANSFER_FUN, SUI_PKG_NAME, TRANSFER_MOD_NAME,
+};
+
+const CAPABILITY_STORE_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::CapabilityStore as u8,
+    "unnecessary 'store' ability on a capability",
+);
+
+const CAP_SUFFIX: &str = "Cap";
+
+const STORE_FUNCTIONS: &[(&str, &str, Option<&str>)] = &[
+    (SUI_PKG_NAME, TRANSFER_MOD_NAME, Some(PUBLIC_TRANSFER_FUN)),
+    (SUI_PKG_NAME, TRANSFER_MOD_NAME, Some(PUBLIC_SHARE_FUN)),
+    (SUI_PKG_NAME, TRANSFER_MOD_NAME, Some(PUBLIC_FREEZE_FUN)),
+    (SUI_PKG_NAME, TRANSFER_MOD_NAME, Some(PUBLIC_RECEIVE_FUN)),
+    (SUI_PKG_NAME, DYNAMIC_FIELD_MOD_NAME, None),
+    (SUI_PKG_NAME, DYNAMIC_OBJECT_FIELD_MOD_NAME, None),
+];
+
+pub struct CapabilityStoreVisitor;
+
+/// Collects the types used with functions requiring `store`
+struct StoreUses {
+    used: BTreeSet<(ModuleIdent, Symbol)>,
+}
+
+impl TypingVisitor for CapabilityStoreVisitor {
+    fn visit(
+        &mut self,
+        env: &mut CompilationEnv,
+        _program_info: &TypingProgramInfo,
+        program: &mut T::Program_,
+    ) {
+        let mut uses = StoreUses {
+            used: BTreeSet::new(),
+        };
+        for (mident, mdef) in program.modules.key_cloned_iter_mut() {
+            for (_, _, sdef) in mdef.structs.iter() {
+                if let N::StructFields::Defined(_, fields) = &sdef.fields {
+                    for (_, _, (_, ftype)) in fields.iter() {
+                        uses.add_type(ftype);
+                    }
+                }
+            }
+            for (fname, fdef) in mdef.functions.key_cloned_iter_mut() {
+                uses.visit_function(mident, fname, fdef);
+            }
+        }
+
+        for (mident, mdef) in program.modules.key_cloned_iter() {
+            if mdef.attributes.is_test_or_test_only() || mident.value.address.is(SUI_ADDR_NAME) {
+                continue;
+            }
+            env.add_warning_filter_scope(mdef.warning_filter.clone());
+            for (sloc, sname, sdef) in mdef.structs.iter() {
+                if sdef.attributes.is_test_or_test_only()
+                    || uses.used.contains(&(mident, *sname))
+                    || !sname.as_str().ends_with(CAP_SUFFIX)
+                    || !sdef.abilities.has_ability_(Ability_::Key)
+                {
+                    continue;
+                }
+                let Some(store_loc) = sdef.abilities.ability_loc_(Ability_::Store) else {
+                    continue;
+                };
+                env.add_warning_filter_scope(sdef.warning_filter.clone());
+                let msg = format!(
+                    "The capability '{sname}' has 'store', allowing its owner to transfer it \
+                     freely"
+                );
+                let store_msg = "Remove 'store' unless the capability is meant to be transferable \
+                                 without the involvement of this module";
+                let mut d = diag!(CAPABILITY_STORE_DIAG, (sloc, msg), (store_loc, store_msg));
+                d.add_note(
+                    "Without 'store', a capability can only be transferred by the module defining \
+                     it, which keeps control over who holds it",
+                );
+                env.add_diag(d);
+                env.pop_warning_filter_scope();
+            }
+            env.pop_warning_filter_scope();
+        }
+    }
+}
+
+impl StoreUses {
+    /// Records the struct types mentioned in a type
+    fn add_type(&mut self, sp!(_, t): &N::Type) {
+        use N::Type_ as T;
+        match t {
+            T::Ref(_, inner_t) => self.add_type(inner_t),
+            T::Apply(_, sp!(_, tname), targs) => {
+                if let N::TypeName_::ModuleType(mident, sname) = tname {
+                    self.used.insert((*mident, sname.value()));
+                }
+                targs.iter().for_each(|t| self.add_type(t));
+            }
+            T::Unit | T::Param(_) | T::Var(_) | T::Anything | T::UnresolvedError => (),
+            T::Fun(args, result) => {
+                args.iter().for_each(|t| self.add_type(t));
+                self.add_type(result);
+            }
+        }
+    }
+}
+
+impl TypingVisitorContext for StoreUses {
+    fn add_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        if let T::UnannotatedExp_::ModuleCall(call) = &exp.exp.value {
+            let needs_store = STORE_FUNCTIONS.iter().any(|(addr, module, fun)| {
+                call.module.value.is(*addr, *module)
+                    && fun.map_or(true, |fun| call.name.value().as_str() == fun)
+            });
+            if needs_store {
+                call.type_arguments.iter().for_each(|t| self.add_type(t));
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags public and entry functions modifying a shared object that follows the
+//! versioned pattern (i.e., an object with a `version: u64` field, or a `sui::versioned::Versioned`
+//! field) without checking the object's version. After a package upgrade, such functions remain
+//! callable through the previous versions of the package, which defeats the purpose of versioning
+//! the object.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::{ModuleIdent, Visibility},
+    naming::ast as N,
+    parser::ast::Ability_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    sui_mode::SUI_ADDR_NAME,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    base_type, LinterDiagnosticCategory, LinterDiagnosticCode, LINT_WARNING_PREFIX,
+    PUBLIC_SHARE_FUN, SHARE_FUN, SUI_PKG_NAME, TRANSFER_MOD_NAME, VERSIONED_MOD_NAME,
+    VERSIONED_STRUCT_NAME, VERSIONED_VERSION_FUN,
+};
+
+const MISSING_VERSION_CHECK_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::MissingVersionCheck as u8,
+    "missing version check on a shared object",
+);
+
+const VERSION_FIELD_NAME: &str = "version";
+
+pub struct MissingVersionCheckVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+/// Collects, for a single function, whether its body checks the version of a versioned object, the
+/// functions of the same module it calls, and the types of the module it shares
+struct FunctionFacts<'a> {
+    module: ModuleIdent,
+    versioned: &'a BTreeMap<Symbol, Loc>,
+    checks_version: bool,
+    callees: BTreeSet<Symbol>,
+    shared: BTreeSet<Symbol>,
+}
+
+impl TypingVisitorConstructor for MissingVersionCheckVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // the whole module is analyzed here, so it is always skipped afterwards
+        if mdef.attributes.is_test_or_test_only() || ident.value.address.is(SUI_ADDR_NAME) {
+            return true;
+        }
+        let candidates: BTreeMap<Symbol, Loc> = mdef
+            .structs
+            .key_cloned_iter()
+            .filter_map(|(sname, sdef)| Some((sname.value(), version_field(sdef)?)))
+            .collect();
+        if candidates.is_empty() {
+            return true;
+        }
+
+        let mut facts = BTreeMap::new();
+        for (fname, fdef) in mdef.functions.key_cloned_iter_mut() {
+            let mut function_facts = FunctionFacts {
+                module: ident,
+                versioned: &candidates,
+                checks_version: false,
+                callees: BTreeSet::new(),
+                shared: BTreeSet::new(),
+            };
+            function_facts.visit_function(ident, fname, fdef);
+            facts.insert(fname.value(), function_facts);
+        }
+
+        // only shared objects are subject to the check, as owned objects cannot be used with a
+        // previous version of the package without the consent of their owner
+        let shared: BTreeSet<Symbol> = facts.values().flat_map(|f| f.shared.clone()).collect();
+        let versioned: BTreeMap<Symbol, Loc> = candidates
+            .iter()
+            .filter(|(sname, _)| shared.contains(sname))
+            .map(|(sname, floc)| (*sname, *floc))
+            .collect();
+        if versioned.is_empty() {
+            return true;
+        }
+
+        // functions checking the version either directly, or by calling a function of the module
+        // that does
+        let mut checking: BTreeSet<Symbol> = facts
+            .iter()
+            .filter(|(_, f)| f.checks_version)
+            .map(|(fname, _)| *fname)
+            .collect();
+        loop {
+            let newly_checking: Vec<Symbol> = facts
+                .iter()
+                .filter(|(fname, f)| {
+                    !checking.contains(*fname) && f.callees.iter().any(|c| checking.contains(c))
+                })
+                .map(|(fname, _)| *fname)
+                .collect();
+            if newly_checking.is_empty() {
+                break;
+            }
+            checking.extend(newly_checking);
+        }
+
+        for (fname, fdef) in mdef.functions.key_cloned_iter() {
+            if fdef.attributes.is_test_or_test_only()
+                || fdef.macro_.is_some()
+                || checking.contains(&fname.value())
+                || (!matches!(fdef.visibility, Visibility::Public(_)) && fdef.entry.is_none())
+            {
+                continue;
+            }
+            self.env
+                .add_warning_filter_scope(fdef.warning_filter.clone());
+            for (_, _, t) in &fdef.signature.parameters {
+                if let Some((sname, floc)) = mut_versioned_ref(t, ident, &versioned) {
+                    let msg = format!(
+                        "'{fname}' modifies the shared object '{sname}' without checking its \
+                         version"
+                    );
+                    let field_msg = format!("'{sname}' is versioned through this field");
+                    let mut d = diag!(MISSING_VERSION_CHECK_DIAG, (t.loc, msg), (floc, field_msg));
+                    d.add_note(
+                        "After a package upgrade, the functions of its previous versions remain \
+                         callable. Check that the version of the object matches the version of \
+                         the package before modifying it",
+                    );
+                    self.env.add_diag(d);
+                }
+            }
+            self.env.pop_warning_filter_scope();
+        }
+        true
+    }
+}
+
+impl TypingVisitorContext for FunctionFacts<'_> {
+    fn add_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        match &exp.exp.value {
+            E::Borrow(_, e, field) if field.value().as_str() == VERSION_FIELD_NAME => {
+                if let Some(sname) = module_struct_name(&e.ty, self.module) {
+                    self.checks_version |= self.versioned.contains_key(&sname);
+                }
+            }
+            E::ModuleCall(call) => {
+                let fname = call.name.value();
+                if call.module.value.is(SUI_PKG_NAME, VERSIONED_MOD_NAME)
+                    && fname.as_str() == VERSIONED_VERSION_FUN
+                {
+                    self.checks_version = true;
+                } else if call.module.value.is(SUI_PKG_NAME, TRANSFER_MOD_NAME)
+                    && (fname.as_str() == SHARE_FUN || fname.as_str() == PUBLIC_SHARE_FUN)
+                {
+                    if let Some(sname) = call
+                        .type_arguments
+                        .first()
+                        .and_then(|t| module_struct_name(t, self.module))
+                    {
+                        self.shared.insert(sname);
+                    }
+                } else if call.module == self.module {
+                    self.callees.insert(fname);
+                }
+            }
+            _ => (),
+        }
+        false
+    }
+}
+
+/// Returns the location of the field through which a struct is versioned, if it has `key` and
+/// either a `version: u64` field or a `sui::versioned::Versioned` field
+fn version_field(sdef: &N::StructDefinition) -> Option<Loc> {
+    if !sdef.abilities.has_ability_(Ability_::Key) {
+        return None;
+    }
+    let N::StructFields::Defined(_, fields) = &sdef.fields else {
+        return None;
+    };
+    fields
+        .iter()
+        .find(|(_, fname, (_, ftype))| {
+            (fname.as_str() == VERSION_FIELD_NAME && is_u64(ftype))
+                || ftype
+                    .value
+                    .is(SUI_PKG_NAME, VERSIONED_MOD_NAME, VERSIONED_STRUCT_NAME)
+        })
+        .map(|(floc, _, _)| floc)
+}
+
+fn is_u64(sp!(_, t): &N::Type) -> bool {
+    matches!(
+        t,
+        N::Type_::Apply(
+            _,
+            sp!(_, N::TypeName_::Builtin(sp!(_, N::BuiltinTypeName_::U64))),
+            _
+        )
+    )
+}
+
+/// Returns the name of the struct (defined in the given module) of a type, looking through
+/// references
+fn module_struct_name(t: &N::Type, module: ModuleIdent) -> Option<Symbol> {
+    let bt = base_type(t)?;
+    let N::Type_::Apply(_, sp!(_, N::TypeName_::ModuleType(mident, sname)), _) = &bt.value else {
+        return None;
+    };
+    (*mident == module).then(|| sname.value())
+}
+
+/// Returns the name of the versioned struct and the location of its version field if the type is
+/// a mutable reference to a versioned struct
+fn mut_versioned_ref(
+    t: &N::Type,
+    module: ModuleIdent,
+    versioned: &BTreeMap<Symbol, Loc>,
+) -> Option<(Symbol, Loc)> {
+    let N::Type_::Ref(true, inner) = &t.value else {
+        return None;
+    };
+    let sname = module_struct_name(inner, module)?;
+    versioned.get(&sname).map(|floc| (sname, *floc))
+}
@@ -14,13 +14,17 @@ use crate::{
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 
+pub mod capability_store;
 pub mod coin_field;
 pub mod collection_equality;
 pub mod custom_state_change;
 pub mod freeze_wrapped;
+pub mod missing_version_check;
 pub mod public_random;
 pub mod self_transfer;
 pub mod share_owned;
+pub mod unbounded_vector_loop;
+pub mod unchecked_destroy_zero;
 
 pub const SUI_PKG_NAME: &str = "sui";
 
@@ -36,6 +40,27 @@ pub const PUBLIC_RECEIVE_FUN: &str = "public_receive";
 
 pub const COIN_MOD_NAME: &str = "coin";
 pub const COIN_STRUCT_NAME: &str = "Coin";
+pub const INTO_BALANCE_FUN: &str = "into_balance";
+pub const FROM_BALANCE_FUN: &str = "from_balance";
+
+pub const BALANCE_MOD_NAME: &str = "balance";
+pub const BALANCE_STRUCT_NAME: &str = "Balance";
+
+pub const VALUE_FUN: &str = "value";
+pub const ZERO_FUN: &str = "zero";
+pub const DESTROY_ZERO_FUN: &str = "destroy_zero";
+
+pub const VERSIONED_MOD_NAME: &str = "versioned";
+pub const VERSIONED_STRUCT_NAME: &str = "Versioned";
+pub const VERSIONED_VERSION_FUN: &str = "version";
+
+pub const DYNAMIC_FIELD_MOD_NAME: &str = "dynamic_field";
+pub const DYNAMIC_OBJECT_FIELD_MOD_NAME: &str = "dynamic_object_field";
+
+pub const STD_PKG_NAME: &str = "std";
+pub const VECTOR_MOD_NAME: &str = "vector";
+pub const VECTOR_LENGTH_FUN: &str = "length";
+pub const VECTOR_IS_EMPTY_FUN: &str = "is_empty";
 
 pub const BAG_MOD_NAME: &str = "bag";
 pub const BAG_STRUCT_NAME: &str = "Bag";
@@ -68,6 +93,10 @@ pub const COIN_FIELD_FILTER_NAME: &str = "coin_field";
 pub const FREEZE_WRAPPED_FILTER_NAME: &str = "freeze_wrapped";
 pub const COLLECTION_EQUALITY_FILTER_NAME: &str = "collection_equality";
 pub const PUBLIC_RANDOM_FILTER_NAME: &str = "public_random";
+pub const MISSING_VERSION_CHECK_FILTER_NAME: &str = "missing_version_check";
+pub const UNBOUNDED_VECTOR_LOOP_FILTER_NAME: &str = "unbounded_vector_loop";
+pub const UNCHECKED_DESTROY_ZERO_FILTER_NAME: &str = "unchecked_destroy_zero";
+pub const CAPABILITY_STORE_FILTER_NAME: &str = "capability_store";
 
 pub const RANDOM_MOD_NAME: &str = "random";
 pub const RANDOM_STRUCT_NAME: &str = "Random";
@@ -84,6 +113,10 @@ pub enum LinterDiagnosticCode {
     FreezeWrapped,
     CollectionEquality,
     PublicRandom,
+    MissingVersionCheck,
+    UnboundedVectorLoop,
+    UncheckedDestroyZero,
+    CapabilityStore,
 }
 
 pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
@@ -131,6 +164,30 @@ pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
             LinterDiagnosticCode::PublicRandom as u8,
             Some(PUBLIC_RANDOM_FILTER_NAME),
         ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::MissingVersionCheck as u8,
+            Some(MISSING_VERSION_CHECK_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::UnboundedVectorLoop as u8,
+            Some(UNBOUNDED_VECTOR_LOOP_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::UncheckedDestroyZero as u8,
+            Some(UNCHECKED_DESTROY_ZERO_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::CapabilityStore as u8,
+            Some(CAPABILITY_STORE_FILTER_NAME),
+        ),
     ];
     (Some(ALLOW_ATTR_CATEGORY.into()), filters)
 }
@@ -147,6 +204,10 @@ pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
                 freeze_wrapped::FreezeWrappedVisitor.visitor(),
                 collection_equality::CollectionEqualityVisitor.visitor(),
                 public_random::PublicRandomVisitor.visitor(),
+                missing_version_check::MissingVersionCheckVisitor.visitor(),
+                unbounded_vector_loop::UnboundedVectorLoopVisitor.visitor(),
+                unchecked_destroy_zero::UncheckedDestroyZeroVerifier.visitor(),
+                capability_store::CapabilityStoreVisitor.visitor(),
             ]
         }
     }
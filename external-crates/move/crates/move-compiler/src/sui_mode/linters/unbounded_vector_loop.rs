@@ -0,0 +1,272 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags loops in public and entry functions iterating over a vector supplied by the
+//! caller, when the length of the vector is never bounded. Since the caller controls the length of
+//! such a vector, it controls the amount of computation done by the function, which may exceed the
+//! transaction's gas budget or be used to grief other users of shared objects.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::{ModuleIdent, Value_, Visibility},
+    naming::ast as N,
+    parser::ast::{BinOp_, FunctionName},
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    LinterDiagnosticCategory, LinterDiagnosticCode, LINT_WARNING_PREFIX, STD_PKG_NAME,
+    VECTOR_IS_EMPTY_FUN, VECTOR_LENGTH_FUN, VECTOR_MOD_NAME,
+};
+
+const UNBOUNDED_VECTOR_LOOP_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::UnboundedVectorLoop as u8,
+    "unbounded loop over a caller-supplied vector",
+);
+
+pub struct UnboundedVectorLoopVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+/// Tracks how the vector parameters of a function are used in its body
+struct VectorUses {
+    /// Locals holding (a reference to) a vector parameter, mapped to that parameter
+    aliases: BTreeMap<N::Var_, N::Var_>,
+    /// Locals holding the length of a vector parameter, mapped to that parameter
+    lengths: BTreeMap<N::Var_, N::Var_>,
+    /// Number of loops enclosing the expression being visited
+    loop_depth: usize,
+    /// Vector parameters whose length is used in a loop, with the location of the first such use
+    looped: BTreeMap<N::Var_, Loc>,
+    /// Vector parameters whose length is compared against a bound outside of loops
+    bounded: BTreeSet<N::Var_>,
+}
+
+impl TypingVisitorConstructor for UnboundedVectorLoopVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, _ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // skips if true
+        mdef.attributes.is_test_or_test_only()
+    }
+
+    fn visit_function_custom(
+        &mut self,
+        module: ModuleIdent,
+        fname: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        if fdef.attributes.is_test_or_test_only()
+            || fdef.macro_.is_some()
+            || (!matches!(fdef.visibility, Visibility::Public(_)) && fdef.entry.is_none())
+        {
+            return true;
+        }
+        let params: BTreeMap<N::Var_, (Symbol, Loc)> = fdef
+            .signature
+            .parameters
+            .iter()
+            .filter(|(_, _, t)| is_vector(t))
+            .map(|(_, v, t)| (v.value, (v.value.name, t.loc)))
+            .collect();
+        if params.is_empty() {
+            return true;
+        }
+
+        let mut uses = VectorUses {
+            aliases: params.keys().map(|v| (*v, *v)).collect(),
+            lengths: BTreeMap::new(),
+            loop_depth: 0,
+            looped: BTreeMap::new(),
+            bounded: BTreeSet::new(),
+        };
+        uses.visit_function(module, fname, fdef);
+
+        for (param, use_loc) in uses.looped {
+            if uses.bounded.contains(&param) {
+                continue;
+            }
+            let (pname, ploc) = params[&param];
+            let msg = format!(
+                "The number of iterations of this loop depends on the length of '{pname}', which \
+                 is supplied by the caller of '{fname}'"
+            );
+            let param_msg = format!("'{pname}' is supplied by the caller");
+            let mut d = diag!(
+                UNBOUNDED_VECTOR_LOOP_DIAG,
+                (use_loc, msg),
+                (ploc, param_msg)
+            );
+            d.add_note(
+                "The caller controls the amount of computation done by the function. Consider \
+                 asserting that the length of the vector does not exceed a maximum",
+            );
+            self.env.add_diag(d);
+        }
+        true
+    }
+}
+
+impl VectorUses {
+    /// Returns the vector parameter an expression refers to, if any
+    fn vector_param(&self, e: &T::Exp) -> Option<N::Var_> {
+        use T::UnannotatedExp_ as E;
+        match &e.exp.value {
+            E::Move { var, .. } | E::Copy { var, .. } | E::Use(var) | E::BorrowLocal(_, var) => {
+                self.aliases.get(&var.value).copied()
+            }
+            E::TempBorrow(_, inner) | E::Annotate(inner, _) => self.vector_param(inner),
+            E::Builtin(bf, inner) if matches!(bf.value, T::BuiltinFunction_::Freeze(_)) => {
+                self.vector_param(inner)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the vector parameter whose length an expression computes or refers to, if any
+    fn length_of(&self, e: &T::Exp) -> Option<N::Var_> {
+        use T::UnannotatedExp_ as E;
+        match &e.exp.value {
+            E::Move { var, .. } | E::Copy { var, .. } | E::Use(var) => {
+                self.lengths.get(&var.value).copied()
+            }
+            E::Annotate(inner, _) => self.length_of(inner),
+            E::ModuleCall(call)
+                if call.module.value.is(STD_PKG_NAME, VECTOR_MOD_NAME)
+                    && (call.name.value().as_str() == VECTOR_LENGTH_FUN
+                        || call.name.value().as_str() == VECTOR_IS_EMPTY_FUN) =>
+            {
+                self.vector_param(&call.arguments)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TypingVisitorContext for VectorUses {
+    fn add_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_seq_item(&mut self, sp!(_, seq_item): &mut T::SequenceItem) {
+        use T::SequenceItem_ as SI;
+        match seq_item {
+            SI::Seq(e) => self.visit_exp(e),
+            SI::Declare(_) => (),
+            SI::Bind(lvalues, _, e) => {
+                self.visit_exp(e);
+                let [sp!(_, T::LValue_::Var { var, .. })] = &lvalues.value[..] else {
+                    return;
+                };
+                if let Some(param) = self.vector_param(e) {
+                    self.aliases.insert(var.value, param);
+                } else if let Some(param) = self.length_of(e) {
+                    self.lengths.insert(var.value, param);
+                }
+            }
+        }
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        match &mut exp.exp.value {
+            E::While(_, cond, body) => {
+                self.loop_depth += 1;
+                self.visit_exp(cond);
+                self.visit_exp(body);
+                self.loop_depth -= 1;
+                true
+            }
+            E::Loop { body, .. } => {
+                self.loop_depth += 1;
+                self.visit_exp(body);
+                self.loop_depth -= 1;
+                true
+            }
+            E::BinopExp(e1, op, _, e2) if self.loop_depth == 0 && is_bound_check(&op.value) => {
+                // comparing against zero only checks for emptiness, which does not bound the
+                // length of the vector
+                let bound = match (self.length_of(e1), self.length_of(e2)) {
+                    (Some(param), _) if !is_zero(e2) => Some(param),
+                    (_, Some(param)) if !is_zero(e1) => Some(param),
+                    _ => None,
+                };
+                self.bounded.extend(bound);
+                false
+            }
+            _ => {
+                if self.loop_depth > 0 {
+                    if let Some(param) = self.length_of(exp) {
+                        self.looped.entry(param).or_insert(exp.exp.loc);
+                    }
+                }
+                false
+            }
+        }
+    }
+}
+
+fn is_vector(sp!(_, t): &N::Type) -> bool {
+    use N::Type_ as T;
+    match t {
+        T::Ref(_, inner_t) => is_vector(inner_t),
+        T::Apply(_, sp!(_, N::TypeName_::Builtin(sp!(_, bt))), _) => {
+            *bt == N::BuiltinTypeName_::Vector
+        }
+        _ => false,
+    }
+}
+
+fn is_bound_check(op: &BinOp_) -> bool {
+    matches!(
+        op,
+        BinOp_::Lt | BinOp_::Le | BinOp_::Gt | BinOp_::Ge | BinOp_::Eq
+    )
+}
+
+fn is_zero(e: &T::Exp) -> bool {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Value(sp!(_, v)) => matches!(
+            v,
+            Value_::U8(0) | Value_::U16(0) | Value_::U32(0) | Value_::U64(0) | Value_::U128(0)
+        ),
+        E::Annotate(inner, _) => is_zero(inner),
+        _ => false,
+    }
+}
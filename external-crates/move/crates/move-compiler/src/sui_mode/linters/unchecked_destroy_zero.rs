@@ -0,0 +1,236 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags calls to `coin::destroy_zero` and `balance::destroy_zero` on a value created
+//! in the function (e.g., by `coin::split` or `balance::withdraw_all`) whose value was never
+//! inspected. Since `destroy_zero` aborts for a non-zero value, such calls usually indicate that
+//! the non-zero case was overlooked, and funds expected to be handled there are not.
+
+use move_ir_types::location::*;
+
+use crate::{
+    cfgir::{
+        absint::JoinResult,
+        ast::Program,
+        visitor::{
+            LocalState, SimpleAbsInt, SimpleAbsIntConstructor, SimpleDomain, SimpleExecutionContext,
+        },
+        CFGContext, MemberName,
+    },
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        Diagnostic, Diagnostics,
+    },
+    hlir::ast::{Exp, Label, ModuleCall, SingleType_, Type, Type_, UnannotatedExp_, Var},
+    shared::CompilationEnv,
+};
+use std::collections::BTreeMap;
+
+use super::{
+    LinterDiagnosticCategory, LinterDiagnosticCode, BALANCE_MOD_NAME, BALANCE_STRUCT_NAME,
+    COIN_MOD_NAME, COIN_STRUCT_NAME, DESTROY_ZERO_FUN, FROM_BALANCE_FUN, INTO_BALANCE_FUN,
+    LINT_WARNING_PREFIX, SUI_PKG_NAME, VALUE_FUN, ZERO_FUN,
+};
+
+const UNCHECKED_DESTROY_ZERO_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::UncheckedDestroyZero as u8,
+    "unchecked 'destroy_zero'",
+);
+
+const VALUE_MODS: &[(&str, &str, &str)] = &[
+    (SUI_PKG_NAME, COIN_MOD_NAME, COIN_STRUCT_NAME),
+    (SUI_PKG_NAME, BALANCE_MOD_NAME, BALANCE_STRUCT_NAME),
+];
+
+//**************************************************************************************************
+// types
+//**************************************************************************************************
+
+pub struct UncheckedDestroyZeroVerifier;
+
+pub struct UncheckedDestroyZeroVerifierAI;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Value {
+    /// a `Coin` or `Balance` created in the function, whose value has not been inspected
+    Created(Loc),
+    #[default]
+    Other,
+}
+
+pub struct ExecutionContext {
+    diags: Diagnostics,
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+    locals: BTreeMap<Var, LocalState<Value>>,
+}
+
+//**************************************************************************************************
+// impls
+//**************************************************************************************************
+
+impl SimpleAbsIntConstructor for UncheckedDestroyZeroVerifier {
+    type AI<'a> = UncheckedDestroyZeroVerifierAI;
+
+    fn new<'a>(
+        _env: &CompilationEnv,
+        program: &'a Program,
+        context: &'a CFGContext<'a>,
+        _init_state: &mut <Self::AI<'a> as SimpleAbsInt>::State,
+    ) -> Option<Self::AI<'a>> {
+        let MemberName::Function(_) = context.member else {
+            return None;
+        };
+        if context.attributes.is_test_or_test_only()
+            || program
+                .modules
+                .get(&context.module)
+                .unwrap()
+                .attributes
+                .is_test_or_test_only()
+        {
+            // No need to check test functions
+            return None;
+        }
+        Some(UncheckedDestroyZeroVerifierAI)
+    }
+}
+
+impl SimpleAbsInt for UncheckedDestroyZeroVerifierAI {
+    type State = State;
+    type ExecutionContext = ExecutionContext;
+
+    fn finish(&mut self, _final_states: BTreeMap<Label, State>, diags: Diagnostics) -> Diagnostics {
+        diags
+    }
+
+    fn start_command(&self, _: &mut State) -> ExecutionContext {
+        ExecutionContext {
+            diags: Diagnostics::new(),
+        }
+    }
+
+    fn finish_command(&self, context: ExecutionContext, _state: &mut State) -> Diagnostics {
+        let ExecutionContext { diags } = context;
+        diags
+    }
+
+    fn call_custom(
+        &self,
+        context: &mut ExecutionContext,
+        state: &mut State,
+        loc: &Loc,
+        return_ty: &Type,
+        f: &ModuleCall,
+        args: Vec<Value>,
+    ) -> Option<Vec<Value>> {
+        for (addr, module, struct_name) in VALUE_MODS {
+            if f.is(addr, module, VALUE_FUN) {
+                // the value is inspected, so it is no longer unchecked
+                if let Some(var) = f.arguments.first().and_then(borrowed_local) {
+                    if let Some(LocalState::Available(_, value)) = state.locals.get_mut(&var) {
+                        *value = Value::Other;
+                    }
+                }
+                return Some(vec![Value::Other]);
+            }
+            if f.is(addr, module, DESTROY_ZERO_FUN) {
+                if let Some(Value::Created(created_loc)) = args.first() {
+                    let msg = format!(
+                        "'{addr}::{module}::{struct_name}' is destroyed without checking that \
+                         its value is zero"
+                    );
+                    let created_msg = "Created here, without its value being checked";
+                    let mut d = diag!(
+                        UNCHECKED_DESTROY_ZERO_DIAG,
+                        (*loc, msg),
+                        (*created_loc, created_msg)
+                    );
+                    d.add_note(
+                        "'destroy_zero' aborts if the value is not zero. Check the value first, \
+                         and handle the case where it is not zero",
+                    );
+                    context.add_diag(d);
+                }
+                return Some(vec![]);
+            }
+            if f.is(addr, module, ZERO_FUN) {
+                return Some(vec![Value::Other]);
+            }
+        }
+        if f.is(SUI_PKG_NAME, COIN_MOD_NAME, INTO_BALANCE_FUN)
+            || f.is(SUI_PKG_NAME, COIN_MOD_NAME, FROM_BALANCE_FUN)
+        {
+            // converting between coins and balances preserves the value
+            return Some(vec![args.first().copied().unwrap_or_default()]);
+        }
+        Some(match &return_ty.value {
+            Type_::Unit => vec![],
+            Type_::Single(_) if is_coin_or_balance(return_ty) => vec![Value::Created(*loc)],
+            Type_::Single(_) => vec![Value::Other],
+            Type_::Multiple(types) => vec![Value::Other; types.len()],
+        })
+    }
+}
+
+/// Returns the local borrowed by an expression, if any
+fn borrowed_local(e: &Exp) -> Option<Var> {
+    match &e.exp.value {
+        UnannotatedExp_::BorrowLocal(_, var) => Some(*var),
+        UnannotatedExp_::Freeze(inner) => borrowed_local(inner),
+        _ => None,
+    }
+}
+
+fn is_coin_or_balance(sp!(_, t_): &Type) -> bool {
+    let Type_::Single(sp!(_, SingleType_::Base(sp!(_, bt_)))) = t_ else {
+        return false;
+    };
+    VALUE_MODS
+        .iter()
+        .any(|(addr, module, name)| bt_.is_apply(addr, module, name).is_some())
+}
+
+impl SimpleDomain for State {
+    type Value = Value;
+
+    fn new(context: &CFGContext, mut locals: BTreeMap<Var, LocalState<Value>>) -> Self {
+        for (_mut, v, _) in &context.signature.parameters {
+            let local_state = locals.get_mut(v).unwrap();
+            if let LocalState::Available(loc, _) = local_state {
+                *local_state = LocalState::Available(*loc, Value::Other);
+            }
+        }
+        State { locals }
+    }
+
+    fn locals_mut(&mut self) -> &mut BTreeMap<Var, LocalState<Value>> {
+        &mut self.locals
+    }
+
+    fn locals(&self) -> &BTreeMap<Var, LocalState<Value>> {
+        &self.locals
+    }
+
+    fn join_value(v1: &Value, v2: &Value) -> Value {
+        match (v1, v2) {
+            // the value is unchecked if it is unchecked on any of the paths
+            (created @ Value::Created(_), _) | (_, created @ Value::Created(_)) => *created,
+            (Value::Other, Value::Other) => Value::Other,
+        }
+    }
+
+    fn join_impl(&mut self, _: &Self, _: &mut JoinResult) {}
+}
+
+impl SimpleExecutionContext for ExecutionContext {
+    fn add_diag(&mut self, diag: Diagnostic) {
+        self.diags.add(diag)
+    }
+}
@@ -6,8 +6,11 @@ use crate::{
     compiled_unit::NamedCompiledModule, diagnostics::FilesSourceText, shared::NumericalAddress,
 };
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    runtime_value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    runtime_value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use std::{collections::BTreeMap, fmt};
 
@@ -32,10 +35,21 @@ pub struct ModuleTestPlan {
 #[derive(Debug, Clone)]
 pub struct TestCase {
     pub test_name: TestName,
-    pub arguments: Vec<MoveValue>,
+    pub arguments: Vec<TestArgument>,
     pub expected_failure: Option<ExpectedFailure>,
 }
 
+#[derive(Debug, Clone)]
+pub enum TestArgument {
+    // value assigned in the #[test(...)] attribute
+    Value(MoveValue),
+    // value generated by the test runner for each run of a #[random_test]
+    Generate {
+        name: String,
+        layout: MoveTypeLayout,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub enum ExpectedFailure {
     // expected failure, but codes are not checked
@@ -77,6 +91,16 @@ impl ModuleTestPlan {
     }
 }
 
+impl TestCase {
+    /// Whether this test is run with randomly generated arguments. A #[random_test] without
+    /// parameters has nothing to generate, and is run once like a #[test].
+    pub fn is_random_test(&self) -> bool {
+        self.arguments
+            .iter()
+            .any(|arg| matches!(arg, TestArgument::Generate { .. }))
+    }
+}
+
 impl TestPlan {
     pub fn new(
         tests: Vec<ModuleTestPlan>,
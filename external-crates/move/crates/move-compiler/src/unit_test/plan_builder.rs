@@ -9,18 +9,22 @@ use crate::{
         self as E, Address, Attribute, AttributeValue, Attributes, ModuleAccess_, ModuleIdent,
         ModuleIdent_,
     },
-    hlir::translate::display_var,
+    hlir::{ast as H, translate::display_var},
+    naming::ast as N,
     parser::ast::ConstantName,
     shared::{
         known_attributes::{self, TestingAttribute},
         unique_map::UniqueMap,
         CompilationEnv, Identifier, NumericalAddress,
     },
-    unit_test::{ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestCase},
+    unit_test::{ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestArgument, TestCase},
 };
 use move_core_types::{
-    account_address::AccountAddress as MoveAddress, language_storage::ModuleId,
-    runtime_value::MoveValue, u256::U256, vm_status::StatusCode,
+    account_address::AccountAddress as MoveAddress,
+    language_storage::ModuleId,
+    runtime_value::{MoveTypeLayout, MoveValue},
+    u256::U256,
+    vm_status::StatusCode,
 };
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
@@ -128,11 +132,12 @@ fn build_test_info<'func>(
     const IN_THIS_TEST_MSG: &str = "Error found in this test";
 
     let test_attribute_opt = get_attrs(TestingAttribute::Test);
+    let rand_test_attribute_opt = get_attrs(TestingAttribute::RandTest);
     let abort_attribute_opt = get_attrs(TestingAttribute::ExpectedFailure);
     let test_only_attribute_opt = get_attrs(TestingAttribute::TestOnly);
 
-    let test_attribute = match test_attribute_opt {
-        None => {
+    let test_attribute = match (test_attribute_opt, rand_test_attribute_opt) {
+        (None, None) => {
             // expected failures cannot be annotated on non-#[test] functions
             if let Some(abort_attribute) = abort_attribute_opt {
                 let fn_msg = "Only functions defined as a test with #[test] can also have an \
@@ -146,8 +151,21 @@ fn build_test_info<'func>(
             }
             return None;
         }
-        Some(test_attribute) => test_attribute,
+        // A function cannot be both a #[test] and a #[random_test]
+        (Some(test_attribute), Some(rand_test_attribute)) => {
+            let msg = "Function annotated as both #[test] and #[random_test]. You need to declare \
+                       it as either one or the other";
+            context.env.add_diag(diag!(
+                Attributes::InvalidUsage,
+                (rand_test_attribute.loc, msg),
+                (test_attribute.loc, PREVIOUSLY_ANNOTATED_MSG),
+                (fn_loc, IN_THIS_TEST_MSG),
+            ));
+            return None;
+        }
+        (Some(test_attribute), None) | (None, Some(test_attribute)) => test_attribute,
     };
+    let is_random_test = rand_test_attribute_opt.is_some();
 
     // A #[test] function cannot also be annotated #[test_only]
     if let Some(test_only_attribute) = test_only_attribute_opt {
@@ -161,17 +179,42 @@ fn build_test_info<'func>(
         ))
     }
 
-    let test_annotation_params = parse_test_attribute(context, test_attribute, 0);
+    let test_annotation_params = if is_random_test {
+        check_rand_test_attribute(context, test_attribute);
+        BTreeMap::new()
+    } else {
+        parse_test_attribute(context, test_attribute, 0)
+    };
     let mut arguments = Vec::new();
-    for (_mut, var, _) in &function.signature.parameters {
+    for (_mut, var, ty) in &function.signature.parameters {
         let sp!(vloc, var_) = var.0;
         let var_ = match display_var(var_) {
             crate::hlir::translate::DisplayVar::Orig(s) => s.into(),
             crate::hlir::translate::DisplayVar::MatchTmp(_) => panic!("ICE temp as parameter"),
             crate::hlir::translate::DisplayVar::Tmp => panic!("ICE temp as parameter"),
         };
+        if is_random_test {
+            match convert_type_to_generated_layout(ty) {
+                Some(layout) => arguments.push(TestArgument::Generate {
+                    name: var_.to_string(),
+                    layout,
+                }),
+                None => {
+                    let msg = "Unsupported type for a generated test parameter. Parameters of a \
+                               #[random_test] must be of a primitive type, e.g. 'u64', 'bool' or \
+                               'address', or a vector of them";
+                    context.env.add_diag(diag!(
+                        Attributes::InvalidTest,
+                        (ty.loc, msg),
+                        (vloc, "Corresponding to this parameter"),
+                        (fn_loc, IN_THIS_TEST_MSG),
+                    ))
+                }
+            }
+            continue;
+        }
         match test_annotation_params.get(&var_) {
-            Some(value) => arguments.push(value.clone()),
+            Some(value) => arguments.push(TestArgument::Value(value.clone())),
             None => {
                 let missing_param_msg = "Missing test parameter assignment in test. Expected a \
                                          parameter to be assigned in this attribute";
@@ -261,6 +304,60 @@ fn parse_test_attribute(
     }
 }
 
+fn check_rand_test_attribute(context: &mut Context, sp!(aloc, rand_test_attribute): &E::Attribute) {
+    use E::Attribute_ as EA;
+
+    match rand_test_attribute {
+        EA::Name(nm) => {
+            assert!(
+                nm.value.as_str() == TestingAttribute::RandTest.name(),
+                "ICE: We should only be parsing a raw random test attribute"
+            );
+        }
+        EA::Assigned(_, _) | EA::Parameterized(_, _) => {
+            let msg = format!(
+                "Unexpected arguments to #[{}]. The arguments of a random test are generated \
+                 when it is run",
+                TestingAttribute::RandTest.name()
+            );
+            context
+                .env
+                .add_diag(diag!(Attributes::InvalidTest, (*aloc, msg)));
+        }
+    }
+}
+
+// The layout of the values to generate for a #[random_test] parameter of type `ty`, if values of
+// that type can be generated.
+fn convert_type_to_generated_layout(ty: &H::SingleType) -> Option<MoveTypeLayout> {
+    match &ty.value {
+        H::SingleType_::Base(bt) => convert_base_type_to_generated_layout(bt),
+        H::SingleType_::Ref(_, _) => None,
+    }
+}
+
+fn convert_base_type_to_generated_layout(ty: &H::BaseType) -> Option<MoveTypeLayout> {
+    use N::BuiltinTypeName_ as B;
+    let H::BaseType_::Apply(_, sp!(_, N::TypeName_::Builtin(sp!(_, builtin))), ty_args) = &ty.value
+    else {
+        return None;
+    };
+    Some(match (builtin, &ty_args[..]) {
+        (B::Bool, []) => MoveTypeLayout::Bool,
+        (B::U8, []) => MoveTypeLayout::U8,
+        (B::U16, []) => MoveTypeLayout::U16,
+        (B::U32, []) => MoveTypeLayout::U32,
+        (B::U64, []) => MoveTypeLayout::U64,
+        (B::U128, []) => MoveTypeLayout::U128,
+        (B::U256, []) => MoveTypeLayout::U256,
+        (B::Address, []) => MoveTypeLayout::Address,
+        (B::Vector, [elem]) => {
+            MoveTypeLayout::Vector(Box::new(convert_base_type_to_generated_layout(elem)?))
+        }
+        _ => return None,
+    })
+}
+
 const BAD_ABORT_VALUE_WARNING: &str = "WARNING: passes for an abort from any module.";
 const INVALID_VALUE: &str = "Invalid value in attribute assignment";
 
@@ -0,0 +1,14 @@
+// #[random_test] parameters must be of a primitive type or a vector of them, and a function
+// cannot be both a #[test] and a #[random_test]
+address 0x1 {
+module M {
+    #[random_test]
+    fun unsupported_type(
+        _s: signer,
+    ) { }
+
+    #[test]
+    #[random_test]
+    fun both(_x: u64) { }
+}
+}
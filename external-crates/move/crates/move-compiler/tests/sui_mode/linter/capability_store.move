@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct AdminCap has key, store {
+        id: UID,
+    }
+
+    // transferred with `public_transfer`, which requires `store`
+    struct MintCap has key, store {
+        id: UID,
+    }
+
+    // wrapped in another object, which requires `store`
+    struct WrappedCap has key, store {
+        id: UID,
+    }
+
+    struct Holder has key {
+        id: UID,
+        cap: WrappedCap,
+    }
+
+    struct OwnerCap has key {
+        id: UID,
+    }
+
+    // not a capability
+    struct Config has key, store {
+        id: UID,
+    }
+
+    #[allow(lint(capability_store))]
+    struct LegacyCap has key, store {
+        id: UID,
+    }
+
+    public fun create(recipient: address, ctx: &mut TxContext) {
+        transfer::transfer(AdminCap { id: object::new(ctx) }, recipient);
+        transfer::public_transfer(MintCap { id: object::new(ctx) }, recipient);
+        let holder = Holder { id: object::new(ctx), cap: WrappedCap { id: object::new(ctx) } };
+        transfer::transfer(holder, recipient);
+        transfer::transfer(OwnerCap { id: object::new(ctx) }, recipient);
+        transfer::transfer(Config { id: object::new(ctx) }, recipient);
+        transfer::transfer(LegacyCap { id: object::new(ctx) }, recipient);
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+    public fun new(_: &mut sui::tx_context::TxContext): UID {
+        abort 0
+    }
+}
+
+module sui::tx_context {
+    struct TxContext has drop {}
+}
+
+module sui::transfer {
+    public fun transfer<T: key>(_: T, _: address) {
+        abort 0
+    }
+
+    public fun public_transfer<T: key + store>(_: T, _: address) {
+        abort 0
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test1 {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    const VERSION: u64 = 1;
+
+    struct Config has key {
+        id: UID,
+        version: u64,
+        value: u64,
+    }
+
+    fun init(ctx: &mut TxContext) {
+        transfer::share_object(Config { id: object::new(ctx), version: VERSION, value: 0 })
+    }
+
+    fun check_version(config: &Config) {
+        assert!(config.version == VERSION, 0);
+    }
+
+    public fun set_checked(config: &mut Config, value: u64) {
+        assert!(config.version == VERSION, 0);
+        config.value = value;
+    }
+
+    public fun set_checked_indirectly(config: &mut Config, value: u64) {
+        check_version(config);
+        config.value = value;
+    }
+
+    public fun set_unchecked(config: &mut Config, value: u64) {
+        config.value = value;
+    }
+
+    entry fun entry_set_unchecked(config: &mut Config, value: u64) {
+        config.value = value;
+    }
+
+    #[allow(lint(missing_version_check))]
+    public fun set_unchecked_suppressed(config: &mut Config, value: u64) {
+        config.value = value;
+    }
+
+    public fun value(config: &Config): u64 {
+        config.value
+    }
+
+    fun set_private(config: &mut Config, value: u64) {
+        config.value = value;
+    }
+
+    public fun set_through_private(config: &mut Config, value: u64) {
+        check_version(config);
+        set_private(config, value);
+    }
+}
+
+module a::test2 {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+    use sui::versioned::{Self, Versioned};
+
+    struct Registry has key {
+        id: UID,
+        inner: Versioned,
+    }
+
+    public fun create(inner: Versioned, ctx: &mut TxContext) {
+        transfer::share_object(Registry { id: object::new(ctx), inner })
+    }
+
+    public fun update_checked(registry: &mut Registry) {
+        assert!(versioned::version(&registry.inner) == 1, 0);
+    }
+
+    public fun update_unchecked(registry: &mut Registry) {
+        versioned::touch(&mut registry.inner);
+    }
+}
+
+// objects that are not shared are not subject to the check
+module a::test3 {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct Owned has key {
+        id: UID,
+        version: u64,
+        value: u64,
+    }
+
+    public fun create(recipient: address, ctx: &mut TxContext) {
+        transfer::transfer(Owned { id: object::new(ctx), version: 1, value: 0 }, recipient)
+    }
+
+    public fun set(owned: &mut Owned, value: u64) {
+        owned.value = value;
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+    public fun new(_: &mut sui::tx_context::TxContext): UID {
+        abort 0
+    }
+}
+
+module sui::tx_context {
+    struct TxContext has drop {}
+}
+
+module sui::transfer {
+    public fun transfer<T: key>(_: T, _: address) {
+        abort 0
+    }
+
+    public fun share_object<T: key>(_: T) {
+        abort 0
+    }
+}
+
+module sui::versioned {
+    use sui::object::UID;
+
+    struct Versioned has key, store {
+        id: UID,
+        version: u64,
+    }
+
+    public fun version(self: &Versioned): u64 {
+        self.version
+    }
+
+    public fun touch(_: &mut Versioned) {}
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use std::vector;
+
+    const MAX_LENGTH: u64 = 100;
+
+    public fun sum(v: &vector<u64>): u64 {
+        let sum = 0;
+        let i = 0;
+        while (i < vector::length(v)) {
+            sum = sum + *vector::borrow(v, i);
+            i = i + 1;
+        };
+        sum
+    }
+
+    public entry fun count(v: vector<u64>) {
+        let len = vector::length(&v);
+        let i = 0;
+        while (i < len) {
+            i = i + 1;
+        };
+    }
+
+    public fun drain(v: vector<u64>) {
+        let w = &mut v;
+        while (!vector::is_empty(w)) {
+            vector::pop_back(w);
+        };
+    }
+
+    public fun bounded_sum(v: &vector<u64>): u64 {
+        assert!(vector::length(v) <= MAX_LENGTH, 0);
+        let sum = 0;
+        let i = 0;
+        while (i < vector::length(v)) {
+            sum = sum + *vector::borrow(v, i);
+            i = i + 1;
+        };
+        sum
+    }
+
+    public fun bounded_count(v: vector<u64>) {
+        let len = vector::length(&v);
+        assert!(len < MAX_LENGTH, 0);
+        let i = 0;
+        while (i < len) {
+            i = i + 1;
+        };
+    }
+
+    // checking for emptiness does not bound the length
+    public fun non_empty_count(v: vector<u64>) {
+        let len = vector::length(&v);
+        assert!(len > 0, 0);
+        let i = 0;
+        while (i < len) {
+            i = i + 1;
+        };
+    }
+
+    #[allow(lint(unbounded_vector_loop))]
+    public fun sum_suppressed(v: &vector<u64>): u64 {
+        let sum = 0;
+        let i = 0;
+        while (i < vector::length(v)) {
+            sum = sum + *vector::borrow(v, i);
+            i = i + 1;
+        };
+        sum
+    }
+
+    // not callable by arbitrary callers
+    fun private_count(v: vector<u64>): u64 {
+        let i = 0;
+        while (i < vector::length(&v)) {
+            i = i + 1;
+        };
+        i
+    }
+
+    public fun call_private(): u64 {
+        private_count(vector[1, 2, 3])
+    }
+}
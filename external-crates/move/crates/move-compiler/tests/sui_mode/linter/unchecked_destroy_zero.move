@@ -0,0 +1,121 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use sui::balance::{Self, Balance};
+    use sui::coin::{Self, Coin};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct SUI {}
+
+    public fun split_and_destroy(c: &mut Coin<SUI>, amount: u64, ctx: &mut TxContext) {
+        let split = coin::split(c, amount, ctx);
+        coin::destroy_zero(split);
+    }
+
+    public fun withdraw_and_destroy(b: &mut Balance<SUI>) {
+        let withdrawn = balance::withdraw_all(b);
+        balance::destroy_zero(withdrawn);
+    }
+
+    public fun checked_on_one_path(c: &mut Coin<SUI>, check: bool, ctx: &mut TxContext) {
+        let split = coin::split(c, 10, ctx);
+        if (check) {
+            assert!(coin::value(&split) == 0, 0);
+        };
+        coin::destroy_zero(split);
+    }
+
+    public fun converted(c: &mut Coin<SUI>, amount: u64, ctx: &mut TxContext) {
+        let split = coin::split(c, amount, ctx);
+        balance::destroy_zero(coin::into_balance(split));
+    }
+
+    public fun checked(c: &mut Coin<SUI>, amount: u64, recipient: address, ctx: &mut TxContext) {
+        let split = coin::split(c, amount, ctx);
+        if (coin::value(&split) == 0) {
+            coin::destroy_zero(split);
+        } else {
+            transfer::public_transfer(split, recipient);
+        }
+    }
+
+    public fun zero_destroyed() {
+        balance::destroy_zero(balance::zero<SUI>());
+    }
+
+    public fun argument_destroyed(c: Coin<SUI>) {
+        coin::destroy_zero(c);
+    }
+
+    #[allow(lint(unchecked_destroy_zero))]
+    public fun split_and_destroy_suppressed(c: &mut Coin<SUI>, amount: u64, ctx: &mut TxContext) {
+        let split = coin::split(c, amount, ctx);
+        coin::destroy_zero(split);
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
+
+module sui::tx_context {
+    struct TxContext has drop {}
+}
+
+module sui::transfer {
+    public fun public_transfer<T: key + store>(_: T, _: address) {
+        abort 0
+    }
+}
+
+module sui::balance {
+    struct Balance<phantom T> has store {
+        value: u64,
+    }
+
+    public fun value<T>(self: &Balance<T>): u64 {
+        self.value
+    }
+
+    public fun zero<T>(): Balance<T> {
+        abort 0
+    }
+
+    public fun withdraw_all<T>(_: &mut Balance<T>): Balance<T> {
+        abort 0
+    }
+
+    public fun destroy_zero<T>(_: Balance<T>) {
+        abort 0
+    }
+}
+
+module sui::coin {
+    use sui::balance::Balance;
+    use sui::object::UID;
+    use sui::tx_context::TxContext;
+
+    struct Coin<phantom T> has key, store {
+        id: UID,
+    }
+
+    public fun value<T>(_: &Coin<T>): u64 {
+        abort 0
+    }
+
+    public fun split<T>(_: &mut Coin<T>, _: u64, _: &mut TxContext): Coin<T> {
+        abort 0
+    }
+
+    public fun into_balance<T>(_: Coin<T>): Balance<T> {
+        abort 0
+    }
+
+    public fun destroy_zero<T>(_: Coin<T>) {
+        abort 0
+    }
+}
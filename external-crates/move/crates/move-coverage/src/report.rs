@@ -0,0 +1,601 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Coverage reports in formats understood by standard coverage tooling: LCOV tracefiles, and
+//! JSON for everything else.
+
+use crate::coverage_map::{ExecCoverageMap, FunctionCoverage};
+use anyhow::{bail, Result};
+use codespan::{ByteIndex, Files};
+use move_binary_format::{
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Coverage of a set of modules, e.g. the modules of a package.
+#[derive(Debug, Serialize)]
+pub struct CoverageReport {
+    pub summary: CoverageSummary,
+    pub modules: Vec<ModuleCoverageReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleCoverageReport {
+    /// The module, as `address::name`
+    pub module_name: String,
+    pub source_path: PathBuf,
+    pub summary: CoverageSummary,
+    pub functions: Vec<FunctionCoverageReport>,
+    /// Execution counts of the source lines (1-based) that have code in this module
+    pub lines: BTreeMap<u32, u64>,
+    pub branches: Vec<BranchCoverageReport>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CoverageSummary {
+    pub instructions_found: u64,
+    pub instructions_hit: u64,
+    pub lines_found: u64,
+    pub lines_hit: u64,
+    pub functions_found: u64,
+    pub functions_hit: u64,
+    pub branches_found: u64,
+    pub branches_hit: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionCoverageReport {
+    pub name: String,
+    /// The line (1-based) the function is defined on
+    pub line: u32,
+    pub is_native: bool,
+    /// Number of times the function was called
+    pub hits: u64,
+    pub instructions_found: u64,
+    pub instructions_hit: u64,
+}
+
+/// A conditional branch instruction.
+#[derive(Debug, Serialize)]
+pub struct BranchCoverageReport {
+    /// The line (1-based) of the branch instruction
+    pub line: u32,
+    pub function: String,
+    /// The code offset of the branch instruction in its function
+    pub offset: CodeOffset,
+    /// Number of times the branch instruction was executed
+    pub hits: u64,
+    pub outcomes: Vec<BranchOutcome>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BranchOutcome {
+    /// The code offset this outcome continues at
+    pub target: CodeOffset,
+    /// Number of times this outcome was taken
+    pub taken: u64,
+}
+
+impl CoverageReport {
+    /// Build a report for `modules`, given as each module with its source map and the path to its
+    /// source file.
+    pub fn new<'a>(
+        modules: impl IntoIterator<Item = (&'a CompiledModule, &'a SourceMap, &'a Path)>,
+        coverage_map: &ExecCoverageMap,
+    ) -> Result<Self> {
+        let modules: Vec<_> = modules
+            .into_iter()
+            .map(|(module, source_map, source_path)| {
+                ModuleCoverageReport::new(module, source_map, source_path, coverage_map)
+            })
+            .collect::<Result<_>>()?;
+        let mut summary = CoverageSummary::default();
+        for module in &modules {
+            summary.add(&module.summary);
+        }
+        Ok(Self { summary, modules })
+    }
+
+    pub fn write_json<W: Write>(&self, writer: &mut W) -> Result<()> {
+        serde_json::to_writer_pretty(&mut *writer, self)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Write the report as an LCOV tracefile, with one record per source file. Functions are named
+    /// `module::function`, as a source file can define more than one module.
+    pub fn write_lcov<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut modules_by_source: BTreeMap<&Path, Vec<&ModuleCoverageReport>> = BTreeMap::new();
+        for module in &self.modules {
+            modules_by_source
+                .entry(module.source_path.as_path())
+                .or_default()
+                .push(module);
+        }
+
+        for (source_path, modules) in modules_by_source {
+            writeln!(writer, "TN:")?;
+            writeln!(writer, "SF:{}", source_path.display())?;
+
+            let mut summary = CoverageSummary::default();
+            let mut lines: BTreeMap<u32, u64> = BTreeMap::new();
+            for module in &modules {
+                summary.add(&module.summary);
+                for (line, hits) in &module.lines {
+                    let line_hits = lines.entry(*line).or_default();
+                    *line_hits = (*line_hits).max(*hits);
+                }
+            }
+
+            let functions = modules.iter().flat_map(|module| {
+                let name = module.module_name.rsplit("::").next().unwrap();
+                module
+                    .functions
+                    .iter()
+                    .filter(|function| !function.is_native)
+                    .map(move |function| (name, function))
+            });
+            for (module_name, function) in functions.clone() {
+                writeln!(
+                    writer,
+                    "FN:{},{module_name}::{}",
+                    function.line, function.name
+                )?;
+            }
+            for (module_name, function) in functions {
+                writeln!(
+                    writer,
+                    "FNDA:{},{module_name}::{}",
+                    function.hits, function.name
+                )?;
+            }
+            writeln!(writer, "FNF:{}", summary.functions_found)?;
+            writeln!(writer, "FNH:{}", summary.functions_hit)?;
+
+            for (block, branch) in modules
+                .iter()
+                .flat_map(|module| &module.branches)
+                .enumerate()
+            {
+                for (index, outcome) in branch.outcomes.iter().enumerate() {
+                    // LCOV marks the outcomes of branches that were never reached with a '-'
+                    let taken = if branch.hits == 0 {
+                        "-".to_string()
+                    } else {
+                        outcome.taken.to_string()
+                    };
+                    writeln!(writer, "BRDA:{},{block},{index},{taken}", branch.line)?;
+                }
+            }
+            writeln!(writer, "BRF:{}", summary.branches_found)?;
+            writeln!(writer, "BRH:{}", summary.branches_hit)?;
+
+            for (line, hits) in &lines {
+                writeln!(writer, "DA:{line},{hits}")?;
+            }
+            writeln!(writer, "LF:{}", lines.len())?;
+            writeln!(
+                writer,
+                "LH:{}",
+                lines.values().filter(|hits| **hits > 0).count()
+            )?;
+            writeln!(writer, "end_of_record")?;
+        }
+        Ok(())
+    }
+}
+
+impl ModuleCoverageReport {
+    pub fn new(
+        module: &CompiledModule,
+        source_map: &SourceMap,
+        source_path: &Path,
+        coverage_map: &ExecCoverageMap,
+    ) -> Result<Self> {
+        let file_contents = fs::read_to_string(source_path)?;
+        if !source_map.check(&file_contents) {
+            bail!(
+                "File contents of {} out of sync with source map",
+                source_path.display()
+            );
+        }
+        let file_hash = source_map.definition_location.file_hash();
+        let mut files = Files::new();
+        let file_id = files.add(source_path.as_os_str().to_os_string(), file_contents);
+        let line_of = |offset: u32| files.line_index(file_id, ByteIndex(offset)).0 + 1;
+
+        let module_id = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+        let no_coverage = FunctionCoverage::new();
+
+        let mut functions = vec![];
+        let mut lines: BTreeMap<u32, u64> = BTreeMap::new();
+        let mut branches = vec![];
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let fdef_idx = FunctionDefinitionIndex(idx as u16);
+            let fn_handle = module.function_handle_at(function_def.function);
+            let fn_name = module.identifier_at(fn_handle.name);
+            let function_map = source_map.get_function_source_map(fdef_idx)?;
+            let line = line_of(function_map.definition_location.start());
+
+            let Some(code_unit) = &function_def.code else {
+                functions.push(FunctionCoverageReport {
+                    name: fn_name.to_string(),
+                    line,
+                    is_native: true,
+                    hits: 0,
+                    instructions_found: 0,
+                    instructions_hit: 0,
+                });
+                continue;
+            };
+            let code = &code_unit.code;
+            let coverage = module_map
+                .and_then(|map| map.get_function_coverage(fn_name))
+                .unwrap_or(&no_coverage);
+            let function_hits = FunctionHits::new(code, coverage);
+            let hits = |offset: CodeOffset| function_hits.at(offset);
+
+            for offset in 0..code.len() as CodeOffset {
+                // Instructions can originate from other files, e.g. through macros, which are
+                // accounted for in the modules defined there
+                let Some(loc) = function_map.get_code_location(offset) else {
+                    continue;
+                };
+                if loc.file_hash() != file_hash {
+                    continue;
+                }
+                let line_hits = lines.entry(line_of(loc.start())).or_default();
+                *line_hits = (*line_hits).max(hits(offset));
+            }
+
+            for (offset, instr) in code.iter().enumerate() {
+                let offset = offset as CodeOffset;
+                if !instr.is_conditional_branch() {
+                    continue;
+                }
+                let Some([(first, first_taken), (second, second_taken)]) =
+                    function_hits.branch_outcomes(offset)
+                else {
+                    continue;
+                };
+                let line = match function_map.get_code_location(offset) {
+                    Some(loc) if loc.file_hash() == file_hash => line_of(loc.start()),
+                    _ => continue,
+                };
+                branches.push(BranchCoverageReport {
+                    line,
+                    function: fn_name.to_string(),
+                    offset,
+                    hits: hits(offset),
+                    outcomes: vec![
+                        BranchOutcome {
+                            target: first,
+                            taken: first_taken,
+                        },
+                        BranchOutcome {
+                            target: second,
+                            taken: second_taken,
+                        },
+                    ],
+                });
+            }
+
+            functions.push(FunctionCoverageReport {
+                name: fn_name.to_string(),
+                line,
+                is_native: false,
+                hits: function_hits.entries(),
+                instructions_found: code.len() as u64,
+                instructions_hit: coverage.len() as u64,
+            });
+        }
+
+        let non_native = functions.iter().filter(|function| !function.is_native);
+        let summary = CoverageSummary {
+            instructions_found: non_native.clone().map(|f| f.instructions_found).sum(),
+            instructions_hit: non_native.clone().map(|f| f.instructions_hit).sum(),
+            lines_found: lines.len() as u64,
+            lines_hit: lines.values().filter(|hits| **hits > 0).count() as u64,
+            functions_found: non_native.clone().count() as u64,
+            functions_hit: non_native.filter(|f| f.hits > 0).count() as u64,
+            branches_found: branches.iter().map(|b| b.outcomes.len() as u64).sum(),
+            branches_hit: branches
+                .iter()
+                .flat_map(|b| &b.outcomes)
+                .filter(|o| o.taken > 0)
+                .count() as u64,
+        };
+
+        Ok(Self {
+            module_name: format!("{}::{}", module_id.address(), module_id.name()),
+            source_path: source_path.to_path_buf(),
+            summary,
+            functions,
+            lines,
+            branches,
+        })
+    }
+}
+
+impl CoverageSummary {
+    fn add(&mut self, other: &CoverageSummary) {
+        self.instructions_found += other.instructions_found;
+        self.instructions_hit += other.instructions_hit;
+        self.lines_found += other.lines_found;
+        self.lines_hit += other.lines_hit;
+        self.functions_found += other.functions_found;
+        self.functions_hit += other.functions_hit;
+        self.branches_found += other.branches_found;
+        self.branches_hit += other.branches_hit;
+    }
+}
+
+/// Execution counts of the instructions of a function.
+struct FunctionHits<'a> {
+    code: &'a [Bytecode],
+    coverage: &'a FunctionCoverage,
+    /// The number of ways each instruction can be reached, counting entry into the function as a
+    /// way to reach the first one.
+    predecessors: Vec<usize>,
+}
+
+impl<'a> FunctionHits<'a> {
+    fn new(code: &'a [Bytecode], coverage: &'a FunctionCoverage) -> Self {
+        let mut predecessors = vec![0; code.len()];
+        if let Some(entry) = predecessors.first_mut() {
+            *entry = 1;
+        }
+        for offset in 0..code.len() as CodeOffset {
+            for successor in Bytecode::get_successors(offset, code) {
+                predecessors[successor as usize] += 1;
+            }
+        }
+        Self {
+            code,
+            coverage,
+            predecessors,
+        }
+    }
+
+    /// Number of times the instruction at `offset` was executed.
+    fn at(&self, offset: CodeOffset) -> u64 {
+        self.coverage.get(&(offset as u64)).copied().unwrap_or(0)
+    }
+
+    /// The targets of the conditional branch at `offset`, with the number of times each was taken.
+    /// Only known exactly when the instruction an outcome continues at can only be reached from the
+    /// branch, or when the other outcome's can, and `None` otherwise.
+    fn branch_outcomes(&self, offset: CodeOffset) -> Option<[(CodeOffset, u64); 2]> {
+        if !self.code[offset as usize].is_conditional_branch() {
+            return None;
+        }
+        let successors = Bytecode::get_successors(offset, self.code);
+        let &[first, second] = successors.as_slice() else {
+            return None;
+        };
+        let exact =
+            |target: CodeOffset| (self.predecessors[target as usize] == 1).then(|| self.at(target));
+        let (first_taken, second_taken) = match (exact(first), exact(second)) {
+            (Some(first_taken), Some(second_taken)) => (first_taken, second_taken),
+            (Some(first_taken), None) => (first_taken, self.at(offset).saturating_sub(first_taken)),
+            (None, Some(second_taken)) => {
+                (self.at(offset).saturating_sub(second_taken), second_taken)
+            }
+            (None, None) => return None,
+        };
+        Some([(first, first_taken), (second, second_taken)])
+    }
+
+    /// Number of times the function was called. The first instruction is also executed when a loop
+    /// jumps back to it, so those jumps are subtracted from its execution count. A jump back whose
+    /// count is not known exactly is assumed to always be taken, but a function that executed at
+    /// all was entered at least once.
+    fn entries(&self) -> u64 {
+        let hits = self.at(0);
+        if self.predecessors.first().map_or(true, |count| *count == 1) {
+            return hits;
+        }
+        let mut back_edges = 0;
+        for offset in 0..self.code.len() as CodeOffset {
+            if !Bytecode::get_successors(offset, self.code).contains(&0) {
+                continue;
+            }
+            // Successors are in ascending order, so a jump back to the start is the first outcome
+            back_edges += match self.branch_outcomes(offset) {
+                Some([(_, taken), _]) => taken,
+                None => self.at(offset),
+            };
+        }
+        hits.saturating_sub(back_edges).max(hits.min(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(hits: &[u64]) -> FunctionCoverage {
+        hits.iter()
+            .enumerate()
+            .filter(|(_, hits)| **hits > 0)
+            .map(|(offset, hits)| (offset as u64, *hits))
+            .collect()
+    }
+
+    #[test]
+    fn entries_without_loop_to_start() {
+        // if (true) { 1 } else { 2 }, called 3 times
+        let code = [
+            Bytecode::LdTrue,
+            Bytecode::BrFalse(4),
+            Bytecode::LdU8(1),
+            Bytecode::Ret,
+            Bytecode::LdU8(2),
+            Bytecode::Ret,
+        ];
+        let coverage = coverage(&[3, 3, 3, 3, 0, 0]);
+        let hits = FunctionHits::new(&code, &coverage);
+        assert_eq!(hits.entries(), 3);
+        assert_eq!(hits.branch_outcomes(1), Some([(2, 3), (4, 0)]));
+        assert_eq!(hits.branch_outcomes(0), None);
+    }
+
+    #[test]
+    fn entries_with_unconditional_loop_to_start() {
+        // while (cond) {}, called twice with 3 iterations each
+        let code = [
+            Bytecode::LdTrue,
+            Bytecode::BrFalse(3),
+            Bytecode::Branch(0),
+            Bytecode::Ret,
+        ];
+        let coverage = coverage(&[8, 8, 6, 2]);
+        let hits = FunctionHits::new(&code, &coverage);
+        assert_eq!(hits.at(0), 8);
+        assert_eq!(hits.entries(), 2);
+        assert_eq!(hits.branch_outcomes(1), Some([(2, 6), (3, 2)]));
+    }
+
+    #[test]
+    fn entries_with_conditional_loop_to_start() {
+        // do {} while (cond), called twice with 5 iterations in total
+        let code = [Bytecode::LdTrue, Bytecode::BrTrue(0), Bytecode::Ret];
+        let coverage = coverage(&[5, 5, 2]);
+        let hits = FunctionHits::new(&code, &coverage);
+        assert_eq!(hits.branch_outcomes(1), Some([(0, 3), (2, 2)]));
+        assert_eq!(hits.entries(), 2);
+    }
+
+    #[test]
+    fn entries_with_inexact_loop_to_start() {
+        // Both outcomes of the branch at 2 can also be reached some other way, so how often it
+        // jumped back to the start is unknown, but the function was entered at least once
+        let code = [
+            Bytecode::LdTrue,
+            Bytecode::BrTrue(3),
+            Bytecode::BrTrue(0),
+            Bytecode::Ret,
+        ];
+        let coverage = coverage(&[4, 4, 3, 1]);
+        let hits = FunctionHits::new(&code, &coverage);
+        assert_eq!(hits.branch_outcomes(2), None);
+        assert_eq!(hits.entries(), 1);
+
+        let hits = FunctionHits::new(&code, &FunctionCoverage::new());
+        assert_eq!(hits.entries(), 0);
+    }
+
+    #[test]
+    fn lcov_merges_modules_of_a_source_file() {
+        let function = |name: &str, line, hits| FunctionCoverageReport {
+            name: name.to_string(),
+            line,
+            is_native: false,
+            hits,
+            instructions_found: 2,
+            instructions_hit: if hits > 0 { 2 } else { 0 },
+        };
+        let module = |name: &str,
+                      functions: Vec<FunctionCoverageReport>,
+                      lines: &[(u32, u64)],
+                      branches: Vec<BranchCoverageReport>| {
+            let mut module = ModuleCoverageReport {
+                module_name: format!("0x2::{name}"),
+                source_path: PathBuf::from("sources/m.move"),
+                summary: CoverageSummary::default(),
+                functions,
+                lines: lines.iter().copied().collect(),
+                branches,
+            };
+            let non_native = module.functions.iter().filter(|f| !f.is_native);
+            module.summary = CoverageSummary {
+                functions_found: non_native.clone().count() as u64,
+                functions_hit: non_native.filter(|f| f.hits > 0).count() as u64,
+                branches_found: module.branches.len() as u64 * 2,
+                branches_hit: module
+                    .branches
+                    .iter()
+                    .flat_map(|b| &b.outcomes)
+                    .filter(|o| o.taken > 0)
+                    .count() as u64,
+                ..CoverageSummary::default()
+            };
+            module
+        };
+        let branch = |line, hits, taken: [u64; 2]| BranchCoverageReport {
+            line,
+            function: "f".to_string(),
+            offset: 1,
+            hits,
+            outcomes: vec![
+                BranchOutcome {
+                    target: 2,
+                    taken: taken[0],
+                },
+                BranchOutcome {
+                    target: 4,
+                    taken: taken[1],
+                },
+            ],
+        };
+        let mut native = function("n", 1, 0);
+        native.is_native = true;
+
+        let report = CoverageReport {
+            summary: CoverageSummary::default(),
+            modules: vec![
+                module(
+                    "a",
+                    vec![function("f", 2, 3), native],
+                    &[(3, 3), (4, 0)],
+                    vec![branch(3, 3, [3, 0])],
+                ),
+                module(
+                    "b",
+                    vec![function("g", 7, 0)],
+                    &[(8, 0)],
+                    vec![branch(8, 0, [0, 0])],
+                ),
+            ],
+        };
+
+        let mut lcov = vec![];
+        report.write_lcov(&mut lcov).unwrap();
+        let expected = "\
+TN:
+SF:sources/m.move
+FN:2,a::f
+FN:7,b::g
+FNDA:3,a::f
+FNDA:0,b::g
+FNF:2
+FNH:1
+BRDA:3,0,0,3
+BRDA:3,0,1,0
+BRDA:8,1,0,-
+BRDA:8,1,1,-
+BRF:4
+BRH:1
+DA:3,3
+DA:4,0
+DA:8,0
+LF:3
+LH:1
+end_of_record
+";
+        assert_eq!(String::from_utf8(lcov).unwrap(), expected);
+    }
+}
@@ -1,11 +1,11 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::source_package::parsed_manifest::{CustomDepInfo, SourceManifest};
+use crate::source_package::parsed_manifest::{CustomDepInfo, OnChainInfo, SourceManifest};
 use anyhow::bail;
 use move_symbol_pool::Symbol;
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::{path::Path, sync::Mutex};
 
 pub type PackageIdentifier = Symbol;
 
@@ -30,6 +30,20 @@ pub trait PackageHooks {
         info: &CustomDepInfo,
     ) -> anyhow::Result<()>;
 
+    /// A resolver for on-chain dependencies in the manifest (`{ id = <id>, network = <network> }`).
+    /// This is called to fetch the published package and write a source package for it at `path`.
+    /// If `path` already holds a package fetched by an earlier build, the hook is responsible for
+    /// checking that it is still current, and replacing it if it is not.
+    fn resolve_on_chain_dependency(
+        &self,
+        dep_name: Symbol,
+        info: &OnChainInfo,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let _ = (info, path);
+        bail!("on-chain dependency '{dep_name}' is not supported by this package system")
+    }
+
     fn custom_resolve_pkg_id(&self, manifest: &SourceManifest)
         -> anyhow::Result<PackageIdentifier>;
 
@@ -55,6 +69,19 @@ pub(crate) fn resolve_custom_dependency(
     }
 }
 
+/// Calls any registered hook to resolve an on-chain dependency. Bails if none is registered.
+pub(crate) fn resolve_on_chain_dependency(
+    dep_name: Symbol,
+    info: &OnChainInfo,
+    path: &Path,
+) -> anyhow::Result<()> {
+    if let Some(hooks) = &*HOOKS.lock().unwrap() {
+        hooks.resolve_on_chain_dependency(dep_name, info, path)
+    } else {
+        bail!("use of unsupported on-chain dependency in package manifest")
+    }
+}
+
 pub(crate) fn custom_dependency_key() -> Option<String> {
     if let Some(hooks) = &*HOOKS.lock().unwrap() {
         hooks.custom_dependency_key()
@@ -56,6 +56,31 @@ impl DependencyCache {
                 package_hooks::resolve_custom_dependency(dep_name, node_info)
             }
 
+            DependencyKind::OnChain(on_chain_info) => {
+                let package_path = repository_path(kind);
+                // check if a give dependency type has already been fetched
+                if !self.fetched_deps.insert(package_path.clone()) {
+                    return Ok(());
+                }
+                // Published packages are immutable, but the network a package was fetched from
+                // may have been reset since, so unless asked not to, leave it to the hook to
+                // decide whether a package that was fetched before is still current.
+                if package_path.exists() {
+                    if self.skip_fetch_latest_git_deps {
+                        return Ok(());
+                    }
+                } else {
+                    writeln!(
+                        progress_output,
+                        "{} {} ({})",
+                        "FETCHING ON-CHAIN DEPENDENCY".bold().green(),
+                        on_chain_info.id,
+                        on_chain_info.network,
+                    )?;
+                }
+                package_hooks::resolve_on_chain_dependency(dep_name, on_chain_info, &package_path)
+            }
+
             DependencyKind::Git(GitInfo {
                 git_url,
                 git_rev,
@@ -10,7 +10,7 @@ use std::{
 };
 
 use crate::{
-    source_package::parsed_manifest::{CustomDepInfo, DependencyKind, GitInfo},
+    source_package::parsed_manifest::{CustomDepInfo, DependencyKind, GitInfo, OnChainInfo},
     BuildConfig,
 };
 
@@ -110,6 +110,23 @@ fn repository_path(kind: &DependencyKind) -> PathBuf {
         ]
         .iter()
         .collect(),
+
+        // Fetched on-chain packages are of the form <sanitized_network>_<id>_<package>
+        DependencyKind::OnChain(OnChainInfo {
+            id,
+            network,
+            package_name,
+        }) => [
+            &*MOVE_HOME,
+            &format!(
+                "{}_{}_{}",
+                url_to_file_name(network.as_str()),
+                id.as_str(),
+                package_name.as_str(),
+            ),
+        ]
+        .iter()
+        .collect(),
     }
 }
 
@@ -14,7 +14,7 @@ use move_package::{
     package_hooks::PackageHooks,
     package_hooks::PackageIdentifier,
     resolution::resolution_graph::Package,
-    source_package::parsed_manifest::{CustomDepInfo, PackageDigest, SourceManifest},
+    source_package::parsed_manifest::{CustomDepInfo, OnChainInfo, PackageDigest, SourceManifest},
     BuildConfig, ModelConfig,
 };
 use move_symbol_pool::Symbol;
@@ -221,6 +221,20 @@ impl PackageHooks for TestHooks {
         )
     }
 
+    fn resolve_on_chain_dependency(
+        &self,
+        dep_name: Symbol,
+        info: &OnChainInfo,
+        _path: &Path,
+    ) -> anyhow::Result<()> {
+        bail!(
+            "TestHooks resolve on-chain dep {:?} = {:?} {:?}",
+            dep_name,
+            info.id,
+            info.network,
+        )
+    }
+
     fn custom_resolve_pkg_id(
         &self,
         manifest: &SourceManifest,
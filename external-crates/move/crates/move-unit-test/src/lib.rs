@@ -4,6 +4,7 @@
 
 pub mod cargo_runner;
 pub mod extensions;
+mod random_inputs;
 pub mod test_reporter;
 pub mod test_runner;
 
@@ -30,6 +31,9 @@ use std::{
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
 
+/// The default number of times a `#[random_test]` is run, each with newly generated arguments.
+const DEFAULT_RAND_NUM_ITERS: u64 = 100;
+
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
 pub struct UnitTestingConfig {
@@ -95,6 +99,19 @@ pub struct UnitTestingConfig {
     /// Verbose mode
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
+
+    /// Number of times to run each `#[random_test]`, each time with newly generated arguments
+    #[clap(
+        name = "rand-num-iters",
+        long = "rand-num-iters",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub rand_num_iters: Option<u64>,
+
+    /// Seed for the arguments generated for `#[random_test]`s. A failing random test reports the
+    /// seed to pass here to reproduce the failure
+    #[clap(name = "seed", long = "seed")]
+    pub seed: Option<u64>,
 }
 
 fn format_module_id(module_id: &ModuleId) -> String {
@@ -119,6 +136,8 @@ impl UnitTestingConfig {
             verbose: false,
             list: false,
             named_address_values: vec![],
+            rand_num_iters: None,
+            seed: None,
         }
     }
 
@@ -186,12 +205,17 @@ impl UnitTestingConfig {
 
         if self.list {
             for (module_id, test_plan) in &test_plan.module_tests {
-                for test_name in test_plan.tests.keys() {
+                for (test_name, test_info) in &test_plan.tests {
                     writeln!(
                         shared_writer.lock().unwrap(),
-                        "{}::{}: test",
+                        "{}::{}: {}",
                         format_module_id(module_id),
-                        test_name
+                        test_name,
+                        if test_info.is_random_test() {
+                            "random test"
+                        } else {
+                            "test"
+                        },
                     )?;
                 }
             }
@@ -203,6 +227,8 @@ impl UnitTestingConfig {
             self.gas_limit.unwrap_or(DEFAULT_EXECUTION_BOUND),
             self.num_threads,
             self.report_stacktrace_on_abort,
+            self.rand_num_iters.unwrap_or(DEFAULT_RAND_NUM_ITERS),
+            self.seed,
             test_plan,
             native_function_table,
             cost_table,
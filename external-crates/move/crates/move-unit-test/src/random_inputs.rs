@@ -0,0 +1,141 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation and shrinking of the arguments passed to `#[random_test]` functions.
+
+use move_core_types::{
+    account_address::AccountAddress,
+    runtime_value::{MoveTypeLayout, MoveValue},
+    u256::U256,
+};
+use rand::{
+    distributions::{Distribution, Standard},
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng,
+};
+
+/// The maximum length of a generated vector.
+const MAX_VECTOR_LENGTH: usize = 16;
+
+/// Generate a random value of type `layout`. Only the layouts the compiler accepts for the
+/// parameters of a `#[random_test]` are supported: primitives and vectors of them.
+pub(crate) fn generate(rng: &mut StdRng, layout: &MoveTypeLayout) -> MoveValue {
+    match layout {
+        MoveTypeLayout::Bool => MoveValue::Bool(rng.gen()),
+        MoveTypeLayout::U8 => MoveValue::U8(generate_int(rng, [0, 1, u8::MAX])),
+        MoveTypeLayout::U16 => MoveValue::U16(generate_int(rng, [0, 1, u16::MAX])),
+        MoveTypeLayout::U32 => MoveValue::U32(generate_int(rng, [0, 1, u32::MAX])),
+        MoveTypeLayout::U64 => MoveValue::U64(generate_int(rng, [0, 1, u64::MAX])),
+        MoveTypeLayout::U128 => MoveValue::U128(generate_int(rng, [0, 1, u128::MAX])),
+        MoveTypeLayout::U256 => {
+            let edges = [U256::zero(), U256::one(), U256::max_value()];
+            MoveValue::U256(if rng.gen_ratio(1, 4) {
+                *edges.choose(rng).unwrap()
+            } else {
+                U256::from_le_bytes(&rng.gen())
+            })
+        }
+        MoveTypeLayout::Address => MoveValue::Address(AccountAddress::new(rng.gen())),
+        MoveTypeLayout::Vector(elem) => {
+            let len = rng.gen_range(0..=MAX_VECTOR_LENGTH);
+            MoveValue::Vector((0..len).map(|_| generate(rng, elem)).collect())
+        }
+        MoveTypeLayout::Signer | MoveTypeLayout::Struct(_) => {
+            unreachable!("ICE: values of type {layout} cannot be generated for a random test")
+        }
+    }
+}
+
+/// Integers are biased towards the edges of their range, where bugs tend to hide.
+fn generate_int<T: Copy>(rng: &mut StdRng, edges: [T; 3]) -> T
+where
+    Standard: Distribution<T>,
+{
+    if rng.gen_ratio(1, 4) {
+        *edges.choose(rng).unwrap()
+    } else {
+        rng.gen()
+    }
+}
+
+/// Values that are strictly simpler than `value`, simplest first. Repeatedly replacing a value by
+/// one of its candidates always terminates.
+pub(crate) fn shrink(value: &MoveValue) -> Vec<MoveValue> {
+    match value {
+        MoveValue::Bool(b) => {
+            if *b {
+                vec![MoveValue::Bool(false)]
+            } else {
+                vec![]
+            }
+        }
+        MoveValue::U8(n) => shrink_int(*n as u128, |n| MoveValue::U8(n as u8)),
+        MoveValue::U16(n) => shrink_int(*n as u128, |n| MoveValue::U16(n as u16)),
+        MoveValue::U32(n) => shrink_int(*n as u128, |n| MoveValue::U32(n as u32)),
+        MoveValue::U64(n) => shrink_int(*n as u128, |n| MoveValue::U64(n as u64)),
+        MoveValue::U128(n) => shrink_int(*n, MoveValue::U128),
+        MoveValue::U256(n) => {
+            let mut candidates = vec![];
+            if *n != U256::zero() {
+                candidates.push(U256::zero());
+                let half = n.checked_div(U256::from(2u128)).unwrap();
+                let pred = n.checked_sub(U256::one()).unwrap();
+                for candidate in [half, pred] {
+                    if !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+            candidates.into_iter().map(MoveValue::U256).collect()
+        }
+        MoveValue::Address(a) => {
+            if *a != AccountAddress::ZERO {
+                vec![MoveValue::Address(AccountAddress::ZERO)]
+            } else {
+                vec![]
+            }
+        }
+        MoveValue::Vector(elems) => {
+            let mut candidates = vec![];
+            if elems.is_empty() {
+                return candidates;
+            }
+            candidates.push(MoveValue::Vector(vec![]));
+            if elems.len() > 2 {
+                let mid = elems.len() / 2;
+                candidates.push(MoveValue::Vector(elems[..mid].to_vec()));
+                candidates.push(MoveValue::Vector(elems[mid..].to_vec()));
+            }
+            if elems.len() > 1 {
+                for i in 0..elems.len() {
+                    let mut smaller = elems.clone();
+                    smaller.remove(i);
+                    candidates.push(MoveValue::Vector(smaller));
+                }
+            }
+            for (i, elem) in elems.iter().enumerate() {
+                for simpler in shrink(elem) {
+                    let mut smaller = elems.clone();
+                    smaller[i] = simpler;
+                    candidates.push(MoveValue::Vector(smaller));
+                }
+            }
+            candidates
+        }
+        MoveValue::Signer(_) | MoveValue::Struct(_) => vec![],
+    }
+}
+
+fn shrink_int(n: u128, to_value: impl Fn(u128) -> MoveValue) -> Vec<MoveValue> {
+    let mut candidates = vec![];
+    if n != 0 {
+        candidates.push(0);
+        for candidate in [n / 2, n - 1] {
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates.into_iter().map(to_value).collect()
+}
@@ -44,6 +44,16 @@ pub struct TestFailure {
     pub test_run_info: TestRunInfo,
     pub vm_error: Option<VMError>,
     pub failure_reason: FailureReason,
+    pub random_test_inputs: Option<RandomTestInputs>,
+}
+
+/// The generated arguments a `#[random_test]` failed with, along with the seed needed to replay
+/// the run that found them.
+#[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
+pub struct RandomTestInputs {
+    pub seed: u64,
+    /// The arguments, as `name = value`, in parameter order.
+    pub arguments: Vec<String>,
 }
 
 #[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
@@ -120,10 +130,36 @@ impl TestFailure {
             test_run_info,
             vm_error,
             failure_reason,
+            random_test_inputs: None,
         }
     }
 
+    pub fn with_random_test_inputs(mut self, random_test_inputs: RandomTestInputs) -> Self {
+        self.random_test_inputs = Some(random_test_inputs);
+        self
+    }
+
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
+        let message = self.render_failure_reason(test_plan);
+        match &self.random_test_inputs {
+            None => message,
+            Some(RandomTestInputs { seed, arguments }) => {
+                let mut message = format!(
+                    "{}\n\nThis test failed with the inputs:\n",
+                    message.trim_end()
+                );
+                for argument in arguments {
+                    message.push_str(&format!("    {argument}\n"));
+                }
+                message.push_str(&format!(
+                    "Rerun with `--seed {seed}` to reproduce this failure.\n"
+                ));
+                message
+            }
+        }
+    }
+
+    fn render_failure_reason(&self, test_plan: &TestPlan) -> String {
         match &self.failure_reason {
             FailureReason::NoError(message) => message.to_string(),
             FailureReason::Timeout(message) => message.to_string(),
@@ -3,35 +3,47 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    extensions, format_module_id,
+    extensions, format_module_id, random_inputs,
     test_reporter::{
-        FailureReason, MoveError, TestFailure, TestResults, TestRunInfo, TestStatistics,
+        FailureReason, MoveError, RandomTestInputs, TestFailure, TestResults, TestRunInfo,
+        TestStatistics,
     },
 };
 use anyhow::Result;
 use colored::*;
 
 use move_binary_format::{
-    errors::{Location, VMResult},
+    errors::{Location, VMError, VMResult},
     file_format::CompiledModule,
 };
 use move_bytecode_utils::Modules;
 use move_command_line_common::error_bitset::ErrorBitset;
 use move_compiler::{
     compiled_unit::NamedCompiledModule,
-    unit_test::{ExpectedFailure, ModuleTestPlan, MoveErrorType, TestCase, TestPlan},
+    unit_test::{ExpectedFailure, ModuleTestPlan, MoveErrorType, TestArgument, TestCase, TestPlan},
 };
 use move_core_types::{
-    account_address::AccountAddress, effects::ChangeSet, identifier::IdentStr,
-    language_storage::ModuleId, runtime_value::serialize_values, vm_status::StatusCode,
+    account_address::AccountAddress,
+    effects::ChangeSet,
+    identifier::IdentStr,
+    language_storage::ModuleId,
+    runtime_value::{serialize_values, MoveValue},
+    vm_status::StatusCode,
 };
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use move_vm_test_utils::{
     gas_schedule::{unit_cost_schedule, CostTable, Gas, GasStatus},
     InMemoryStorage,
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    marker::Send,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 
@@ -42,8 +54,16 @@ pub struct SharedTestingConfig {
     cost_table: CostTable,
     native_function_table: NativeFunctionTable,
     starting_storage_state: InMemoryStorage,
+    rand_num_iters: u64,
+    seed: Option<u64>,
 }
 
+/// The maximum number of times a failing random test is re-run while shrinking its inputs.
+const MAX_SHRINK_RUNS: usize = 1_000;
+
+/// The outcome of running a test once: either it behaved as expected, or why it did not.
+type TestResult = std::result::Result<(), (FailureReason, Option<VMError>)>;
+
 pub struct TestRunner {
     num_threads: usize,
     testing_config: SharedTestingConfig,
@@ -102,6 +122,8 @@ impl TestRunner {
         execution_bound: u64,
         num_threads: usize,
         report_stacktrace_on_abort: bool,
+        rand_num_iters: u64,
+        seed: Option<u64>,
         tests: TestPlan,
         // TODO: maybe we should require the clients to always pass in a list of native functions so
         // we don't have to make assumptions about their gas parameters.
@@ -128,6 +150,8 @@ impl TestRunner {
                 //
                 // From the API standpoint, we should let the client specify the cost table.
                 cost_table: cost_table.unwrap_or_else(unit_cost_schedule),
+                rand_num_iters,
+                seed,
             },
             num_threads,
             tests,
@@ -222,7 +246,7 @@ impl SharedTestingConfig {
         &self,
         test_plan: &ModuleTestPlan,
         function_name: &str,
-        test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<NativeContextExtensions>,
@@ -250,7 +274,7 @@ impl SharedTestingConfig {
             &test_plan.module_id,
             IdentStr::new(function_name).unwrap(),
             vec![], // no ty args, at least for now
-            serialize_values(test_info.arguments.iter()),
+            serialize_values(arguments),
             &mut gas_meter,
         );
         let mut return_result = serialized_return_values_result.map(|res| {
@@ -289,100 +313,45 @@ impl SharedTestingConfig {
         let mut stats = TestStatistics::new();
 
         for (function_name, test_info) in &test_plan.tests {
-            let (_cs_result, _ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
-
-            match exec_result {
-                Err(err) => {
-                    let sub_status = err.sub_status().and_then(|status| {
-                        convert_clever_move_abort_error(status, err.location(), global_test_context)
-                    });
-                    let actual_err =
-                        MoveError(err.major_status(), sub_status, err.location().clone());
-                    assert!(err.major_status() != StatusCode::EXECUTED);
-                    match test_info.expected_failure.as_ref() {
-                        Some(ExpectedFailure::Expected) => {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        Some(ExpectedFailure::ExpectedWithError(expected_err))
-                            if expected_err == &actual_err =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
-                            if actual_err.0 == StatusCode::ABORTED
-                                && actual_err.1.is_some()
-                                && actual_err.1.as_ref().unwrap() == code =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        // incorrect cases
-                        Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_error(expected_err.clone(), actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_abort_deprecated(
-                                        expected_code.clone(),
-                                        actual_err,
-                                    ),
-                                    test_run_info,
-                                    Some(err),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        None if err.major_status() == StatusCode::OUT_OF_GAS => {
-                            // Ran out of ticks, report a test timeout and log a test failure
-                            output.timeout(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::timeout(),
-                                    test_run_info,
-                                    Some(err),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        None => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::unexpected_error(actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                ),
-                                test_plan,
-                            )
+            let (result, test_run_info, random_test_inputs) = if test_info.is_random_test() {
+                self.exec_random_test(test_plan, function_name, test_info, global_test_context)
+            } else {
+                let arguments: Vec<_> = test_info
+                    .arguments
+                    .iter()
+                    .map(|arg| match arg {
+                        TestArgument::Value(value) => value.clone(),
+                        TestArgument::Generate { .. } => {
+                            unreachable!("ICE: generated argument in a non-random test")
                         }
-                    }
+                    })
+                    .collect();
+                let (result, test_run_info) = self.exec_test(
+                    test_plan,
+                    function_name,
+                    test_info,
+                    &arguments,
+                    global_test_context,
+                );
+                (result, test_run_info, None)
+            };
+
+            match result {
+                Ok(()) => {
+                    output.pass(function_name);
+                    stats.test_success(test_run_info, test_plan);
                 }
-                Ok(_) => {
-                    // Expected the test to fail, but it executed
-                    if test_info.expected_failure.is_some() {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(FailureReason::no_error(), test_run_info, None),
-                            test_plan,
-                        )
+                Err((failure_reason, vm_error)) => {
+                    if matches!(failure_reason, FailureReason::Timeout(_)) {
+                        output.timeout(function_name);
                     } else {
-                        // Expected the test to execute fully and it did
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
+                        output.fail(function_name);
                     }
+                    let mut failure = TestFailure::new(failure_reason, test_run_info, vm_error);
+                    if let Some(random_test_inputs) = random_test_inputs {
+                        failure = failure.with_random_test_inputs(random_test_inputs);
+                    }
+                    stats.test_failure(failure, test_plan)
                 }
             }
         }
@@ -390,6 +359,188 @@ impl SharedTestingConfig {
         stats
     }
 
+    /// Run a test once with `arguments`, and check its outcome against its expected failure.
+    fn exec_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        arguments: &[MoveValue],
+        global_test_context: &BTreeMap<ModuleId, NamedCompiledModule>,
+    ) -> (TestResult, TestRunInfo) {
+        let (_cs_result, _ext_result, exec_result, test_run_info) =
+            self.execute_via_move_vm(test_plan, function_name, arguments);
+
+        let result = match exec_result {
+            Err(err) => {
+                let sub_status = err.sub_status().and_then(|status| {
+                    convert_clever_move_abort_error(status, err.location(), global_test_context)
+                });
+                let actual_err = MoveError(err.major_status(), sub_status, err.location().clone());
+                assert!(err.major_status() != StatusCode::EXECUTED);
+                match test_info.expected_failure.as_ref() {
+                    Some(ExpectedFailure::Expected) => Ok(()),
+                    Some(ExpectedFailure::ExpectedWithError(expected_err))
+                        if expected_err == &actual_err =>
+                    {
+                        Ok(())
+                    }
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
+                        if actual_err.0 == StatusCode::ABORTED
+                            && actual_err.1.is_some()
+                            && actual_err.1.as_ref().unwrap() == code =>
+                    {
+                        Ok(())
+                    }
+                    // incorrect cases
+                    Some(ExpectedFailure::ExpectedWithError(expected_err)) => Err((
+                        FailureReason::wrong_error(expected_err.clone(), actual_err),
+                        Some(err),
+                    )),
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => Err((
+                        FailureReason::wrong_abort_deprecated(expected_code.clone(), actual_err),
+                        Some(err),
+                    )),
+                    // Ran out of ticks, report a test timeout and log a test failure
+                    None if err.major_status() == StatusCode::OUT_OF_GAS => {
+                        Err((FailureReason::timeout(), Some(err)))
+                    }
+                    None => Err((FailureReason::unexpected_error(actual_err), Some(err))),
+                }
+            }
+            // Expected the test to fail, but it executed
+            Ok(_) if test_info.expected_failure.is_some() => Err((FailureReason::no_error(), None)),
+            // Expected the test to execute fully and it did
+            Ok(_) => Ok(()),
+        };
+        (result, test_run_info)
+    }
+
+    /// Run a `#[random_test]` with freshly generated arguments, `rand_num_iters` times or until it
+    /// fails. The arguments of a failing run are shrunk, and reported along with the seed that
+    /// replays the run.
+    fn exec_random_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        global_test_context: &BTreeMap<ModuleId, NamedCompiledModule>,
+    ) -> (TestResult, TestRunInfo, Option<RandomTestInputs>) {
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut rng = StdRng::seed_from_u64(seed);
+        let params: Vec<_> = test_info
+            .arguments
+            .iter()
+            .map(|arg| match arg {
+                TestArgument::Generate { name, layout } => (name, layout),
+                TestArgument::Value(_) => {
+                    unreachable!("ICE: fixed argument in a random test")
+                }
+            })
+            .collect();
+
+        let mut elapsed_time = Duration::ZERO;
+        let mut instructions_executed = 0;
+        for _ in 0..self.rand_num_iters {
+            let arguments: Vec<_> = params
+                .iter()
+                .map(|(_, layout)| random_inputs::generate(&mut rng, layout))
+                .collect();
+            let (result, test_run_info) = self.exec_test(
+                test_plan,
+                function_name,
+                test_info,
+                &arguments,
+                global_test_context,
+            );
+            elapsed_time += test_run_info.elapsed_time;
+            instructions_executed += test_run_info.instructions_executed;
+
+            if let Err(failure) = result {
+                let (arguments, failure, test_run_info) = self.shrink_failing_arguments(
+                    test_plan,
+                    function_name,
+                    test_info,
+                    global_test_context,
+                    (arguments, failure, test_run_info),
+                );
+                let random_test_inputs = RandomTestInputs {
+                    seed,
+                    arguments: params
+                        .iter()
+                        .zip(arguments)
+                        .map(|((name, _), value)| format!("{name} = {value}"))
+                        .collect(),
+                };
+                return (Err(failure), test_run_info, Some(random_test_inputs));
+            }
+        }
+
+        let test_run_info = TestRunInfo::new(
+            function_name.to_string(),
+            elapsed_time,
+            instructions_executed,
+        );
+        (Ok(()), test_run_info, None)
+    }
+
+    /// Greedily simplify the arguments of a failing run of a random test, one argument at a time,
+    /// for as long as the test keeps failing in the same way (or until `MAX_SHRINK_RUNS` runs).
+    fn shrink_failing_arguments(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        global_test_context: &BTreeMap<ModuleId, NamedCompiledModule>,
+        failing_run: (
+            Vec<MoveValue>,
+            (FailureReason, Option<VMError>),
+            TestRunInfo,
+        ),
+    ) -> (
+        Vec<MoveValue>,
+        (FailureReason, Option<VMError>),
+        TestRunInfo,
+    ) {
+        let (mut arguments, mut failure, mut test_run_info) = failing_run;
+        let mut runs = 0;
+        'shrink: loop {
+            for i in 0..arguments.len() {
+                for candidate in random_inputs::shrink(&arguments[i]) {
+                    if runs == MAX_SHRINK_RUNS {
+                        break 'shrink;
+                    }
+                    runs += 1;
+
+                    let mut candidate_arguments = arguments.clone();
+                    candidate_arguments[i] = candidate;
+                    let (result, candidate_run_info) = self.exec_test(
+                        test_plan,
+                        function_name,
+                        test_info,
+                        &candidate_arguments,
+                        global_test_context,
+                    );
+                    match result {
+                        Err(candidate_failure)
+                            if std::mem::discriminant(&candidate_failure.0)
+                                == std::mem::discriminant(&failure.0) =>
+                        {
+                            arguments = candidate_arguments;
+                            failure = candidate_failure;
+                            test_run_info = candidate_run_info;
+                            continue 'shrink;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            // No simpler arguments fail
+            break;
+        }
+        (arguments, failure, test_run_info)
+    }
+
     // TODO: comparison of results via different backends
 
     fn exec_module_tests(
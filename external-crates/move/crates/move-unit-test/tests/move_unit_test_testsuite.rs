@@ -46,6 +46,8 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
             .into_iter()
             .collect(),
         report_stacktrace_on_abort: true,
+        // Keep the arguments generated for random tests stable across runs
+        seed: Some(0),
 
         ..UnitTestingConfig::default_with_bound(None)
     };
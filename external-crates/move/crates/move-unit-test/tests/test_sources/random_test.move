@@ -0,0 +1,26 @@
+address 0x1 {
+module M {
+    use std::vector;
+
+    #[random_test]
+    fun add_commutes(x: u32, y: u32) {
+        assert!((x as u64) + (y as u64) == (y as u64) + (x as u64), 0);
+    }
+
+    #[random_test]
+    #[expected_failure(abort_code = 0, location = 0x1::M)]
+    fun long_vectors_only(v: vector<u64>) {
+        if (vector::length(&v) <= 16) abort 0
+    }
+
+    #[random_test]
+    fun only_zero(x: u64) {
+        if (x != 0) abort 1
+    }
+
+    #[random_test]
+    fun short_vectors_only(v: vector<u8>) {
+        if (vector::length(&v) > 1) abort 2
+    }
+}
+}
@@ -4,6 +4,8 @@ use move_vm_config::runtime::VMProfilerConfig;
 use serde::Serialize;
 use std::collections::BTreeMap;
 
+pub mod tracer;
+
 #[cfg(feature = "gas-profiler")]
 use tracing::info;
 
@@ -340,6 +342,57 @@ macro_rules! profile_close_instr {
     };
 }
 
+#[macro_export]
+macro_rules! tracer_open_frame {
+    ($gas_meter:expr, $function:expr, $args:expr, $is_native:expr) => {
+        #[cfg(feature = "gas-profiler")]
+        {
+            let gas_rem = $gas_meter.remaining_gas().into();
+            if let Some(tracer) = $gas_meter.get_tracer_mut() {
+                tracer.open_frame($function, $args, $is_native, gas_rem)
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! tracer_close_frame {
+    ($gas_meter:expr, $return_values:expr) => {
+        #[cfg(feature = "gas-profiler")]
+        {
+            let gas_rem = $gas_meter.remaining_gas().into();
+            if let Some(tracer) = $gas_meter.get_tracer_mut() {
+                tracer.close_frame($return_values, gas_rem)
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! tracer_instr {
+    ($gas_meter:expr, $pc:expr, $instr:expr, $locals:expr, $stack:expr) => {
+        #[cfg(feature = "gas-profiler")]
+        {
+            let gas_rem = $gas_meter.remaining_gas().into();
+            if let Some(tracer) = $gas_meter.get_tracer_mut() {
+                tracer.instruction($pc, format!("{:?}", $instr), gas_rem, $locals, $stack)
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! tracer_effect {
+    ($gas_meter:expr, $effect:expr) => {
+        #[cfg(feature = "gas-profiler")]
+        {
+            if let Some(tracer) = $gas_meter.get_tracer_mut() {
+                tracer.effect($effect)
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! profile_dump_file {
     ($profiler:expr) => {
@@ -0,0 +1,295 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Step-level execution traces. When an `ExecutionTracer` is attached to the gas meter, the
+//! interpreter records every frame it opens and closes and every instruction it executes, along
+//! with the gas remaining, the locals of the frame and the operand stack. The adapter adds the
+//! effects of the transaction (objects read and written, events emitted) as they happen.
+//! Traces are kept in memory until they are written with `ExecutionTracer::finish`, so they stop
+//! recording after a maximum number of events.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Default maximum number of events recorded in a trace
+pub const DEFAULT_MAX_EVENTS: usize = 1_000_000;
+
+#[cfg(feature = "gas-profiler")]
+use tracing::info;
+
+/// A function as seen by the interpreter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TracedFunction {
+    /// Address of the module, as a hex literal
+    pub address: String,
+    pub module: String,
+    pub name: String,
+    /// Index of the function definition in the module, used to look up source maps
+    pub index: u16,
+    pub type_args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteKind {
+    Created,
+    Mutated,
+    Deleted,
+}
+
+/// Effects of the transaction, recorded by the adapter rather than the interpreter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceEffect {
+    ObjectRead {
+        id: String,
+        version: u64,
+        type_: Option<String>,
+    },
+    ObjectWrite {
+        id: String,
+        kind: WriteKind,
+        type_: Option<String>,
+    },
+    Event {
+        type_: String,
+        /// BCS bytes of the event
+        contents: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceEvent {
+    OpenFrame {
+        frame: u64,
+        function: TracedFunction,
+        args: Vec<String>,
+        is_native: bool,
+        gas_left: u64,
+    },
+    Instruction {
+        frame: u64,
+        pc: u16,
+        instruction: String,
+        gas_left: u64,
+        /// Locals of the frame before the instruction executes, `-` for unavailable ones
+        locals: Vec<String>,
+        /// Operand stack before the instruction executes, shared across frames
+        stack: Vec<String>,
+    },
+    CloseFrame {
+        frame: u64,
+        return_values: Vec<String>,
+        gas_left: u64,
+    },
+    Effect {
+        effect: TraceEffect,
+    },
+}
+
+/// The trace of a transaction, as written to file by the `ExecutionTracer`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub name: String,
+    pub start_gas: u64,
+    pub events: Vec<TraceEvent>,
+    /// Whether events were dropped because the trace reached its maximum number of events
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct ExecutionTracer {
+    trace: ExecutionTrace,
+    output: PathBuf,
+    /// Identifiers of the frames currently open, innermost last
+    open_frames: Vec<u64>,
+    next_frame: u64,
+    max_events: usize,
+}
+
+#[cfg(feature = "gas-profiler")]
+impl ExecutionTracer {
+    pub fn init(output: PathBuf, name: String, start_gas: u64, max_events: usize) -> Self {
+        ExecutionTracer {
+            trace: ExecutionTrace {
+                name,
+                start_gas,
+                events: vec![],
+                truncated: false,
+            },
+            output,
+            open_frames: vec![],
+            next_frame: 0,
+            max_events,
+        }
+    }
+
+    fn current_frame(&self) -> u64 {
+        self.open_frames.last().copied().unwrap_or(u64::MAX)
+    }
+
+    fn record(&mut self, event: TraceEvent) {
+        if self.trace.events.len() < self.max_events {
+            self.trace.events.push(event);
+        } else {
+            self.trace.truncated = true;
+        }
+    }
+
+    pub fn open_frame(
+        &mut self,
+        function: TracedFunction,
+        args: Vec<String>,
+        is_native: bool,
+        gas_left: u64,
+    ) {
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        self.open_frames.push(frame);
+        self.record(TraceEvent::OpenFrame {
+            frame,
+            function,
+            args,
+            is_native,
+            gas_left,
+        });
+    }
+
+    pub fn close_frame(&mut self, return_values: Vec<String>, gas_left: u64) {
+        let frame = self.current_frame();
+        self.open_frames.pop();
+        self.record(TraceEvent::CloseFrame {
+            frame,
+            return_values,
+            gas_left,
+        });
+    }
+
+    pub fn instruction(
+        &mut self,
+        pc: u16,
+        instruction: String,
+        gas_left: u64,
+        locals: Vec<String>,
+        stack: Vec<String>,
+    ) {
+        let frame = self.current_frame();
+        self.record(TraceEvent::Instruction {
+            frame,
+            pc,
+            instruction,
+            gas_left,
+            locals,
+            stack,
+        });
+    }
+
+    pub fn effect(&mut self, effect: TraceEffect) {
+        self.record(TraceEvent::Effect { effect });
+    }
+
+    pub fn trace(&self) -> &ExecutionTrace {
+        &self.trace
+    }
+
+    /// Writes the trace to its output file
+    pub fn finish(self) -> std::io::Result<()> {
+        use std::fs::File;
+        use std::io::{BufWriter, Write};
+
+        let mut writer = BufWriter::new(File::create(&self.output)?);
+        serde_json::to_writer(&mut writer, &self.trace)?;
+        writer.flush()?;
+        info!("Execution trace written to file: {}", self.output.display());
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "gas-profiler"))]
+mod tests {
+    use super::*;
+
+    fn function(name: &str) -> TracedFunction {
+        TracedFunction {
+            address: "0x2".to_string(),
+            module: "m".to_string(),
+            name: name.to_string(),
+            index: 0,
+            type_args: vec![],
+        }
+    }
+
+    fn tracer(output: PathBuf, max_events: usize) -> ExecutionTracer {
+        let mut tracer = ExecutionTracer::init(output, "tx".to_string(), 100, max_events);
+        tracer.open_frame(function("f"), vec!["1".to_string()], false, 100);
+        tracer.instruction(0, "Call".to_string(), 99, vec![], vec!["1".to_string()]);
+        tracer.open_frame(function("g"), vec![], true, 98);
+        tracer.close_frame(vec![], 97);
+        tracer.instruction(1, "Ret".to_string(), 96, vec![], vec![]);
+        tracer.close_frame(vec!["2".to_string()], 95);
+        tracer.effect(TraceEffect::Event {
+            type_: "0x2::m::E".to_string(),
+            contents: vec![0],
+        });
+        tracer
+    }
+
+    fn frames(trace: &ExecutionTrace) -> Vec<Option<u64>> {
+        trace
+            .events
+            .iter()
+            .map(|event| match event {
+                TraceEvent::OpenFrame { frame, .. }
+                | TraceEvent::Instruction { frame, .. }
+                | TraceEvent::CloseFrame { frame, .. } => Some(*frame),
+                TraceEvent::Effect { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_events_in_their_frames() {
+        let tracer = tracer(PathBuf::new(), DEFAULT_MAX_EVENTS);
+        let trace = tracer.trace();
+        assert!(!trace.truncated);
+        assert_eq!(
+            frames(trace),
+            vec![Some(0), Some(0), Some(1), Some(1), Some(0), Some(0), None]
+        );
+    }
+
+    #[test]
+    fn truncates_at_max_events() {
+        let mut tracer = tracer(PathBuf::new(), 3);
+        assert!(tracer.trace().truncated);
+        assert_eq!(frames(tracer.trace()), vec![Some(0), Some(0), Some(1)]);
+
+        // frames opened after the limit are still numbered and closed in order
+        tracer.open_frame(function("h"), vec![], false, 90);
+        tracer.close_frame(vec![], 90);
+        assert_eq!(tracer.trace().events.len(), 3);
+        assert_eq!(tracer.next_frame, 3);
+        assert!(tracer.open_frames.is_empty());
+    }
+
+    #[test]
+    fn finish_writes_trace() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("trace.json");
+        tracer(output.clone(), DEFAULT_MAX_EVENTS).finish().unwrap();
+
+        let trace: ExecutionTrace =
+            serde_json::from_reader(std::fs::File::open(&output).unwrap()).unwrap();
+        assert_eq!(trace.name, "tx");
+        assert_eq!(trace.start_gas, 100);
+        assert_eq!(trace.events.len(), 7);
+        assert!(!trace.truncated);
+
+        // failing to write the trace is reported rather than panicking
+        let output = dir.path().join("missing").join("trace.json");
+        assert!(tracer(output, DEFAULT_MAX_EVENTS).finish().is_err());
+    }
+}
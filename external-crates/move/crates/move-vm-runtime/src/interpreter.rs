@@ -19,11 +19,12 @@ use move_core_types::{
     vm_status::{StatusCode, StatusType},
 };
 use move_vm_config::runtime::VMRuntimeLimitsConfig;
-#[cfg(feature = "gas-profiler")]
-use move_vm_profiler::GasProfiler;
 use move_vm_profiler::{
     profile_close_frame, profile_close_instr, profile_open_frame, profile_open_instr,
+    tracer_close_frame, tracer_instr, tracer_open_frame,
 };
+#[cfg(feature = "gas-profiler")]
+use move_vm_profiler::{tracer::TracedFunction, GasProfiler};
 use move_vm_types::{
     data_store::DataStore,
     gas::{GasMeter, SimpleInstruction},
@@ -95,6 +96,39 @@ impl<'a, 'b> TypeView for TypeWithLoader<'a, 'b> {
     }
 }
 
+/// Describes a function, instantiated with `ty_args`, for the execution trace
+#[cfg(feature = "gas-profiler")]
+fn traced_function(loader: &Loader, function: &Function, ty_args: &[Type]) -> TracedFunction {
+    let module = function.module_id();
+    TracedFunction {
+        address: module.address().to_hex_literal(),
+        module: module.name().to_string(),
+        name: function.name().to_string(),
+        index: function.index().0,
+        type_args: ty_args
+            .iter()
+            .map(|ty| match loader.type_to_type_tag(ty) {
+                Ok(tag) => tag.to_string(),
+                Err(_) => "<unknown>".to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// Prints values for the execution trace
+#[cfg(feature = "gas-profiler")]
+fn traced_values<'a>(vals: impl IntoIterator<Item = &'a Value>) -> Vec<String> {
+    vals.into_iter()
+        .map(|val| {
+            let mut buf = String::new();
+            match values::debug::print_value(&mut buf, val) {
+                Ok(()) => buf,
+                Err(_) => "<unknown>".to_string(),
+            }
+        })
+        .collect()
+}
+
 impl Interpreter {
     /// Limits imposed at runtime
     pub fn runtime_limits_config(&self) -> &VMRuntimeLimitsConfig {
@@ -117,6 +151,12 @@ impl Interpreter {
             runtime_limits_config: loader.vm_config().runtime_limits_config.clone(),
         };
         profile_open_frame!(gas_meter, function.pretty_string());
+        tracer_open_frame!(
+            gas_meter,
+            traced_function(loader, &function, &ty_args),
+            traced_values(&args),
+            function.is_native()
+        );
 
         if function.is_native() {
             for arg in args {
@@ -142,6 +182,7 @@ impl Interpreter {
                 })?;
 
             profile_close_frame!(gas_meter, function.pretty_string());
+            tracer_close_frame!(gas_meter, traced_values(&return_values));
 
             Ok(return_values.into_iter().collect())
         } else {
@@ -202,6 +243,15 @@ impl Interpreter {
                         .map_err(|e| self.set_location(e))?;
 
                     profile_close_frame!(gas_meter, current_frame.function.pretty_string());
+                    tracer_close_frame!(
+                        gas_meter,
+                        traced_values(
+                            self.operand_stack
+                                .last_n(current_frame.function.return_type_count())
+                                .into_iter()
+                                .flatten()
+                        )
+                    );
 
                     if let Some(frame) = self.call_stack.pop() {
                         // Note: the caller will find the callee's return values at the top of the shared operand stack
@@ -216,7 +266,20 @@ impl Interpreter {
                     let func = resolver.function_from_handle(fh_idx);
                     #[cfg(feature = "gas-profiler")]
                     let func_name = func.pretty_string();
+                    #[cfg(feature = "gas-profiler")]
+                    let return_count = func.return_type_count();
                     profile_open_frame!(gas_meter, func_name.clone());
+                    tracer_open_frame!(
+                        gas_meter,
+                        traced_function(loader, &func, &[]),
+                        traced_values(
+                            self.operand_stack
+                                .last_n(func.arg_count())
+                                .into_iter()
+                                .flatten()
+                        ),
+                        func.is_native()
+                    );
 
                     // Charge gas
                     let module_id = func.module_id();
@@ -237,6 +300,15 @@ impl Interpreter {
                         current_frame.pc += 1; // advance past the Call instruction in the caller
 
                         profile_close_frame!(gas_meter, func_name.clone());
+                        tracer_close_frame!(
+                            gas_meter,
+                            traced_values(
+                                self.operand_stack
+                                    .last_n(return_count)
+                                    .into_iter()
+                                    .flatten()
+                            )
+                        );
                         continue;
                     }
                     let frame = self
@@ -259,7 +331,20 @@ impl Interpreter {
                     let func = resolver.function_from_instantiation(idx);
                     #[cfg(feature = "gas-profiler")]
                     let func_name = func.pretty_string();
+                    #[cfg(feature = "gas-profiler")]
+                    let return_count = func.return_type_count();
                     profile_open_frame!(gas_meter, func_name.clone());
+                    tracer_open_frame!(
+                        gas_meter,
+                        traced_function(loader, &func, &ty_args),
+                        traced_values(
+                            self.operand_stack
+                                .last_n(func.arg_count())
+                                .into_iter()
+                                .flatten()
+                        ),
+                        func.is_native()
+                    );
 
                     // Charge gas
                     let module_id = func.module_id();
@@ -279,6 +364,15 @@ impl Interpreter {
                         self.call_native(&resolver, gas_meter, extensions, func, ty_args)?;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         profile_close_frame!(gas_meter, func_name.clone());
+                        tracer_close_frame!(
+                            gas_meter,
+                            traced_values(
+                                self.operand_stack
+                                    .last_n(return_count)
+                                    .into_iter()
+                                    .flatten()
+                            )
+                        );
 
                         continue;
                     }
@@ -1303,6 +1397,13 @@ impl Frame {
                 });
 
                 profile_open_instr!(gas_meter, format!("{:?}", instruction));
+                tracer_instr!(
+                    gas_meter,
+                    self.pc,
+                    instruction,
+                    values::debug::locals_to_strings(&self.locals).unwrap_or_default(),
+                    traced_values(&interpreter.operand_stack.value)
+                );
 
                 let r = Self::execute_instruction(
                     &mut self.pc,
@@ -25,7 +25,7 @@ use move_core_types::{
     u256,
     vm_status::StatusCode,
 };
-use move_vm_profiler::GasProfiler;
+use move_vm_profiler::{tracer::ExecutionTracer, GasProfiler};
 use move_vm_types::{
     gas::{GasMeter, SimpleInstruction},
     views::{TypeView, ValueView},
@@ -114,6 +114,7 @@ pub struct GasStatus<'a> {
     gas_left: InternalGas,
     charge: bool,
     profiler: Option<GasProfiler>,
+    tracer: Option<ExecutionTracer>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -127,6 +128,7 @@ impl<'a> GasStatus<'a> {
             cost_table,
             charge: true,
             profiler: None,
+            tracer: None,
         }
     }
 
@@ -140,6 +142,7 @@ impl<'a> GasStatus<'a> {
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
             profiler: None,
+            tracer: None,
         }
     }
 
@@ -476,6 +479,14 @@ impl<'b> GasMeter for GasStatus<'b> {
     fn set_profiler(&mut self, profiler: GasProfiler) {
         self.profiler = Some(profiler);
     }
+
+    fn get_tracer_mut(&mut self) -> Option<&mut ExecutionTracer> {
+        self.tracer.as_mut()
+    }
+
+    fn set_tracer(&mut self, tracer: ExecutionTracer) {
+        self.tracer = Some(tracer);
+    }
 }
 
 pub fn new_from_instructions(mut instrs: Vec<(Bytecode, GasCost)>) -> CostTable {
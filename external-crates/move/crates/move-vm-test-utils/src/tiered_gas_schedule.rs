@@ -17,7 +17,7 @@ use move_core_types::{
     vm_status::StatusCode,
 };
 
-use move_vm_profiler::GasProfiler;
+use move_vm_profiler::{tracer::ExecutionTracer, GasProfiler};
 use move_vm_types::{
     gas::{GasMeter, SimpleInstruction},
     loaded_data::runtime_types::Type,
@@ -181,6 +181,7 @@ pub struct GasStatus<'a> {
     instructions_current_tier_mult: u64,
 
     profiler: Option<GasProfiler>,
+    tracer: Option<ExecutionTracer>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -211,6 +212,7 @@ impl<'a> GasStatus<'a> {
             stack_size_next_tier_start,
             instructions_next_tier_start,
             profiler: None,
+            tracer: None,
         }
     }
 
@@ -235,6 +237,7 @@ impl<'a> GasStatus<'a> {
             stack_size_next_tier_start: None,
             instructions_next_tier_start: None,
             profiler: None,
+            tracer: None,
         }
     }
 
@@ -707,6 +710,14 @@ impl<'b> GasMeter for GasStatus<'b> {
     fn set_profiler(&mut self, profiler: GasProfiler) {
         self.profiler = Some(profiler);
     }
+
+    fn get_tracer_mut(&mut self) -> Option<&mut ExecutionTracer> {
+        self.tracer.as_mut()
+    }
+
+    fn set_tracer(&mut self, tracer: ExecutionTracer) {
+        self.tracer = Some(tracer);
+    }
 }
 
 pub fn zero_cost_schedule() -> CostTable {
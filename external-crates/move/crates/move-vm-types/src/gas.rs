@@ -8,7 +8,7 @@ use move_core_types::{
     language_storage::ModuleId,
 };
 
-use move_vm_profiler::GasProfiler;
+use move_vm_profiler::{tracer::ExecutionTracer, GasProfiler};
 
 /// Enum of instructions that do not need extra information for gas metering.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -190,6 +190,10 @@ pub trait GasMeter {
     fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler>;
 
     fn set_profiler(&mut self, profiler: GasProfiler);
+
+    fn get_tracer_mut(&mut self) -> Option<&mut ExecutionTracer>;
+
+    fn set_tracer(&mut self, tracer: ExecutionTracer);
 }
 
 /// A dummy gas meter that does not meter anything.
@@ -367,4 +371,10 @@ impl GasMeter for UnmeteredGasMeter {
     }
 
     fn set_profiler(&mut self, _profiler: GasProfiler) {}
+
+    fn get_tracer_mut(&mut self) -> Option<&mut ExecutionTracer> {
+        None
+    }
+
+    fn set_tracer(&mut self, _tracer: ExecutionTracer) {}
 }
@@ -2907,6 +2907,20 @@ pub mod debug {
     pub fn print_value<B: Write>(buf: &mut B, val: &Value) -> PartialVMResult<()> {
         print_value_impl(buf, &val.0)
     }
+
+    /// Prints each local to its own string, in order, with `-` for invalid locals.
+    pub fn locals_to_strings(locals: &Locals) -> PartialVMResult<Vec<String>> {
+        locals
+            .0
+            .borrow()
+            .iter()
+            .map(|val| {
+                let mut buf = String::new();
+                print_value_impl(&mut buf, val)?;
+                Ok(buf)
+            })
+            .collect()
+    }
 }
 
 /***************************************************************************************
@@ -128,7 +128,18 @@ export type GasData = {
  *
  * Indications the expiration time for a transaction.
  */
-export type TransactionExpiration = { None: null } | { Epoch: number };
+export type TransactionExpiration =
+	| { None: null }
+	| { Epoch: number }
+	| { Timestamp: number }
+	| {
+			ValidDuring: {
+				minEpoch: number | null;
+				maxEpoch: number | null;
+				minTimestampMs: number | null;
+				maxTimestampMs: number | null;
+			};
+	  };
 
 const bcsRegistry = new BcsRegistry({
 	...getSuiMoveConfig(),
@@ -347,6 +358,13 @@ const TransactionKind = bcs.enum('TransactionKind', {
 const TransactionExpiration = bcs.enum('TransactionExpiration', {
 	None: null,
 	Epoch: unsafe_u64(),
+	Timestamp: unsafe_u64(),
+	ValidDuring: bcs.struct('ValidDuring', {
+		minEpoch: bcs.option(unsafe_u64()),
+		maxEpoch: bcs.option(unsafe_u64()),
+		minTimestampMs: bcs.option(unsafe_u64()),
+		maxTimestampMs: bcs.option(unsafe_u64()),
+	}),
 });
 
 const StructTag = bcs.struct('StructTag', {
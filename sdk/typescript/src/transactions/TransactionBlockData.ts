@@ -26,7 +26,19 @@ import { create } from './utils.js';
 
 export const TransactionExpiration = optional(
 	nullable(
-		union([object({ Epoch: integer() }), object({ None: union([literal(true), literal(null)]) })]),
+		union([
+			object({ Epoch: integer() }),
+			object({ Timestamp: integer() }),
+			object({
+				ValidDuring: object({
+					minEpoch: nullable(integer()),
+					maxEpoch: nullable(integer()),
+					minTimestampMs: nullable(integer()),
+					maxTimestampMs: nullable(integer()),
+				}),
+			}),
+			object({ None: union([literal(true), literal(null)]) }),
+		]),
 	),
 );
 export type TransactionExpiration = Infer<typeof TransactionExpiration>;
@@ -107,6 +107,7 @@ mod checked {
         let mut transaction_dependencies = input_objects.transaction_dependencies();
         let contains_deleted_input = input_objects.contains_deleted_objects();
         let congested_objects = input_objects.get_congested_objects();
+        let expired = input_objects.is_expired();
 
         let mut temporary_store = TemporaryStore::new(
             store,
@@ -141,6 +142,7 @@ mod checked {
             deny_cert,
             contains_deleted_input,
             congested_objects,
+            expired,
         );
 
         let status = if let Err(error) = &execution_result {
@@ -274,6 +276,7 @@ mod checked {
         deny_cert: bool,
         contains_deleted_input: bool,
         congested_objects: Option<Vec<ObjectID>>,
+        expired: bool,
     ) -> (
         GasCostSummary,
         Result<Mode::ExecutionResults, ExecutionError>,
@@ -310,6 +313,11 @@ mod checked {
                     },
                     None,
                 ))
+            } else if expired {
+                Err(ExecutionError::new(
+                    ExecutionErrorKind::ExecutionCancelledDueToExpiration,
+                    None,
+                ))
             } else {
                 execution_loop::<Mode>(
                     temporary_store,
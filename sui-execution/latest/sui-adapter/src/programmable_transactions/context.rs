@@ -147,7 +147,7 @@ mod checked {
                         call_arg,
                     )
                 })
-                .collect::<Result<_, ExecutionError>>()?;
+                .collect::<Result<Vec<_>, ExecutionError>>()?;
             let gas = if let Some(gas_coin) = gas_charger.gas_coin() {
                 let mut gas = load_object(
                     protocol_config,
@@ -215,6 +215,39 @@ mod checked {
                     ));
             }
 
+            // Record the input objects in the execution trace, if one is being recorded
+            #[skip_checked_arithmetic]
+            move_vm_profiler::gas_profiler_feature_enabled! {
+                use move_vm_profiler::tracer::TraceEffect;
+                use move_vm_types::gas::GasMeter;
+
+                for input in inputs.iter().chain(std::iter::once(&gas)) {
+                    let InputValue {
+                        object_metadata: Some(InputObjectMetadata::InputObject { id, version, .. }),
+                        inner: ResultValue { value, .. },
+                    } = input
+                    else {
+                        continue;
+                    };
+                    let type_ = match value {
+                        Some(Value::Object(obj)) => vm
+                            .get_runtime()
+                            .get_type_tag(&obj.type_)
+                            .ok()
+                            .map(|tag| tag.to_canonical_string(/* with_prefix */ true)),
+                        _ => None,
+                    };
+                    move_vm_profiler::tracer_effect!(
+                        gas_charger.move_gas_status_mut(),
+                        TraceEffect::ObjectRead {
+                            id: id.to_string(),
+                            version: version.value(),
+                            type_,
+                        }
+                    );
+                }
+            }
+
             Ok(Self {
                 protocol_config,
                 metrics,
@@ -328,6 +361,22 @@ mod checked {
                     Ok((module_id.clone(), tag, bytes))
                 })
                 .collect::<Result<Vec<_>, ExecutionError>>()?;
+
+            #[skip_checked_arithmetic]
+            move_vm_profiler::gas_profiler_feature_enabled! {
+                use move_vm_profiler::tracer::TraceEffect;
+                use move_vm_types::gas::GasMeter;
+
+                for (_, tag, contents) in &new_events {
+                    move_vm_profiler::tracer_effect!(
+                        self.gas_charger.move_gas_status_mut(),
+                        TraceEffect::Event {
+                            type_: tag.to_canonical_string(/* with_prefix */ true),
+                            contents: contents.clone(),
+                        }
+                    );
+                }
+            }
             self.user_events.extend(new_events);
             Ok(())
         }
@@ -720,6 +769,25 @@ mod checked {
                 "Events should be taken after every Move call"
             );
 
+            // Objects loaded at runtime are only known once execution is done, so they are recorded
+            // at the end of the execution trace
+            #[skip_checked_arithmetic]
+            move_vm_profiler::gas_profiler_feature_enabled! {
+                use move_vm_profiler::tracer::TraceEffect;
+                use move_vm_types::gas::GasMeter;
+
+                for (id, loaded) in &loaded_child_objects {
+                    move_vm_profiler::tracer_effect!(
+                        gas_charger.move_gas_status_mut(),
+                        TraceEffect::ObjectRead {
+                            id: id.to_string(),
+                            version: loaded.version.value(),
+                            type_: None,
+                        }
+                    );
+                }
+            }
+
             loaded_runtime_objects.extend(loaded_child_objects);
 
             let mut written_objects = BTreeMap::new();
@@ -830,6 +898,40 @@ mod checked {
                 }
             }
 
+            #[skip_checked_arithmetic]
+            move_vm_profiler::gas_profiler_feature_enabled! {
+                use move_vm_profiler::tracer::{TraceEffect, WriteKind};
+                use move_vm_types::gas::GasMeter;
+
+                for (id, object) in &written_objects {
+                    let kind = if created_object_ids.contains(id) {
+                        WriteKind::Created
+                    } else {
+                        WriteKind::Mutated
+                    };
+                    move_vm_profiler::tracer_effect!(
+                        gas_charger.move_gas_status_mut(),
+                        TraceEffect::ObjectWrite {
+                            id: id.to_string(),
+                            kind,
+                            type_: object
+                                .struct_tag()
+                                .map(|tag| tag.to_canonical_string(/* with_prefix */ true)),
+                        }
+                    );
+                }
+                for id in &deleted_object_ids {
+                    move_vm_profiler::tracer_effect!(
+                        gas_charger.move_gas_status_mut(),
+                        TraceEffect::ObjectWrite {
+                            id: id.to_string(),
+                            kind: WriteKind::Deleted,
+                            type_: None,
+                        }
+                    );
+                }
+            }
+
             let user_events = user_events
                 .into_iter()
                 .map(|(module_id, tag, contents)| {
@@ -11,4 +11,5 @@ pub mod group_ops;
 pub mod hash;
 pub mod hmac;
 pub mod poseidon;
+pub mod schnorr;
 pub mod zklogin;
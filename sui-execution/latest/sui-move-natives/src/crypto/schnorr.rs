@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::object_runtime::ObjectRuntime;
+use crate::NativesCostTable;
+use fastcrypto::secp256k1::rust_secp256k1::{
+    schnorr::Signature, Message, Secp256k1, XOnlyPublicKey,
+};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
+use move_core_types::vm_status::StatusCode;
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::natives::function::PartialVMError;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub const NOT_SUPPORTED_ERROR: u64 = 0;
+
+fn is_supported(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<ObjectRuntime>()
+        .protocol_config
+        .enable_schnorr_bip340()
+}
+
+#[derive(Clone)]
+pub struct SchnorrBip340VerifyCostParams {
+    /// Base cost for invoking the `bip340_verify` function
+    pub schnorr_bip340_verify_cost_base: Option<InternalGas>,
+}
+
+/***************************************************************************************************
+ * native fun bip340_verify
+ * Implementation of the Move native function `schnorr::bip340_verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;`
+ *   gas cost: schnorr_bip340_verify_cost_base   | base cost for function call and fixed opers
+ * Note: `signature`, `public_key` and `msg` are fixed size, so their costs are included in the base cost.
+ *       Inputs of any other size are rejected before any cryptographic operation.
+ **************************************************************************************************/
+pub fn bip340_verify(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let cost = context.gas_used();
+    if !is_supported(context) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
+    // Load the cost parameters from the protocol config
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .schnorr_bip340_verify_cost_params
+        .clone();
+
+    // Charge the base cost for this oper
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .schnorr_bip340_verify_cost_base
+            .ok_or_else(
+                || PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message("Gas cost for bip340_verify not available".to_string())
+            )?
+    );
+
+    let msg = pop_arg!(args, VectorRef);
+    let msg_ref = msg.as_bytes_ref();
+    let public_key_bytes = pop_arg!(args, VectorRef);
+    let public_key_bytes_ref = public_key_bytes.as_bytes_ref();
+    let signature_bytes = pop_arg!(args, VectorRef);
+    let signature_bytes_ref = signature_bytes.as_bytes_ref();
+
+    let cost = context.gas_used();
+
+    let Ok(signature) = Signature::from_slice(&signature_bytes_ref) else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    // Fails if the key is not the x coordinate of a point on the curve
+    let Ok(public_key) = XOnlyPublicKey::from_slice(&public_key_bytes_ref) else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let Ok(msg) = Message::from_slice(&msg_ref) else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let result = Secp256k1::verification_only()
+        .verify_schnorr(&signature, &msg, &public_key)
+        .is_ok();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(result)]))
+}
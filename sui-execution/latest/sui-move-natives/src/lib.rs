@@ -37,6 +37,8 @@ use self::{
 use crate::crypto::group_ops;
 use crate::crypto::group_ops::GroupOpsCostParams;
 use crate::crypto::poseidon::PoseidonBN254CostParams;
+use crate::crypto::schnorr;
+use crate::crypto::schnorr::SchnorrBip340VerifyCostParams;
 use crate::crypto::zklogin;
 use crate::crypto::zklogin::{CheckZkloginIdCostParams, CheckZkloginIssuerCostParams};
 use better_any::{Tid, TidAble};
@@ -146,6 +148,9 @@ pub struct NativesCostTable {
     // poseidon
     pub poseidon_bn254_cost_params: PoseidonBN254CostParams,
 
+    // schnorr
+    pub schnorr_bip340_verify_cost_params: SchnorrBip340VerifyCostParams,
+
     // hmac
     pub hmac_hmac_sha3_256_cost_params: HmacHmacSha3256CostParams,
 
@@ -518,6 +523,11 @@ impl NativesCostTable {
                     .poseidon_bn254_cost_per_block_as_option()
                     .map(Into::into),
             },
+            schnorr_bip340_verify_cost_params: SchnorrBip340VerifyCostParams {
+                schnorr_bip340_verify_cost_base: protocol_config
+                    .schnorr_bip340_verify_cost_base_as_option()
+                    .map(Into::into),
+            },
             group_ops_cost_params: GroupOpsCostParams {
                 bls12381_decode_scalar_cost: protocol_config
                     .group_ops_bls12381_decode_scalar_cost_as_option()
@@ -883,6 +893,11 @@ pub fn all_natives(silent: bool) -> NativeFunctionTable {
             "poseidon_bn254_internal",
             make_native!(poseidon::poseidon_bn254_internal),
         ),
+        (
+            "schnorr",
+            "bip340_verify",
+            make_native!(schnorr::bip340_verify),
+        ),
     ];
     let sui_framework_natives_iter =
         sui_framework_natives